@@ -0,0 +1,199 @@
+//! Per-task activity log.
+//!
+//! `save_project` compares each save with the file it replaces and appends
+//! what changed (created, renamed, duration or anchor moved, completed,
+//! deleted) to the project's log, alongside comments added through
+//! [`add_task_comment`]. The log is append-only: whatever the editor sends is
+//! ignored in favour of the stored one.
+
+use crate::project::{load_project, store_project, Project};
+use crate::scheduler::TaskDuration;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Activity {
+    Created,
+    Renamed {
+        from: String,
+        to: String,
+    },
+    DurationChanged {
+        from: TaskDuration,
+        to: TaskDuration,
+    },
+    /// The task's anchor was set, moved or cleared.
+    AnchorChanged {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Completed,
+    Reopened,
+    Deleted,
+    Comment {
+        text: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ActivityEntry {
+    pub task_id: String,
+    /// When it happened (RFC 3339).
+    pub at: String,
+    #[serde(flatten)]
+    pub activity: Activity,
+}
+
+/// Appends to `project.activity` what changed since `previous`.
+pub(crate) fn record_changes(previous: Option<&Project>, project: &mut Project, now: &str) {
+    let mut log = Vec::new();
+    let mut add = |task_id: &str, activity: Activity| {
+        log.push(ActivityEntry {
+            task_id: task_id.to_string(),
+            at: now.to_string(),
+            activity,
+        })
+    };
+    let before = |id: &str| previous.and_then(|p| p.tasks.iter().find(|t| t.id == id));
+
+    for task in &project.tasks {
+        let Some(old) = before(&task.id) else {
+            add(&task.id, Activity::Created);
+            continue;
+        };
+        if old.name != task.name {
+            add(
+                &task.id,
+                Activity::Renamed {
+                    from: old.name.clone(),
+                    to: task.name.clone(),
+                },
+            );
+        }
+        if old.duration != task.duration {
+            add(
+                &task.id,
+                Activity::DurationChanged {
+                    from: old.duration,
+                    to: task.duration,
+                },
+            );
+        }
+        let anchor = |p: &Project| p.anchors.get(&task.id).cloned();
+        let (from, to) = (previous.and_then(anchor), anchor(project));
+        if from != to {
+            add(&task.id, Activity::AnchorChanged { from, to });
+        }
+        match (old.completed, task.completed) {
+            (false, true) => add(&task.id, Activity::Completed),
+            (true, false) => add(&task.id, Activity::Reopened),
+            _ => {}
+        }
+    }
+    for old in previous.iter().flat_map(|p| &p.tasks) {
+        if !project.tasks.iter().any(|t| t.id == old.id) {
+            add(&old.id, Activity::Deleted);
+        }
+    }
+    project.activity.extend(log);
+}
+
+/// Adds a comment to a task's activity log.
+#[tauri::command]
+pub fn add_task_comment(
+    app: AppHandle,
+    project_id: String,
+    task_id: String,
+    text: String,
+) -> Result<ActivityEntry, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Comment is empty".to_string());
+    }
+    let project = load_project(app.clone(), project_id)?;
+    if !project.tasks.iter().any(|t| t.id == task_id) {
+        return Err(format!("Task {} not found", task_id));
+    }
+    let entry = ActivityEntry {
+        task_id,
+        at: chrono::Local::now().to_rfc3339(),
+        activity: Activity::Comment {
+            text: text.to_string(),
+        },
+    };
+    store_project(&app, project, vec![entry.clone()])?;
+    Ok(entry)
+}
+
+/// A task's log, or the whole project's when no task is given, oldest first.
+#[tauri::command]
+pub fn get_task_activity(
+    app: AppHandle,
+    project_id: String,
+    task_id: Option<String>,
+) -> Result<Vec<ActivityEntry>, String> {
+    let project = load_project(app, project_id)?;
+    Ok(project
+        .activity
+        .into_iter()
+        .filter(|e| task_id.as_ref().is_none_or(|id| &e.task_id == id))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::Task;
+
+    #[test]
+    fn test_changes_between_saves_are_logged() {
+        let task = |id: &str, days: i64| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(days),
+            ..Default::default()
+        };
+        let mut project = Project {
+            tasks: vec![task("draft", 3), task("review", 1)],
+            ..Default::default()
+        };
+        record_changes(None, &mut project, "t1");
+        assert_eq!(project.activity.len(), 2);
+
+        let previous = project.clone();
+        project.tasks[0].duration = TaskDuration::days(5);
+        project.tasks[0].completed = true;
+        project.tasks.remove(1);
+        project
+            .anchors
+            .insert("draft".into(), "2026-03-10T17:00:00".into());
+        record_changes(Some(&previous), &mut project, "t2");
+
+        let logged: Vec<_> = project.activity[2..]
+            .iter()
+            .map(|e| (e.task_id.as_str(), e.activity.clone()))
+            .collect();
+        assert_eq!(
+            logged,
+            vec![
+                (
+                    "draft",
+                    Activity::DurationChanged {
+                        from: TaskDuration::days(3),
+                        to: TaskDuration::days(5)
+                    }
+                ),
+                (
+                    "draft",
+                    Activity::AnchorChanged {
+                        from: None,
+                        to: Some("2026-03-10T17:00:00".into())
+                    }
+                ),
+                ("draft", Activity::Completed),
+                ("review", Activity::Deleted),
+            ]
+        );
+    }
+}
@@ -0,0 +1,407 @@
+//! Daily agenda (time blocking).
+//!
+//! Turns a schedule into concrete work blocks: from now until the last
+//! deadline, each working day is filled with the most urgent work whose
+//! dependencies are done, up to the configured daily capacity.
+
+use crate::config::load_config;
+use crate::project::load_project;
+use crate::scheduler::{calculate_backwards_schedule, DurationUnit, ScheduledTask, Task};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+/// When work can be scheduled into an agenda.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorkingHours {
+    /// HH:MM
+    pub day_start: String,
+    /// HH:MM
+    pub day_end: String,
+    pub days: Vec<Weekday>,
+    /// Most hours of task work planned into one day.
+    pub capacity_hours: f64,
+    /// Days that differ from the usual hours, e.g. a half day on Dec 24 or
+    /// six-hour Fridays.
+    #[serde(default)]
+    pub exceptions: Vec<WorkingException>,
+}
+
+/// Different hours for one date or every given weekday. Unset fields keep
+/// the usual value; a dated exception beats a weekday one and also applies
+/// to days that are normally off.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct WorkingException {
+    #[serde(default)]
+    pub date: Option<NaiveDate>,
+    #[serde(default)]
+    pub weekday: Option<Weekday>,
+    /// HH:MM
+    #[serde(default)]
+    pub day_start: Option<String>,
+    /// HH:MM
+    #[serde(default)]
+    pub day_end: Option<String>,
+    /// 0 makes the day off.
+    #[serde(default)]
+    pub capacity_hours: Option<f64>,
+}
+
+fn parse_time(s: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(s, "%H:%M")
+        .map_err(|e| format!("Invalid working hours time '{}': {}", s, e))
+}
+
+impl WorkingHours {
+    /// Usual working minutes in a day.
+    fn workday_minutes(&self) -> Result<i64, String> {
+        let (start, end) = (parse_time(&self.day_start)?, parse_time(&self.day_end)?);
+        if end <= start {
+            return Err("Working day must end after it starts".to_string());
+        }
+        Ok((end - start).num_minutes())
+    }
+
+    /// `date`'s working window and capacity in minutes, or `None` on a day off.
+    pub(crate) fn hours_on(
+        &self,
+        date: NaiveDate,
+    ) -> Result<Option<(NaiveTime, NaiveTime, i64)>, String> {
+        let exception = self
+            .exceptions
+            .iter()
+            .find(|e| e.date == Some(date))
+            .or_else(|| {
+                self.exceptions
+                    .iter()
+                    .find(|e| e.date.is_none() && e.weekday == Some(date.weekday()))
+                    .filter(|_| self.days.contains(&date.weekday()))
+            });
+        if exception.is_none() && !self.days.contains(&date.weekday()) {
+            return Ok(None);
+        }
+
+        let start = exception.and_then(|e| e.day_start.as_deref());
+        let end = exception.and_then(|e| e.day_end.as_deref());
+        let start = parse_time(start.unwrap_or(&self.day_start))?;
+        let end = parse_time(end.unwrap_or(&self.day_end))?;
+        let capacity = exception
+            .and_then(|e| e.capacity_hours)
+            .unwrap_or(self.capacity_hours);
+        let capacity = (capacity * 60.0).round() as i64;
+        Ok((end > start && capacity > 0).then_some((start, end, capacity)))
+    }
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        Self {
+            day_start: "09:00".to_string(),
+            day_end: "17:00".to_string(),
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            capacity_hours: 8.0,
+            exceptions: Vec::new(),
+        }
+    }
+}
+
+/// A stretch of time set aside for one task.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AgendaBlock {
+    pub task_id: String,
+    pub task_name: String,
+    pub start: String,
+    pub end: String,
+    pub hours: f64,
+    /// The block ends after the task's scheduled finish.
+    pub past_deadline: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AgendaDay {
+    /// YYYY-MM-DD
+    pub date: String,
+    pub blocks: Vec<AgendaBlock>,
+    pub hours: f64,
+}
+
+/// Work that didn't fit before the last deadline.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UnallocatedWork {
+    pub task_id: String,
+    pub hours: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct Agenda {
+    pub days: Vec<AgendaDay>,
+    pub unallocated: Vec<UnallocatedWork>,
+}
+
+fn hours(minutes: i64) -> f64 {
+    (minutes as f64 / 60.0 * 100.0).round() / 100.0
+}
+
+/// Allocates the incomplete work in `schedule` into working days from `now`.
+///
+/// Minute and hour durations need that much work; day and week durations
+/// need a full working day per day. Work is picked earliest-deadline first
+/// and only once the task's providers are finished. Elapsed tasks and
+/// milestones take no one's time, so they neither appear nor hold work up.
+pub fn plan_agenda(
+    tasks: &[Task],
+    schedule: &[ScheduledTask],
+    working: &WorkingHours,
+    now: NaiveDateTime,
+) -> Result<Agenda, String> {
+    let workday_minutes = working.workday_minutes()?;
+
+    let definitions: HashMap<&str, &Task> = tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+    struct Work<'a> {
+        row: &'a ScheduledTask,
+        providers: Vec<&'a str>,
+        deadline: NaiveDateTime,
+        remaining: i64,
+        finished_at: Option<NaiveDateTime>,
+    }
+    let mut work: Vec<Work> = schedule
+        .iter()
+        .filter(|row| !row.completed && !row.is_milestone && !row.is_elapsed)
+        .filter_map(|row| {
+            let task = definitions.get(row.id.as_str())?;
+            let deadline =
+                NaiveDateTime::parse_from_str(&row.end_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+            let planned = row.estimate_minutes + row.buffer_minutes;
+            let remaining = match task.duration.unit {
+                DurationUnit::Minutes | DurationUnit::Hours => planned,
+                DurationUnit::Days | DurationUnit::Weeks => planned * workday_minutes / 1440,
+            };
+            Some(Work {
+                row,
+                providers: task.dependencies.iter().map(String::as_str).collect(),
+                deadline,
+                remaining,
+                finished_at: None,
+            })
+        })
+        .collect();
+    let index: HashMap<&str, usize> = work
+        .iter()
+        .enumerate()
+        .map(|(i, w)| (w.row.id.as_str(), i))
+        .collect();
+
+    let mut agenda = Agenda::default();
+    let Some(last_day) = work.iter().map(|w| w.deadline.date()).max() else {
+        return Ok(agenda);
+    };
+    for date in now.date().iter_days().take_while(|&d| d <= last_day) {
+        let Some((day_start, day_end, capacity)) = working.hours_on(date)? else {
+            continue;
+        };
+        let open = date.and_time(day_start).max(now);
+        let close = date.and_time(day_end);
+        let mut cursor = open;
+        let mut left = capacity.min((close - open).num_minutes());
+        let mut day = AgendaDay {
+            date: date.format("%Y-%m-%d").to_string(),
+            blocks: Vec::new(),
+            hours: 0.0,
+        };
+
+        while left > 0 {
+            // Ready: providers (that need work) are done by now in the day
+            let next = (0..work.len())
+                .filter(|&i| work[i].remaining > 0)
+                .filter(|&i| {
+                    work[i].providers.iter().all(|p| {
+                        index
+                            .get(p)
+                            .is_none_or(|&j| work[j].finished_at.is_some_and(|at| at <= cursor))
+                    })
+                })
+                .min_by(|&a, &b| {
+                    (work[a].deadline, &work[a].row.id).cmp(&(work[b].deadline, &work[b].row.id))
+                });
+            let Some(i) = next else { break };
+
+            let minutes = work[i].remaining.min(left);
+            let end = cursor + Duration::minutes(minutes);
+            work[i].remaining -= minutes;
+            if work[i].remaining == 0 {
+                work[i].finished_at = Some(end);
+            }
+            day.blocks.push(AgendaBlock {
+                task_id: work[i].row.id.clone(),
+                task_name: work[i].row.name.clone(),
+                start: cursor.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                end: end.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                hours: hours(minutes),
+                past_deadline: end > work[i].deadline,
+            });
+            cursor = end;
+            left -= minutes;
+        }
+
+        if !day.blocks.is_empty() {
+            day.hours = hours((cursor - open).num_minutes());
+            agenda.days.push(day);
+        }
+    }
+
+    agenda.unallocated = work
+        .iter()
+        .filter(|w| w.remaining > 0)
+        .map(|w| UnallocatedWork {
+            task_id: w.row.id.clone(),
+            hours: hours(w.remaining),
+        })
+        .collect();
+    Ok(agenda)
+}
+
+/// Time-blocked agenda for a saved project, from now until its last deadline.
+#[tauri::command]
+pub fn generate_agenda(app: AppHandle, project_id: String) -> Result<Agenda, String> {
+    let working = load_config(app.clone())?.working_hours;
+    let project = load_project(app, project_id)?;
+    let now = chrono::Local::now().naive_local();
+    let mut request = project.schedule_request();
+    request.reference_time = Some(now.format("%Y-%m-%dT%H:%M:%S").to_string());
+    let result = calculate_backwards_schedule(request).map_err(|e| e.to_string())?;
+    plan_agenda(&project.tasks, &result.scheduled_tasks, &working, now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{ScheduleRequest, TaskDuration};
+
+    #[test]
+    fn test_work_is_blocked_into_working_days() {
+        let mut review = Task {
+            id: "review".into(),
+            name: "Review".into(),
+            duration: TaskDuration::days(1),
+            ..Default::default()
+        };
+        review.dependencies = vec!["draft".into()];
+        let tasks = vec![
+            Task {
+                id: "draft".into(),
+                name: "Draft".into(),
+                duration: TaskDuration::hours(8),
+                ..Default::default()
+            },
+            review,
+        ];
+        let request = ScheduleRequest {
+            tasks: tasks.clone(),
+            anchors: [("review".into(), "2026-03-09T17:00:00".into())].into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+        let schedule = calculate_backwards_schedule(request)
+            .unwrap()
+            .scheduled_tasks;
+        let working = WorkingHours {
+            capacity_hours: 6.0,
+            ..Default::default()
+        };
+        // Friday morning: the weekend is skipped
+        let now =
+            NaiveDateTime::parse_from_str("2026-03-06T08:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+
+        let agenda = plan_agenda(&tasks, &schedule, &working, now).unwrap();
+        let days: Vec<(&str, Vec<(&str, f64)>)> = agenda
+            .days
+            .iter()
+            .map(|d| {
+                (
+                    d.date.as_str(),
+                    d.blocks
+                        .iter()
+                        .map(|b| (b.task_id.as_str(), b.hours))
+                        .collect(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            days,
+            vec![
+                ("2026-03-06", vec![("draft", 6.0)]),
+                ("2026-03-09", vec![("draft", 2.0), ("review", 4.0)]),
+            ]
+        );
+        assert_eq!(agenda.days[1].blocks[0].start, "2026-03-09T09:00:00");
+        assert_eq!(
+            agenda.unallocated,
+            vec![UnallocatedWork {
+                task_id: "review".into(),
+                hours: 4.0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_exceptions_shorten_days() {
+        let tasks = vec![Task {
+            id: "wrap".into(),
+            name: "Wrap".into(),
+            duration: TaskDuration::hours(12),
+            ..Default::default()
+        }];
+        let request = ScheduleRequest {
+            tasks: tasks.clone(),
+            anchors: [("wrap".into(), "2026-12-31T17:00:00".into())].into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+        let schedule = calculate_backwards_schedule(request)
+            .unwrap()
+            .scheduled_tasks;
+        let working = WorkingHours {
+            exceptions: vec![
+                WorkingException {
+                    date: NaiveDate::from_ymd_opt(2026, 12, 24),
+                    day_end: Some("12:00".into()),
+                    ..Default::default()
+                },
+                WorkingException {
+                    weekday: Some(Weekday::Thu),
+                    capacity_hours: Some(6.0),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        // Wednesday Dec 23
+        let now =
+            NaiveDateTime::parse_from_str("2026-12-23T09:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+
+        let agenda = plan_agenda(&tasks, &schedule, &working, now).unwrap();
+        let days: Vec<(&str, f64)> = agenda
+            .days
+            .iter()
+            .map(|d| (d.date.as_str(), d.hours))
+            .collect();
+
+        // The dated half day wins over six-hour Thursdays
+        assert_eq!(
+            days,
+            vec![
+                ("2026-12-23", 8.0),
+                ("2026-12-24", 3.0),
+                ("2026-12-25", 1.0)
+            ]
+        );
+    }
+}
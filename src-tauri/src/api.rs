@@ -0,0 +1,547 @@
+//! Local HTTP API.
+//!
+//! Lets external systems use Anchor as a backward-scheduling engine without a
+//! stored project, and lets automation tools (Zapier, n8n, ...) poll the
+//! stored projects for schedule changes; `GET /capabilities` reports what this
+//! version supports. The server only starts when `api` is set in the app
+//! config (read at launch) and binds to localhost; every request must carry
+//! the configured token as `Authorization: Bearer <token>`.
+
+use crate::cache::ScheduleCache;
+use crate::capabilities::capabilities;
+use crate::project::Project;
+use crate::scheduler::{calculate_backwards_schedule, ScheduleRequest, ScheduledTask};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiSettings {
+    pub port: u16,
+    /// Shared secret clients send as a bearer token.
+    pub token: String,
+}
+
+/// How a task's scheduled row changed between two polls.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Updated,
+    Removed,
+}
+
+/// One entry of the `/changes` feed.
+#[derive(Debug, Serialize, Clone)]
+pub struct ScheduleChange {
+    /// Position in the feed; pass the last one seen as `since`.
+    pub cursor: u64,
+    pub project_id: String,
+    pub task_id: String,
+    pub kind: ChangeKind,
+    /// The task's current row; absent for removals.
+    pub task: Option<ScheduledTask>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChangesResponse<'a> {
+    changes: Vec<&'a ScheduleChange>,
+    /// Cursor to pass as `since` on the next poll.
+    cursor: u64,
+    /// True when `since` is older than the retained history (or from before a
+    /// restart), so some changes were missed and clients should resync.
+    reset: bool,
+}
+
+/// A task scheduled to be in progress on the requested day.
+#[derive(Debug, Serialize)]
+struct AgendaItem {
+    project_id: String,
+    project_name: String,
+    task: ScheduledTask,
+}
+
+#[derive(Debug, Serialize)]
+struct AgendaResponse {
+    date: String,
+    items: Vec<AgendaItem>,
+}
+
+/// Changes retained for polling; older cursors get `reset`.
+const FEED_CAPACITY: usize = 1000;
+
+type Rows = HashMap<String, ScheduledTask>;
+
+/// A project with its schedule rows, if it could be scheduled.
+type ProjectRows = (Project, Option<Vec<ScheduledTask>>);
+
+/// Schedule changes detected between polls.
+#[derive(Default)]
+struct ChangeFeed {
+    last_cursor: u64,
+    /// Rows as of the last poll, per project.
+    snapshot: HashMap<String, Rows>,
+    /// Recent changes, oldest first.
+    log: VecDeque<ScheduleChange>,
+}
+
+impl ChangeFeed {
+    fn push(
+        &mut self,
+        project_id: &str,
+        task_id: &str,
+        kind: ChangeKind,
+        task: Option<&ScheduledTask>,
+    ) {
+        self.last_cursor += 1;
+        self.log.push_back(ScheduleChange {
+            cursor: self.last_cursor,
+            project_id: project_id.to_string(),
+            task_id: task_id.to_string(),
+            kind,
+            task: task.cloned(),
+        });
+        if self.log.len() > FEED_CAPACITY {
+            self.log.pop_front();
+        }
+    }
+
+    /// Diffs the current schedules against the last snapshot and logs the
+    /// differences. Projects missing from `current` are treated as deleted;
+    /// ones without rows (they failed to schedule) keep their last snapshot.
+    fn record(&mut self, mut current: Vec<(String, Option<Vec<ScheduledTask>>)>) {
+        current.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut previous = std::mem::take(&mut self.snapshot);
+
+        for (project_id, rows) in current {
+            let before = previous.remove(&project_id).unwrap_or_default();
+            let Some(rows) = rows else {
+                self.snapshot.insert(project_id, before);
+                continue;
+            };
+            for row in &rows {
+                match before.get(&row.id) {
+                    None => self.push(&project_id, &row.id, ChangeKind::Added, Some(row)),
+                    Some(old) if old != row => {
+                        self.push(&project_id, &row.id, ChangeKind::Updated, Some(row))
+                    }
+                    _ => {}
+                }
+            }
+            let now: Rows = rows.into_iter().map(|r| (r.id.clone(), r)).collect();
+            let mut removed: Vec<&String> =
+                before.keys().filter(|id| !now.contains_key(*id)).collect();
+            removed.sort();
+            for id in removed {
+                self.push(&project_id, id, ChangeKind::Removed, None);
+            }
+            self.snapshot.insert(project_id, now);
+        }
+
+        let mut deleted: Vec<(String, Rows)> = previous.into_iter().collect();
+        deleted.sort_by(|a, b| a.0.cmp(&b.0));
+        for (project_id, rows) in deleted {
+            let mut ids: Vec<&String> = rows.keys().collect();
+            ids.sort();
+            for id in ids {
+                self.push(&project_id, id, ChangeKind::Removed, None);
+            }
+        }
+    }
+
+    fn since(&self, since: u64) -> ChangesResponse<'_> {
+        let oldest = self.log.front().map_or(self.last_cursor + 1, |c| c.cursor);
+        let reset = since > self.last_cursor || since + 1 < oldest;
+        ChangesResponse {
+            changes: self
+                .log
+                .iter()
+                .filter(|c| reset || c.cursor > since)
+                .collect(),
+            cursor: self.last_cursor,
+            reset,
+        }
+    }
+}
+
+/// Loads the stored projects for the polling endpoints.
+pub type ProjectSource = Box<dyn Fn() -> Result<Vec<Project>, String> + Send + Sync>;
+
+/// Request handling state shared by the server thread.
+pub struct Api {
+    settings: ApiSettings,
+    projects: ProjectSource,
+    cache: ScheduleCache,
+    feed: Mutex<ChangeFeed>,
+}
+
+/// Compares without short-circuiting so response timing doesn't leak the token.
+/// An empty expected token matches nothing.
+fn token_matches(expected: &str, given: &str) -> bool {
+    !expected.is_empty()
+        && expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+fn json_response<T: Serialize>(value: &T) -> (u16, String) {
+    match serde_json::to_string(value) {
+        Ok(json) => (200, json),
+        Err(e) => (500, error_body(&e.to_string())),
+    }
+}
+
+/// Value of `key` in a query string. Values are cursors and dates, so no
+/// percent-decoding is needed.
+fn query_param<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v)
+}
+
+fn parse_row_date(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").ok()
+}
+
+impl Api {
+    pub fn new(settings: ApiSettings, projects: ProjectSource) -> Self {
+        Self {
+            settings,
+            projects,
+            cache: ScheduleCache::default(),
+            feed: Mutex::new(ChangeFeed::default()),
+        }
+    }
+
+    /// Schedules every stored project as of `now`; rows are `None` for
+    /// projects that fail to schedule.
+    fn schedules(&self, now: NaiveDateTime) -> Result<Vec<ProjectRows>, String> {
+        Ok((self.projects)()?
+            .into_iter()
+            .map(|project| {
+                let rows = self.cache.schedule(&project, now).ok();
+                (project, rows.map(|r| r.scheduled_tasks))
+            })
+            .collect())
+    }
+
+    fn changes(&self, query: &str, now: NaiveDateTime) -> (u16, String) {
+        let since = match query_param(query, "since").map(str::parse::<u64>) {
+            None => 0,
+            Some(Ok(since)) => since,
+            Some(Err(_)) => return (400, error_body("'since' must be a cursor")),
+        };
+        let schedules = match self.schedules(now) {
+            Ok(s) => s,
+            Err(e) => return (500, error_body(&e)),
+        };
+
+        let mut feed = self.feed.lock().unwrap();
+        feed.record(
+            schedules
+                .into_iter()
+                .map(|(project, rows)| (project.id, rows))
+                .collect(),
+        );
+        json_response(&feed.since(since))
+    }
+
+    fn agenda(&self, query: &str, now: NaiveDateTime) -> (u16, String) {
+        let date = match query_param(query, "date") {
+            None => now.date(),
+            Some(d) => match NaiveDate::parse_from_str(d, "%Y-%m-%d") {
+                Ok(date) => date,
+                Err(_) => return (400, error_body("'date' must be YYYY-MM-DD")),
+            },
+        };
+        let day_start = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+        let day_end = day_start + Duration::days(1);
+
+        let schedules = match self.schedules(now) {
+            Ok(s) => s,
+            Err(e) => return (500, error_body(&e)),
+        };
+        let mut items: Vec<AgendaItem> = schedules
+            .into_iter()
+            .flat_map(|(project, rows)| {
+                rows.into_iter()
+                    .flatten()
+                    .filter(|t| !t.completed)
+                    .filter(|t| {
+                        let start = parse_row_date(&t.start_date);
+                        let end = parse_row_date(&t.end_date);
+                        // Milestones have no length; count one finishing today
+                        start.is_some_and(|s| s < day_end)
+                            && end.is_some_and(|e| {
+                                e > day_start || (e == day_start && t.is_milestone)
+                            })
+                    })
+                    .map(|task| AgendaItem {
+                        project_id: project.id.clone(),
+                        project_name: project.name.clone(),
+                        task,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        items.sort_by(|a, b| {
+            (&a.task.start_date, &a.project_id, &a.task.id).cmp(&(
+                &b.task.start_date,
+                &b.project_id,
+                &b.task.id,
+            ))
+        });
+
+        json_response(&AgendaResponse {
+            date: date.format("%Y-%m-%d").to_string(),
+            items,
+        })
+    }
+
+    /// Handles one request, returning the status code and JSON body.
+    fn route(
+        &self,
+        method: &str,
+        url: &str,
+        authorization: Option<&str>,
+        body: &str,
+        now: NaiveDateTime,
+    ) -> (u16, String) {
+        let given = authorization.and_then(|h| h.strip_prefix("Bearer "));
+        if !given.is_some_and(|t| token_matches(&self.settings.token, t)) {
+            return (401, error_body("Missing or invalid bearer token"));
+        }
+
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        match (method, path) {
+            ("POST", "/schedule") => {
+                let request: ScheduleRequest = match serde_json::from_str(body) {
+                    Ok(r) => r,
+                    Err(e) => return (400, error_body(&e.to_string())),
+                };
+                match calculate_backwards_schedule(request) {
+                    Ok(result) => json_response(&result),
+                    Err(e) => (422, error_body(&e.to_string())),
+                }
+            }
+            ("GET", "/changes") => self.changes(query, now),
+            ("GET", "/agenda") => self.agenda(query, now),
+            ("GET", "/capabilities") => json_response(&capabilities()),
+            (_, "/schedule") => (405, error_body("Use POST")),
+            (_, "/changes" | "/agenda" | "/capabilities") => (405, error_body("Use GET")),
+            _ => (404, error_body("Not found")),
+        }
+    }
+}
+
+/// Starts the API server on a background thread. Refuses to start without a
+/// token.
+pub fn start(settings: ApiSettings, projects: ProjectSource) -> Result<(), String> {
+    if settings.token.trim().is_empty() {
+        return Err("Not starting API: api.token is empty".to_string());
+    }
+    let server = tiny_http::Server::http(("127.0.0.1", settings.port))
+        .map_err(|e| format!("Could not start API on port {}: {}", settings.port, e))?;
+    let api = Api::new(settings, projects);
+
+    std::thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let authorization = request
+                .headers()
+                .iter()
+                .find(|h| h.field.equiv("Authorization"))
+                .map(|h| h.value.as_str().to_string());
+            let mut body = String::new();
+            let (status, json) = match request.as_reader().read_to_string(&mut body) {
+                Ok(_) => api.route(
+                    request.method().as_str(),
+                    request.url(),
+                    authorization.as_deref(),
+                    &body,
+                    chrono::Local::now().naive_local(),
+                ),
+                Err(e) => (400, error_body(&e.to_string())),
+            };
+
+            let header = tiny_http::Header::from_bytes("Content-Type", "application/json")
+                .expect("static header is valid");
+            let response = tiny_http::Response::from_string(json)
+                .with_status_code(status)
+                .with_header(header);
+            let _ = request.respond(response);
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{Task, TaskDuration};
+    use std::sync::Arc;
+
+    fn settings() -> ApiSettings {
+        ApiSettings {
+            port: 0,
+            token: "secret".into(),
+        }
+    }
+
+    fn now() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2026-01-01T00:00:00", "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    fn project(duration_days: i64) -> Project {
+        Project {
+            id: "p".into(),
+            name: "Launch".into(),
+            tasks: vec![
+                Task {
+                    id: "a".into(),
+                    name: "Draft".into(),
+                    duration: TaskDuration::days(duration_days),
+                    ..Default::default()
+                },
+                Task {
+                    id: "b".into(),
+                    name: "Ship".into(),
+                    duration: TaskDuration::days(1),
+                    dependencies: vec!["a".into()],
+                    ..Default::default()
+                },
+            ],
+            anchors: [("b".into(), "2026-01-10T00:00:00".into())].into(),
+            ..Default::default()
+        }
+    }
+
+    /// An API over a project list the test can edit between requests.
+    fn api(projects: Vec<Project>) -> (Api, Arc<Mutex<Vec<Project>>>) {
+        let store = Arc::new(Mutex::new(projects));
+        let source = store.clone();
+        let api = Api::new(
+            settings(),
+            Box::new(move || Ok(source.lock().unwrap().clone())),
+        );
+        (api, store)
+    }
+
+    fn get(api: &Api, url: &str) -> serde_json::Value {
+        let (status, body) = api.route("GET", url, Some("Bearer secret"), "", now());
+        assert_eq!(status, 200, "{}", body);
+        serde_json::from_str(&body).unwrap()
+    }
+
+    const BODY: &str = r#"{
+        "tasks": [{"id": "a", "name": "A", "duration_days": 2, "duration_minutes": null, "dependencies": []}],
+        "anchors": {"a": "2026-01-10T00:00:00"}
+    }"#;
+
+    #[test]
+    fn test_schedule_requires_token() {
+        let (api, _) = api(Vec::new());
+        let (status, _) = api.route("POST", "/schedule", None, BODY, now());
+        assert_eq!(status, 401);
+
+        let (status, _) = api.route("POST", "/schedule", Some("Bearer wrong!"), BODY, now());
+        assert_eq!(status, 401);
+    }
+
+    #[test]
+    fn test_empty_token_is_refused() {
+        let api = Api::new(
+            ApiSettings {
+                port: 0,
+                token: String::new(),
+            },
+            Box::new(|| Ok(Vec::new())),
+        );
+        let (status, _) = api.route("GET", "/capabilities", Some("Bearer "), "", now());
+        assert_eq!(status, 401);
+
+        let blank = ApiSettings {
+            port: 0,
+            token: "  ".into(),
+        };
+        assert!(start(blank, Box::new(|| Ok(Vec::new()))).is_err());
+    }
+
+    #[test]
+    fn test_schedule_returns_result() {
+        let (api, _) = api(Vec::new());
+        let (status, body) = api.route("POST", "/schedule", Some("Bearer secret"), BODY, now());
+
+        assert_eq!(status, 200);
+        let result: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            result["scheduled_tasks"][0]["start_date"],
+            "2026-01-08T00:00:00"
+        );
+    }
+
+    #[test]
+    fn test_changes_are_cursored() {
+        let (api, store) = api(vec![project(2)]);
+
+        let first = get(&api, "/changes");
+        assert_eq!(first["changes"].as_array().unwrap().len(), 2);
+        assert_eq!(first["cursor"], 2);
+
+        // Nothing moved since the last cursor
+        let idle = get(&api, "/changes?since=2");
+        assert!(idle["changes"].as_array().unwrap().is_empty());
+
+        store.lock().unwrap()[0].tasks[0].duration = TaskDuration::days(3);
+        let edited = get(&api, "/changes?since=2");
+        let changes = edited["changes"].as_array().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0]["task_id"], "a");
+        assert_eq!(changes[0]["kind"], "updated");
+        assert_eq!(changes[0]["task"]["start_date"], "2026-01-06T00:00:00");
+        assert_eq!(edited["reset"], false);
+
+        store.lock().unwrap().clear();
+        let deleted = get(&api, "/changes?since=3");
+        let kinds: Vec<&str> = deleted["changes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["kind"].as_str().unwrap())
+            .collect();
+        assert_eq!(kinds, vec!["removed", "removed"]);
+
+        // A cursor from the future (e.g. before a restart) forces a resync
+        assert_eq!(get(&api, "/changes?since=99")["reset"], true);
+    }
+
+    #[test]
+    fn test_agenda_lists_tasks_in_progress_on_date() {
+        let (api, _) = api(vec![project(2)]);
+
+        let agenda = get(&api, "/agenda?date=2026-01-08");
+        let items = agenda["items"].as_array().unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["task"]["id"], "a");
+        assert_eq!(items[0]["project_name"], "Launch");
+
+        let (status, _) = api.route(
+            "GET",
+            "/agenda?date=tomorrow",
+            Some("Bearer secret"),
+            "",
+            now(),
+        );
+        assert_eq!(status, 400);
+    }
+}
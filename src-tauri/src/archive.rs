@@ -0,0 +1,243 @@
+//! Completed-task archive.
+//!
+//! Finished work can be moved out of a project into a sidecar file
+//! (`projects/archive/<id>.json`) so it no longer slows scheduling or clutters
+//! outputs, while staying available for history and analytics.
+
+use crate::project::{get_projects_dir, load_project, save_project, Project};
+use crate::scheduler::Task;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+/// A task moved out of its project.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchivedTask {
+    pub task: Task,
+    /// The task's anchor date, if it had one.
+    #[serde(default)]
+    pub anchor: Option<String>,
+    pub archived_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProjectArchive {
+    pub project_id: String,
+    pub tasks: Vec<ArchivedTask>,
+}
+
+pub(crate) fn archive_path(app: &AppHandle, project_id: &str) -> Result<PathBuf, String> {
+    let dir = get_projects_dir(app)?.join("archive");
+    if !dir.exists() {
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    }
+    Ok(dir.join(format!("{}.json", project_id)))
+}
+
+/// IDs of completed tasks whose whole upstream is completed too, i.e. subtrees
+/// that can leave the project without cutting a dependency of unfinished work.
+fn archivable_ids(tasks: &[Task]) -> HashSet<String> {
+    let by_id: HashMap<&str, &Task> = tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+    let mut memo: HashMap<&str, bool> = HashMap::new();
+
+    fn check<'a>(
+        id: &'a str,
+        by_id: &HashMap<&'a str, &'a Task>,
+        memo: &mut HashMap<&'a str, bool>,
+    ) -> bool {
+        if let Some(&known) = memo.get(id) {
+            return known;
+        }
+        // Provisionally false so a dependency cycle can't recurse forever
+        memo.insert(id, false);
+        let eligible = by_id.get(id).is_some_and(|task| {
+            task.completed
+                && task
+                    .dependencies
+                    .iter()
+                    .all(|dep| check(dep.as_str(), by_id, memo))
+        });
+        memo.insert(id, eligible);
+        eligible
+    }
+
+    tasks
+        .iter()
+        .filter(|t| check(&t.id, &by_id, &mut memo))
+        .map(|t| t.id.clone())
+        .collect()
+}
+
+/// Removes archivable tasks from `project` and returns them.
+///
+/// With `root`, only that task and its upstream are taken, and every one of
+/// them must be completed. References to archived tasks are dropped from the
+/// remaining tasks, exclusion groups and spacing rules.
+pub fn take_completed(
+    project: &mut Project,
+    root: Option<&str>,
+    archived_at: &str,
+) -> Result<Vec<ArchivedTask>, String> {
+    let mut ids = archivable_ids(&project.tasks);
+
+    if let Some(root) = root {
+        if !ids.contains(root) {
+            return Err(format!(
+                "Task {} or work it depends on is not completed yet",
+                root
+            ));
+        }
+        let by_id: HashMap<&str, &Task> =
+            project.tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+        let mut upstream = HashSet::new();
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            if upstream.insert(id.to_string()) {
+                if let Some(task) = by_id.get(id) {
+                    stack.extend(task.dependencies.iter().map(String::as_str));
+                }
+            }
+        }
+        ids.retain(|id| upstream.contains(id));
+    }
+
+    let (archived, kept): (Vec<Task>, Vec<Task>) = std::mem::take(&mut project.tasks)
+        .into_iter()
+        .partition(|t| ids.contains(&t.id));
+    project.tasks = kept;
+
+    for task in &mut project.tasks {
+        task.dependencies.retain(|dep| !ids.contains(dep));
+    }
+    for group in &mut project.exclusions {
+        group.task_ids.retain(|id| !ids.contains(id));
+    }
+    project.exclusions.retain(|group| group.task_ids.len() > 1);
+    project
+        .anchor_spacing
+        .retain(|rule| !ids.contains(&rule.before) && !ids.contains(&rule.after));
+    project.anchor_windows.retain(|id, _| !ids.contains(id));
+
+    Ok(archived
+        .into_iter()
+        .map(|task| ArchivedTask {
+            anchor: project.anchors.remove(&task.id),
+            task,
+            archived_at: archived_at.to_string(),
+        })
+        .collect())
+}
+
+/// Loads a project's archive; empty if nothing has been archived yet.
+#[tauri::command]
+pub fn load_archive(app: AppHandle, project_id: String) -> Result<ProjectArchive, String> {
+    let path = archive_path(&app, &project_id)?;
+    if !path.exists() {
+        return Ok(ProjectArchive {
+            project_id,
+            tasks: Vec::new(),
+        });
+    }
+    let json = crate::encryption::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&json).map_err(|e| e.to_string())
+}
+
+/// Moves completed subtrees (or just the one ending at `task_id`) into the
+/// project's archive. Returns how many tasks were archived.
+#[tauri::command]
+pub fn archive_completed_tasks(
+    app: AppHandle,
+    project_id: String,
+    task_id: Option<String>,
+) -> Result<usize, String> {
+    let mut project = load_project(app.clone(), project_id.clone())?;
+    let now = chrono::Local::now().to_rfc3339();
+    let taken = take_completed(&mut project, task_id.as_deref(), &now)?;
+    if taken.is_empty() {
+        return Ok(0);
+    }
+    let count = taken.len();
+
+    // Write the archive first so a failure can't lose tasks
+    let mut archive = load_archive(app.clone(), project_id.clone())?;
+    archive.tasks.extend(taken);
+    let json = serde_json::to_string_pretty(&archive).map_err(|e| e.to_string())?;
+    crate::encryption::write(archive_path(&app, &project_id)?, json).map_err(|e| e.to_string())?;
+
+    save_project(app, project)?;
+    Ok(count)
+}
+
+/// The project with its archived tasks restored, for history and analytics.
+pub fn with_history(app: &AppHandle, project: &Project) -> Result<Project, String> {
+    let archive = load_archive(app.clone(), project.id.clone())?;
+    let mut full = project.clone();
+    full.tasks.extend(archive.tasks.into_iter().map(|a| a.task));
+    Ok(full)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::TaskDuration;
+
+    fn task(id: &str, completed: bool, deps: &[&str]) -> Task {
+        Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(1),
+            completed,
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn project() -> Project {
+        // a (done) -> b (done) -> c (open); x (done) -> y (open) -> z (done)
+        Project {
+            id: "p".into(),
+            tasks: vec![
+                task("a", true, &[]),
+                task("b", true, &["a"]),
+                task("c", false, &["b"]),
+                task("x", true, &[]),
+                task("y", false, &["x"]),
+                task("z", true, &["y"]),
+            ],
+            anchors: [
+                ("c".into(), "2026-01-10T00:00:00".into()),
+                ("z".into(), "2026-01-10T00:00:00".into()),
+            ]
+            .into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_only_fully_completed_subtrees_are_archived() {
+        let mut project = project();
+
+        let taken = take_completed(&mut project, None, "now").unwrap();
+        let mut ids: Vec<&str> = taken.iter().map(|a| a.task.id.as_str()).collect();
+        ids.sort();
+
+        // z is done but depends on unfinished y
+        assert_eq!(ids, vec!["a", "b", "x"]);
+        let c = project.tasks.iter().find(|t| t.id == "c").unwrap();
+        assert!(c.dependencies.is_empty());
+        assert!(crate::scheduler::calculate_backwards_schedule(project.schedule_request()).is_ok());
+    }
+
+    #[test]
+    fn test_root_limits_archive_to_its_upstream() {
+        let mut project = project();
+
+        let taken = take_completed(&mut project, Some("x"), "now").unwrap();
+
+        assert_eq!(taken.len(), 1);
+        assert_eq!(project.tasks.len(), 5);
+        assert!(take_completed(&mut project, Some("z"), "now").is_err());
+    }
+}
@@ -0,0 +1,231 @@
+//! Files and links attached to tasks.
+//!
+//! Files are copied into `projects/attachments/<project id>/` so they move
+//! with the projects folder (and its sync) rather than pointing at a path
+//! that may not exist later.
+
+use crate::project::{get_projects_dir, load_project, save_project, Project};
+use crate::scheduler::{Attachment, Task};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+use uuid::Uuid;
+
+pub(crate) fn attachments_dir(projects_dir: &Path) -> PathBuf {
+    projects_dir.join("attachments")
+}
+
+/// Where an attachment's stored `file` lives under `root`. Stored names come
+/// from project files, which may have been imported or synced, so only
+/// `<project_id>/<file name>` is accepted.
+pub(crate) fn stored_path(root: &Path, project_id: &str, file: &str) -> Result<PathBuf, String> {
+    let name = Some(file)
+        .filter(|_| crate::backup::is_safe_id(project_id))
+        .and_then(|file| file.strip_prefix(project_id))
+        .and_then(|rest| rest.strip_prefix('/'))
+        .filter(|name| !name.contains(['/', '\\']))
+        .filter(|name| {
+            let mut components = Path::new(name).components();
+            matches!(components.next(), Some(Component::Normal(_))) && components.next().is_none()
+        })
+        .ok_or_else(|| format!("Not a stored attachment: {}", file))?;
+    Ok(root.join(project_id).join(name))
+}
+
+fn find_task<'a>(project: &'a mut Project, task_id: &str) -> Result<&'a mut Task, String> {
+    project
+        .tasks
+        .iter_mut()
+        .find(|t| t.id == task_id)
+        .ok_or_else(|| format!("Task {} not found", task_id))
+}
+
+/// Builds an attachment for a link, or for `path` copied into `root`'s
+/// folder for `project_id`.
+fn create(
+    root: &Path,
+    project_id: &str,
+    path: Option<&Path>,
+    url: Option<&str>,
+    name: Option<String>,
+) -> Result<Attachment, String> {
+    let id = Uuid::new_v4().to_string();
+    let added_at = chrono::Local::now().to_rfc3339();
+    match (path, url) {
+        (Some(path), None) => {
+            let file_name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .ok_or_else(|| format!("Not a file: {}", path.display()))?;
+            let dir = root.join(project_id);
+            fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            let stored = format!("{}-{}", id, file_name);
+            fs::copy(path, dir.join(&stored)).map_err(|e| e.to_string())?;
+            Ok(Attachment {
+                id,
+                name: name.unwrap_or_else(|| file_name.to_string()),
+                url: None,
+                file: Some(format!("{}/{}", project_id, stored)),
+                added_at,
+            })
+        }
+        (None, Some(url)) => {
+            let url = url.trim();
+            let scheme = url.split_once(':').map(|(scheme, _)| scheme.to_lowercase());
+            if !matches!(scheme.as_deref(), Some("http" | "https" | "mailto")) {
+                return Err(format!("Not a web or mail link: {}", url));
+            }
+            Ok(Attachment {
+                id,
+                name: name.unwrap_or_else(|| url.to_string()),
+                url: Some(url.to_string()),
+                file: None,
+                added_at,
+            })
+        }
+        _ => Err("Attach either a file or a link".to_string()),
+    }
+}
+
+/// Attaches a file (copied into the projects folder) or a link to a task.
+#[tauri::command]
+pub fn add_attachment(
+    app: AppHandle,
+    project_id: String,
+    task_id: String,
+    path: Option<String>,
+    url: Option<String>,
+    name: Option<String>,
+) -> Result<Attachment, String> {
+    let mut project = load_project(app.clone(), project_id.clone())?;
+    let root = attachments_dir(&get_projects_dir(&app)?);
+    let task = find_task(&mut project, &task_id)?;
+    let attachment = create(
+        &root,
+        &project_id,
+        path.as_deref().map(Path::new),
+        url.as_deref(),
+        name,
+    )?;
+    task.attachments.push(attachment.clone());
+    save_project(app, project)?;
+    Ok(attachment)
+}
+
+/// Removes an attachment from a task, deleting its copy of the file.
+#[tauri::command]
+pub fn remove_attachment(
+    app: AppHandle,
+    project_id: String,
+    task_id: String,
+    attachment_id: String,
+) -> Result<(), String> {
+    let mut project = load_project(app.clone(), project_id.clone())?;
+    let task = find_task(&mut project, &task_id)?;
+    let Some(index) = task.attachments.iter().position(|a| a.id == attachment_id) else {
+        return Err(format!("Attachment {} not found", attachment_id));
+    };
+    let path = match &task.attachments[index].file {
+        Some(file) => Some(stored_path(
+            &attachments_dir(&get_projects_dir(&app)?),
+            &project_id,
+            file,
+        )?),
+        None => None,
+    };
+    task.attachments.remove(index);
+    save_project(app, project)?;
+    if let Some(path) = path {
+        if path.exists() {
+            fs::remove_file(path).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Opens an attachment with the system's default app or browser.
+#[tauri::command]
+pub fn open_attachment(
+    app: AppHandle,
+    project_id: String,
+    task_id: String,
+    attachment_id: String,
+) -> Result<(), String> {
+    let project = load_project(app.clone(), project_id.clone())?;
+    let attachment = project
+        .tasks
+        .iter()
+        .filter(|t| t.id == task_id)
+        .flat_map(|t| &t.attachments)
+        .find(|a| a.id == attachment_id)
+        .ok_or_else(|| format!("Attachment {} not found", attachment_id))?;
+    match (&attachment.file, &attachment.url) {
+        (Some(file), _) => {
+            let root = attachments_dir(&get_projects_dir(&app)?);
+            let path = stored_path(&root, &project_id, file)?;
+            if !path.exists() {
+                return Err(format!("{} is missing", attachment.name));
+            }
+            app.opener()
+                .open_path(path.to_string_lossy(), None::<&str>)
+                .map_err(|e| e.to_string())
+        }
+        (None, Some(url)) => app
+            .opener()
+            .open_url(url, None::<&str>)
+            .map_err(|e| e.to_string()),
+        (None, None) => Err(format!("{} has nothing to open", attachment.name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_files_are_copied_and_links_checked() {
+        let root = std::env::temp_dir().join(format!("anchor-attach-{}", Uuid::new_v4()));
+        fs::create_dir_all(&root).unwrap();
+        let brief = root.join("brief.txt");
+        fs::write(&brief, "Client brief").unwrap();
+
+        let file = create(&root, "p", Some(&brief), None, None).unwrap();
+        assert_eq!(file.name, "brief.txt");
+        let stored = root.join(file.file.as_deref().unwrap());
+        assert!(stored.starts_with(root.join("p")));
+        assert_eq!(fs::read_to_string(stored).unwrap(), "Client brief");
+
+        let link = create(
+            &root,
+            "p",
+            None,
+            Some(" https://example.com/ticket/42 "),
+            Some("Ticket".into()),
+        )
+        .unwrap();
+        assert_eq!(link.url.as_deref(), Some("https://example.com/ticket/42"));
+        assert_eq!(link.name, "Ticket");
+
+        assert!(create(&root, "p", None, Some("javascript:alert(1)"), None).is_err());
+        assert!(create(&root, "p", Some(&brief), Some("https://example.com"), None).is_err());
+        assert!(create(&root, "p", None, None, None).is_err());
+
+        let file = file.file.as_deref().unwrap();
+        assert!(stored_path(&root, "p", file)
+            .unwrap()
+            .starts_with(root.join("p")));
+        for hostile in [
+            "p/../../.ssh/id_rsa",
+            "p/..",
+            "p/sub/file",
+            "p/..\\x",
+            "p/",
+            "q/file",
+            "/etc/passwd",
+        ] {
+            assert!(stored_path(&root, "p", hostile).is_err(), "{}", hostile);
+        }
+        fs::remove_dir_all(root).unwrap();
+    }
+}
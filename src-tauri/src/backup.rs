@@ -0,0 +1,619 @@
+//! Whole-data backups.
+//!
+//! Packs every project, its completed-task archive and the app config into
+//! one zip file, and restores such a file either over the current data or
+//! merged into it. Entries mirror the data directory layout:
+//! `config.json`, `projects/<id>.json` and `projects/archive/<id>.json`, with
+//! `projects/` standing for the projects folder wherever it is configured.
+//! Encrypted files are packed as they are, along with `projects/.encryption`,
+//! so a backup restores on another machine given its passphrase.
+
+use crate::cache::ScheduleCache;
+use crate::encryption::{EncryptionError, SETTINGS_FILE};
+use crate::history::History;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How a restore treats projects that already exist.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreMode {
+    /// Keeps current data; a project in both takes the later-modified copy.
+    #[default]
+    Merge,
+    /// Removes every current project and the config before restoring.
+    Replace,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct BackupSummary {
+    pub project_count: usize,
+    pub includes_config: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct RestoreSummary {
+    /// Projects that weren't here before.
+    pub added: Vec<String>,
+    /// Existing projects overwritten from the backup.
+    pub replaced: Vec<String>,
+    /// Existing projects kept because they were modified more recently.
+    pub kept: Vec<String>,
+    pub config_restored: bool,
+    /// The backup's encryption settings were restored, so its passphrase now
+    /// unlocks these projects.
+    pub encryption_restored: bool,
+}
+
+// Minimal zip: deflated entries, no zip64, no encryption. Enough for a few
+// megabytes of JSON and readable by any unzip tool.
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_DIRECTORY: u32 = 0x0605_4b50;
+
+fn put16(buf: &mut Vec<u8>, v: u16) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn put32(buf: &mut Vec<u8>, v: u32) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn get16(bytes: &[u8], at: usize) -> Result<u16, String> {
+    bytes
+        .get(at..at + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| "Backup is truncated".to_string())
+}
+
+fn get32(bytes: &[u8], at: usize) -> Result<u32, String> {
+    bytes
+        .get(at..at + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| "Backup is truncated".to_string())
+}
+
+pub(crate) fn write_zip(entries: &BTreeMap<String, Vec<u8>>) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let mut directory = Vec::new();
+    for (name, data) in entries {
+        let mut crc = Crc::new();
+        crc.update(data);
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).map_err(|e| e.to_string())?;
+        let compressed = encoder.finish().map_err(|e| e.to_string())?;
+        let offset = out.len() as u32;
+
+        // version, UTF-8 flag, deflate, DOS time and date (1980-01-01)
+        let common = |buf: &mut Vec<u8>| {
+            put16(buf, 20);
+            put16(buf, 0x0800);
+            put16(buf, 8);
+            put16(buf, 0);
+            put16(buf, 0x21);
+            put32(buf, crc.sum());
+            put32(buf, compressed.len() as u32);
+            put32(buf, data.len() as u32);
+            put16(buf, name.len() as u16);
+            put16(buf, 0);
+        };
+        put32(&mut out, LOCAL_HEADER);
+        common(&mut out);
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(&compressed);
+
+        put32(&mut directory, CENTRAL_HEADER);
+        put16(&mut directory, 20);
+        common(&mut directory);
+        // comment, disk, attributes
+        directory.extend_from_slice(&[0; 10]);
+        put32(&mut directory, offset);
+        directory.extend_from_slice(name.as_bytes());
+    }
+
+    let directory_offset = out.len() as u32;
+    out.extend_from_slice(&directory);
+    put32(&mut out, END_OF_DIRECTORY);
+    put32(&mut out, 0);
+    put16(&mut out, entries.len() as u16);
+    put16(&mut out, entries.len() as u16);
+    put32(&mut out, directory.len() as u32);
+    put32(&mut out, directory_offset);
+    put16(&mut out, 0);
+    Ok(out)
+}
+
+pub(crate) fn read_zip(bytes: &[u8]) -> Result<BTreeMap<String, Vec<u8>>, String> {
+    let end = (0..bytes.len().saturating_sub(21))
+        .rev()
+        .find(|&i| get32(bytes, i) == Ok(END_OF_DIRECTORY))
+        .ok_or_else(|| "Not a zip file".to_string())?;
+    let count = get16(bytes, end + 10)? as usize;
+    let mut at = get32(bytes, end + 16)? as usize;
+
+    let mut entries = BTreeMap::new();
+    for _ in 0..count {
+        if get32(bytes, at)? != CENTRAL_HEADER {
+            return Err("Backup directory is damaged".to_string());
+        }
+        let method = get16(bytes, at + 10)?;
+        let crc = get32(bytes, at + 16)?;
+        let size = get32(bytes, at + 20)? as usize;
+        let name_len = get16(bytes, at + 28)? as usize;
+        let skip = name_len + get16(bytes, at + 30)? as usize + get16(bytes, at + 32)? as usize;
+        let local = get32(bytes, at + 42)? as usize;
+        let name = bytes
+            .get(at + 46..at + 46 + name_len)
+            .map(|n| String::from_utf8_lossy(n).into_owned())
+            .ok_or_else(|| "Backup is truncated".to_string())?;
+        at += 46 + skip;
+
+        let start =
+            local + 30 + get16(bytes, local + 26)? as usize + get16(bytes, local + 28)? as usize;
+        let raw = bytes
+            .get(start..start + size)
+            .ok_or_else(|| "Backup is truncated".to_string())?;
+        let data = match method {
+            0 => raw.to_vec(),
+            8 => {
+                let mut data = Vec::new();
+                DeflateDecoder::new(raw)
+                    .read_to_end(&mut data)
+                    .map_err(|e| format!("{}: {}", name, e))?;
+                data
+            }
+            _ => return Err(format!("{}: unsupported compression", name)),
+        };
+        let mut check = Crc::new();
+        check.update(&data);
+        if check.sum() != crc {
+            return Err(format!("{} is corrupt", name));
+        }
+        if !name.ends_with('/') {
+            entries.insert(name, data);
+        }
+    }
+    Ok(entries)
+}
+
+fn project_files(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(files) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    files
+        .flatten()
+        .filter_map(|file| {
+            let name = file.file_name().into_string().ok()?;
+            let id = name.strip_suffix(".json")?.to_string();
+            Some((id, file.path()))
+        })
+        .collect()
+}
+
+/// Whether `id` can name a project file without leaving the projects folder.
+pub(crate) fn is_safe_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Zips the config in `data_dir` and the projects in `projects`.
+fn pack(data_dir: &Path, projects: &Path) -> Result<(Vec<u8>, BackupSummary), String> {
+    let mut entries = BTreeMap::new();
+    let mut summary = BackupSummary::default();
+    let config = data_dir.join("config.json");
+    if let Ok(data) = fs::read(&config) {
+        entries.insert("config.json".to_string(), data);
+        summary.includes_config = true;
+    }
+    for (id, path) in project_files(projects) {
+        entries.insert(
+            format!("projects/{}.json", id),
+            fs::read(path).map_err(|e| e.to_string())?,
+        );
+        summary.project_count += 1;
+    }
+    for (id, path) in project_files(&projects.join("archive")) {
+        entries.insert(
+            format!("projects/archive/{}.json", id),
+            fs::read(path).map_err(|e| e.to_string())?,
+        );
+    }
+    if let Ok(data) = fs::read(projects.join(SETTINGS_FILE)) {
+        entries.insert(format!("projects/{}", SETTINGS_FILE), data);
+    }
+    Ok((write_zip(&entries)?, summary))
+}
+
+/// Restores a backup: the config into `data_dir`, projects into
+/// `projects_dir`. Encrypted projects need the backup's `passphrase` unless
+/// `projects_dir` uses the same encryption settings and is unlocked.
+fn unpack(
+    data_dir: &Path,
+    projects_dir: &Path,
+    bytes: &[u8],
+    mode: RestoreMode,
+    passphrase: Option<&str>,
+) -> Result<RestoreSummary, String> {
+    let entries = read_zip(bytes)?;
+    let archive_dir = projects_dir.join("archive");
+
+    let settings = entries
+        .get(&format!("projects/{}", SETTINGS_FILE))
+        .map(|data| String::from_utf8_lossy(data).into_owned());
+    let key = match (&settings, passphrase) {
+        (Some(settings), Some(passphrase)) => {
+            Some(crate::encryption::key_for(settings, passphrase).map_err(|e| e.to_string())?)
+        }
+        _ => None,
+    };
+    let open = |name: &str, data: &[u8]| {
+        let text = String::from_utf8_lossy(data).into_owned();
+        let opened = match &key {
+            Some(key) => crate::encryption::open_with_key(key, text),
+            None => crate::encryption::open(text),
+        };
+        opened.map_err(|e| match e {
+            EncryptionError::Locked | EncryptionError::Damaged if settings.is_some() => {
+                "This backup is encrypted; enter its passphrase to restore it".to_string()
+            }
+            e => format!("{}: {}", name, e),
+        })
+    };
+
+    // Files sealed with the backup's key are kept as they are when this
+    // folder has no encryption yet (the settings come along) or the same
+    // settings; otherwise they're sealed again with this session's key.
+    let local_settings = fs::read_to_string(projects_dir.join(SETTINGS_FILE)).ok();
+    let restore_settings = key.is_some() && local_settings.is_none();
+    let reseal = key.is_some() && !restore_settings && local_settings != settings;
+    if reseal && crate::encryption::is_locked() {
+        return Err(EncryptionError::Locked.to_string());
+    }
+    let contents = |name: &str, data: &[u8]| -> Result<String, String> {
+        if reseal {
+            crate::encryption::seal(open(name, data)?).map_err(|e| e.to_string())
+        } else {
+            Ok(String::from_utf8_lossy(data).into_owned())
+        }
+    };
+
+    // Parse everything before touching anything
+    let mut incoming = Vec::new();
+    for (name, data) in &entries {
+        let Some(id) = name
+            .strip_prefix("projects/")
+            .and_then(|rest| rest.strip_suffix(".json"))
+        else {
+            continue;
+        };
+        // Archives are picked up with their project
+        if id.starts_with("archive/") {
+            continue;
+        }
+        if !is_safe_id(id) {
+            return Err(format!("{} is not a valid project entry", name));
+        }
+        let project = crate::migrate::parse_project(&open(name, data)?)
+            .map_err(|e| format!("{}: {}", name, e))?;
+        if project.id != id {
+            return Err(format!("{} holds project {}", name, project.id));
+        }
+        let archive_name = format!("projects/archive/{}.json", id);
+        let archive = entries
+            .get(&archive_name)
+            .map(|archive| contents(&archive_name, archive))
+            .transpose()?;
+        incoming.push((project, contents(name, data)?, archive));
+    }
+    let config = entries.get("config.json");
+    if incoming.is_empty() && config.is_none() {
+        return Err("The file holds no Anchor data".to_string());
+    }
+
+    let mut summary = RestoreSummary::default();
+    fs::create_dir_all(&archive_dir).map_err(|e| e.to_string())?;
+    if let (true, Some(settings)) = (restore_settings, &settings) {
+        fs::write(projects_dir.join(SETTINGS_FILE), settings).map_err(|e| e.to_string())?;
+        summary.encryption_restored = true;
+    }
+    if mode == RestoreMode::Replace {
+        for (_, path) in project_files(projects_dir)
+            .into_iter()
+            .chain(project_files(&archive_dir))
+        {
+            fs::remove_file(&path).map_err(|e| e.to_string())?;
+            crate::repair::remove_sidecars(&path);
+        }
+    }
+
+    for (project, data, archive) in incoming {
+        let path = projects_dir.join(format!("{}.json", project.id));
+        let current = crate::encryption::read_to_string(&path)
+            .ok()
+            .and_then(|json| crate::migrate::parse_project(&json).ok());
+        match current {
+            Some(current) if current.last_modified >= project.last_modified => {
+                summary.kept.push(project.id);
+                continue;
+            }
+            Some(_) => summary.replaced.push(project.id.clone()),
+            None => summary.added.push(project.id.clone()),
+        }
+        crate::repair::write_atomic(&path, &data)?;
+        let archive_path = archive_dir.join(format!("{}.json", project.id));
+        match archive {
+            Some(archive) => fs::write(archive_path, archive).map_err(|e| e.to_string())?,
+            None if archive_path.exists() => {
+                fs::remove_file(archive_path).map_err(|e| e.to_string())?
+            }
+            None => {}
+        }
+    }
+
+    let config_path = data_dir.join("config.json");
+    if let Some(config) = config {
+        if mode == RestoreMode::Replace || !config_path.exists() {
+            fs::write(config_path, config).map_err(|e| e.to_string())?;
+            summary.config_restored = true;
+        }
+    }
+    Ok(summary)
+}
+
+fn data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().app_data_dir().map_err(|e| e.to_string())
+}
+
+/// Writes every project and the config to a zip file at `path`.
+#[tauri::command]
+pub fn export_backup(app: AppHandle, path: String) -> Result<BackupSummary, String> {
+    let projects = crate::project::get_projects_dir(&app)?;
+    let (bytes, summary) = pack(&data_dir(&app)?, &projects)?;
+    fs::write(path, bytes).map_err(|e| e.to_string())?;
+    Ok(summary)
+}
+
+/// Restores a backup made by `export_backup`. A replace first saves the
+/// current data to `backups/` in the data directory. Encrypted backups need
+/// the `passphrase` they were made with, unless this machine uses the same
+/// encryption settings.
+#[tauri::command]
+pub fn import_backup(
+    app: AppHandle,
+    path: String,
+    mode: Option<RestoreMode>,
+    passphrase: Option<String>,
+) -> Result<RestoreSummary, String> {
+    let bytes = fs::read(&path).map_err(|e| e.to_string())?;
+    let dir = data_dir(&app)?;
+    let projects = crate::project::get_projects_dir(&app)?;
+    let mode = mode.unwrap_or_default();
+    if mode == RestoreMode::Replace {
+        let (current, _) = pack(&dir, &projects)?;
+        let backups = dir.join("backups");
+        fs::create_dir_all(&backups).map_err(|e| e.to_string())?;
+        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        fs::write(
+            backups.join(format!("before-restore-{}.zip", stamp)),
+            current,
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    let summary = unpack(&dir, &projects, &bytes, mode, passphrase.as_deref())?;
+    if let (true, Some(passphrase)) = (summary.encryption_restored, passphrase) {
+        crate::encryption::unlock_projects(app.clone(), passphrase)?;
+        // Projects that were here before the restore are still plaintext
+        crate::encryption::encrypt_project_files(app.clone())?;
+    }
+    for id in summary.added.iter().chain(&summary.replaced) {
+        app.state::<ScheduleCache>().invalidate(id);
+        app.state::<History>().forget(id);
+        crate::journal::discard(&app, id)?;
+    }
+    crate::events::project_changed(&app, None, crate::events::ChangeKind::Restored, true);
+    if summary.config_restored {
+        if let Ok(config) = crate::config::load_config(app.clone()) {
+            let _ = app.emit("config-changed", &config);
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::Project;
+
+    fn save(dir: &Path, id: &str, name: &str, modified: &str) {
+        let project = Project {
+            id: id.into(),
+            name: name.into(),
+            last_modified: modified.into(),
+            ..Default::default()
+        };
+        fs::create_dir_all(dir.join("projects")).unwrap();
+        fs::write(
+            dir.join("projects").join(format!("{}.json", id)),
+            serde_json::to_string(&project).unwrap(),
+        )
+        .unwrap();
+    }
+
+    fn name(dir: &Path, id: &str) -> Option<String> {
+        let json = fs::read_to_string(dir.join("projects").join(format!("{}.json", id))).ok()?;
+        serde_json::from_str::<Project>(&json).ok().map(|p| p.name)
+    }
+
+    #[test]
+    fn test_restore_merges_or_replaces() {
+        let root = std::env::temp_dir().join(format!("anchor-backup-{}", uuid::Uuid::new_v4()));
+        let (old, new) = (root.join("old"), root.join("new"));
+        save(&old, "a", "A from backup", "2026-01-02");
+        save(&old, "b", "B from backup", "2026-01-01");
+        fs::write(old.join("config.json"), "{\"theme\": \"dark\"}").unwrap();
+        let (bytes, summary) = pack(&old, &old.join("projects")).unwrap();
+        assert_eq!(summary.project_count, 2);
+        assert!(summary.includes_config);
+
+        // a is older here, b newer, c only here
+        save(&new, "a", "A here", "2026-01-01");
+        save(&new, "b", "B here", "2026-01-05");
+        save(&new, "c", "C here", "2026-01-05");
+        fs::write(new.join("config.json"), "{\"theme\": \"light\"}").unwrap();
+
+        let merged = unpack(
+            &new,
+            &new.join("projects"),
+            &bytes,
+            RestoreMode::Merge,
+            None,
+        )
+        .unwrap();
+        assert_eq!(merged.replaced, vec!["a"]);
+        assert_eq!(merged.kept, vec!["b"]);
+        assert!(!merged.config_restored);
+        assert_eq!(name(&new, "a").as_deref(), Some("A from backup"));
+        assert_eq!(name(&new, "b").as_deref(), Some("B here"));
+        assert!(name(&new, "c").is_some());
+
+        let replaced = unpack(
+            &new,
+            &new.join("projects"),
+            &bytes,
+            RestoreMode::Replace,
+            None,
+        )
+        .unwrap();
+        assert_eq!(replaced.added, vec!["a", "b"]);
+        assert!(replaced.config_restored);
+        assert_eq!(name(&new, "b").as_deref(), Some("B from backup"));
+        assert!(name(&new, "c").is_none());
+
+        assert!(unpack(
+            &new,
+            &new.join("projects"),
+            b"not a zip",
+            RestoreMode::Merge,
+            None,
+        )
+        .is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_round_trip_uses_the_configured_projects_folder() {
+        let root = std::env::temp_dir().join(format!("anchor-backup-{}", uuid::Uuid::new_v4()));
+        // Projects live in a sync folder outside the data directory
+        let (data, synced) = (root.join("data"), root.join("sync"));
+        save(&synced, "p", "Synced", "2026-01-01");
+        let (bytes, summary) = pack(&data, &synced.join("projects")).unwrap();
+        assert_eq!(summary.project_count, 1);
+
+        let (data, synced) = (root.join("data2"), root.join("sync2"));
+        let restored = unpack(
+            &data,
+            &synced.join("projects"),
+            &bytes,
+            RestoreMode::Merge,
+            None,
+        )
+        .unwrap();
+        assert_eq!(restored.added, vec!["p"]);
+        assert_eq!(name(&synced, "p").as_deref(), Some("Synced"));
+        assert!(!data.join("projects").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_encrypted_backup_restores_with_its_passphrase() {
+        let root = std::env::temp_dir().join(format!("anchor-backup-{}", uuid::Uuid::new_v4()));
+        let (old, new) = (
+            root.join("old").join("projects"),
+            root.join("new").join("projects"),
+        );
+        let (key, settings) = crate::encryption::new_settings("correct horse", 1_000).unwrap();
+        let project = Project {
+            id: "p".into(),
+            name: "Secret".into(),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&project).unwrap();
+        fs::create_dir_all(&old).unwrap();
+        fs::write(old.join(SETTINGS_FILE), settings).unwrap();
+        fs::write(
+            old.join("p.json"),
+            crate::encryption::seal_with(&key, &json).unwrap(),
+        )
+        .unwrap();
+        let (bytes, _) = pack(&root.join("old"), &old).unwrap();
+
+        // A machine without encryption can't read it without the passphrase
+        let data = root.join("new");
+        assert!(unpack(&data, &new, &bytes, RestoreMode::Merge, None).is_err());
+        assert!(unpack(&data, &new, &bytes, RestoreMode::Merge, Some("wrong")).is_err());
+        let summary = unpack(
+            &data,
+            &new,
+            &bytes,
+            RestoreMode::Merge,
+            Some("correct horse"),
+        )
+        .unwrap();
+        assert_eq!(summary.added, vec!["p"]);
+        assert!(summary.encryption_restored);
+
+        // Still sealed, and the restored settings unlock it
+        let settings = fs::read_to_string(new.join(SETTINGS_FILE)).unwrap();
+        let key = crate::encryption::key_for(&settings, "correct horse").unwrap();
+        let sealed = fs::read_to_string(new.join("p.json")).unwrap();
+        assert!(!sealed.contains("Secret"));
+        let json = crate::encryption::open_with_key(&key, sealed).unwrap();
+        assert_eq!(crate::migrate::parse_project(&json).unwrap().name, "Secret");
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_restore_refuses_unsafe_ids_and_newer_files() {
+        let root = std::env::temp_dir().join(format!("anchor-backup-{}", uuid::Uuid::new_v4()));
+        let project = |id: &str, schema_version: u32| {
+            serde_json::to_string(&Project {
+                id: id.into(),
+                schema_version,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        for (name, json) in [
+            ("projects/..\\evil.json", project("..\\evil", 0)),
+            ("projects/...json", project("..", 0)),
+            ("projects/p.json", project("p", 999)),
+        ] {
+            let zip = write_zip(&BTreeMap::from([(name.to_string(), json.into_bytes())])).unwrap();
+            assert!(
+                unpack(
+                    &root,
+                    &root.join("projects"),
+                    &zip,
+                    RestoreMode::Merge,
+                    None
+                )
+                .is_err(),
+                "{}",
+                name
+            );
+        }
+        // Nothing was written
+        assert!(!root.exists());
+    }
+}
@@ -0,0 +1,281 @@
+//! Shareable `.anchor` bundles.
+//!
+//! A bundle is a zip holding one project (`project.json`) and, optionally,
+//! its completed-task archive (`archive.json`) and attached files
+//! (`attachments/<stored name>`), so a plan can be passed to someone else as
+//! a single file. Contents are always plaintext, even with encryption on.
+
+use crate::archive::ProjectArchive;
+use crate::attachments::{attachments_dir, stored_path};
+use crate::backup::{is_safe_id, read_zip, write_zip};
+use crate::project::{get_projects_dir, load_project, store_project, Project};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+const FORMAT: &str = "anchor-bundle";
+const VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    format: String,
+    version: u32,
+    exported_at: String,
+}
+
+/// What reading a bundle produced, ready to be stored.
+#[derive(Debug)]
+struct Unpacked {
+    project: Project,
+    archive: Option<ProjectArchive>,
+    /// Attached files by stored name.
+    files: Vec<(String, Vec<u8>)>,
+}
+
+/// The stored name of an attachment file (`<project id>/<stored name>`).
+fn stored_name(file: &str) -> &str {
+    file.rsplit('/').next().unwrap_or(file)
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Vec<u8>, String> {
+    serde_json::to_vec_pretty(value).map_err(|e| e.to_string())
+}
+
+/// Zips `project`, with `archive` and, when `include_attachments`, the files
+/// it attaches from `projects_dir`.
+fn pack(
+    projects_dir: &Path,
+    project: &Project,
+    archive: Option<&ProjectArchive>,
+    include_attachments: bool,
+) -> Result<Vec<u8>, String> {
+    let mut entries = BTreeMap::new();
+    let manifest = Manifest {
+        format: FORMAT.to_string(),
+        version: VERSION,
+        exported_at: chrono::Local::now().to_rfc3339(),
+    };
+    entries.insert("bundle.json".to_string(), to_json(&manifest)?);
+    entries.insert("project.json".to_string(), to_json(project)?);
+    if let Some(archive) = archive.filter(|a| !a.tasks.is_empty()) {
+        entries.insert("archive.json".to_string(), to_json(archive)?);
+    }
+    if include_attachments {
+        let root = attachments_dir(projects_dir);
+        for file in project
+            .tasks
+            .iter()
+            .flat_map(|t| &t.attachments)
+            .filter_map(|a| a.file.as_deref())
+        {
+            // Missing copies are skipped; the import drops them
+            let Ok(path) = stored_path(&root, &project.id, file) else {
+                continue;
+            };
+            if let Ok(data) = fs::read(path) {
+                entries.insert(format!("attachments/{}", stored_name(file)), data);
+            }
+        }
+    }
+    write_zip(&entries)
+}
+
+/// Reads a bundle, giving the project a new ID when `taken` says its own is
+/// in use. File attachments whose file isn't in the bundle are dropped.
+fn unpack(bytes: &[u8], taken: impl Fn(&str) -> bool) -> Result<Unpacked, String> {
+    let mut entries = read_zip(bytes)?;
+    let manifest: Manifest = entries
+        .get("bundle.json")
+        .and_then(|data| serde_json::from_slice(data).ok())
+        .filter(|m: &Manifest| m.format == FORMAT)
+        .ok_or_else(|| "Not an Anchor bundle".to_string())?;
+    if manifest.version > VERSION {
+        return Err(format!(
+            "Bundle version {} is newer than this app supports ({})",
+            manifest.version, VERSION
+        ));
+    }
+    let json = entries
+        .remove("project.json")
+        .ok_or_else(|| "Bundle holds no project".to_string())?;
+    let mut project = crate::migrate::parse_project(&String::from_utf8_lossy(&json))
+        .map_err(|e| format!("project.json: {}", e))?;
+    let mut archive: Option<ProjectArchive> = entries
+        .remove("archive.json")
+        .map(|data| serde_json::from_slice(&data).map_err(|e| format!("archive.json: {}", e)))
+        .transpose()?;
+
+    // The ID names files, so one from someone else must be safe to use
+    if !is_safe_id(&project.id) || taken(&project.id) {
+        project.id = Uuid::new_v4().to_string();
+    }
+    if let Some(archive) = &mut archive {
+        archive.project_id = project.id.clone();
+    }
+    let files: BTreeMap<String, Vec<u8>> = entries
+        .into_iter()
+        .filter_map(|(name, data)| {
+            let stored = name.strip_prefix("attachments/")?;
+            let safe = !stored.is_empty() && !stored.contains(['/', '\\']) && stored != "..";
+            safe.then(|| (stored.to_string(), data))
+        })
+        .collect();
+    for task in &mut project.tasks {
+        task.attachments.retain_mut(|attachment| {
+            let Some(file) = &attachment.file else {
+                return true;
+            };
+            let stored = stored_name(file).to_string();
+            attachment.file = Some(format!("{}/{}", project.id, stored));
+            files.contains_key(&stored)
+        });
+    }
+    Ok(Unpacked {
+        project,
+        archive,
+        files: files.into_iter().collect(),
+    })
+}
+
+/// Writes a project to a `.anchor` bundle at `path`, optionally with its
+/// archive and attached files.
+#[tauri::command]
+pub fn export_project_bundle(
+    app: AppHandle,
+    id: String,
+    path: String,
+    include_archive: bool,
+    include_attachments: bool,
+) -> Result<(), String> {
+    let project = load_project(app.clone(), id.clone())?;
+    let archive = if include_archive {
+        Some(crate::archive::load_archive(app.clone(), id)?)
+    } else {
+        None
+    };
+    let bytes = pack(
+        &get_projects_dir(&app)?,
+        &project,
+        archive.as_ref(),
+        include_attachments,
+    )?;
+    fs::write(path, bytes).map_err(|e| e.to_string())
+}
+
+/// Imports a `.anchor` bundle as a project. If a project with its ID already
+/// exists, or the ID isn't usable as a file name, the import gets a new ID,
+/// so nothing is overwritten.
+#[tauri::command]
+pub fn import_project_bundle(app: AppHandle, path: String) -> Result<Project, String> {
+    let bytes = fs::read(&path).map_err(|e| e.to_string())?;
+    let dir = get_projects_dir(&app)?;
+    let Unpacked {
+        mut project,
+        archive,
+        files,
+    } = unpack(&bytes, |id| dir.join(format!("{}.json", id)).exists())?;
+
+    if !files.is_empty() {
+        let target = attachments_dir(&dir).join(&project.id);
+        fs::create_dir_all(&target).map_err(|e| e.to_string())?;
+        for (name, data) in files {
+            fs::write(target.join(name), data).map_err(|e| e.to_string())?;
+        }
+    }
+    if let Some(archive) = archive {
+        let json = serde_json::to_string_pretty(&archive).map_err(|e| e.to_string())?;
+        crate::encryption::write(crate::archive::archive_path(&app, &project.id)?, json)
+            .map_err(|e| e.to_string())?;
+    }
+    // The bundled log is kept, following the entries recording the import
+    let log = std::mem::take(&mut project.activity);
+    store_project(&app, project.clone(), log)?;
+    load_project(app, project.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::ArchivedTask;
+    use crate::scheduler::{Attachment, Task};
+
+    #[test]
+    fn test_bundle_round_trip_with_new_id() {
+        let root = std::env::temp_dir().join(format!("anchor-bundle-{}", Uuid::new_v4()));
+        let files = attachments_dir(&root).join("p1");
+        fs::create_dir_all(&files).unwrap();
+        fs::write(files.join("a1-brief.txt"), "Client brief").unwrap();
+
+        let attachment = |id: &str, file: &str| Attachment {
+            id: id.into(),
+            name: id.into(),
+            url: None,
+            file: Some(file.into()),
+            added_at: String::new(),
+        };
+        let project = Project {
+            id: "p1".into(),
+            name: "Launch".into(),
+            tasks: vec![Task {
+                id: "t1".into(),
+                attachments: vec![
+                    attachment("brief", "p1/a1-brief.txt"),
+                    attachment("lost", "p1/a2-gone.txt"),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let archive = ProjectArchive {
+            project_id: "p1".into(),
+            tasks: vec![ArchivedTask {
+                task: Task {
+                    id: "old".into(),
+                    ..Default::default()
+                },
+                anchor: None,
+                archived_at: String::new(),
+            }],
+        };
+        let bytes = pack(&root, &project, Some(&archive), true).unwrap();
+
+        let unpacked = unpack(&bytes, |id| id == "p1").unwrap();
+        let id = unpacked.project.id.clone();
+        assert_ne!(id, "p1");
+        assert_eq!(unpacked.project.name, "Launch");
+        let attachments = &unpacked.project.tasks[0].attachments;
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(
+            attachments[0].file.as_deref(),
+            Some(format!("{}/a1-brief.txt", id).as_str())
+        );
+        assert_eq!(
+            unpacked.files,
+            vec![("a1-brief.txt".to_string(), b"Client brief".to_vec())]
+        );
+        assert_eq!(unpacked.archive.unwrap().project_id, id);
+
+        // Without a clash the ID is kept
+        let bytes = pack(&root, &project, None, false).unwrap();
+        let unpacked = unpack(&bytes, |_| false).unwrap();
+        assert_eq!(unpacked.project.id, "p1");
+        assert!(unpacked.project.tasks[0].attachments.is_empty());
+        assert!(unpacked.archive.is_none());
+
+        // An ID that would lead out of the projects folder is replaced
+        let hostile = Project {
+            id: "../../x".into(),
+            ..project.clone()
+        };
+        let bytes = pack(&root, &hostile, Some(&archive), false).unwrap();
+        let unpacked = unpack(&bytes, |_| false).unwrap();
+        assert!(is_safe_id(&unpacked.project.id));
+        assert_eq!(unpacked.archive.unwrap().project_id, unpacked.project.id);
+
+        assert!(unpack(b"not a zip", |_| false).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,171 @@
+//! Schedule result cache.
+//!
+//! Project listings and the widget schedule every project on every call.
+//! [`ScheduleCache`] is kept in managed state and reuses a project's last
+//! result until the scheduler input changes.
+
+use crate::project::Project;
+use crate::scheduler::{
+    assess_feasibility, calculate_backwards_schedule, ScheduleError, ScheduleRequest,
+    ScheduleResult, ScheduledTask,
+};
+use chrono::NaiveDateTime;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+/// Last computed schedule per project, keyed by a hash of its scheduler input.
+#[derive(Default)]
+pub struct ScheduleCache {
+    entries: Mutex<HashMap<String, (u64, ScheduleResult)>>,
+}
+
+/// Content hash of a request. Serializing through `serde_json::Value` sorts
+/// map keys, so equal requests hash equally regardless of map order.
+fn request_hash(request: &ScheduleRequest) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_value(request)
+        .map(|v| v.to_string())
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Re-derives the parts of a result that depend on "now".
+fn refresh(result: &mut ScheduleResult, now: NaiveDateTime) {
+    for task in &mut result.scheduled_tasks {
+        task.starts_in_past = !task.completed
+            && NaiveDateTime::parse_from_str(&task.start_date, "%Y-%m-%dT%H:%M:%S")
+                .is_ok_and(|start| start < now);
+    }
+    let spacing_violations = std::mem::take(&mut result.feasibility.spacing_violations);
+    let anchor_conflicts = std::mem::take(&mut result.feasibility.anchor_conflicts);
+    result.feasibility = assess_feasibility(&result.scheduled_tasks, now);
+    result.feasibility.is_feasible &= spacing_violations.is_empty() && anchor_conflicts.is_empty();
+    result.feasibility.spacing_violations = spacing_violations;
+    result.feasibility.anchor_conflicts = anchor_conflicts;
+}
+
+impl ScheduleCache {
+    /// Schedules `project` as of `now`, reusing the cached result when its
+    /// tasks, anchors and constraints are unchanged.
+    pub fn schedule(
+        &self,
+        project: &Project,
+        now: NaiveDateTime,
+    ) -> Result<ScheduleResult, ScheduleError> {
+        let mut request = project.schedule_request();
+        let hash = request_hash(&request);
+
+        if let Some((cached_hash, result)) = self.entries.lock().unwrap().get(&project.id) {
+            if *cached_hash == hash {
+                let mut result = result.clone();
+                refresh(&mut result, now);
+                return Ok(result);
+            }
+        }
+
+        request.reference_time = Some(now.format("%Y-%m-%dT%H:%M:%S").to_string());
+        let result = calculate_backwards_schedule(request)?;
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(project.id.clone(), (hash, result.clone()));
+        Ok(result)
+    }
+
+    /// The schedule as of `now`, and the same schedule with no unfinished
+    /// work starting before `now` (`floor_starts`). The two only differ when
+    /// work should already have started, so only then is the second
+    /// computed afresh.
+    pub fn schedule_from_now(
+        &self,
+        project: &Project,
+        now: NaiveDateTime,
+    ) -> Result<(ScheduleResult, Vec<ScheduledTask>), ScheduleError> {
+        let result = self.schedule(project, now)?;
+        if !result.scheduled_tasks.iter().any(|t| t.starts_in_past) {
+            let floored = result.scheduled_tasks.clone();
+            return Ok((result, floored));
+        }
+        let mut request = project.schedule_request();
+        request.reference_time = Some(now.format("%Y-%m-%dT%H:%M:%S").to_string());
+        request.floor_starts = true;
+        let floored = calculate_backwards_schedule(request)?.scheduled_tasks;
+        Ok((result, floored))
+    }
+
+    /// Drops a project's entry, e.g. when the project is deleted.
+    pub fn invalidate(&self, project_id: &str) {
+        self.entries.lock().unwrap().remove(project_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{Task, TaskDuration};
+
+    fn project() -> Project {
+        Project {
+            id: "p".into(),
+            tasks: vec![Task {
+                id: "a".into(),
+                name: "Task A".into(),
+                duration: TaskDuration::days(2),
+                ..Default::default()
+            }],
+            anchors: [("a".into(), "2026-01-10T00:00:00".into())].into(),
+            ..Default::default()
+        }
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_hit_is_refreshed_for_now() {
+        let cache = ScheduleCache::default();
+        let project = project();
+
+        let first = cache.schedule(&project, at("2026-01-01T00:00:00")).unwrap();
+        let later = cache.schedule(&project, at("2026-01-09T00:00:00")).unwrap();
+
+        assert!(first.feasibility.is_feasible);
+        assert_eq!(later.scheduled_tasks[0].start_date, "2026-01-08T00:00:00");
+        assert!(later.scheduled_tasks[0].starts_in_past);
+        assert!(!later.feasibility.is_feasible);
+    }
+
+    #[test]
+    fn test_floored_schedule_reuses_result_until_late() {
+        let cache = ScheduleCache::default();
+        let project = project();
+
+        let (result, floored) = cache
+            .schedule_from_now(&project, at("2026-01-01T00:00:00"))
+            .unwrap();
+        assert_eq!(floored, result.scheduled_tasks);
+
+        let (result, floored) = cache
+            .schedule_from_now(&project, at("2026-01-09T00:00:00"))
+            .unwrap();
+        assert_eq!(result.scheduled_tasks[0].start_date, "2026-01-08T00:00:00");
+        assert_eq!(floored[0].start_date, "2026-01-09T00:00:00");
+    }
+
+    #[test]
+    fn test_edit_misses_cache() {
+        let cache = ScheduleCache::default();
+        let mut project = project();
+        let now = at("2026-01-01T00:00:00");
+        cache.schedule(&project, now).unwrap();
+
+        project.tasks[0].duration = TaskDuration::days(3);
+        let result = cache.schedule(&project, now).unwrap();
+
+        assert_eq!(result.scheduled_tasks[0].start_date, "2026-01-07T00:00:00");
+    }
+}
@@ -0,0 +1,543 @@
+//! CalDAV sync.
+//!
+//! Links a project to a CalDAV calendar collection. A sync first pulls
+//! completion and date changes made in the calendar since the last sync,
+//! then pushes the rescheduled tasks back as one calendar object per task
+//! (`<collection>/<project id>-<task id>.ics`). What was last pushed is kept
+//! in `projects/caldav/<project id>.json` so changes on each side can be told
+//! apart.
+
+use crate::cache::ScheduleCache;
+use crate::config::{load_config, save_config};
+use crate::datetime::Zone;
+use crate::ics::{render_components, wrap_calendar, FeedFilter, IcsComponent};
+use crate::project::{get_projects_dir, load_project, save_project, Project};
+use crate::scheduler::{calculate_backwards_schedule, ScheduledTask};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// Which side wins for a task changed in both Anchor and the calendar since
+/// the last sync.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    #[default]
+    PreferAnchor,
+    PreferCalendar,
+}
+
+/// A project's CalDAV calendar.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalDavLink {
+    pub project_id: String,
+    /// URL of the calendar collection, e.g.
+    /// `https://dav.example.com/calendars/sam/work/`.
+    pub calendar_url: String,
+    pub username: String,
+    pub password: String,
+    #[serde(default)]
+    pub component: IcsComponent,
+    #[serde(default)]
+    pub conflicts: ConflictPolicy,
+}
+
+/// A task's times and completion as last seen on one side.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+struct Synced {
+    start_date: String,
+    end_date: String,
+    completed: bool,
+}
+
+impl Synced {
+    fn of(row: &ScheduledTask) -> Self {
+        Self {
+            start_date: row.start_date.clone(),
+            end_date: row.end_date.clone(),
+            completed: row.completed,
+        }
+    }
+}
+
+/// What was last pushed, by task ID.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct SyncState {
+    objects: HashMap<String, Synced>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SyncConflict {
+    pub task_id: String,
+    pub task_name: String,
+    /// The side whose change was kept.
+    pub resolution: ConflictPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct SyncReport {
+    /// Tasks updated from calendar changes.
+    pub pulled: Vec<String>,
+    pub pushed: usize,
+    /// Calendar objects removed because their task is gone.
+    pub removed: usize,
+    pub conflicts: Vec<SyncConflict>,
+    /// Objects that couldn't be read or written; the next sync retries them.
+    pub errors: Vec<String>,
+}
+
+/// Calendar object storage, by object name.
+trait Calendar {
+    /// The object's body and ETag, or `None` if it doesn't exist.
+    fn get(&self, name: &str) -> Result<Option<(String, Option<String>)>, String>;
+    /// Creates or replaces an object, only over `etag` when given.
+    fn put(&mut self, name: &str, body: &str, etag: Option<&str>) -> Result<(), String>;
+    fn delete(&mut self, name: &str) -> Result<(), String>;
+}
+
+struct HttpCalendar<'a> {
+    link: &'a CalDavLink,
+}
+
+impl HttpCalendar<'_> {
+    fn request(&self, method: &str, name: &str) -> ureq::Request {
+        let url = format!("{}/{}", self.link.calendar_url.trim_end_matches('/'), name);
+        let credentials = format!("{}:{}", self.link.username, self.link.password);
+        ureq::request(method, &url).set(
+            "Authorization",
+            &format!("Basic {}", STANDARD.encode(credentials)),
+        )
+    }
+}
+
+impl Calendar for HttpCalendar<'_> {
+    fn get(&self, name: &str) -> Result<Option<(String, Option<String>)>, String> {
+        match self.request("GET", name).call() {
+            Ok(response) => {
+                let etag = response.header("ETag").map(String::from);
+                let body = response.into_string().map_err(|e| e.to_string())?;
+                Ok(Some((body, etag)))
+            }
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    fn put(&mut self, name: &str, body: &str, etag: Option<&str>) -> Result<(), String> {
+        let mut request = self
+            .request("PUT", name)
+            .set("Content-Type", "text/calendar; charset=utf-8");
+        if let Some(etag) = etag {
+            request = request.set("If-Match", etag);
+        }
+        request.send_string(body).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    fn delete(&mut self, name: &str) -> Result<(), String> {
+        match self.request("DELETE", name).call() {
+            Ok(_) | Err(ureq::Error::Status(404, _)) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+fn object_name(project_id: &str, task_id: &str) -> String {
+    format!("{}-{}.ics", project_id, task_id)
+}
+
+/// A DTSTART/DTEND/DUE value as wall-clock time in `zone`.
+fn parse_value(value: &str, zone: Zone) -> Option<String> {
+    let wall = if value.len() == 8 {
+        NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()?
+            .and_hms_opt(0, 0, 0)?
+    } else if let Some(utc) = value.strip_suffix('Z') {
+        zone.to_wall(NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?)
+    } else {
+        NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?
+    };
+    Some(wall.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+/// Reads the times and completion of the event or to-do in `text`.
+fn parse_object(text: &str, zone: Zone) -> Option<Synced> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')) {
+            Some(rest) if !lines.is_empty() => lines.last_mut().unwrap().push_str(rest),
+            _ => lines.push(line.trim_end().to_string()),
+        }
+    }
+
+    let (mut start, mut end, mut completed) = (None, None, false);
+    for line in &lines {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.split(';').next().unwrap_or(key).to_ascii_uppercase();
+        match key.as_str() {
+            "DTSTART" => start = parse_value(value, zone),
+            "DTEND" | "DUE" => end = parse_value(value, zone),
+            "STATUS" => completed = value.eq_ignore_ascii_case("COMPLETED"),
+            "COMPLETED" => completed = true,
+            "END" if value == "VEVENT" || value == "VTODO" => break,
+            _ => {}
+        }
+    }
+    let start = start?;
+    Some(Synced {
+        end_date: end.unwrap_or_else(|| start.clone()),
+        start_date: start,
+        completed,
+    })
+}
+
+fn schedule(project: &Project, now: NaiveDateTime) -> Result<Vec<ScheduledTask>, String> {
+    let mut request = project.schedule_request();
+    request.reference_time = Some(now.format("%Y-%m-%dT%H:%M:%S").to_string());
+    calculate_backwards_schedule(request)
+        .map(|result| result.scheduled_tasks)
+        .map_err(|e| e.to_string())
+}
+
+/// Pulls calendar changes into `project`, reschedules and pushes the result.
+fn sync(
+    project: &mut Project,
+    link: &CalDavLink,
+    state: &mut SyncState,
+    calendar: &mut dyn Calendar,
+    now: NaiveDateTime,
+) -> Result<SyncReport, String> {
+    let zone = Zone::parse(project.timezone.as_deref())?;
+    let mut report = SyncReport::default();
+    let mut rows: HashMap<String, Synced> = schedule(project, now)?
+        .iter()
+        .map(|row| (row.id.clone(), Synced::of(row)))
+        .collect();
+
+    // The calendar's current copy of each object, with its ETag
+    let mut remote: HashMap<String, (Synced, Option<String>)> = HashMap::new();
+    let mut synced_ids: Vec<String> = state.objects.keys().cloned().collect();
+    synced_ids.sort();
+    for task_id in &synced_ids {
+        let last = &state.objects[task_id];
+        let Some(local) = rows.get(task_id) else {
+            continue;
+        };
+        let name = object_name(&project.id, task_id);
+        let (body, etag) = match calendar.get(&name) {
+            Ok(Some(object)) => object,
+            // Deleted in the calendar; pushed again below
+            Ok(None) => continue,
+            Err(e) => {
+                report.errors.push(format!("{}: {}", name, e));
+                // Unknown remote state, so leave the object alone this time
+                remote.insert(task_id.clone(), (local.clone(), None));
+                continue;
+            }
+        };
+        let Some(theirs) = parse_object(&body, zone) else {
+            report
+                .errors
+                .push(format!("{}: unreadable calendar object", name));
+            continue;
+        };
+        remote.insert(task_id.clone(), (theirs.clone(), etag));
+        if theirs == *last {
+            continue;
+        }
+        let Some(task) = project.tasks.iter_mut().find(|t| t.id == *task_id) else {
+            continue;
+        };
+        if local != last {
+            report.conflicts.push(SyncConflict {
+                task_id: task_id.clone(),
+                task_name: task.name.clone(),
+                resolution: link.conflicts,
+            });
+            if link.conflicts == ConflictPolicy::PreferAnchor {
+                continue;
+            }
+        }
+
+        // A moved end moves an anchor; otherwise a moved start pins the task
+        task.completed = theirs.completed;
+        if theirs.end_date != last.end_date && project.anchors.contains_key(task_id) {
+            project
+                .anchors
+                .insert(task_id.clone(), theirs.end_date.clone());
+        } else if theirs.start_date != last.start_date {
+            task.pinned_start = Some(theirs.start_date.clone());
+        }
+        report.pulled.push(task_id.clone());
+    }
+
+    // Reschedule around whatever was pulled
+    let scheduled = schedule(project, now)?;
+    rows = scheduled
+        .iter()
+        .map(|row| (row.id.clone(), Synced::of(row)))
+        .collect();
+
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    for row in &scheduled {
+        let current = &rows[&row.id];
+        let etag = match remote.get(&row.id) {
+            Some((theirs, _)) if theirs == current => {
+                state.objects.insert(row.id.clone(), current.clone());
+                continue;
+            }
+            Some((_, etag)) => etag.as_deref(),
+            None => None,
+        };
+        let name = object_name(&project.id, &row.id);
+        let body = wrap_calendar(
+            &project.name,
+            &render_components(
+                project,
+                std::slice::from_ref(row),
+                &FeedFilter::default(),
+                link.component,
+                &stamp,
+            ),
+        );
+        match calendar.put(&name, &body, etag) {
+            Ok(()) => {
+                state.objects.insert(row.id.clone(), current.clone());
+                report.pushed += 1;
+            }
+            Err(e) => report.errors.push(format!("{}: {}", name, e)),
+        }
+    }
+
+    for task_id in synced_ids {
+        if rows.contains_key(&task_id) {
+            continue;
+        }
+        let name = object_name(&project.id, &task_id);
+        match calendar.delete(&name) {
+            Ok(()) => {
+                state.objects.remove(&task_id);
+                report.removed += 1;
+            }
+            Err(e) => report.errors.push(format!("{}: {}", name, e)),
+        }
+    }
+    Ok(report)
+}
+
+fn state_path(app: &AppHandle, project_id: &str) -> Result<PathBuf, String> {
+    let dir = get_projects_dir(app)?.join("caldav");
+    if !dir.exists() {
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    }
+    Ok(dir.join(format!("{}.json", project_id)))
+}
+
+#[tauri::command]
+pub fn list_caldav_links(app: AppHandle) -> Result<Vec<CalDavLink>, String> {
+    Ok(load_config(app)?.caldav)
+}
+
+/// Creates or replaces a project's link.
+#[tauri::command]
+pub fn save_caldav_link(app: AppHandle, link: CalDavLink) -> Result<(), String> {
+    let mut config = load_config(app.clone())?;
+    match config
+        .caldav
+        .iter_mut()
+        .find(|l| l.project_id == link.project_id)
+    {
+        Some(existing) => *existing = link,
+        None => config.caldav.push(link),
+    }
+    save_config(app, config)
+}
+
+/// Unlinks a project. Objects already in the calendar are left there.
+#[tauri::command]
+pub fn delete_caldav_link(app: AppHandle, project_id: String) -> Result<(), String> {
+    let mut config = load_config(app.clone())?;
+    config.caldav.retain(|l| l.project_id != project_id);
+    save_config(app.clone(), config)?;
+    let path = state_path(&app, &project_id)?;
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Syncs a linked project with its calendar.
+#[tauri::command]
+pub fn sync_caldav(app: AppHandle, project_id: String) -> Result<SyncReport, String> {
+    let link = load_config(app.clone())?
+        .caldav
+        .into_iter()
+        .find(|l| l.project_id == project_id)
+        .ok_or_else(|| format!("Project {} isn't linked to a calendar", project_id))?;
+    let mut project = load_project(app.clone(), project_id.clone())?;
+    let path = state_path(&app, &project_id)?;
+    // Unreadable state (e.g. while locked) must not pass for a first sync
+    let mut state: SyncState = if path.exists() {
+        let json = crate::encryption::read_to_string(&path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&json).unwrap_or_default()
+    } else {
+        SyncState::default()
+    };
+
+    let now = chrono::Local::now().naive_local();
+    let report = sync(
+        &mut project,
+        &link,
+        &mut state,
+        &mut HttpCalendar { link: &link },
+        now,
+    )?;
+    if !report.pulled.is_empty() {
+        save_project(app.clone(), project)?;
+        app.state::<ScheduleCache>().invalidate(&project_id);
+    }
+    let json = serde_json::to_string_pretty(&state).map_err(|e| e.to_string())?;
+    crate::encryption::write(path, json).map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{Task, TaskDuration};
+
+    #[derive(Default)]
+    struct Memory {
+        objects: HashMap<String, String>,
+    }
+
+    impl Calendar for Memory {
+        fn get(&self, name: &str) -> Result<Option<(String, Option<String>)>, String> {
+            Ok(self.objects.get(name).map(|body| (body.clone(), None)))
+        }
+
+        fn put(&mut self, name: &str, body: &str, _etag: Option<&str>) -> Result<(), String> {
+            self.objects.insert(name.into(), body.into());
+            Ok(())
+        }
+
+        fn delete(&mut self, name: &str) -> Result<(), String> {
+            self.objects.remove(name);
+            Ok(())
+        }
+    }
+
+    fn link(conflicts: ConflictPolicy) -> CalDavLink {
+        CalDavLink {
+            project_id: "p".into(),
+            calendar_url: "https://dav.example.com/cal/".into(),
+            username: "sam".into(),
+            password: "secret".into(),
+            component: IcsComponent::Event,
+            conflicts,
+        }
+    }
+
+    fn project() -> Project {
+        let task = |id: &str, deps: &[&str]| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(1),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        Project {
+            id: "p".into(),
+            name: "Launch".into(),
+            tasks: vec![task("draft", &[]), task("ship", &["draft"])],
+            anchors: [("ship".into(), "2026-03-10T17:00:00".into())].into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        }
+    }
+
+    fn now() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2026-03-01T09:00:00", "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_sync_pushes_then_pulls_calendar_changes() {
+        let mut project = project();
+        let mut state = SyncState::default();
+        let mut calendar = Memory::default();
+        let link = link(ConflictPolicy::PreferAnchor);
+
+        let first = sync(&mut project, &link, &mut state, &mut calendar, now()).unwrap();
+        assert_eq!(first.pushed, 2);
+        assert!(calendar.objects["p-ship.ics"].contains("DTEND:20260310T170000Z\r\n"));
+
+        // Nothing changed on either side
+        let again = sync(&mut project, &link, &mut state, &mut calendar, now()).unwrap();
+        assert_eq!(again, SyncReport::default());
+
+        // Ship moved a day later and draft ticked off in the calendar
+        let ship = calendar.objects["p-ship.ics"]
+            .replace("DTEND:20260310T170000Z", "DTEND:20260311T170000Z")
+            .replace("DTSTART:20260309T170000Z", "DTSTART:20260310T170000Z");
+        calendar.objects.insert("p-ship.ics".into(), ship);
+        let draft =
+            calendar.objects["p-draft.ics"].replace("END:VEVENT", "STATUS:COMPLETED\r\nEND:VEVENT");
+        calendar.objects.insert("p-draft.ics".into(), draft);
+
+        let pulled = sync(&mut project, &link, &mut state, &mut calendar, now()).unwrap();
+        assert_eq!(pulled.pulled, vec!["draft", "ship"]);
+        assert_eq!(project.anchors["ship"], "2026-03-11T17:00:00");
+        assert!(project.tasks[0].completed);
+        assert!(pulled.conflicts.is_empty());
+
+        // A task removed from the project leaves the calendar too
+        project.tasks.remove(0);
+        project.tasks[0].dependencies.clear();
+        let removed = sync(&mut project, &link, &mut state, &mut calendar, now()).unwrap();
+        assert_eq!(removed.removed, 1);
+        assert!(!calendar.objects.contains_key("p-draft.ics"));
+    }
+
+    #[test]
+    fn test_conflicts_follow_policy() {
+        for policy in [ConflictPolicy::PreferAnchor, ConflictPolicy::PreferCalendar] {
+            let mut project = project();
+            let mut state = SyncState::default();
+            let mut calendar = Memory::default();
+            let link = link(policy);
+            sync(&mut project, &link, &mut state, &mut calendar, now()).unwrap();
+
+            // Both sides move ship's deadline
+            project
+                .anchors
+                .insert("ship".into(), "2026-03-12T17:00:00".into());
+            let ship = calendar.objects["p-ship.ics"]
+                .replace("DTEND:20260310T170000Z", "DTEND:20260313T170000Z");
+            calendar.objects.insert("p-ship.ics".into(), ship);
+
+            let report = sync(&mut project, &link, &mut state, &mut calendar, now()).unwrap();
+            assert_eq!(report.conflicts.len(), 1);
+            assert_eq!(report.conflicts[0].resolution, policy);
+            let expected = match policy {
+                ConflictPolicy::PreferAnchor => "2026-03-12T17:00:00",
+                ConflictPolicy::PreferCalendar => "2026-03-13T17:00:00",
+            };
+            assert_eq!(project.anchors["ship"], expected);
+            assert_eq!(
+                parse_object(
+                    &calendar.objects["p-ship.ics"],
+                    Zone::parse(Some("UTC")).unwrap()
+                )
+                .unwrap()
+                .end_date,
+                expected
+            );
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Calendar grouping.
+//!
+//! Buckets scheduled tasks into weeks and days, so the widget's mini
+//! calendar and the main calendar view read the same answer to "what
+//! happens on this day". Weeks start on Sunday, as both views draw them.
+//! Only days with something on them are listed.
+
+use crate::scheduler::ScheduledTask;
+use chrono::{NaiveDate, NaiveDateTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CalendarDay {
+    /// YYYY-MM-DD
+    pub date: String,
+    /// IDs of tasks starting, finishing, or under way all day.
+    pub starting: Vec<String>,
+    pub ending: Vec<String>,
+    pub in_progress: Vec<String>,
+    /// Milestones fall on their date only.
+    pub milestones: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CalendarWeek {
+    /// The Sunday the week starts on, YYYY-MM-DD.
+    pub week_start: String,
+    pub days: Vec<CalendarDay>,
+}
+
+/// Groups `(id, start_date, end_date, is_milestone)` by week and day.
+/// Tasks whose dates don't parse are left out.
+pub fn group_by_week<'a>(
+    tasks: impl IntoIterator<Item = (&'a str, &'a str, &'a str, bool)>,
+) -> Vec<CalendarWeek> {
+    fn day(days: &mut BTreeMap<NaiveDate, CalendarDay>, date: NaiveDate) -> &mut CalendarDay {
+        days.entry(date).or_insert_with(|| CalendarDay {
+            date: date.format("%Y-%m-%d").to_string(),
+            ..Default::default()
+        })
+    }
+    let mut days = BTreeMap::new();
+    for (id, start, end, is_milestone) in tasks {
+        let (Ok(start), Ok(end)) = (
+            NaiveDateTime::parse_from_str(start, "%Y-%m-%dT%H:%M:%S"),
+            NaiveDateTime::parse_from_str(end, "%Y-%m-%dT%H:%M:%S"),
+        ) else {
+            continue;
+        };
+        if is_milestone {
+            day(&mut days, end.date()).milestones.push(id.to_string());
+            continue;
+        }
+        day(&mut days, start.date()).starting.push(id.to_string());
+        day(&mut days, end.date()).ending.push(id.to_string());
+        for date in start
+            .date()
+            .iter_days()
+            .skip(1)
+            .take_while(|d| *d < end.date())
+        {
+            day(&mut days, date).in_progress.push(id.to_string());
+        }
+    }
+
+    let mut weeks: Vec<CalendarWeek> = Vec::new();
+    for (date, day) in days {
+        let week_start = date
+            .week(Weekday::Sun)
+            .first_day()
+            .format("%Y-%m-%d")
+            .to_string();
+        match weeks.last_mut() {
+            Some(week) if week.week_start == week_start => week.days.push(day),
+            _ => weeks.push(CalendarWeek {
+                week_start,
+                days: vec![day],
+            }),
+        }
+    }
+    weeks
+}
+
+/// Weeks of a schedule, for the main calendar view.
+pub fn schedule_weeks(tasks: &[ScheduledTask]) -> Vec<CalendarWeek> {
+    group_by_week(tasks.iter().map(|t| {
+        (
+            t.id.as_str(),
+            t.start_date.as_str(),
+            t.end_date.as_str(),
+            t.is_milestone,
+        )
+    }))
+}
+
+#[tauri::command]
+pub fn group_calendar(tasks: Vec<ScheduledTask>) -> Vec<CalendarWeek> {
+    schedule_weeks(&tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_by_week_and_day() {
+        let task = |id: &str, start: &str, end: &str, is_milestone| ScheduledTask {
+            id: id.into(),
+            start_date: start.into(),
+            end_date: end.into(),
+            is_milestone,
+            ..Default::default()
+        };
+        // Friday to the following Monday, and a milestone that Tuesday
+        let weeks = schedule_weeks(&[
+            task("draft", "2026-03-13T09:00:00", "2026-03-16T17:00:00", false),
+            task("review", "2026-03-17T12:00:00", "2026-03-17T12:00:00", true),
+            task("broken", "soon", "later", false),
+        ]);
+
+        let starts: Vec<_> = weeks.iter().map(|w| w.week_start.as_str()).collect();
+        assert_eq!(starts, vec!["2026-03-08", "2026-03-15"]);
+        let dates: Vec<_> = weeks[0].days.iter().map(|d| d.date.as_str()).collect();
+        assert_eq!(dates, vec!["2026-03-13", "2026-03-14"]);
+        assert_eq!(weeks[0].days[0].starting, vec!["draft"]);
+        assert_eq!(weeks[0].days[1].in_progress, vec!["draft"]);
+
+        let next = &weeks[1].days;
+        assert_eq!(next[0].in_progress, vec!["draft"]);
+        assert_eq!(next[1].ending, vec!["draft"]);
+        assert_eq!(next[2].milestones, vec!["review"]);
+        assert!(next[2].starting.is_empty());
+    }
+}
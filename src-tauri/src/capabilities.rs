@@ -0,0 +1,276 @@
+//! Capability discovery.
+//!
+//! Describes what this build of the backend supports so the frontend and
+//! local API clients can adapt to the installed version instead of guessing.
+
+use crate::import::ImportFormat;
+use serde::Serialize;
+
+/// Bumped when the on-disk project format changes incompatibly.
+pub const PROJECT_SCHEMA_VERSION: u32 = crate::migrate::CURRENT_SCHEMA_VERSION;
+/// Bumped when the archive file format changes incompatibly.
+pub const ARCHIVE_SCHEMA_VERSION: u32 = 1;
+/// Bumped when `config.json` changes incompatibly.
+pub const CONFIG_SCHEMA_VERSION: u32 = 1;
+/// Bumped when local API routes or payloads change incompatibly.
+pub const API_SCHEMA_VERSION: u32 = 1;
+
+/// Optional behaviours clients may want to check before relying on them.
+const FEATURES: &[&str] = &[
+    "duration_units",
+    "elapsed_durations",
+    "pinned_starts",
+    "partial_overlap_dependencies",
+    "serialize_work",
+    "busy_slots",
+    "spread_slack",
+    "anchor_windows",
+    "anchor_spacing",
+    "anchor_conflicts",
+    "exclusions",
+    "incremental_reschedule",
+    "bulk_task_operations",
+    "undo_history",
+    "crash_recovery",
+    "project_repair",
+    "data_backup",
+    "scenarios",
+    "delay_simulation",
+    "anchor_suggestions",
+    "rollup",
+    "resource_load",
+    "health_checks",
+    "chain_slack",
+    "daily_agenda",
+    "today_view",
+    "calendar_weeks",
+    "archive",
+    "calendar_feeds",
+    "caldav_sync",
+    "google_calendar",
+    "gantt_svg_export",
+    "pdf_report",
+    "project_trash",
+    "project_templates",
+    "task_tags",
+    "full_text_search",
+    "project_index",
+    "dashboard",
+    "focus_task",
+    "external_change_watch",
+    "sync_folder",
+    "encryption_at_rest",
+    "cross_project_task_import",
+    "task_attachments",
+    "task_activity_log",
+    "project_stats",
+    "project_bundles",
+    "todoist_import",
+    "trello_import",
+    "csv_column_mapping",
+    "jira_import",
+    "deep_links",
+    "portfolio",
+    "next_actions",
+    "email_notifications",
+    "task_reminders",
+    "task_reminder_offsets",
+    "tray_quick_actions",
+    "reminder_snooze",
+    "deadline_monitor",
+    "quick_add",
+    "natural_language_entry",
+    "natural_language_dates",
+    "time_tracking",
+    "local_api",
+    "recalculation_triggers",
+    "file_drop_import",
+    "csv_task_import",
+];
+
+/// Commands registered with the Tauri invoke handler. Keep in sync with `lib.rs`.
+const COMMANDS: &[&str] = &[
+    "schedule",
+    "reschedule_task",
+    "suggest_anchor_dates",
+    "get_capabilities",
+    "test_notification",
+    "show_main_window",
+    "create_project",
+    "load_project",
+    "save_project",
+    "apply_task_operations",
+    "undo",
+    "redo",
+    "journal_project",
+    "list_unsaved_changes",
+    "recover_unsaved_changes",
+    "discard_unsaved_changes",
+    "repair_project",
+    "export_backup",
+    "import_backup",
+    "list_projects",
+    "get_dashboard",
+    "recalculate",
+    "delete_project",
+    "rename_project",
+    "set_focus_task",
+    "set_project_pinned",
+    "reorder_projects",
+    "load_config",
+    "save_config",
+    "get_next_deadline",
+    "get_widget_info",
+    "evaluate_scenario",
+    "simulate_delay",
+    "notify_project",
+    "list_calendar_feeds",
+    "save_calendar_feed",
+    "delete_calendar_feed",
+    "export_calendar_feed",
+    "export_ics",
+    "list_caldav_links",
+    "save_caldav_link",
+    "delete_caldav_link",
+    "sync_caldav",
+    "google_begin_auth",
+    "google_disconnect",
+    "list_google_calendars",
+    "link_google_calendar",
+    "unlink_google_calendar",
+    "sync_google_calendar",
+    "export_gantt_svg",
+    "generate_report_pdf",
+    "list_trashed",
+    "restore_project",
+    "purge_trash",
+    "list_templates",
+    "create_template",
+    "delete_template",
+    "instantiate_template",
+    "query_tasks",
+    "search",
+    "rebuild_project_index",
+    "get_sync_conflicts",
+    "clear_sync_conflicts",
+    "get_encryption_status",
+    "enable_encryption",
+    "unlock_projects",
+    "lock_projects",
+    "encrypt_project_files",
+    "disable_encryption",
+    "import_file",
+    "import_csv_tasks",
+    "import_tasks",
+    "add_attachment",
+    "remove_attachment",
+    "open_attachment",
+    "add_task_comment",
+    "get_task_activity",
+    "get_project_stats",
+    "export_project_bundle",
+    "import_project_bundle",
+    "list_todoist_projects",
+    "import_todoist",
+    "read_csv_columns",
+    "import_mapped_csv",
+    "import_jira",
+    "take_deep_link",
+    "get_portfolio",
+    "get_next_actions",
+    "get_schedule_rollup",
+    "get_resource_load",
+    "check_schedule_health",
+    "generate_agenda",
+    "get_today",
+    "list_reminders",
+    "snooze_reminder",
+    "dismiss_reminder",
+    "group_calendar",
+    "quick_add_task",
+    "parse_task_entry",
+    "normalize_date",
+    "start_timer",
+    "stop_timer",
+    "active_timer",
+    "get_chain_slack",
+    "load_archive",
+    "archive_completed_tasks",
+];
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Ics,
+    Svg,
+    Pdf,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SchemaVersions {
+    pub project: u32,
+    pub archive: u32,
+    pub config: u32,
+    pub api: u32,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct Capabilities {
+    /// Application version, e.g. "0.1.0".
+    pub version: &'static str,
+    pub features: &'static [&'static str],
+    pub commands: &'static [&'static str],
+    pub import_formats: Vec<ImportFormat>,
+    pub export_formats: Vec<ExportFormat>,
+    pub schemas: SchemaVersions,
+}
+
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION"),
+        features: FEATURES,
+        commands: COMMANDS,
+        import_formats: vec![
+            ImportFormat::Csv,
+            ImportFormat::Ics,
+            ImportFormat::Mspdi,
+            ImportFormat::Bundle,
+            ImportFormat::Todoist,
+            ImportFormat::Trello,
+            ImportFormat::Jira,
+        ],
+        export_formats: vec![ExportFormat::Ics, ExportFormat::Svg, ExportFormat::Pdf],
+        schemas: SchemaVersions {
+            project: PROJECT_SCHEMA_VERSION,
+            archive: ARCHIVE_SCHEMA_VERSION,
+            config: CONFIG_SCHEMA_VERSION,
+            api: API_SCHEMA_VERSION,
+        },
+    }
+}
+
+#[tauri::command]
+pub fn get_capabilities() -> Capabilities {
+    capabilities()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_serialize() {
+        let json = serde_json::to_value(capabilities()).unwrap();
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(json["import_formats"][2], "mspdi");
+        assert_eq!(json["export_formats"][0], "ics");
+        assert_eq!(json["export_formats"][1], "svg");
+        assert_eq!(json["export_formats"][2], "pdf");
+        assert_eq!(json["schemas"]["project"], PROJECT_SCHEMA_VERSION);
+        assert!(json["commands"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|c| c == "get_capabilities"));
+    }
+}
@@ -0,0 +1,339 @@
+//! Quick capture.
+//!
+//! A global shortcut brings up the small quick-add window from anywhere;
+//! what's typed there goes through [`quick_add_task`], which appends the task
+//! to a project without opening it and hands back the new schedule. The
+//! text is a task name with optional extras: `#tag`, `!date` to anchor it
+//! (`!2026-03-20`, `!friday`, `!+2w`), and a duration such as `3h` or `2d`
+//! (one day when left out).
+//!
+//! [`parse_task_entry`] reads looser, conversational text ("Write report,
+//! 3 days, after Literature review, due May 12") for the editor to confirm
+//! before adding the task.
+
+use crate::cache::ScheduleCache;
+use crate::import::{new_task, parse_duration};
+use crate::project::load_project;
+use crate::scheduler::{ScheduleResult, Task, TaskDuration};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+/// Label of the capture window in tauri.conf.json.
+pub const WINDOW: &str = "quick-add";
+
+/// What was captured, and the project's schedule with it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuickAddResult {
+    pub task: Task,
+    pub schedule: ScheduleResult,
+}
+
+/// Splits quick-add text into a task and its anchor date, if any.
+pub fn parse_quick_add(text: &str, now: NaiveDateTime) -> Result<(Task, Option<String>), String> {
+    let mut name = Vec::new();
+    let mut tags = Vec::new();
+    let mut anchor = None;
+    let mut duration = None;
+    for word in text.split_whitespace() {
+        if let Some(tag) = word.strip_prefix('#').filter(|t| !t.is_empty()) {
+            tags.push(tag.to_string());
+        } else if let Some(date) = word.strip_prefix('!').filter(|d| !d.is_empty()) {
+            anchor = Some(crate::dates::normalize(date, now)?);
+        } else if let Some(parsed) = word
+            .contains(|c: char| c.is_ascii_alphabetic())
+            .then(|| parse_duration(word))
+            .flatten()
+        {
+            // A bare number stays in the name ("Read chapter 3")
+            duration = Some(parsed);
+        } else {
+            name.push(word);
+        }
+    }
+    if name.is_empty() {
+        return Err("Task name is empty".to_string());
+    }
+
+    let mut task = new_task(&name.join(" "));
+    task.duration = duration.unwrap_or(TaskDuration::days(1));
+    task.tags = tags;
+    Ok((task, anchor))
+}
+
+/// Appends a task parsed from `text` to a project and returns the updated
+/// schedule.
+#[tauri::command]
+pub fn quick_add_task(
+    app: AppHandle,
+    project_id: String,
+    text: String,
+) -> Result<QuickAddResult, String> {
+    let (task, anchor) = parse_quick_add(&text, chrono::Local::now().naive_local())?;
+    crate::project::add_task(&app, &project_id, task.clone(), anchor)?;
+
+    let project = load_project(app.clone(), project_id)?;
+    let now = chrono::Local::now().naive_local();
+    let schedule = app
+        .state::<ScheduleCache>()
+        .schedule(&project, now)
+        .map_err(|e| e.to_string())?;
+    Ok(QuickAddResult { task, schedule })
+}
+
+/// Shows the capture window on top of whatever has focus.
+pub fn show(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(WINDOW) {
+        let _ = window.center();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Registers the configured shortcut; the plugin's handler opens the window.
+pub fn register_shortcut(app: &AppHandle) {
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+    let Some(shortcut) = crate::config::load_config(app.clone())
+        .unwrap_or_default()
+        .quick_add_shortcut
+    else {
+        return;
+    };
+    if let Err(e) = app.global_shortcut().register(shortcut.as_str()) {
+        eprintln!(
+            "Failed to register the quick-add shortcut {}: {}",
+            shortcut, e
+        );
+    }
+}
+
+/// A task read from conversational text, for confirmation before it's added.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskEntryParse {
+    pub task: Task,
+    /// Suggested anchor (YYYY-MM-DD, or YYYY-MM-DDTHH:MM:SS when a time is
+    /// given), from "due ..." or "by ...".
+    pub anchor: Option<String>,
+    /// Names after "after" that match no task in the project.
+    pub unresolved: Vec<String>,
+}
+
+/// Words that can start a clause partway through a comma-separated part.
+const KEYWORDS: &[&str] = &["after", "due", "by", "for"];
+
+enum Clause {
+    Duration(TaskDuration),
+    After(Vec<String>),
+    Due(String),
+    Milestone,
+}
+
+/// "3 days", "2w", "an hour", "90 minutes".
+fn parse_natural_duration(text: &str) -> Option<TaskDuration> {
+    let text = text.trim().to_ascii_lowercase();
+    let text = match text.split_once(' ') {
+        Some(("a" | "an" | "one", unit)) => format!("1{}", unit),
+        _ => text.replace(' ', ""),
+    };
+    text.starts_with(|c: char| c.is_ascii_digit())
+        .then(|| parse_duration(&text))
+        .flatten()
+}
+
+/// The task named `name`, or the only one whose name contains it.
+fn find_task<'a>(tasks: &'a [Task], name: &str) -> Option<&'a Task> {
+    let name = name.to_lowercase();
+    if let Some(task) = tasks.iter().find(|t| t.name.to_lowercase() == name) {
+        return Some(task);
+    }
+    let mut matches = tasks
+        .iter()
+        .filter(|t| t.name.to_lowercase().contains(&name));
+    match (matches.next(), matches.next()) {
+        (Some(task), None) => Some(task),
+        _ => None,
+    }
+}
+
+/// Reads a clause. Only `first` clauses (those starting a comma-separated
+/// part) may name unknown dependencies, so "Stand by the door" stays a name.
+fn parse_clause(text: &str, tasks: &[Task], now: NaiveDateTime, first: bool) -> Option<Clause> {
+    let lower = text.to_ascii_lowercase();
+    if lower == "milestone" {
+        return Some(Clause::Milestone);
+    }
+    if let Some(duration) = parse_natural_duration(&lower) {
+        return Some(Clause::Duration(duration));
+    }
+    let (keyword, rest) = text.split_once(' ')?;
+    match keyword.to_ascii_lowercase().as_str() {
+        "for" => parse_natural_duration(rest).map(Clause::Duration),
+        "due" | "by" => crate::dates::normalize(rest, now).ok().map(Clause::Due),
+        "after" => {
+            let names: Vec<String> = rest
+                .split(" and ")
+                .flat_map(|n| n.split('&'))
+                .map(|n| n.trim().to_string())
+                .filter(|n| !n.is_empty())
+                .collect();
+            let known = names.iter().all(|n| find_task(tasks, n).is_some());
+            (!names.is_empty() && (first || known)).then_some(Clause::After(names))
+        }
+        _ => None,
+    }
+}
+
+/// Reads conversational task text against the project's `tasks`.
+pub fn parse_task_text(
+    text: &str,
+    tasks: &[Task],
+    now: NaiveDateTime,
+) -> Result<TaskEntryParse, String> {
+    let mut name = Vec::new();
+    let mut tags = Vec::new();
+    let mut clauses = Vec::new();
+    for part in text.split([',', ';']) {
+        let words: Vec<&str> = part
+            .split_whitespace()
+            .filter(|w| match w.strip_prefix('#').filter(|t| !t.is_empty()) {
+                Some(tag) => {
+                    tags.push(tag.to_string());
+                    false
+                }
+                None => true,
+            })
+            .collect();
+        let mut cuts: Vec<usize> = (1..words.len())
+            .filter(|&i| KEYWORDS.contains(&words[i].to_ascii_lowercase().as_str()))
+            .collect();
+        cuts.insert(0, 0);
+        cuts.push(words.len());
+        // Segments that aren't clauses after all are part of the name
+        let mut part_name: Vec<&str> = Vec::new();
+        for (i, window) in cuts.windows(2).enumerate() {
+            let segment = &words[window[0]..window[1]];
+            if segment.is_empty() {
+                continue;
+            }
+            match parse_clause(&segment.join(" "), tasks, now, i == 0) {
+                Some(clause) => clauses.push(clause),
+                None => part_name.extend(segment),
+            }
+        }
+        if !part_name.is_empty() {
+            name.push(part_name.join(" "));
+        }
+    }
+    if name.is_empty() {
+        return Err("Task name is empty".to_string());
+    }
+
+    let mut task = new_task(&name.join(", "));
+    task.duration = TaskDuration::days(1);
+    task.tags = tags;
+    let mut anchor = None;
+    let mut unresolved = Vec::new();
+    for clause in clauses {
+        match clause {
+            Clause::Duration(duration) => task.duration = duration,
+            Clause::Due(date) => anchor = Some(date),
+            Clause::Milestone => {
+                task.is_milestone = true;
+                task.duration = TaskDuration::minutes(0);
+            }
+            Clause::After(names) => {
+                for name in names {
+                    match find_task(tasks, &name) {
+                        Some(dependency) if !task.dependencies.contains(&dependency.id) => {
+                            task.dependencies.push(dependency.id.clone())
+                        }
+                        Some(_) => {}
+                        None => unresolved.push(name),
+                    }
+                }
+            }
+        }
+    }
+    Ok(TaskEntryParse {
+        task,
+        anchor,
+        unresolved,
+    })
+}
+
+/// Parses conversational task text for confirmation; nothing is saved.
+/// Dependencies are looked up in the project, when one is given.
+#[tauri::command]
+pub fn parse_task_entry(
+    app: AppHandle,
+    project_id: Option<String>,
+    text: String,
+) -> Result<TaskEntryParse, String> {
+    let tasks = match project_id {
+        Some(id) => load_project(app, id)?.tasks,
+        None => Vec::new(),
+    };
+    parse_task_text(&text, &tasks, chrono::Local::now().naive_local())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_quick_add_text() {
+        let now = crate::scheduler::parse_date_string("2026-03-10T12:00:00").unwrap();
+        let (task, anchor) = parse_quick_add("Draft intro 3h #writing !2026-03-20", now).unwrap();
+        assert_eq!(task.name, "Draft intro");
+        assert_eq!(task.duration, TaskDuration::hours(3));
+        assert_eq!(task.tags, vec!["writing"]);
+        assert_eq!(anchor.as_deref(), Some("2026-03-20"));
+
+        let (task, anchor) = parse_quick_add("Read chapter 3", now).unwrap();
+        assert_eq!(task.name, "Read chapter 3");
+        assert_eq!(task.duration, TaskDuration::days(1));
+        assert!(anchor.is_none());
+
+        assert!(parse_quick_add("  #writing 2h ", now).is_err());
+        assert!(parse_quick_add("Ship !someday", now).is_err());
+        let (_, anchor) = parse_quick_add("Ship !+2w", now).unwrap();
+        assert_eq!(anchor.as_deref(), Some("2026-03-24"));
+    }
+
+    #[test]
+    fn test_parses_conversational_text() {
+        // A Monday
+        let now = crate::scheduler::parse_date_string("2026-06-01T09:00:00").unwrap();
+        let tasks = vec![Task {
+            id: "lit".into(),
+            name: "Literature review".into(),
+            ..Default::default()
+        }];
+        let parse = parse_task_text(
+            "Write report, 3 days, after literature review and Outline, due May 12",
+            &tasks,
+            now,
+        )
+        .unwrap();
+        assert_eq!(parse.task.name, "Write report");
+        assert_eq!(parse.task.duration, TaskDuration::days(3));
+        assert_eq!(parse.task.dependencies, vec!["lit"]);
+        assert_eq!(parse.unresolved, vec!["Outline"]);
+        // May 12 has passed this year
+        assert_eq!(parse.anchor.as_deref(), Some("2027-05-12"));
+
+        // Keywords inside a part only count when the rest reads as a clause
+        let parse =
+            parse_task_text("Stand by the door for 2 hours by friday", &tasks, now).unwrap();
+        assert_eq!(parse.task.name, "Stand by the door");
+        assert_eq!(parse.task.duration, TaskDuration::hours(2));
+        assert_eq!(parse.anchor.as_deref(), Some("2026-06-05"));
+
+        let parse = parse_task_text("Submit after Literature, milestone", &tasks, now).unwrap();
+        assert_eq!(parse.task.name, "Submit");
+        assert!(parse.task.is_milestone);
+        assert_eq!(parse.task.dependencies, vec!["lit"]);
+
+        assert!(parse_task_text("3 days, due tomorrow", &tasks, now).is_err());
+    }
+}
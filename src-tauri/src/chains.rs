@@ -0,0 +1,149 @@
+//! Per-anchor chain slack.
+//!
+//! Groups tasks into the stream of work feeding each anchor and reports how
+//! much room each stream has, so the anchor driving the crunch stands out.
+
+use crate::cache::ScheduleCache;
+use crate::project::load_project;
+use crate::scheduler::{ScheduleRequest, ScheduledTask};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use tauri::{AppHandle, Manager};
+
+/// The work feeding one anchor.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ChainSlack {
+    pub anchor_id: String,
+    pub anchor_name: String,
+    /// The anchor and every task it depends on, in schedule order.
+    pub task_ids: Vec<String>,
+    /// Total slack of the chain: how far the anchor's own work can slip
+    /// before it becomes critical. `None` once the anchor is done.
+    pub slack_minutes: Option<i64>,
+    /// The incomplete task in the chain with the least slack, which may be
+    /// shared with a tighter chain.
+    pub tightest_task_id: Option<String>,
+    /// True for the anchor(s) with the least slack in the project.
+    pub is_driving: bool,
+}
+
+/// One entry per anchor in `request`, tightest first.
+pub fn chain_slack(request: &ScheduleRequest, schedule: &[ScheduledTask]) -> Vec<ChainSlack> {
+    let rows: HashMap<&str, &ScheduledTask> = schedule.iter().map(|t| (t.id.as_str(), t)).collect();
+    let providers: HashMap<&str, &Vec<String>> = request
+        .tasks
+        .iter()
+        .map(|t| (t.id.as_str(), &t.dependencies))
+        .collect();
+    let anchor_ids: BTreeSet<&String> = request
+        .anchors
+        .keys()
+        .chain(request.anchor_windows.keys())
+        .collect();
+
+    let mut chains: Vec<ChainSlack> = anchor_ids
+        .into_iter()
+        .filter_map(|anchor_id| {
+            let anchor = rows.get(anchor_id.as_str())?;
+            let mut stream: HashSet<&str> = HashSet::new();
+            let mut stack = vec![anchor_id.as_str()];
+            while let Some(id) = stack.pop() {
+                if stream.insert(id) {
+                    if let Some(deps) = providers.get(id) {
+                        stack.extend(deps.iter().map(String::as_str));
+                    }
+                }
+            }
+
+            let tightest = schedule
+                .iter()
+                .filter(|t| stream.contains(t.id.as_str()) && !t.completed)
+                .min_by_key(|t| t.slack_minutes);
+            Some(ChainSlack {
+                anchor_id: anchor_id.clone(),
+                anchor_name: anchor.name.clone(),
+                task_ids: schedule
+                    .iter()
+                    .filter(|t| stream.contains(t.id.as_str()))
+                    .map(|t| t.id.clone())
+                    .collect(),
+                slack_minutes: (!anchor.completed).then_some(anchor.slack_minutes),
+                tightest_task_id: tightest.map(|t| t.id.clone()),
+                is_driving: false,
+            })
+        })
+        .collect();
+
+    // Finished chains sort last
+    chains.sort_by_key(|c| (c.slack_minutes.is_none(), c.slack_minutes));
+    let least = chains.first().and_then(|c| c.slack_minutes);
+    for chain in &mut chains {
+        chain.is_driving = least.is_some() && chain.slack_minutes == least;
+    }
+    chains
+}
+
+/// Chain slack per anchor for a saved project as of now.
+#[tauri::command]
+pub fn get_chain_slack(app: AppHandle, project_id: String) -> Result<Vec<ChainSlack>, String> {
+    let project = load_project(app.clone(), project_id)?;
+    let now = chrono::Local::now().naive_local();
+    let result = app
+        .state::<ScheduleCache>()
+        .schedule(&project, now)
+        .map_err(|e| e.to_string())?;
+    Ok(chain_slack(
+        &project.schedule_request(),
+        &result.scheduled_tasks,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{calculate_backwards_schedule, Task, TaskDuration};
+
+    fn task(id: &str, days: i64, deps: &[&str]) -> Task {
+        Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(days),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_tightest_anchor_drives() {
+        // beta pulls the shared spec earlier, leaving launch a day to spare
+        let request = ScheduleRequest {
+            tasks: vec![
+                task("spec", 2, &[]),
+                task("build", 3, &["spec"]),
+                task("launch", 1, &["build"]),
+                task("beta", 1, &["spec"]),
+            ],
+            anchors: [
+                ("launch".into(), "2026-01-10T00:00:00".into()),
+                ("beta".into(), "2026-01-06T00:00:00".into()),
+            ]
+            .into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+        let result = calculate_backwards_schedule(request.clone()).expect("Schedule failed");
+
+        let chains = chain_slack(&request, &result.scheduled_tasks);
+
+        assert_eq!(chains.len(), 2);
+        assert_eq!(chains[0].anchor_id, "beta");
+        assert_eq!(chains[0].task_ids, vec!["spec", "beta"]);
+        assert_eq!(chains[0].slack_minutes, Some(0));
+        assert!(chains[0].is_driving);
+        assert_eq!(chains[1].anchor_id, "launch");
+        assert_eq!(chains[1].task_ids, vec!["spec", "build", "launch"]);
+        assert_eq!(chains[1].slack_minutes, Some(24 * 60));
+        assert!(!chains[1].is_driving);
+        assert_eq!(chains[1].tightest_task_id.as_deref(), Some("spec"));
+    }
+}
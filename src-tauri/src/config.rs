@@ -1,3 +1,8 @@
+use crate::agenda::WorkingHours;
+use crate::api::ApiSettings;
+use crate::caldav::CalDavLink;
+use crate::google::{GoogleCalendarLink, GoogleSettings};
+use crate::ics::CalendarFeed;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -6,12 +11,155 @@ use tauri::{AppHandle, Emitter, Manager};
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
     pub theme: String, // "light", "dark", "system"
+    /// Outgoing mail server used by projects that route notifications to email.
+    #[serde(default)]
+    pub smtp: Option<SmtpSettings>,
+    #[serde(default)]
+    pub calendar_feeds: Vec<CalendarFeed>,
+    /// CalDAV calendars projects sync with.
+    #[serde(default)]
+    pub caldav: Vec<CalDavLink>,
+    /// OAuth client for the Google Calendar connector.
+    #[serde(default)]
+    pub google: Option<GoogleSettings>,
+    /// Projects pushed to Google calendars.
+    #[serde(default)]
+    pub google_links: Vec<GoogleCalendarLink>,
+    /// Local HTTP API; disabled when unset. Read at launch.
+    #[serde(default)]
+    pub api: Option<ApiSettings>,
+    /// When the open project's schedule is recalculated.
+    #[serde(default)]
+    pub recalculation: RecalculationTrigger,
+    /// Hours and daily capacity used when building agendas.
+    #[serde(default)]
+    pub working_hours: WorkingHours,
+    /// Days deleted projects stay in the trash; 0 keeps them until purged.
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Folder projects are kept in, e.g. inside Dropbox, iCloud Drive or
+    /// Syncthing; `<app data>/projects` when unset. Existing projects aren't
+    /// moved. The file watcher picks up a new folder at the next launch.
+    #[serde(default)]
+    pub projects_dir: Option<String>,
+    /// Projects kept at the top of the dashboard and widget picker.
+    #[serde(default)]
+    pub pinned_projects: Vec<String>,
+    /// Manual project order, see [`crate::project::reorder_projects`].
+    #[serde(default)]
+    pub project_order: Vec<String>,
+    /// Which tasks the desktop widget lists.
+    #[serde(default)]
+    pub widget: WidgetSettings,
+    /// Notifications about tasks past their late finish.
+    #[serde(default)]
+    pub overdue: OverdueSettings,
+    /// Global shortcut for the quick-add window, e.g. "CommandOrControl+Shift+Space";
+    /// none when unset. Read at launch.
+    #[serde(default = "default_quick_add_shortcut")]
+    pub quick_add_shortcut: Option<String>,
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+fn default_quick_add_shortcut() -> Option<String> {
+    Some("CommandOrControl+Shift+Space".to_string())
+}
+
+/// When the editor recalculates a schedule. Large projects can switch away
+/// from recalculating on every edit.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum RecalculationTrigger {
+    #[default]
+    OnEdit,
+    /// After each (debounced) save.
+    OnSave,
+    /// Only through the `recalculate` command.
+    Manual,
+    /// Every `interval_seconds` while there are unscheduled changes.
+    Timer { interval_seconds: u64 },
+}
+
+/// Limits on the tasks the widget shows. The focus view always shows the
+/// task in progress, whatever these say.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct WidgetSettings {
+    /// Length of the "Up Next" list.
+    pub max_tasks: usize,
+    /// Days ahead the month view covers; unlimited when unset.
+    pub lookahead_days: Option<u32>,
+    /// List milestones only.
+    pub milestones_only: bool,
+    /// Leave tasks starting more than this many days out off the "Up Next"
+    /// list; active and overdue tasks are always listed.
+    pub hide_future_after_days: Option<u32>,
+}
+
+impl Default for WidgetSettings {
+    fn default() -> Self {
+        Self {
+            max_tasks: 5,
+            lookahead_days: None,
+            milestones_only: false,
+            hide_future_after_days: None,
+        }
+    }
+}
+
+/// How tasks past the date they had to finish by are followed up, see
+/// [`crate::monitor`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct OverdueSettings {
+    /// Notify once as each task becomes overdue.
+    pub notify: bool,
+    /// Then, once a day from this hour, a summary per project of what's
+    /// still overdue; never when unset.
+    pub daily_summary_hour: Option<u32>,
+}
+
+impl Default for OverdueSettings {
+    fn default() -> Self {
+        Self {
+            notify: true,
+            daily_summary_hour: Some(9),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SmtpSettings {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    /// Sender address, e.g. "Anchor <anchor@example.com>".
+    pub from: String,
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             theme: "system".to_string(),
+            smtp: None,
+            calendar_feeds: Vec::new(),
+            caldav: Vec::new(),
+            google: None,
+            google_links: Vec::new(),
+            api: None,
+            recalculation: RecalculationTrigger::OnEdit,
+            working_hours: WorkingHours::default(),
+            trash_retention_days: default_trash_retention_days(),
+            projects_dir: None,
+            pinned_projects: Vec::new(),
+            project_order: Vec::new(),
+            widget: WidgetSettings::default(),
+            overdue: OverdueSettings::default(),
+            quick_add_shortcut: default_quick_add_shortcut(),
         }
     }
 }
@@ -48,3 +196,25 @@ pub fn save_config(app: AppHandle, config: AppConfig) -> Result<(), String> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recalculation_defaults_to_on_edit() {
+        let config: AppConfig = serde_json::from_str(r#"{"theme": "dark"}"#).unwrap();
+        assert_eq!(config.recalculation, RecalculationTrigger::OnEdit);
+
+        let config: AppConfig = serde_json::from_str(
+            r#"{"theme": "dark", "recalculation": {"mode": "timer", "interval_seconds": 30}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.recalculation,
+            RecalculationTrigger::Timer {
+                interval_seconds: 30
+            }
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! Date entry.
+//!
+//! Dates are typed many ways: "next Friday", "end of March", "+6 weeks",
+//! "2026-03-01 17:00". [`normalize`] turns them into the two forms
+//! projects store (`YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SS`). Relative dates
+//! are resolved once, on entry, so a saved anchor doesn't drift; the
+//! scheduler only ever reads stored forms.
+
+use crate::project::Project;
+use crate::scheduler::parse_date_string;
+use chrono::{Datelike, Month, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Weekday};
+
+/// Amount and unit of "+6 weeks", "3d" or "an hour".
+fn parse_offset(text: &str) -> Option<(i64, &str)> {
+    let text = text.trim();
+    let (number, unit) = match text.split_once(' ') {
+        Some(("a" | "an" | "one", unit)) => ("1", unit),
+        _ => text.split_at(text.find(|c: char| !c.is_ascii_digit())?),
+    };
+    Some((number.parse().ok()?, unit.trim()))
+}
+
+/// `date` moved by an offset in days, weeks, months or years; `None` when
+/// out of range.
+fn add_offset(date: NaiveDate, amount: i64, unit: &str) -> Option<NaiveDate> {
+    let months = |n: i64| u32::try_from(n).ok().map(Months::new);
+    match unit {
+        "d" | "day" | "days" => date.checked_add_signed(TimeDelta::try_days(amount)?),
+        "w" | "wk" | "week" | "weeks" => date.checked_add_signed(TimeDelta::try_weeks(amount)?),
+        "mo" | "month" | "months" => date.checked_add_months(months(amount)?),
+        "y" | "yr" | "year" | "years" => date.checked_add_months(months(amount.checked_mul(12)?)?),
+        _ => None,
+    }
+}
+
+/// `now` moved by an offset in hours or minutes; `None` when out of range.
+fn add_time_offset(now: NaiveDateTime, amount: i64, unit: &str) -> Option<NaiveDateTime> {
+    let offset = match unit {
+        "h" | "hr" | "hrs" | "hour" | "hours" => TimeDelta::try_hours(amount)?,
+        "m" | "min" | "mins" | "minute" | "minutes" => TimeDelta::try_minutes(amount)?,
+        _ => return None,
+    };
+    now.checked_add_signed(offset)
+}
+
+fn last_day_of_month(year: i32, month: u32) -> Option<NaiveDate> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    first.checked_add_months(Months::new(1))?.pred_opt()
+}
+
+/// The next `month`/`day` on or after `today`, or in `year` when given.
+fn month_day(month: Month, day: u32, year: Option<&str>, today: NaiveDate) -> Option<NaiveDate> {
+    let month = month.number_from_month();
+    match year {
+        Some(year) => NaiveDate::from_ymd_opt(year.parse().ok()?, month, day),
+        None => NaiveDate::from_ymd_opt(today.year(), month, day)
+            .filter(|date| *date >= today)
+            .or_else(|| NaiveDate::from_ymd_opt(today.year() + 1, month, day)),
+    }
+}
+
+/// A calendar date written in words, relative to `today`: "tomorrow",
+/// "friday", "next friday", "in 3 days", "+6 weeks", "end of March",
+/// "end of month", "May 12", "12 May 2027" or "2026-05-12". Dates without a
+/// year are the next one to come.
+pub(crate) fn natural_date(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let text = text.trim().to_lowercase().replace(',', " ");
+    if let Ok(date) = NaiveDate::parse_from_str(&text, "%Y-%m-%d") {
+        return Some(date);
+    }
+    if let Some(offset) = text.strip_prefix('+') {
+        let (amount, unit) = parse_offset(offset)?;
+        return add_offset(today, amount, unit);
+    }
+    let words: Vec<&str> = text.split_whitespace().collect();
+    match words[..] {
+        ["today"] => Some(today),
+        ["tomorrow"] => today.succ_opt(),
+        ["yesterday"] => today.pred_opt(),
+        ["in", ..] => {
+            let (amount, unit) = parse_offset(text.strip_prefix("in ")?)?;
+            add_offset(today, amount, unit)
+        }
+        ["next" | "this", day] | [day] if day.parse::<Weekday>().is_ok() => {
+            let weekday = day.parse::<Weekday>().ok()?;
+            let ahead = (6 + weekday.num_days_from_monday() as i64
+                - today.weekday().num_days_from_monday() as i64)
+                % 7
+                + 1;
+            today.checked_add_signed(TimeDelta::days(ahead))
+        }
+        ["end", "of", "week"] => {
+            // Weeks run Sunday to Saturday, as the calendars draw them
+            Some(today.week(Weekday::Sun).last_day())
+        }
+        ["end", "of", "month"] => last_day_of_month(today.year(), today.month()),
+        ["end", "of", "year"] => NaiveDate::from_ymd_opt(today.year(), 12, 31),
+        ["end", "of", month, ref year @ ..] if year.len() <= 1 => {
+            let month = month.parse::<Month>().ok()?.number_from_month();
+            let end = match year.first() {
+                Some(year) => last_day_of_month(year.parse().ok()?, month)?,
+                None => last_day_of_month(today.year(), month)
+                    .filter(|date| *date >= today)
+                    .or_else(|| last_day_of_month(today.year() + 1, month))?,
+            };
+            Some(end)
+        }
+        [a, b] | [a, b, _] => {
+            let year = words.get(2).copied();
+            let (month, day) = match a.parse::<Month>() {
+                Ok(month) => (month, b),
+                Err(_) => (b.parse::<Month>().ok()?, a),
+            };
+            let day = day.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+            month_day(month, day.parse().ok()?, year, today)
+        }
+        _ => None,
+    }
+}
+
+/// "17:00", "5pm" or "5:30pm".
+fn parse_time(text: &str) -> Option<NaiveTime> {
+    let text = text.to_lowercase();
+    if let Ok(time) = NaiveTime::parse_from_str(&text, "%H:%M") {
+        return Some(time);
+    }
+    let (clock, afternoon) = match (text.strip_suffix("am"), text.strip_suffix("pm")) {
+        (Some(clock), _) => (clock, false),
+        (_, Some(clock)) => (clock, true),
+        _ => return None,
+    };
+    let (hour, minute) = clock.split_once(':').unwrap_or((clock, "0"));
+    let hour: u32 = hour.parse().ok()?;
+    if !(1..=12).contains(&hour) {
+        return None;
+    }
+    NaiveTime::from_hms_opt(
+        hour % 12 + if afternoon { 12 } else { 0 },
+        minute.parse().ok()?,
+        0,
+    )
+}
+
+/// Normalizes a typed date to a stored form, resolving relative ones
+/// against `now`. Stored forms come back unchanged, so this is safe to run
+/// on every save.
+pub fn normalize(text: &str, now: NaiveDateTime) -> Result<String, String> {
+    let text = text.trim();
+    if NaiveDate::parse_from_str(text, "%Y-%m-%d").is_ok() {
+        return Ok(text.to_string());
+    }
+    if let Ok(at) = parse_date_string(text) {
+        return Ok(at.format("%Y-%m-%dT%H:%M:%S").to_string());
+    }
+    let unrecognized = || {
+        format!(
+            "Could not understand the date '{}'; try 2026-03-01, 2026-03-01 17:00, \
+             next friday, end of March or +6 weeks",
+            text
+        )
+    };
+
+    // "+4 hours" and "in 30 minutes" are times, not dates
+    let lower = text.to_lowercase();
+    let offset = lower
+        .strip_prefix('+')
+        .or_else(|| lower.strip_prefix("in "))
+        .and_then(parse_offset);
+    if let Some(at) = offset.and_then(|(amount, unit)| add_time_offset(now, amount, unit)) {
+        return Ok(at.format("%Y-%m-%dT%H:%M:%S").to_string());
+    }
+
+    // An optional time of day at the end: "friday 17:00", "May 12 at 5pm"
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let (date, time) = match words.split_last() {
+        Some((last, rest)) if !rest.is_empty() && parse_time(last).is_some() => {
+            let rest = match rest.split_last() {
+                Some((at, before)) if at.eq_ignore_ascii_case("at") => before,
+                _ => rest,
+            };
+            (rest.join(" "), parse_time(last))
+        }
+        _ => (text.to_string(), None),
+    };
+    let date = natural_date(&date, now.date()).ok_or_else(unrecognized)?;
+    Ok(match time {
+        Some(time) => date.and_time(time).format("%Y-%m-%dT%H:%M:%S").to_string(),
+        None => date.format("%Y-%m-%d").to_string(),
+    })
+}
+
+/// Normalizes the dates a project stores: anchors, anchor windows, pinned
+/// starts and allowed windows. Dates that can't be read are left for the
+/// scheduler to report.
+pub(crate) fn normalize_project(project: &mut Project, now: NaiveDateTime) {
+    let fix = |date: &mut String| {
+        if let Ok(normalized) = normalize(date, now) {
+            *date = normalized;
+        }
+    };
+    project.anchors.values_mut().for_each(fix);
+    for window in project.anchor_windows.values_mut() {
+        fix(&mut window.earliest);
+        fix(&mut window.latest);
+    }
+    for task in &mut project.tasks {
+        if let Some(start) = &mut task.pinned_start {
+            fix(start);
+        }
+        for window in &mut task.only_during {
+            fix(&mut window.start);
+            fix(&mut window.end);
+        }
+    }
+}
+
+/// Normalizes a date typed into the editor; see [`normalize`].
+#[tauri::command]
+pub fn normalize_date(text: String) -> Result<String, String> {
+    normalize(&text, chrono::Local::now().naive_local())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalizes_dates() {
+        // A Wednesday afternoon
+        let now = parse_date_string("2026-04-15T14:30:00").unwrap();
+        let normalized = |text: &str| normalize(text, now).unwrap();
+
+        assert_eq!(normalized("2026-03-01"), "2026-03-01");
+        assert_eq!(normalized("2026-03-01T17:00:00"), "2026-03-01T17:00:00");
+        assert_eq!(normalized("2026-03-01 17:00"), "2026-03-01T17:00:00");
+        assert_eq!(normalized("next Friday"), "2026-04-17");
+        assert_eq!(normalized("wednesday"), "2026-04-22");
+        assert_eq!(normalized("friday at 5pm"), "2026-04-17T17:00:00");
+        assert_eq!(normalized("tomorrow 9:30"), "2026-04-16T09:30:00");
+        assert_eq!(normalized("+6 weeks"), "2026-05-27");
+        assert_eq!(normalized("in 2 months"), "2026-06-15");
+        assert_eq!(normalized("+4h"), "2026-04-15T18:30:00");
+        assert_eq!(normalized("end of March"), "2027-03-31");
+        assert_eq!(normalized("end of April"), "2026-04-30");
+        assert_eq!(normalized("end of February 2028"), "2028-02-29");
+        assert_eq!(normalized("end of week"), "2026-04-18");
+        assert_eq!(normalized("May 12"), "2026-05-12");
+        assert_eq!(normalized("12th March, 2027"), "2027-03-12");
+
+        assert!(normalize("someday", now).is_err());
+        assert!(normalize("friday 13pm", now).is_err());
+        // Out of range offsets are refused rather than overflowing
+        assert!(normalize("+999999999999 days", now).is_err());
+        assert!(normalize("+999999999999 weeks", now).is_err());
+        assert!(normalize("+999999999999999999 years", now).is_err());
+        assert!(normalize("+9999999999 hours", now).is_err());
+        assert!(normalize("in 999999999999999 minutes", now).is_err());
+    }
+}
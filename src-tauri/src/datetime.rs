@@ -0,0 +1,137 @@
+//! Calendar-aware date arithmetic.
+//!
+//! Schedules are expressed in wall-clock time. Day-based durations move by
+//! civil days (same clock time on another date) while minute-based ones
+//! measure real elapsed time, so neither drifts an hour around DST changes.
+
+use chrono::{Days, Duration, LocalResult, NaiveDateTime, Offset, TimeZone};
+use chrono_tz::Tz;
+
+/// The time zone wall-clock schedule times are interpreted in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Zone {
+    /// The system's local zone.
+    Local,
+    Named(Tz),
+}
+
+/// Wall-clock → UTC. Ambiguous times (clocks falling back) resolve to the
+/// first occurrence; times skipped by clocks springing forward move past the gap.
+fn wall_to_utc<T: TimeZone>(tz: &T, wall: NaiveDateTime) -> NaiveDateTime {
+    match tz.from_local_datetime(&wall) {
+        LocalResult::Single(dt) => dt.naive_utc(),
+        LocalResult::Ambiguous(first, _) => first.naive_utc(),
+        LocalResult::None => {
+            let before = tz
+                .offset_from_utc_datetime(&(wall - Duration::days(1)))
+                .fix();
+            wall - Duration::seconds(before.local_minus_utc() as i64)
+        }
+    }
+}
+
+fn utc_to_wall<T: TimeZone>(tz: &T, utc: NaiveDateTime) -> NaiveDateTime {
+    tz.from_utc_datetime(&utc).naive_local()
+}
+
+impl Zone {
+    /// Parses an IANA zone name, falling back to the local zone when absent.
+    pub fn parse(name: Option<&str>) -> Result<Self, String> {
+        match name {
+            None | Some("") => Ok(Self::Local),
+            Some(name) => name
+                .parse::<Tz>()
+                .map(Self::Named)
+                .map_err(|_| format!("Unknown time zone '{}'", name)),
+        }
+    }
+
+    pub(crate) fn to_utc(self, wall: NaiveDateTime) -> NaiveDateTime {
+        match self {
+            Self::Local => wall_to_utc(&chrono::Local, wall),
+            Self::Named(tz) => wall_to_utc(&tz, wall),
+        }
+    }
+
+    pub(crate) fn to_wall(self, utc: NaiveDateTime) -> NaiveDateTime {
+        match self {
+            Self::Local => utc_to_wall(&chrono::Local, utc),
+            Self::Named(tz) => utc_to_wall(&tz, utc),
+        }
+    }
+
+    /// Moves a wall-clock time that doesn't exist (inside a DST gap) past the gap.
+    pub fn normalize(self, wall: NaiveDateTime) -> NaiveDateTime {
+        self.to_wall(self.to_utc(wall))
+    }
+
+    /// Adds civil days: the same clock time on a later (or, if negative, earlier) date.
+    pub fn add_days(self, wall: NaiveDateTime, days: i64) -> NaiveDateTime {
+        let shifted = if days >= 0 {
+            wall.checked_add_days(Days::new(days as u64))
+        } else {
+            wall.checked_sub_days(Days::new(days.unsigned_abs()))
+        };
+        self.normalize(shifted.unwrap_or(wall))
+    }
+
+    /// Adds real elapsed minutes (negative to go back).
+    pub fn add_minutes(self, wall: NaiveDateTime, minutes: i64) -> NaiveDateTime {
+        self.to_wall(self.to_utc(wall) + Duration::minutes(minutes))
+    }
+
+    /// Real elapsed minutes from `from` to `to`.
+    pub fn minutes_between(self, from: NaiveDateTime, to: NaiveDateTime) -> i64 {
+        (self.to_utc(to) - self.to_utc(from)).num_minutes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dt(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    // New York springs forward at 02:00 on 2026-03-08.
+    fn new_york() -> Zone {
+        Zone::parse(Some("America/New_York")).unwrap()
+    }
+
+    #[test]
+    fn test_civil_days_keep_clock_time_across_dst() {
+        let zone = new_york();
+        assert_eq!(
+            zone.add_days(dt("2026-03-09T09:00:00"), -2),
+            dt("2026-03-07T09:00:00")
+        );
+        // ...even though only 47 hours actually elapse.
+        assert_eq!(
+            zone.minutes_between(dt("2026-03-07T09:00:00"), dt("2026-03-09T09:00:00")),
+            47 * 60
+        );
+    }
+
+    #[test]
+    fn test_minutes_are_elapsed_time_across_dst() {
+        let zone = new_york();
+        assert_eq!(
+            zone.add_minutes(dt("2026-03-08T03:30:00"), -60),
+            dt("2026-03-08T01:30:00")
+        );
+    }
+
+    #[test]
+    fn test_times_in_gap_move_forward() {
+        assert_eq!(
+            new_york().normalize(dt("2026-03-08T02:30:00")),
+            dt("2026-03-08T03:30:00")
+        );
+    }
+
+    #[test]
+    fn test_unknown_zone_is_rejected() {
+        assert!(Zone::parse(Some("Mars/Olympus_Mons")).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+//! `anchor://` links.
+//!
+//! - `anchor://project/<id>` opens a project;
+//! - `anchor://project/<id>/task/<id>` opens a task's details;
+//! - `anchor://new?name=...&deadline=...` creates a project, with a deadline
+//!   milestone anchored on `deadline` when given.
+//!
+//! Each link is routed to a [`DeepLinkTarget`] sent to the main window as a
+//! `deep-link` event. The target is also kept until [`take_deep_link`], for
+//! links that launched the app before the window was listening. Bad links
+//! emit `deep-link-failed`.
+
+use crate::project::{create_project, save_project};
+use crate::scheduler::{Task, TaskDuration};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use uuid::Uuid;
+
+pub const SCHEME: &str = "anchor";
+
+/// The last routed link, until the frontend takes it.
+static PENDING: Mutex<Option<DeepLinkTarget>> = Mutex::new(None);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeepLink {
+    Project {
+        project_id: String,
+    },
+    Task {
+        project_id: String,
+        task_id: String,
+    },
+    NewProject {
+        name: String,
+        deadline: Option<String>,
+    },
+}
+
+/// Where the main window should go.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeepLinkTarget {
+    pub project_id: String,
+    pub task_id: Option<String>,
+}
+
+/// Payload of the `deep-link-failed` event.
+#[derive(Debug, Serialize, Clone)]
+pub struct DeepLinkFailure {
+    pub url: String,
+    pub error: String,
+}
+
+/// Decodes `%XX` escapes, and `+` as a space when `query`.
+fn decode(s: &str, query: bool) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        out.push(byte);
+                        i += 3;
+                        continue;
+                    }
+                    Err(_) => out.push(b'%'),
+                }
+            }
+            b'+' if query => out.push(b' '),
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Parses an `anchor://` link.
+pub fn parse(url: &str) -> Result<DeepLink, String> {
+    let rest = url
+        .split_once("://")
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case(SCHEME))
+        .map(|(_, rest)| rest)
+        .ok_or_else(|| format!("Not an {}:// link", SCHEME))?;
+    let rest = rest.split('#').next().unwrap_or(rest);
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| decode(s, false))
+        .collect();
+    let param = |key: &str| {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('=').or(Some((pair, ""))))
+            .find(|(k, _)| decode(k, true) == key)
+            .map(|(_, v)| decode(v, true))
+            .filter(|v| !v.trim().is_empty())
+    };
+
+    match segments.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["project", project_id] => Ok(DeepLink::Project {
+            project_id: project_id.to_string(),
+        }),
+        ["project", project_id, "task", task_id] => Ok(DeepLink::Task {
+            project_id: project_id.to_string(),
+            task_id: task_id.to_string(),
+        }),
+        ["new"] => {
+            let now = chrono::Local::now().naive_local();
+            let deadline = param("deadline")
+                .map(|deadline| crate::dates::normalize(&deadline, now))
+                .transpose()?;
+            Ok(DeepLink::NewProject {
+                name: param("name").unwrap_or_else(|| "New project".to_string()),
+                deadline,
+            })
+        }
+        _ => Err(format!("Unknown link: {}", url)),
+    }
+}
+
+/// Carries out a link, returning where to go.
+fn route(app: &AppHandle, link: DeepLink) -> Result<DeepLinkTarget, String> {
+    match link {
+        DeepLink::Project { project_id } => Ok(DeepLinkTarget {
+            project_id,
+            task_id: None,
+        }),
+        DeepLink::Task {
+            project_id,
+            task_id,
+        } => Ok(DeepLinkTarget {
+            project_id,
+            task_id: Some(task_id),
+        }),
+        DeepLink::NewProject { name, deadline } => {
+            let mut project = create_project(app.clone(), name)?;
+            if let Some(deadline) = deadline {
+                let task = Task {
+                    id: Uuid::new_v4().to_string(),
+                    name: "Deadline".to_string(),
+                    duration: TaskDuration::minutes(0),
+                    is_milestone: true,
+                    ..Default::default()
+                };
+                project.anchors.insert(task.id.clone(), deadline);
+                project.tasks.push(task);
+                save_project(app.clone(), project.clone())?;
+            }
+            Ok(DeepLinkTarget {
+                project_id: project.id,
+                task_id: None,
+            })
+        }
+    }
+}
+
+/// Brings the main window up on `target`, as a link to it would.
+pub fn open(app: &AppHandle, target: DeepLinkTarget) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    *PENDING.lock().unwrap_or_else(|e| e.into_inner()) = Some(target.clone());
+    let _ = app.emit("deep-link", target);
+}
+
+/// Handles links the app was opened with.
+pub fn handle(app: &AppHandle, urls: &[String]) {
+    for url in urls {
+        match parse(url).and_then(|link| route(app, link)) {
+            Ok(target) => open(app, target),
+            Err(error) => {
+                let _ = app.emit(
+                    "deep-link-failed",
+                    DeepLinkFailure {
+                        url: url.clone(),
+                        error,
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// The last link's target, if the frontend hasn't taken it yet.
+#[tauri::command]
+pub fn take_deep_link() -> Option<DeepLinkTarget> {
+    PENDING.lock().unwrap_or_else(|e| e.into_inner()).take()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_links() {
+        assert_eq!(
+            parse("anchor://project/p1/task/t%201").unwrap(),
+            DeepLink::Task {
+                project_id: "p1".into(),
+                task_id: "t 1".into()
+            }
+        );
+        assert_eq!(
+            parse("ANCHOR://project/p1/").unwrap(),
+            DeepLink::Project {
+                project_id: "p1".into()
+            }
+        );
+        assert_eq!(
+            parse("anchor://new?name=Tax+return%20%F0%9F%A7%BE&deadline=2026-04-15").unwrap(),
+            DeepLink::NewProject {
+                name: "Tax return 🧾".into(),
+                deadline: Some("2026-04-15".into())
+            }
+        );
+        assert_eq!(
+            parse("anchor://new").unwrap(),
+            DeepLink::NewProject {
+                name: "New project".into(),
+                deadline: None
+            }
+        );
+        assert!(parse("anchor://new?deadline=soon").is_err());
+        assert!(parse("anchor://settings").is_err());
+        assert!(parse("https://project/p1").is_err());
+    }
+}
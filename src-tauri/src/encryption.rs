@@ -0,0 +1,383 @@
+//! Optional encryption at rest.
+//!
+//! When enabled, project files, their archives, journal and trash entries,
+//! the listing index and the sync conflict report are written as sealed
+//! envelopes (ChaCha20-Poly1305) under a key derived from a passphrase with
+//! PBKDF2. The salt and a check value live in `projects/.encryption`, so
+//! synced copies share them. The key is only held in memory: after launch
+//! projects stay locked until `unlock_projects`. Reads go through
+//! [`read_to_string`], which passes plaintext files through, so files from
+//! before encryption was enabled stay readable until
+//! `encrypt_project_files` rewrites them.
+
+use crate::project::get_projects_dir;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use thiserror::Error;
+
+const PBKDF2_ITERATIONS: u32 = 600_000;
+/// Sealed files start with this, so plaintext can be told apart cheaply.
+const ENVELOPE_PREFIX: &str = "{\"anchor_encrypted\"";
+const CHECK_VALUE: &str = "anchor";
+/// Name of the settings file in the projects folder.
+pub(crate) const SETTINGS_FILE: &str = ".encryption";
+
+pub(crate) type Key = [u8; 32];
+
+/// Whether encryption is on, and the key once unlocked. Global rather than
+/// managed state so every reader of project files decrypts the same way.
+static STATE: Mutex<(bool, Option<Key>)> = Mutex::new((false, None));
+
+#[derive(Debug, Error)]
+pub enum EncryptionError {
+    #[error("Projects are encrypted; unlock them with your passphrase first")]
+    Locked,
+    #[error("Wrong passphrase")]
+    WrongPassphrase,
+    #[error("Could not decrypt a file; it is damaged or was encrypted with another passphrase")]
+    Damaged,
+    #[error("Encryption is not enabled")]
+    NotEnabled,
+    #[error("Encryption is already enabled")]
+    AlreadyEnabled,
+    #[error("Encryption failed")]
+    Crypto,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    anchor_encrypted: u32,
+    nonce: String,
+    data: String,
+}
+
+/// Stored in `projects/.encryption`.
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptionSettings {
+    salt: String,
+    iterations: u32,
+    /// [`CHECK_VALUE`] sealed with the key, to verify passphrases.
+    check: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EncryptionStatus {
+    pub enabled: bool,
+    pub unlocked: bool,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Key {
+    let mut key = [0; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(iterations).unwrap_or(NonZeroU32::MIN),
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    key
+}
+
+fn cipher(key: &Key) -> Result<LessSafeKey, EncryptionError> {
+    let key = UnboundKey::new(&CHACHA20_POLY1305, key).map_err(|_| EncryptionError::Crypto)?;
+    Ok(LessSafeKey::new(key))
+}
+
+pub(crate) fn seal_with(key: &Key, plaintext: &str) -> Result<String, EncryptionError> {
+    let mut nonce = [0; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| EncryptionError::Crypto)?;
+    let mut data = plaintext.as_bytes().to_vec();
+    cipher(key)?
+        .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| EncryptionError::Crypto)?;
+    let envelope = Envelope {
+        anchor_encrypted: 1,
+        nonce: STANDARD.encode(nonce),
+        data: STANDARD.encode(data),
+    };
+    serde_json::to_string(&envelope).map_err(|_| EncryptionError::Crypto)
+}
+
+fn open_with(key: &Key, sealed: &str) -> Result<String, EncryptionError> {
+    let envelope: Envelope = serde_json::from_str(sealed).map_err(|_| EncryptionError::Damaged)?;
+    let nonce = STANDARD
+        .decode(envelope.nonce)
+        .ok()
+        .and_then(|nonce| Nonce::try_assume_unique_for_key(&nonce).ok())
+        .ok_or(EncryptionError::Damaged)?;
+    let mut data = STANDARD
+        .decode(envelope.data)
+        .map_err(|_| EncryptionError::Damaged)?;
+    let plaintext = cipher(key)?
+        .open_in_place(nonce, Aad::empty(), &mut data)
+        .map_err(|_| EncryptionError::Damaged)?;
+    String::from_utf8(plaintext.to_vec()).map_err(|_| EncryptionError::Damaged)
+}
+
+fn is_sealed(text: &str) -> bool {
+    text.trim_start().starts_with(ENVELOPE_PREFIX)
+}
+
+/// Settings for a new passphrase, as stored in `projects/.encryption`, and
+/// the key they unlock to.
+pub(crate) fn new_settings(
+    passphrase: &str,
+    iterations: u32,
+) -> Result<(Key, String), EncryptionError> {
+    let mut salt = [0; 16];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| EncryptionError::Crypto)?;
+    let key = derive_key(passphrase, &salt, iterations);
+    let settings = EncryptionSettings {
+        salt: STANDARD.encode(salt),
+        iterations,
+        check: seal_with(&key, CHECK_VALUE)?,
+    };
+    let json = serde_json::to_string_pretty(&settings).map_err(|_| EncryptionError::Crypto)?;
+    Ok((key, json))
+}
+
+fn unlock(settings: &EncryptionSettings, passphrase: &str) -> Result<Key, EncryptionError> {
+    let salt = STANDARD
+        .decode(&settings.salt)
+        .map_err(|_| EncryptionError::Damaged)?;
+    let key = derive_key(passphrase, &salt, settings.iterations);
+    if open_with(&key, &settings.check).ok().as_deref() != Some(CHECK_VALUE) {
+        return Err(EncryptionError::WrongPassphrase);
+    }
+    Ok(key)
+}
+
+/// The key for files sealed under `settings`, the contents of some other
+/// `.encryption` file (e.g. one in a backup).
+pub(crate) fn key_for(settings: &str, passphrase: &str) -> Result<Key, EncryptionError> {
+    let settings = serde_json::from_str(settings).map_err(|_| EncryptionError::Damaged)?;
+    unlock(&settings, passphrase)
+}
+
+/// [`open`] with an explicit key rather than the session's.
+pub(crate) fn open_with_key(key: &Key, text: String) -> Result<String, EncryptionError> {
+    if is_sealed(&text) {
+        open_with(key, &text)
+    } else {
+        Ok(text)
+    }
+}
+
+/// Whether encryption is on but the passphrase hasn't been entered yet.
+pub(crate) fn is_locked() -> bool {
+    matches!(*STATE.lock().unwrap(), (true, None))
+}
+
+/// Seals `text` if encryption is on; refuses to write while locked.
+pub(crate) fn seal(text: String) -> Result<String, EncryptionError> {
+    match *STATE.lock().unwrap() {
+        (false, _) => Ok(text),
+        (true, None) => Err(EncryptionError::Locked),
+        (true, Some(key)) => seal_with(&key, &text),
+    }
+}
+
+/// Opens sealed `text`; plaintext is returned as is.
+pub(crate) fn open(text: String) -> Result<String, EncryptionError> {
+    if !is_sealed(&text) {
+        return Ok(text);
+    }
+    match STATE.lock().unwrap().1 {
+        Some(key) => open_with(&key, &text),
+        None => Err(EncryptionError::Locked),
+    }
+}
+
+/// [`fs::read_to_string`] for files that may be sealed.
+pub(crate) fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    open(fs::read_to_string(path)?).map_err(io::Error::other)
+}
+
+/// [`fs::write`] that seals when encryption is on.
+pub(crate) fn write(path: impl AsRef<Path>, contents: impl Into<String>) -> io::Result<()> {
+    fs::write(path, seal(contents.into()).map_err(io::Error::other)?)
+}
+
+fn settings_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(get_projects_dir(app)?.join(SETTINGS_FILE))
+}
+
+fn read_settings(app: &AppHandle) -> Result<Option<EncryptionSettings>, String> {
+    let path = settings_path(app)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+/// Files under `dir` (recursively) that hold project data.
+fn data_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if path.is_dir() {
+            // Attached files are kept as they were added
+            if name != "attachments" {
+                data_files(&path, files);
+            }
+        } else if name.ends_with(".json") || name.ends_with(".json.bak") {
+            files.push(path);
+        }
+    }
+}
+
+/// Rewrites every data file with `rewrite` where `needed` holds. Returns how
+/// many files were rewritten.
+fn rewrite_all(
+    app: &AppHandle,
+    needed: impl Fn(&str) -> bool,
+    rewrite: impl Fn(String) -> Result<String, EncryptionError>,
+) -> Result<usize, String> {
+    let mut files = Vec::new();
+    data_files(&get_projects_dir(app)?, &mut files);
+    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    files.extend(
+        ["project_index.json", "sync_conflicts.json"]
+            .into_iter()
+            .map(|name| data_dir.join(name))
+            .filter(|path| path.exists()),
+    );
+
+    let mut count = 0;
+    for path in files {
+        let text = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        if !needed(&text) {
+            continue;
+        }
+        let text = rewrite(text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(".tmp");
+        fs::write(&tmp, text).map_err(|e| e.to_string())?;
+        fs::rename(&tmp, &path).map_err(|e| e.to_string())?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Reads the settings at launch; emits `projects-locked` if encryption is on.
+pub(crate) fn init(app: &AppHandle) {
+    let enabled = matches!(read_settings(app), Ok(Some(_)));
+    STATE.lock().unwrap().0 = enabled;
+    if enabled {
+        let _ = app.emit("projects-locked", ());
+    }
+}
+
+#[tauri::command]
+pub fn get_encryption_status() -> EncryptionStatus {
+    let (enabled, key) = *STATE.lock().unwrap();
+    EncryptionStatus {
+        enabled,
+        unlocked: key.is_some(),
+    }
+}
+
+/// Turns encryption on with `passphrase` and encrypts existing files.
+/// Returns how many files were encrypted.
+#[tauri::command]
+pub fn enable_encryption(app: AppHandle, passphrase: String) -> Result<usize, String> {
+    if read_settings(&app)?.is_some() {
+        return Err(EncryptionError::AlreadyEnabled.to_string());
+    }
+    if passphrase.is_empty() {
+        return Err("Passphrase can't be empty".to_string());
+    }
+    let (key, json) = new_settings(&passphrase, PBKDF2_ITERATIONS).map_err(|e| e.to_string())?;
+    fs::write(settings_path(&app)?, json).map_err(|e| e.to_string())?;
+    *STATE.lock().unwrap() = (true, Some(key));
+    encrypt_project_files(app)
+}
+
+/// Unlocks encrypted projects for this session.
+#[tauri::command]
+pub fn unlock_projects(app: AppHandle, passphrase: String) -> Result<(), String> {
+    let settings = read_settings(&app)?.ok_or(EncryptionError::NotEnabled.to_string())?;
+    let key = unlock(&settings, &passphrase).map_err(|e| e.to_string())?;
+    *STATE.lock().unwrap() = (true, Some(key));
+    crate::events::project_changed(&app, None, crate::events::ChangeKind::Unlocked, true);
+    // Journaled edits couldn't be read while locked
+    crate::journal::announce(&app);
+    Ok(())
+}
+
+/// Forgets the key until the passphrase is entered again.
+#[tauri::command]
+pub fn lock_projects(app: AppHandle) {
+    let mut state = STATE.lock().unwrap();
+    if state.0 {
+        state.1 = None;
+        let _ = app.emit("projects-locked", ());
+    }
+}
+
+/// Encrypts files still stored as plaintext, e.g. ones restored from a
+/// backup or synced from a machine without encryption. Returns how many
+/// files were encrypted.
+#[tauri::command]
+pub fn encrypt_project_files(app: AppHandle) -> Result<usize, String> {
+    if !STATE.lock().unwrap().0 {
+        return Err(EncryptionError::NotEnabled.to_string());
+    }
+    rewrite_all(&app, |text| !is_sealed(text), seal)
+}
+
+/// Decrypts every file and turns encryption off. Returns how many files
+/// were decrypted.
+#[tauri::command]
+pub fn disable_encryption(app: AppHandle) -> Result<usize, String> {
+    if is_locked() {
+        return Err(EncryptionError::Locked.to_string());
+    }
+    let count = rewrite_all(&app, is_sealed, open)?;
+    fs::remove_file(settings_path(&app)?).map_err(|e| e.to_string())?;
+    *STATE.lock().unwrap() = (false, None);
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sealed_text_only_opens_with_the_right_key() {
+        let key = derive_key("correct horse", b"salt", 1_000);
+        let json = "{\"id\": \"p\", \"name\": \"Client X\"}";
+        let sealed = seal_with(&key, json).unwrap();
+        assert!(is_sealed(&sealed) && !sealed.contains("Client X"));
+        assert_ne!(sealed, seal_with(&key, json).unwrap());
+        assert_eq!(open_with(&key, &sealed).unwrap(), json);
+
+        let other = derive_key("battery staple", b"salt", 1_000);
+        assert!(matches!(
+            open_with(&other, &sealed),
+            Err(EncryptionError::Damaged)
+        ));
+        let tampered = sealed.replacen("\"data\":\"", "\"data\":\"AAAA", 1);
+        assert!(open_with(&key, &tampered).is_err());
+        assert!(!is_sealed(json));
+    }
+}
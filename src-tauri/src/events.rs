@@ -0,0 +1,168 @@
+//! Project change events.
+//!
+//! `project-update` carries a [`ProjectUpdate`] saying which project changed
+//! and how. Bursts are folded together: the first change of a kind to a
+//! project schedules its event [`DEBOUNCE`] later, and the same change
+//! repeated before then rides along (dragging a task saves many times a
+//! second). `widget-refresh` follows with the same payload only when what the
+//! widget shows changed, so it doesn't refetch for edits to notes.
+
+use crate::project::Project;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Created,
+    Saved,
+    /// Saved after merging changes made elsewhere; open editors should reload.
+    Merged,
+    Renamed,
+    /// The focus task was chosen or cleared.
+    Focused,
+    /// A task was marked done outside the editor, e.g. from the tray.
+    Completed,
+    /// A task was appended outside the editor, e.g. by quick capture.
+    TaskAdded,
+    /// A task's timer started or stopped.
+    Tracked,
+    Deleted,
+    /// Back from the trash, a backup or a repair.
+    Restored,
+    /// Edited on disk by something else.
+    ChangedOnDisk,
+    /// Pinning or manual order changed.
+    Reordered,
+    /// Encrypted projects became readable.
+    Unlocked,
+}
+
+/// Payload of `project-update` and `widget-refresh`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectUpdate {
+    /// Unset when any number of projects may have changed.
+    pub project_id: Option<String>,
+    pub kind: ChangeKind,
+}
+
+/// Updates waiting to be emitted, with whether the widget needs refreshing.
+static PENDING: Mutex<Vec<(ProjectUpdate, bool)>> = Mutex::new(Vec::new());
+
+/// Adds `update` to `pending`, returning whether it's new rather than
+/// folded into one already waiting.
+fn fold(pending: &mut Vec<(ProjectUpdate, bool)>, update: &ProjectUpdate, widget: bool) -> bool {
+    match pending.iter_mut().find(|(u, _)| u == update) {
+        Some((_, refresh)) => {
+            *refresh |= widget;
+            false
+        }
+        None => {
+            pending.push((update.clone(), widget));
+            true
+        }
+    }
+}
+
+/// What the widget shows of a project, to tell whether a save changed it.
+fn widget_view(project: &Project) -> serde_json::Value {
+    let mut request = project.schedule_request();
+    for task in &mut request.tasks {
+        task.notes = None;
+        task.subtasks.clear();
+        task.completed_at = None;
+        task.assignee = None;
+        task.updated_at = None;
+        task.attachments.clear();
+        task.reminders.clear();
+    }
+    serde_json::json!([
+        project.name,
+        project.color,
+        project.icon,
+        project.description,
+        project.focus_task_id,
+        request
+    ])
+}
+
+/// Whether saving `after` over `before` changes what the widget shows.
+pub(crate) fn affects_widget(before: Option<&Project>, after: &Project) -> bool {
+    before.is_none_or(|before| widget_view(before) != widget_view(after))
+}
+
+/// Announces a change, after [`DEBOUNCE`] unless it's already on its way.
+/// `widget` says whether it also needs a `widget-refresh`.
+pub(crate) fn project_changed(
+    app: &AppHandle,
+    project_id: Option<&str>,
+    kind: ChangeKind,
+    widget: bool,
+) {
+    let update = ProjectUpdate {
+        project_id: project_id.map(str::to_string),
+        kind,
+    };
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    if !fold(&mut pending, &update, widget) {
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(DEBOUNCE);
+        let widget = {
+            let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(i) = pending.iter().position(|(u, _)| u == &update) else {
+                return;
+            };
+            pending.remove(i).1
+        };
+        let _ = app.emit("project-update", &update);
+        crate::tray::refresh(&app);
+        if widget {
+            let _ = app.emit("widget-refresh", &update);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::Task;
+
+    #[test]
+    fn test_bursts_fold_and_widget_relevance() {
+        let update = |id: &str, kind| ProjectUpdate {
+            project_id: Some(id.into()),
+            kind,
+        };
+        let mut pending = Vec::new();
+        assert!(fold(&mut pending, &update("a", ChangeKind::Saved), false));
+        assert!(!fold(&mut pending, &update("a", ChangeKind::Saved), true));
+        assert!(fold(&mut pending, &update("a", ChangeKind::Merged), false));
+        assert!(fold(&mut pending, &update("b", ChangeKind::Saved), false));
+        assert_eq!(pending.len(), 3);
+        // A widget change anywhere in the burst refreshes the widget
+        assert!(pending[0].1);
+
+        let project = Project {
+            tasks: vec![Task {
+                id: "t".into(),
+                name: "Draft".into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(affects_widget(None, &project));
+        let mut edited = project.clone();
+        edited.tasks[0].notes = Some("Start with the outline".into());
+        edited.last_modified = "2026-03-10T09:00:00".into();
+        assert!(!affects_widget(Some(&project), &edited));
+        edited.tasks[0].completed = true;
+        assert!(affects_widget(Some(&project), &edited));
+    }
+}
@@ -0,0 +1,391 @@
+//! Rate-of-progress forecasting.
+//!
+//! Measures how fast planned work has actually been completed and projects
+//! when the remaining work will realistically be done at that pace, and how
+//! likely each anchor is to be met given three-point estimates.
+
+use crate::project::Project;
+use crate::scheduler::{parse_date_string, Task};
+use chrono::{DateTime, Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Forecast finish compared to the project's final anchor.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProgressForecast {
+    /// Minutes of planned work completed per elapsed day.
+    pub velocity_minutes_per_day: f64,
+    pub remaining_minutes: i64,
+    pub forecast_finish: String,
+    pub anchor_date: Option<String>,
+    /// Positive when the forecast lands after the anchor, negative when before.
+    pub days_late: Option<i64>,
+    pub summary: String,
+}
+
+/// Forecasts the finish date from completion timestamps.
+///
+/// Returns `None` when nothing has been completed yet (no pace to measure)
+/// or when no work remains.
+pub fn forecast_completion(project: &Project, now: NaiveDateTime) -> Option<ProgressForecast> {
+    let completions: Vec<(NaiveDateTime, i64)> = project
+        .tasks
+        .iter()
+        .filter(|t| t.completed)
+        .filter_map(|t| {
+            let at = DateTime::parse_from_rfc3339(t.completed_at.as_deref()?).ok()?;
+            Some((at.naive_local(), t.duration().num_minutes()))
+        })
+        .collect();
+
+    let first_completion = completions.iter().map(|(at, _)| *at).min()?;
+    let remaining_minutes: i64 = project
+        .tasks
+        .iter()
+        .filter(|t| !t.completed)
+        .map(|t| t.duration().num_minutes())
+        .sum();
+    if remaining_minutes <= 0 {
+        return None;
+    }
+
+    // Measure pace from when the project was created, or from the first
+    // completion for projects that predate completion tracking.
+    let tracking_start = DateTime::parse_from_rfc3339(&project.created_at)
+        .map(|d| d.naive_local().min(first_completion))
+        .unwrap_or(first_completion);
+    let elapsed_days = ((now - tracking_start).num_minutes() as f64 / 1440.0).max(1.0);
+    let completed_minutes: i64 = completions.iter().map(|(_, mins)| mins).sum();
+    let velocity = completed_minutes as f64 / elapsed_days;
+    if velocity <= 0.0 {
+        return None;
+    }
+
+    let days_needed = remaining_minutes as f64 / velocity;
+    let forecast_finish = now + Duration::minutes((days_needed * 1440.0).round() as i64);
+
+    let anchor = project
+        .anchors
+        .values()
+        .chain(project.anchor_windows.values().map(|w| &w.latest))
+        .filter_map(|d| parse_date_string(d).ok())
+        .max();
+    let days_late = anchor.map(|a| (forecast_finish - a).num_days());
+
+    let summary = match days_late {
+        Some(d) if d > 0 => format!("At current pace you'll finish {} days late", d),
+        Some(d) if d < 0 => format!("At current pace you'll finish {} days early", -d),
+        Some(_) => "At current pace you'll finish on time".to_string(),
+        None => format!(
+            "At current pace you'll finish on {}",
+            forecast_finish.format("%Y-%m-%d")
+        ),
+    };
+
+    Some(ProgressForecast {
+        velocity_minutes_per_day: velocity,
+        remaining_minutes,
+        forecast_finish: forecast_finish.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        anchor_date: anchor.map(|a| a.format("%Y-%m-%dT%H:%M:%S").to_string()),
+        days_late,
+        summary,
+    })
+}
+
+/// Fraction of a task's subtasks checked off, or `None` without subtasks.
+/// A completed task is done whatever its subtasks say.
+pub fn subtask_progress(task: &Task) -> Option<f32> {
+    if task.subtasks.is_empty() {
+        return None;
+    }
+    if task.completed {
+        return Some(1.0);
+    }
+    let done = task.subtasks.iter().filter(|s| s.completed).count();
+    Some(done as f32 / task.subtasks.len() as f32)
+}
+
+/// Percentage (0-100) of the planned work in `tasks` that's done, crediting
+/// unfinished tasks for their checked-off subtasks. Tasks count by duration,
+/// or all alike when none has one. `None` without tasks.
+pub fn completion_percent<'a>(tasks: impl IntoIterator<Item = &'a Task>) -> Option<f32> {
+    let parts: Vec<(f64, f64)> = tasks
+        .into_iter()
+        .map(|t| {
+            let done = if t.completed {
+                1.0
+            } else {
+                subtask_progress(t).unwrap_or(0.0) as f64
+            };
+            (t.duration().num_minutes().max(0) as f64, done)
+        })
+        .collect();
+    if parts.is_empty() {
+        return None;
+    }
+    let total: f64 = parts.iter().map(|(minutes, _)| minutes).sum();
+    let done: f64 = if total > 0.0 {
+        parts
+            .iter()
+            .map(|(minutes, done)| minutes * done)
+            .sum::<f64>()
+            / total
+    } else {
+        parts.iter().map(|(_, done)| done).sum::<f64>() / parts.len() as f64
+    };
+    Some((done * 100.0) as f32)
+}
+
+/// Chance of an anchor finishing by its date.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AnchorProbability {
+    pub task_id: String,
+    pub anchor_date: String,
+    /// Mean finish if the remaining work starts now.
+    pub expected_finish: String,
+    /// 0.0 to 1.0.
+    pub probability: f64,
+}
+
+/// PERT mean and variance of a task's duration, in minutes. Tasks without
+/// optimistic or pessimistic bounds are certain.
+fn pert(task: &Task) -> (f64, f64) {
+    let likely = task.duration().num_minutes() as f64;
+    let best = task
+        .optimistic
+        .map_or(likely, |d| d.as_duration().num_minutes() as f64)
+        .min(likely);
+    let worst = task
+        .pessimistic
+        .map_or(likely, |d| d.as_duration().num_minutes() as f64)
+        .max(likely);
+    let spread = (worst - best) / 6.0;
+    ((best + 4.0 * likely + worst) / 6.0, spread * spread)
+}
+
+/// Standard normal CDF (Abramowitz & Stegun 7.1.26, error below 1e-7).
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    0.5 * (1.0 + erf.copysign(z))
+}
+
+/// Probability of meeting each anchor if the remaining work starts now.
+///
+/// A PERT forward pass: each incomplete task starts when the latest of its
+/// dependencies is expected to finish, inheriting that path's variance.
+/// Contingency buffers aren't counted; they are there to absorb the spread.
+pub fn on_time_probabilities(project: &Project, now: NaiveDateTime) -> Vec<AnchorProbability> {
+    let tasks: HashMap<&str, &Task> = project.tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+
+    // Expected finish (minutes after now) and its variance, per task
+    let mut finish: HashMap<&str, (f64, f64)> = HashMap::new();
+    let mut waiting: HashMap<&str, usize> = project
+        .tasks
+        .iter()
+        .map(|t| {
+            let known = t
+                .dependencies
+                .iter()
+                .filter(|d| tasks.contains_key(d.as_str()));
+            (t.id.as_str(), known.count())
+        })
+        .collect();
+    let mut consumers: HashMap<&str, Vec<&str>> = HashMap::new();
+    for task in &project.tasks {
+        for dep in &task.dependencies {
+            consumers
+                .entry(dep.as_str())
+                .or_default()
+                .push(task.id.as_str());
+        }
+    }
+    let mut ready: Vec<&str> = waiting
+        .iter()
+        .filter(|(_, n)| **n == 0)
+        .map(|(id, _)| *id)
+        .collect();
+    while let Some(id) = ready.pop() {
+        let task = tasks[id];
+        let (start, variance) = task
+            .dependencies
+            .iter()
+            .filter_map(|d| finish.get(d.as_str()))
+            .fold((0.0, 0.0), |best, &f| if f.0 > best.0 { f } else { best });
+        let (mean, spread) = if task.completed {
+            (0.0, 0.0)
+        } else {
+            pert(task)
+        };
+        finish.insert(id, (start + mean, variance + spread));
+
+        for &c in consumers.get(id).into_iter().flatten() {
+            if let Some(n) = waiting.get_mut(c) {
+                *n -= 1;
+                if *n == 0 {
+                    ready.push(c);
+                }
+            }
+        }
+    }
+
+    let mut anchors: BTreeMap<&str, NaiveDateTime> = BTreeMap::new();
+    let windows = project.anchor_windows.iter().map(|(id, w)| (id, &w.latest));
+    for (id, date) in project.anchors.iter().chain(windows) {
+        if let Ok(date) = parse_date_string(date) {
+            let entry = anchors.entry(id.as_str()).or_insert(date);
+            *entry = (*entry).min(date);
+        }
+    }
+
+    anchors
+        .into_iter()
+        .filter_map(|(id, anchor)| {
+            let (mean, variance) = *finish.get(id)?;
+            let expected = now + Duration::minutes(mean.round() as i64);
+            let margin = (anchor - now).num_minutes() as f64 - mean;
+            let probability = if variance > 0.0 {
+                normal_cdf(margin / variance.sqrt())
+            } else if margin >= 0.0 {
+                1.0
+            } else {
+                0.0
+            };
+            Some(AnchorProbability {
+                task_id: id.to_string(),
+                anchor_date: anchor.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                expected_finish: expected.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                probability,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::TaskDuration;
+
+    #[test]
+    fn test_slow_pace_forecasts_late_finish() {
+        // Two days of work done over four days; four days of work left.
+        // At half speed that takes eight more days: Jan 13, six days after the Jan 7 anchor.
+        let project = Project {
+            id: "p".into(),
+            name: "Forecast".into(),
+            created_at: "2026-01-01T00:00:00+00:00".into(),
+            last_modified: String::new(),
+            tasks: vec![
+                Task {
+                    id: "a".into(),
+                    name: "Done".into(),
+                    duration: TaskDuration::days(2),
+                    completed: true,
+                    completed_at: Some("2026-01-04T00:00:00+00:00".into()),
+                    ..Default::default()
+                },
+                Task {
+                    id: "b".into(),
+                    name: "Left".into(),
+                    duration: TaskDuration::days(4),
+                    dependencies: vec!["a".into()],
+                    ..Default::default()
+                },
+            ],
+            anchors: [("b".into(), "2026-01-07T00:00:00".into())].into(),
+            ..Default::default()
+        };
+        let now = parse_date_string("2026-01-05T00:00:00").unwrap();
+
+        let forecast = forecast_completion(&project, now).expect("Should forecast");
+
+        assert_eq!(forecast.forecast_finish, "2026-01-13T00:00:00");
+        assert_eq!(forecast.days_late, Some(6));
+        assert_eq!(
+            forecast.summary,
+            "At current pace you'll finish 6 days late"
+        );
+    }
+
+    #[test]
+    fn test_on_time_probability_from_three_point_estimates() {
+        let task = |id: &str, days: i64, deps: &[&str]| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(days),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        // draft: 1 / 2 / 9 days, so a 3-day mean
+        let mut draft = task("draft", 2, &[]);
+        draft.optimistic = Some(TaskDuration::days(1));
+        draft.pessimistic = Some(TaskDuration::days(9));
+        let project = Project {
+            tasks: vec![draft, task("publish", 1, &["draft"]), task("call", 1, &[])],
+            anchors: [
+                ("publish".into(), "2026-01-05T00:00:00".into()),
+                ("call".into(), "2026-01-03T00:00:00".into()),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        let now = parse_date_string("2026-01-01T00:00:00").unwrap();
+
+        let odds = on_time_probabilities(&project, now);
+
+        assert_eq!(odds.len(), 2);
+        assert_eq!(odds[0].task_id, "call");
+        assert_eq!(odds[0].probability, 1.0);
+        // The anchor sits exactly on the expected finish: a coin toss
+        assert_eq!(odds[1].task_id, "publish");
+        assert_eq!(odds[1].expected_finish, "2026-01-05T00:00:00");
+        assert!((odds[1].probability - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_completion_counts_subtasks() {
+        let sub = |completed: bool| crate::scheduler::SubTask {
+            id: String::new(),
+            name: String::new(),
+            completed,
+        };
+        let tasks = vec![
+            Task {
+                duration: TaskDuration::days(1),
+                completed: true,
+                ..Default::default()
+            },
+            Task {
+                duration: TaskDuration::days(2),
+                subtasks: vec![sub(true), sub(false), sub(false), sub(false)],
+                ..Default::default()
+            },
+            Task {
+                duration: TaskDuration::days(1),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(subtask_progress(&tasks[1]), Some(0.25));
+        assert_eq!(subtask_progress(&tasks[2]), None);
+        // One day done plus a quarter of two: 1.5 of 4 days
+        assert_eq!(completion_percent(&tasks), Some(37.5));
+
+        let milestones = vec![
+            Task {
+                is_milestone: true,
+                completed: true,
+                ..Default::default()
+            },
+            Task {
+                is_milestone: true,
+                ..Default::default()
+            },
+        ];
+        assert_eq!(completion_percent(&milestones), Some(50.0));
+        assert_eq!(completion_percent(&[]), None);
+    }
+}
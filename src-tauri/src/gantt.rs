@@ -0,0 +1,305 @@
+//! Gantt chart export.
+//!
+//! Renders a computed schedule as a standalone SVG (bars, dependency arrows,
+//! the critical path and a line for today), independent of the frontend so
+//! exports look the same wherever they're embedded.
+
+use crate::cache::ScheduleCache;
+use crate::project::{load_project, Project};
+use crate::scheduler::ScheduledTask;
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use tauri::{AppHandle, Manager};
+
+const LABEL_WIDTH: f64 = 220.0;
+const HEADER_HEIGHT: f64 = 36.0;
+const BAR_INSET: f64 = 6.0;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GanttOptions {
+    /// Also writes the SVG here when set.
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Width of the timeline, excluding task names.
+    #[serde(default = "default_width")]
+    pub width: u32,
+    #[serde(default = "default_row_height")]
+    pub row_height: u32,
+    #[serde(default = "yes")]
+    pub include_completed: bool,
+    #[serde(default = "yes")]
+    pub show_dependencies: bool,
+}
+
+fn default_width() -> u32 {
+    960
+}
+
+fn default_row_height() -> u32 {
+    28
+}
+
+fn yes() -> bool {
+    true
+}
+
+impl Default for GanttOptions {
+    fn default() -> Self {
+        Self {
+            path: None,
+            width: default_width(),
+            row_height: default_row_height(),
+            include_completed: true,
+            show_dependencies: true,
+        }
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn parse(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").ok()
+}
+
+/// Days between date ticks so labels don't crowd.
+fn tick_days(span_days: i64, width: f64) -> i64 {
+    let fits = (width / 56.0).max(1.0) as i64;
+    [1, 2, 7, 14, 28, 91, 182, 365]
+        .into_iter()
+        .find(|d| span_days / d <= fits)
+        .unwrap_or(365)
+}
+
+/// Renders `schedule` as an SVG document.
+pub fn render_gantt(
+    project: &Project,
+    schedule: &[ScheduledTask],
+    options: &GanttOptions,
+    now: NaiveDateTime,
+) -> String {
+    let rows: Vec<(&ScheduledTask, NaiveDateTime, NaiveDateTime)> = schedule
+        .iter()
+        .filter(|t| options.include_completed || !t.completed)
+        .filter_map(|t| Some((t, parse(&t.start_date)?, parse(&t.end_date)?)))
+        .collect();
+
+    let width = options.width.max(100) as f64;
+    let row_height = options.row_height.max(12) as f64;
+    let height = HEADER_HEIGHT + row_height * rows.len().max(1) as f64;
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="system-ui, sans-serif" font-size="12">"#,
+        w = LABEL_WIDTH + width,
+        h = height,
+    );
+    svg.push_str(concat!(
+        "<style>",
+        ".bar{fill:#4f7fd9}.critical{fill:#d9534f}.done{fill:#b5b5b5}",
+        ".label{fill:#222}.tick{fill:#666;font-size:10px}.grid{stroke:#eee}",
+        ".dep{fill:none;stroke:#888;stroke-width:1}.today{stroke:#e08a00;stroke-dasharray:4 3}",
+        "</style>",
+        r##"<defs><marker id="arrow" viewBox="0 0 6 6" refX="6" refY="3" markerWidth="6" markerHeight="6" orient="auto"><path d="M0,0 L6,3 L0,6 z" fill="#888"/></marker></defs>"##,
+    ));
+    let _ = write!(
+        svg,
+        r##"<title>{}</title><rect width="100%" height="100%" fill="#fff"/>"##,
+        escape_xml(&project.name)
+    );
+
+    let (Some(first), Some(last)) = (
+        rows.iter().map(|r| r.1).min(),
+        rows.iter().map(|r| r.2).max(),
+    ) else {
+        svg.push_str("</svg>");
+        return svg;
+    };
+    // Start at midnight with a day of margin at the end
+    let t0 = first.date().and_hms_opt(0, 0, 0).unwrap_or(first);
+    let t1 = (last + Duration::days(1)).max(t0 + Duration::days(1));
+    let span = (t1 - t0).num_minutes() as f64;
+    let x = |t: NaiveDateTime| LABEL_WIDTH + (t - t0).num_minutes() as f64 / span * width;
+
+    // Date ticks and grid
+    let step = tick_days((t1 - t0).num_days(), width);
+    let mut tick = t0;
+    while tick <= t1 {
+        let _ = write!(
+            svg,
+            r#"<line class="grid" x1="{x:.1}" y1="{top}" x2="{x:.1}" y2="{h}"/><text class="tick" x="{tx:.1}" y="22">{label}</text>"#,
+            x = x(tick),
+            tx = x(tick) + 3.0,
+            top = HEADER_HEIGHT - 8.0,
+            h = height,
+            label = tick.format("%b %-d"),
+        );
+        tick += Duration::days(step);
+    }
+
+    let index: HashMap<&str, usize> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, r)| (r.0.id.as_str(), i))
+        .collect();
+    let mid = |i: usize| HEADER_HEIGHT + row_height * (i as f64 + 0.5);
+
+    if options.show_dependencies {
+        for (i, (task, start, _)) in rows.iter().enumerate() {
+            let Some(source) = project.tasks.iter().find(|t| t.id == task.id) else {
+                continue;
+            };
+            for dep in &source.dependencies {
+                let Some(&j) = index.get(dep.as_str()) else {
+                    continue;
+                };
+                let (from, to) = (x(rows[j].2), x(*start));
+                let _ = write!(
+                    svg,
+                    r#"<path class="dep" d="M{:.1},{:.1} H{:.1} V{:.1} H{:.1}" marker-end="url(#arrow)"/>"#,
+                    from,
+                    mid(j),
+                    from.max(to - 8.0) + 4.0,
+                    mid(i),
+                    to,
+                );
+            }
+        }
+    }
+
+    for (i, (task, start, end)) in rows.iter().enumerate() {
+        let class = if task.completed {
+            "done"
+        } else if task.is_critical {
+            "critical"
+        } else {
+            "bar"
+        };
+        let _ = write!(
+            svg,
+            r#"<text class="label" x="8" y="{:.1}" dominant-baseline="middle">{}</text>"#,
+            mid(i),
+            escape_xml(&task.name)
+        );
+        if task.is_milestone || end == start {
+            let (cx, cy, r) = (x(*end), mid(i), row_height / 2.0 - BAR_INSET + 2.0);
+            let _ = write!(
+                svg,
+                r#"<path class="{}" d="M{cx:.1},{:.1} L{:.1},{cy:.1} L{cx:.1},{:.1} L{:.1},{cy:.1} z"><title>{}</title></path>"#,
+                class,
+                cy - r,
+                cx + r,
+                cy + r,
+                cx - r,
+                escape_xml(&task.name),
+            );
+        } else {
+            let _ = write!(
+                svg,
+                r#"<rect class="{}" x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" rx="3"><title>{}</title></rect>"#,
+                class,
+                x(*start),
+                HEADER_HEIGHT + row_height * i as f64 + BAR_INSET,
+                (x(*end) - x(*start)).max(2.0),
+                row_height - 2.0 * BAR_INSET,
+                escape_xml(&task.name),
+            );
+        }
+    }
+
+    if now >= t0 && now <= t1 {
+        let _ = write!(
+            svg,
+            r#"<line class="today" x1="{x:.1}" y1="{top}" x2="{x:.1}" y2="{h}"/>"#,
+            x = x(now),
+            top = HEADER_HEIGHT - 8.0,
+            h = height,
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Renders a project's current schedule as an SVG Gantt chart, writing it
+/// to `options.path` as well when set.
+#[tauri::command]
+pub fn export_gantt_svg(
+    app: AppHandle,
+    project_id: String,
+    options: Option<GanttOptions>,
+) -> Result<String, String> {
+    let options = options.unwrap_or_default();
+    let project = load_project(app.clone(), project_id)?;
+    let now = chrono::Local::now().naive_local();
+    let result = app
+        .state::<ScheduleCache>()
+        .schedule(&project, now)
+        .map_err(|e| e.to_string())?;
+    let svg = render_gantt(&project, &result.scheduled_tasks, &options, now);
+    if let Some(path) = &options.path {
+        fs::write(path, &svg).map_err(|e| e.to_string())?;
+    }
+    Ok(svg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{calculate_backwards_schedule, Task, TaskDuration};
+
+    #[test]
+    fn test_chart_has_bars_arrows_and_today() {
+        let task = |id: &str, name: &str, days: i64, deps: &[&str]| Task {
+            id: id.into(),
+            name: name.into(),
+            duration: TaskDuration::days(days),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let project = Project {
+            name: "Launch".into(),
+            tasks: vec![
+                task("a", "Spec & plan", 2, &[]),
+                task("b", "Side quest", 1, &[]),
+                task("c", "Build", 3, &["a", "b"]),
+                Task {
+                    is_milestone: true,
+                    ..task("d", "Go live", 0, &["c"])
+                },
+            ],
+            anchors: [("d".into(), "2026-03-10T17:00:00".into())].into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+        let schedule = calculate_backwards_schedule(project.schedule_request())
+            .unwrap()
+            .scheduled_tasks;
+        let now = parse("2026-03-06T12:00:00").unwrap();
+
+        let svg = render_gantt(&project, &schedule, &GanttOptions::default(), now);
+
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<rect class=").count(), 3);
+        // The side task has slack, everything else is critical
+        assert_eq!(svg.matches(r#"class="critical""#).count(), 3);
+        assert_eq!(svg.matches(r#"class="bar""#).count(), 1);
+        assert_eq!(svg.matches(r#"<path class="dep""#).count(), 3);
+        assert_eq!(svg.matches(r#"class="today""#).count(), 1);
+        assert!(svg.contains("Spec &amp; plan"));
+
+        let options = GanttOptions {
+            show_dependencies: false,
+            ..Default::default()
+        };
+        let later = parse("2026-04-01T00:00:00").unwrap();
+        let svg = render_gantt(&project, &schedule, &options, later);
+        assert!(!svg.contains(r#"class="dep""#) && !svg.contains(r#"class="today""#));
+    }
+}
@@ -0,0 +1,602 @@
+//! Google Calendar connector.
+//!
+//! Connecting runs the OAuth installed-app flow: [`google_begin_auth`] returns
+//! a consent URL for the frontend to open and waits on a loopback port for
+//! Google's redirect, storing the token in `google_token.json` in the app data
+//! directory. Linked projects push one event per scheduled task, tagged with
+//! private extended properties so later syncs update those events instead of
+//! adding duplicates.
+
+use crate::cache::ScheduleCache;
+use crate::config::{load_config, save_config};
+use crate::datetime::Zone;
+use crate::project::{load_project, Project};
+use crate::scheduler::ScheduledTask;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+
+const AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const API_URL: &str = "https://www.googleapis.com/calendar/v3";
+const SCOPE: &str = "https://www.googleapis.com/auth/calendar";
+/// How long the loopback listener waits for the user to consent.
+const AUTH_TIMEOUT_SECS: u64 = 300;
+
+/// The OAuth client Anchor authenticates as, from the Google Cloud console
+/// ("Desktop app" type).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GoogleSettings {
+    pub client_id: String,
+    #[serde(default)]
+    pub client_secret: Option<String>,
+}
+
+/// A project pushed to a Google calendar.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GoogleCalendarLink {
+    pub project_id: String,
+    pub calendar_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct Token {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    /// Unix seconds.
+    expires_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GoogleCalendar {
+    pub id: String,
+    pub summary: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct GoogleSyncReport {
+    pub inserted: usize,
+    pub updated: usize,
+    pub deleted: usize,
+}
+
+/// An Anchor-managed event already in the calendar.
+#[derive(Debug, Clone, PartialEq)]
+struct RemoteEvent {
+    id: String,
+    task_id: String,
+    body: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Change {
+    Insert(Value),
+    Update(String, Value),
+    Delete(String),
+}
+
+fn percent_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(b) => {
+                        out.push(b);
+                        i += 3;
+                        continue;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            b'+' => out.push(b' '),
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn query_param(url: &str, key: &str) -> Option<String> {
+    let query = url.split_once('?')?.1;
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| percent_decode(v))
+}
+
+/// RFC 3339 instant for a wall-clock schedule time in `zone`.
+fn instant(wall: &str, zone: Zone) -> Option<String> {
+    let wall = NaiveDateTime::parse_from_str(wall, "%Y-%m-%dT%H:%M:%S").ok()?;
+    Some(zone.to_utc(wall).format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
+
+/// The event Anchor wants in the calendar for one scheduled task.
+fn event_body(project: &Project, row: &ScheduledTask, zone: Zone) -> Option<Value> {
+    Some(json!({
+        "summary": row.name,
+        "description": row.notes.clone().unwrap_or_default(),
+        "start": { "dateTime": instant(&row.start_date, zone)? },
+        "end": { "dateTime": instant(&row.end_date, zone)? },
+        "transparency": if row.is_milestone { "transparent" } else { "opaque" },
+        "extendedProperties": {
+            "private": { "anchorProjectId": project.id, "anchorTaskId": row.id }
+        },
+    }))
+}
+
+/// Whether `remote` already shows what `wanted` describes. Google echoes
+/// times in the calendar's own offset, so instants are compared.
+fn same_event(wanted: &Value, remote: &Value) -> bool {
+    let at = |v: &Value, key: &str| {
+        v[key]["dateTime"]
+            .as_str()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|d| d.with_timezone(&Utc))
+    };
+    let text = |v: &Value, key: &str| v[key].as_str().unwrap_or_default().to_string();
+    at(wanted, "start") == at(remote, "start")
+        && at(wanted, "end") == at(remote, "end")
+        && text(wanted, "summary") == text(remote, "summary")
+        && text(wanted, "description") == text(remote, "description")
+        && text(wanted, "transparency") == text(remote, "transparency")
+}
+
+/// Inserts, updates and deletes that bring the calendar in line with the
+/// schedule. Extra events for one task (e.g. from an interrupted sync) go.
+fn plan_changes(
+    project: &Project,
+    schedule: &[ScheduledTask],
+    existing: &[RemoteEvent],
+) -> Vec<Change> {
+    let zone = Zone::parse(project.timezone.as_deref()).unwrap_or(Zone::Local);
+    let mut by_task: HashMap<&str, Vec<&RemoteEvent>> = HashMap::new();
+    for event in existing {
+        by_task
+            .entry(event.task_id.as_str())
+            .or_default()
+            .push(event);
+    }
+
+    let mut changes = Vec::new();
+    let mut seen = HashSet::new();
+    for row in schedule {
+        let Some(wanted) = event_body(project, row, zone) else {
+            continue;
+        };
+        seen.insert(row.id.as_str());
+        match by_task.get(row.id.as_str()).map(Vec::as_slice) {
+            None | Some([]) => changes.push(Change::Insert(wanted)),
+            Some([first, rest @ ..]) => {
+                if !same_event(&wanted, &first.body) {
+                    changes.push(Change::Update(first.id.clone(), wanted));
+                }
+                changes.extend(rest.iter().map(|e| Change::Delete(e.id.clone())));
+            }
+        }
+    }
+    for event in existing {
+        if !seen.contains(event.task_id.as_str()) {
+            changes.push(Change::Delete(event.id.clone()));
+        }
+    }
+    changes
+}
+
+fn token_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    if !dir.exists() {
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    }
+    Ok(dir.join("google_token.json"))
+}
+
+fn settings(app: &AppHandle) -> Result<GoogleSettings, String> {
+    load_config(app.clone())?
+        .google
+        .ok_or_else(|| "Add a Google OAuth client ID in the settings first".to_string())
+}
+
+fn save_token(app: &AppHandle, response: Value, previous: Option<&Token>) -> Result<Token, String> {
+    let access_token = response["access_token"]
+        .as_str()
+        .ok_or("Google returned no access token")?
+        .to_string();
+    let token = Token {
+        access_token,
+        // Refreshes usually omit the refresh token; keep the one we had
+        refresh_token: response["refresh_token"]
+            .as_str()
+            .map(String::from)
+            .or_else(|| previous.and_then(|t| t.refresh_token.clone())),
+        expires_at: Utc::now().timestamp() + response["expires_in"].as_i64().unwrap_or(3600),
+    };
+    let json = serde_json::to_string_pretty(&token).map_err(|e| e.to_string())?;
+    fs::write(token_path(app)?, json).map_err(|e| e.to_string())?;
+    Ok(token)
+}
+
+fn request_token(
+    app: &AppHandle,
+    form: &[(&str, &str)],
+    previous: Option<&Token>,
+) -> Result<Token, String> {
+    let settings = settings(app)?;
+    let mut form = form.to_vec();
+    form.push(("client_id", &settings.client_id));
+    if let Some(secret) = &settings.client_secret {
+        form.push(("client_secret", secret));
+    }
+    let response: Value = ureq::post(TOKEN_URL)
+        .send_form(&form)
+        .map_err(|e| e.to_string())?
+        .into_json()
+        .map_err(|e| e.to_string())?;
+    save_token(app, response, previous)
+}
+
+/// A current access token, refreshed when about to expire.
+fn access_token(app: &AppHandle) -> Result<String, String> {
+    let token: Token = fs::read_to_string(token_path(app)?)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .ok_or("Connect a Google account first")?;
+    if token.expires_at > Utc::now().timestamp() + 60 {
+        return Ok(token.access_token);
+    }
+    let refresh = token
+        .refresh_token
+        .clone()
+        .ok_or("The Google session expired; connect the account again")?;
+    let form = [
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh.as_str()),
+    ];
+    Ok(request_token(app, &form, Some(&token))?.access_token)
+}
+
+fn api(method: &str, path: &str, token: &str) -> ureq::Request {
+    ureq::request(method, &format!("{}{}", API_URL, path))
+        .set("Authorization", &format!("Bearer {}", token))
+}
+
+/// Every Anchor-managed event for `project_id` in a calendar.
+fn managed_events(
+    calendar_id: &str,
+    project_id: &str,
+    token: &str,
+) -> Result<Vec<RemoteEvent>, String> {
+    let path = format!("/calendars/{}/events", percent_encode(calendar_id));
+    let filter = format!("anchorProjectId={}", project_id);
+    let mut events = Vec::new();
+    let mut page: Option<String> = None;
+    loop {
+        let mut request = api("GET", &path, token)
+            .query("privateExtendedProperty", &filter)
+            .query("maxResults", "2500");
+        if let Some(page) = &page {
+            request = request.query("pageToken", page);
+        }
+        let response: Value = request
+            .call()
+            .map_err(|e| e.to_string())?
+            .into_json()
+            .map_err(|e| e.to_string())?;
+        for item in response["items"].as_array().into_iter().flatten() {
+            let (Some(id), Some(task_id)) = (
+                item["id"].as_str(),
+                item["extendedProperties"]["private"]["anchorTaskId"].as_str(),
+            ) else {
+                continue;
+            };
+            events.push(RemoteEvent {
+                id: id.to_string(),
+                task_id: task_id.to_string(),
+                body: item.clone(),
+            });
+        }
+        match response["nextPageToken"].as_str() {
+            Some(next) => page = Some(next.to_string()),
+            None => return Ok(events),
+        }
+    }
+}
+
+/// Waits for Google's redirect on `server` and trades the code for a token.
+fn finish_auth(
+    app: &AppHandle,
+    server: tiny_http::Server,
+    redirect_uri: &str,
+    state: &str,
+    verifier: &str,
+) -> Result<(), String> {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(AUTH_TIMEOUT_SECS);
+    // Browsers may ask for a favicon too; wait for the redirect itself
+    let (request, url) = loop {
+        let left = deadline.saturating_duration_since(std::time::Instant::now());
+        let request = server
+            .recv_timeout(left)
+            .map_err(|e| e.to_string())?
+            .ok_or("Timed out waiting for Google sign-in")?;
+        let url = request.url().to_string();
+        if query_param(&url, "state").is_some() {
+            break (request, url);
+        }
+        let _ = request.respond(tiny_http::Response::empty(404));
+    };
+    let outcome = match (query_param(&url, "state"), query_param(&url, "code")) {
+        (Some(s), Some(code)) if s == state => Ok(code),
+        (Some(s), _) if s == state => Err(query_param(&url, "error")
+            .map(|e| format!("Google sign-in failed: {}", e))
+            .unwrap_or_else(|| "Google sign-in failed".to_string())),
+        _ => Err("Unexpected sign-in response".to_string()),
+    };
+    let page = match &outcome {
+        Ok(_) => "Anchor is connected to Google Calendar. You can close this tab.",
+        Err(_) => "Anchor couldn't connect to Google Calendar. You can close this tab.",
+    };
+    let _ = request.respond(tiny_http::Response::from_string(page));
+
+    let code = outcome?;
+    let form = [
+        ("grant_type", "authorization_code"),
+        ("code", code.as_str()),
+        ("redirect_uri", redirect_uri),
+        ("code_verifier", verifier),
+    ];
+    request_token(app, &form, None).map(|_| ())
+}
+
+/// Starts connecting a Google account and returns the consent URL to open.
+/// Emits `google-connected`, or `google-auth-failed` with the reason.
+#[tauri::command]
+pub fn google_begin_auth(app: AppHandle) -> Result<String, String> {
+    let settings = settings(&app)?;
+    let server = tiny_http::Server::http("127.0.0.1:0").map_err(|e| e.to_string())?;
+    let port = server
+        .server_addr()
+        .to_ip()
+        .map(|addr| addr.port())
+        .ok_or("Could not open a loopback port")?;
+    let redirect_uri = format!("http://127.0.0.1:{}", port);
+    let state = uuid::Uuid::new_v4().simple().to_string();
+    // PKCE with the plain method, as there's no SHA-256 at hand; `state`
+    // guards the loopback port against forged redirects
+    let verifier = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+
+    let url = format!(
+        "{}?client_id={}&redirect_uri={}&response_type=code&scope={}&access_type=offline&prompt=consent&state={}&code_challenge={}&code_challenge_method=plain",
+        AUTH_URL,
+        percent_encode(&settings.client_id),
+        percent_encode(&redirect_uri),
+        percent_encode(SCOPE),
+        state,
+        verifier,
+    );
+
+    std::thread::spawn(move || {
+        let _ = match finish_auth(&app, server, &redirect_uri, &state, &verifier) {
+            Ok(()) => app.emit("google-connected", ()),
+            Err(e) => app.emit("google-auth-failed", e),
+        };
+    });
+    Ok(url)
+}
+
+/// Forgets the stored Google token.
+#[tauri::command]
+pub fn google_disconnect(app: AppHandle) -> Result<(), String> {
+    let path = token_path(&app)?;
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Calendars the connected account can write to.
+#[tauri::command]
+pub async fn list_google_calendars(app: AppHandle) -> Result<Vec<GoogleCalendar>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let token = access_token(&app)?;
+        let response: Value = api("GET", "/users/me/calendarList", &token)
+            .query("minAccessRole", "writer")
+            .call()
+            .map_err(|e| e.to_string())?
+            .into_json()
+            .map_err(|e| e.to_string())?;
+        serde_json::from_value(response["items"].clone()).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Links a project to a calendar, replacing any previous link.
+#[tauri::command]
+pub fn link_google_calendar(
+    app: AppHandle,
+    project_id: String,
+    calendar_id: String,
+) -> Result<(), String> {
+    let mut config = load_config(app.clone())?;
+    config.google_links.retain(|l| l.project_id != project_id);
+    config.google_links.push(GoogleCalendarLink {
+        project_id,
+        calendar_id,
+    });
+    save_config(app, config)
+}
+
+/// Unlinks a project. Events already pushed are left in the calendar.
+#[tauri::command]
+pub fn unlink_google_calendar(app: AppHandle, project_id: String) -> Result<(), String> {
+    let mut config = load_config(app.clone())?;
+    config.google_links.retain(|l| l.project_id != project_id);
+    save_config(app, config)
+}
+
+/// Pushes a linked project's schedule to its calendar, updating the events
+/// of earlier syncs and removing those of deleted tasks.
+#[tauri::command]
+pub async fn sync_google_calendar(
+    app: AppHandle,
+    project_id: String,
+) -> Result<GoogleSyncReport, String> {
+    let link = load_config(app.clone())?
+        .google_links
+        .into_iter()
+        .find(|l| l.project_id == project_id)
+        .ok_or_else(|| format!("Project {} isn't linked to a Google calendar", project_id))?;
+    let project = load_project(app.clone(), project_id)?;
+    let now = chrono::Local::now().naive_local();
+    let schedule = app
+        .state::<ScheduleCache>()
+        .schedule(&project, now)
+        .map_err(|e| e.to_string())?
+        .scheduled_tasks;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let token = access_token(&app)?;
+        let existing = managed_events(&link.calendar_id, &project.id, &token)?;
+        let events = format!("/calendars/{}/events", percent_encode(&link.calendar_id));
+        let mut report = GoogleSyncReport::default();
+        for change in plan_changes(&project, &schedule, &existing) {
+            match change {
+                Change::Insert(body) => {
+                    api("POST", &events, &token)
+                        .send_json(body)
+                        .map_err(|e| e.to_string())?;
+                    report.inserted += 1;
+                }
+                Change::Update(id, body) => {
+                    api(
+                        "PUT",
+                        &format!("{}/{}", events, percent_encode(&id)),
+                        &token,
+                    )
+                    .send_json(body)
+                    .map_err(|e| e.to_string())?;
+                    report.updated += 1;
+                }
+                Change::Delete(id) => {
+                    match api(
+                        "DELETE",
+                        &format!("{}/{}", events, percent_encode(&id)),
+                        &token,
+                    )
+                    .call()
+                    {
+                        Ok(_) | Err(ureq::Error::Status(404 | 410, _)) => {}
+                        Err(e) => return Err(e.to_string()),
+                    }
+                    report.deleted += 1;
+                }
+            }
+        }
+        Ok(report)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{calculate_backwards_schedule, Task, TaskDuration};
+
+    fn project() -> Project {
+        let task = |id: &str, deps: &[&str]| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(1),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        Project {
+            id: "p".into(),
+            name: "Launch".into(),
+            tasks: vec![task("draft", &[]), task("ship", &["draft"])],
+            anchors: [("ship".into(), "2026-03-10T17:00:00".into())].into(),
+            timezone: Some("Europe/Paris".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resync_updates_instead_of_duplicating() {
+        let project = project();
+        let schedule = calculate_backwards_schedule(project.schedule_request())
+            .unwrap()
+            .scheduled_tasks;
+
+        let first = plan_changes(&project, &schedule, &[]);
+        assert_eq!(first.len(), 2);
+        let Change::Insert(ship) = &first[1] else {
+            panic!("expected an insert");
+        };
+        assert_eq!(ship["end"]["dateTime"], "2026-03-10T16:00:00Z");
+        assert_eq!(
+            ship["extendedProperties"]["private"]["anchorTaskId"],
+            "ship"
+        );
+
+        // Google echoes ship in the calendar's offset; draft was moved by
+        // hand, a stray copy of ship exists and "old" was deleted in Anchor
+        let mut echoed = ship.clone();
+        echoed["end"]["dateTime"] = json!("2026-03-10T17:00:00+01:00");
+        let mut moved = match &first[0] {
+            Change::Insert(body) => body.clone(),
+            _ => unreachable!(),
+        };
+        moved["start"]["dateTime"] = json!("2026-03-01T09:00:00Z");
+        let remote = |id: &str, task_id: &str, body: &Value| RemoteEvent {
+            id: id.into(),
+            task_id: task_id.into(),
+            body: body.clone(),
+        };
+        let existing = vec![
+            remote("e1", "draft", &moved),
+            remote("e2", "ship", &echoed),
+            remote("e3", "ship", &echoed),
+            remote("e4", "old", &echoed),
+        ];
+
+        let changes = plan_changes(&project, &schedule, &existing);
+        assert_eq!(changes.len(), 3);
+        assert!(matches!(&changes[0], Change::Update(id, _) if id == "e1"));
+        assert_eq!(changes[1], Change::Delete("e3".into()));
+        assert_eq!(changes[2], Change::Delete("e4".into()));
+    }
+
+    #[test]
+    fn test_redirect_query_is_decoded() {
+        let url = "/?state=abc&code=4%2F0Ab-x+y&scope=z";
+        assert_eq!(query_param(url, "code").as_deref(), Some("4/0Ab-x y"));
+        assert_eq!(percent_encode("sam@example.com"), "sam%40example.com");
+    }
+}
@@ -0,0 +1,299 @@
+//! Schedule health diagnostics.
+//!
+//! DCMA-style checks over a project's logic and its computed schedule: open
+//! ends, disconnected work, oversized tasks, negative and excessive slack,
+//! and tasks with too many dependencies.
+
+use crate::project::load_project;
+use crate::scheduler::{calculate_backwards_schedule, ScheduleRequest};
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use tauri::AppHandle;
+
+/// DCMA's high-duration and high-float threshold (44 working days),
+/// in calendar days.
+const LONG_DAYS: i64 = 44;
+/// Being this far behind a late start counts as excessive negative slack.
+const BEHIND_DAYS: i64 = 1;
+/// More dependencies than this make a task a merge-bias hot spot.
+const MAX_FAN_IN: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthCheckKind {
+    /// The project can't be scheduled at all.
+    Unschedulable,
+    /// Dependencies naming tasks that don't exist.
+    MissingDependency,
+    /// Tasks that nothing depends on and that aren't anchored or pinned.
+    NoSuccessors,
+    /// Tasks whose successors never reach an anchor or pin.
+    Dangling,
+    /// Tasks longer than 44 days.
+    LongDuration,
+    /// Incomplete tasks already more than a day past their latest start.
+    NegativeSlack,
+    /// Tasks with more than 44 days of slack.
+    HighSlack,
+    /// Tasks depending on more than five others.
+    HighFanIn,
+}
+
+/// One failed check and the tasks that failed it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HealthCheck {
+    pub kind: HealthCheckKind,
+    pub severity: Severity,
+    pub message: String,
+    pub task_ids: Vec<String>,
+}
+
+fn check(
+    kind: HealthCheckKind,
+    severity: Severity,
+    message: String,
+    mut task_ids: Vec<String>,
+) -> HealthCheck {
+    task_ids.sort();
+    task_ids.dedup();
+    HealthCheck {
+        kind,
+        severity,
+        message,
+        task_ids,
+    }
+}
+
+/// Runs every check against `request` as of `now`. Only failed checks are
+/// returned, most severe first.
+pub fn check_health(request: &ScheduleRequest, now: NaiveDateTime) -> Vec<HealthCheck> {
+    let tasks = &request.tasks;
+    let known: HashSet<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+    let mut checks = Vec::new();
+
+    let missing: Vec<String> = tasks
+        .iter()
+        .filter(|t| t.dependencies.iter().any(|d| !known.contains(d.as_str())))
+        .map(|t| t.id.clone())
+        .collect();
+    if !missing.is_empty() {
+        checks.push(check(
+            HealthCheckKind::MissingDependency,
+            Severity::Error,
+            "Depends on a task that doesn't exist".into(),
+            missing,
+        ));
+    }
+
+    // Anything not upstream of an anchor or pin has nothing to schedule it from
+    let depended_on: HashSet<&str> = tasks
+        .iter()
+        .flat_map(|t| t.dependencies.iter().map(String::as_str))
+        .collect();
+    let roots: Vec<&str> = tasks
+        .iter()
+        .filter(|t| {
+            request.anchors.contains_key(&t.id)
+                || request.anchor_windows.contains_key(&t.id)
+                || t.pinned_start.is_some()
+        })
+        .map(|t| t.id.as_str())
+        .collect();
+    let providers: HashMap<&str, &Vec<String>> = tasks
+        .iter()
+        .map(|t| (t.id.as_str(), &t.dependencies))
+        .collect();
+    let mut reached: HashSet<&str> = HashSet::new();
+    let mut stack = roots;
+    while let Some(id) = stack.pop() {
+        if reached.insert(id) {
+            if let Some(deps) = providers.get(id) {
+                stack.extend(deps.iter().map(String::as_str));
+            }
+        }
+    }
+
+    let (open_ends, dangling): (Vec<_>, Vec<_>) = tasks
+        .iter()
+        .filter(|t| !reached.contains(t.id.as_str()))
+        .partition(|t| !depended_on.contains(t.id.as_str()));
+    if !open_ends.is_empty() {
+        checks.push(check(
+            HealthCheckKind::NoSuccessors,
+            Severity::Error,
+            "Nothing depends on these tasks and they have no anchor".into(),
+            open_ends.iter().map(|t| t.id.clone()).collect(),
+        ));
+    }
+    if !dangling.is_empty() {
+        checks.push(check(
+            HealthCheckKind::Dangling,
+            Severity::Error,
+            "No chain of dependents leads to an anchor".into(),
+            dangling.iter().map(|t| t.id.clone()).collect(),
+        ));
+    }
+
+    let long: Vec<String> = tasks
+        .iter()
+        .filter(|t| t.duration() > Duration::days(LONG_DAYS))
+        .map(|t| t.id.clone())
+        .collect();
+    if !long.is_empty() {
+        checks.push(check(
+            HealthCheckKind::LongDuration,
+            Severity::Warning,
+            format!(
+                "Longer than {} days; consider breaking them down",
+                LONG_DAYS
+            ),
+            long,
+        ));
+    }
+
+    let fan_in: Vec<String> = tasks
+        .iter()
+        .filter(|t| t.dependencies.len() > MAX_FAN_IN)
+        .map(|t| t.id.clone())
+        .collect();
+    if !fan_in.is_empty() {
+        checks.push(check(
+            HealthCheckKind::HighFanIn,
+            Severity::Warning,
+            format!("More than {} dependencies", MAX_FAN_IN),
+            fan_in,
+        ));
+    }
+
+    let mut request = request.clone();
+    request.reference_time = Some(now.format("%Y-%m-%dT%H:%M:%S").to_string());
+    match calculate_backwards_schedule(request) {
+        Ok(result) => {
+            let behind: Vec<String> = result
+                .scheduled_tasks
+                .iter()
+                .filter(|t| !t.completed)
+                .filter(|t| {
+                    NaiveDateTime::parse_from_str(&t.start_date, "%Y-%m-%dT%H:%M:%S")
+                        .is_ok_and(|ls| now - ls > Duration::days(BEHIND_DAYS))
+                })
+                .map(|t| t.id.clone())
+                .collect();
+            if !behind.is_empty() {
+                checks.push(check(
+                    HealthCheckKind::NegativeSlack,
+                    Severity::Error,
+                    "More than a day past their latest start".into(),
+                    behind,
+                ));
+            }
+
+            let slack: Vec<String> = result
+                .scheduled_tasks
+                .iter()
+                .filter(|t| t.slack_minutes > LONG_DAYS * 24 * 60)
+                .map(|t| t.id.clone())
+                .collect();
+            if !slack.is_empty() {
+                checks.push(check(
+                    HealthCheckKind::HighSlack,
+                    Severity::Info,
+                    format!(
+                        "More than {} days of slack; logic may be missing",
+                        LONG_DAYS
+                    ),
+                    slack,
+                ));
+            }
+        }
+        Err(e) => checks.push(check(
+            HealthCheckKind::Unschedulable,
+            Severity::Error,
+            e.to_string(),
+            Vec::new(),
+        )),
+    }
+
+    checks.sort_by_key(|c| Reverse(c.severity));
+    checks
+}
+
+/// Health diagnostics for a saved project as of now.
+#[tauri::command]
+pub fn check_schedule_health(
+    app: AppHandle,
+    project_id: String,
+) -> Result<Vec<HealthCheck>, String> {
+    let project = load_project(app, project_id)?;
+    let now = chrono::Local::now().naive_local();
+    Ok(check_health(&project.schedule_request(), now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{Task, TaskDuration};
+
+    fn task(id: &str, days: i64, deps: &[&str]) -> Task {
+        Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(days),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn now() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2026-01-01T00:00:00", "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_healthy_project_has_no_findings() {
+        let request = ScheduleRequest {
+            tasks: vec![task("a", 2, &[]), task("b", 1, &["a"])],
+            anchors: [("b".into(), "2026-01-10T00:00:00".into())].into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+        assert!(check_health(&request, now()).is_empty());
+    }
+
+    #[test]
+    fn test_structural_problems_are_reported() {
+        let deps = ["p1", "p2", "p3", "p4", "p5", "p6"];
+        let mut tasks: Vec<Task> = deps.iter().map(|id| task(id, 1, &[])).collect();
+        tasks.push(task("merge", 60, &deps));
+        tasks.push(task("feeder", 1, &[]));
+        tasks.push(task("orphan", 1, &["feeder"]));
+        let request = ScheduleRequest {
+            tasks,
+            anchors: [("merge".into(), "2026-06-01T00:00:00".into())].into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+
+        let checks = check_health(&request, now());
+        let find = |kind| checks.iter().find(|c| c.kind == kind).unwrap();
+
+        assert_eq!(find(HealthCheckKind::NoSuccessors).task_ids, vec!["orphan"]);
+        assert_eq!(find(HealthCheckKind::Dangling).task_ids, vec!["feeder"]);
+        assert_eq!(find(HealthCheckKind::LongDuration).task_ids, vec!["merge"]);
+        assert_eq!(find(HealthCheckKind::HighFanIn).task_ids, vec!["merge"]);
+        // The open ends are scheduled by fallback rather than failing
+        assert!(checks
+            .iter()
+            .all(|c| c.kind != HealthCheckKind::Unschedulable));
+        assert_eq!(checks.last().unwrap().severity, Severity::Warning);
+    }
+}
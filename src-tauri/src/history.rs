@@ -0,0 +1,132 @@
+//! Undo and redo for task edits.
+//!
+//! [`History`] is kept in managed state and holds, per project, the inverse of
+//! each batch applied through `apply_task_operations`. Edits saved any other
+//! way are not recorded, and a stale entry that no longer applies fails
+//! without changing the project.
+
+use crate::operations::{apply_operations, TaskOperation};
+use crate::project::{load_project, save_project, Project};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// Batches kept per project before the oldest is dropped.
+const MAX_HISTORY: usize = 50;
+
+#[derive(Default)]
+struct Stacks {
+    undo: VecDeque<Vec<TaskOperation>>,
+    redo: Vec<Vec<TaskOperation>>,
+}
+
+/// Undo and redo stacks per project.
+#[derive(Default)]
+pub struct History {
+    projects: Mutex<HashMap<String, Stacks>>,
+}
+
+impl History {
+    /// Records the inverse of a fresh edit, which clears the redo stack.
+    pub fn record(&self, project_id: &str, inverse: Vec<TaskOperation>) {
+        let mut projects = self.projects.lock().unwrap();
+        let stacks = projects.entry(project_id.to_string()).or_default();
+        push_bounded(&mut stacks.undo, inverse);
+        stacks.redo.clear();
+    }
+
+    /// Drops a project's history, e.g. once it is deleted.
+    pub fn forget(&self, project_id: &str) {
+        self.projects.lock().unwrap().remove(project_id);
+    }
+
+    fn pop(&self, project_id: &str, redo: bool) -> Option<Vec<TaskOperation>> {
+        let mut projects = self.projects.lock().unwrap();
+        let stacks = projects.get_mut(project_id)?;
+        if redo {
+            stacks.redo.pop()
+        } else {
+            stacks.undo.pop_back()
+        }
+    }
+
+    fn push(&self, project_id: &str, redo: bool, ops: Vec<TaskOperation>) {
+        let mut projects = self.projects.lock().unwrap();
+        let stacks = projects.entry(project_id.to_string()).or_default();
+        if redo {
+            stacks.redo.push(ops);
+        } else {
+            push_bounded(&mut stacks.undo, ops);
+        }
+    }
+}
+
+fn push_bounded(stack: &mut VecDeque<Vec<TaskOperation>>, ops: Vec<TaskOperation>) {
+    if stack.len() == MAX_HISTORY {
+        stack.pop_front();
+    }
+    stack.push_back(ops);
+}
+
+/// Applies the top of one stack and pushes its inverse onto the other.
+fn step(app: AppHandle, project_id: String, redo: bool) -> Result<Option<Project>, String> {
+    let history = app.state::<History>();
+    let Some(ops) = history.pop(&project_id, redo) else {
+        return Ok(None);
+    };
+    let mut project = load_project(app.clone(), project_id.clone())?;
+    let inverse = match apply_operations(&mut project, ops.clone()) {
+        Ok(inverse) => inverse,
+        Err(e) => {
+            // Keep the entry so the stacks stay in step
+            history.push(&project_id, redo, ops);
+            return Err(e);
+        }
+    };
+    save_project(app.clone(), project)?;
+    history.push(&project_id, !redo, inverse);
+    load_project(app, project_id).map(Some)
+}
+
+/// Reverts the last recorded batch of task operations. Returns `None` when
+/// there is nothing to undo.
+#[tauri::command]
+pub fn undo(app: AppHandle, project_id: String) -> Result<Option<Project>, String> {
+    step(app, project_id, false)
+}
+
+/// Re-applies the last undone batch. Returns `None` when there is nothing to
+/// redo.
+#[tauri::command]
+pub fn redo(app: AppHandle, project_id: String) -> Result<Option<Project>, String> {
+    step(app, project_id, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn complete(id: &str) -> Vec<TaskOperation> {
+        vec![TaskOperation::Complete {
+            task_id: id.into(),
+            completed: true,
+        }]
+    }
+
+    #[test]
+    fn test_history_is_bounded_and_new_edits_clear_redo() {
+        let history = History::default();
+        for i in 0..MAX_HISTORY + 5 {
+            history.record("p", complete(&i.to_string()));
+        }
+        history.push("p", true, complete("r"));
+        history.record("p", complete("last"));
+
+        assert!(history.pop("p", true).is_none());
+        let mut undone = 0;
+        while history.pop("p", false).is_some() {
+            undone += 1;
+        }
+        assert_eq!(undone, MAX_HISTORY);
+    }
+}
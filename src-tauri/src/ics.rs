@@ -0,0 +1,363 @@
+//! iCalendar (RFC 5545) export.
+//!
+//! Calendar feeds are named, filtered views over one or more projects'
+//! schedules, configured in the app config and rendered as VEVENTs or
+//! VTODOs. A single project can also be exported with [`export_ics`].
+
+use crate::cache::ScheduleCache;
+use crate::config::{load_config, save_config};
+use crate::datetime::Zone;
+use crate::project::{load_all_projects, load_project, Project};
+use crate::scheduler::{calculate_backwards_schedule, ScheduledTask, Task};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use tauri::{AppHandle, Manager};
+
+/// How scheduled tasks appear in a calendar.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum IcsComponent {
+    /// Events blocking out each task's scheduled time.
+    #[default]
+    Event,
+    /// To-dos due at each task's scheduled end, for task-list apps.
+    Todo,
+}
+
+/// Which scheduled tasks a feed publishes.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FeedFilter {
+    #[serde(default)]
+    pub milestones_only: bool,
+    #[serde(default)]
+    pub critical_only: bool,
+    /// Only tasks assigned to this person (case-insensitive).
+    #[serde(default)]
+    pub assignee: Option<String>,
+}
+
+impl FeedFilter {
+    fn matches(&self, scheduled: &ScheduledTask, task: Option<&Task>) -> bool {
+        if self.milestones_only && !scheduled.is_milestone {
+            return false;
+        }
+        if self.critical_only && !scheduled.is_critical {
+            return false;
+        }
+        if let Some(wanted) = &self.assignee {
+            let assignee = task.and_then(|t| t.assignee.as_deref());
+            if !assignee.is_some_and(|a| a.eq_ignore_ascii_case(wanted)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A named calendar export.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalendarFeed {
+    pub id: String,
+    pub name: String,
+    /// Projects included in the feed; empty means every project.
+    #[serde(default)]
+    pub project_ids: Vec<String>,
+    #[serde(default)]
+    pub filter: FeedFilter,
+    #[serde(default)]
+    pub component: IcsComponent,
+}
+
+/// Escapes a TEXT value (RFC 5545 §3.3.11).
+fn escape_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line to 75 octets (RFC 5545 §3.1).
+fn fold_line(line: &str) -> String {
+    let mut out = String::with_capacity(line.len() + line.len() / 74 * 3);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+    out
+}
+
+/// Formats a schedule time. Projects pinned to a named zone are written in
+/// UTC so calendars elsewhere place them correctly; the rest stay floating
+/// (local) time.
+fn format_ics_date(s: &str, zone: Zone) -> Option<String> {
+    let wall = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").ok()?;
+    Some(match zone {
+        Zone::Local => wall.format("%Y%m%dT%H%M%S").to_string(),
+        Zone::Named(_) => zone.to_utc(wall).format("%Y%m%dT%H%M%SZ").to_string(),
+    })
+}
+
+/// Renders the components of one project's schedule that pass `filter`.
+pub(crate) fn render_components(
+    project: &Project,
+    schedule: &[ScheduledTask],
+    filter: &FeedFilter,
+    component: IcsComponent,
+    stamp: &str,
+) -> String {
+    let tasks: HashMap<&str, &Task> = project.tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+    let zone = Zone::parse(project.timezone.as_deref()).unwrap_or(Zone::Local);
+    let name = match component {
+        IcsComponent::Event => "VEVENT",
+        IcsComponent::Todo => "VTODO",
+    };
+    let mut out = String::new();
+
+    for scheduled in schedule {
+        if !filter.matches(scheduled, tasks.get(scheduled.id.as_str()).copied()) {
+            continue;
+        }
+        let (Some(start), Some(end)) = (
+            format_ics_date(&scheduled.start_date, zone),
+            format_ics_date(&scheduled.end_date, zone),
+        ) else {
+            continue;
+        };
+
+        out.push_str(&format!("BEGIN:{}\r\n", name));
+        out.push_str(&fold_line(&format!(
+            "UID:{}-{}@anchor",
+            project.id, scheduled.id
+        )));
+        out.push_str(&format!("DTSTAMP:{}\r\n", stamp));
+        out.push_str(&format!("DTSTART:{}\r\n", start));
+        match component {
+            IcsComponent::Event => {
+                out.push_str(&format!("DTEND:{}\r\n", end));
+                if scheduled.is_milestone {
+                    // A moment, not time to block out
+                    out.push_str("TRANSP:TRANSPARENT\r\n");
+                }
+            }
+            IcsComponent::Todo => {
+                out.push_str(&format!("DUE:{}\r\n", end));
+                let status = if scheduled.completed {
+                    "COMPLETED"
+                } else {
+                    "NEEDS-ACTION"
+                };
+                out.push_str(&format!("STATUS:{}\r\n", status));
+            }
+        }
+        out.push_str(&fold_line(&format!(
+            "SUMMARY:{}",
+            escape_text(&scheduled.name)
+        )));
+        if let Some(notes) = scheduled.notes.as_deref().filter(|n| !n.is_empty()) {
+            out.push_str(&fold_line(&format!("DESCRIPTION:{}", escape_text(notes))));
+        }
+        out.push_str(&fold_line(&format!(
+            "CATEGORIES:{}",
+            escape_text(&project.name)
+        )));
+        out.push_str(&format!("END:{}\r\n", name));
+    }
+
+    out
+}
+
+/// Wraps rendered components in a VCALENDAR.
+pub(crate) fn wrap_calendar(name: &str, components: &str) -> String {
+    let mut out =
+        String::from("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Anchor//Anchor//EN\r\n");
+    out.push_str(&fold_line(&format!("X-WR-CALNAME:{}", escape_text(name))));
+    out.push_str(components);
+    out.push_str("END:VCALENDAR\r\n");
+    out
+}
+
+/// Renders a feed over the given projects.
+pub fn render_feed(feed: &CalendarFeed, projects: &[Project]) -> String {
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut events = String::new();
+
+    for project in projects {
+        if !feed.project_ids.is_empty() && !feed.project_ids.contains(&project.id) {
+            continue;
+        }
+        // Projects that can't be scheduled yet have nothing to publish
+        if let Ok(result) = calculate_backwards_schedule(project.schedule_request()) {
+            events.push_str(&render_components(
+                project,
+                &result.scheduled_tasks,
+                &feed.filter,
+                feed.component,
+                &stamp,
+            ));
+        }
+    }
+
+    wrap_calendar(&feed.name, &events)
+}
+
+#[tauri::command]
+pub fn list_calendar_feeds(app: AppHandle) -> Result<Vec<CalendarFeed>, String> {
+    Ok(load_config(app)?.calendar_feeds)
+}
+
+/// Creates or replaces a feed (matched by ID).
+#[tauri::command]
+pub fn save_calendar_feed(app: AppHandle, feed: CalendarFeed) -> Result<(), String> {
+    let mut config = load_config(app.clone())?;
+    match config.calendar_feeds.iter_mut().find(|f| f.id == feed.id) {
+        Some(existing) => *existing = feed,
+        None => config.calendar_feeds.push(feed),
+    }
+    save_config(app, config)
+}
+
+#[tauri::command]
+pub fn delete_calendar_feed(app: AppHandle, feed_id: String) -> Result<(), String> {
+    let mut config = load_config(app.clone())?;
+    config.calendar_feeds.retain(|f| f.id != feed_id);
+    save_config(app, config)
+}
+
+/// Writes a feed's current contents to an .ics file.
+#[tauri::command]
+pub fn export_calendar_feed(app: AppHandle, feed_id: String, path: String) -> Result<(), String> {
+    let feed = load_config(app.clone())?
+        .calendar_feeds
+        .into_iter()
+        .find(|f| f.id == feed_id)
+        .ok_or_else(|| format!("Calendar feed {} not found", feed_id))?;
+    let projects = load_all_projects(&app)?;
+    fs::write(path, render_feed(&feed, &projects)).map_err(|e| e.to_string())
+}
+
+/// Writes one project's schedule to an .ics file, as events unless
+/// `component` asks for to-dos.
+#[tauri::command]
+pub fn export_ics(
+    app: AppHandle,
+    project_id: String,
+    path: String,
+    component: Option<IcsComponent>,
+) -> Result<(), String> {
+    let project = load_project(app.clone(), project_id)?;
+    let now = chrono::Local::now().naive_local();
+    let result = app
+        .state::<ScheduleCache>()
+        .schedule(&project, now)
+        .map_err(|e| e.to_string())?;
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let components = render_components(
+        &project,
+        &result.scheduled_tasks,
+        &FeedFilter::default(),
+        component.unwrap_or_default(),
+        &stamp,
+    );
+    fs::write(path, wrap_calendar(&project.name, &components)).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::TaskDuration;
+
+    fn project() -> Project {
+        Project {
+            id: "p".into(),
+            name: "Launch".into(),
+            tasks: vec![
+                Task {
+                    id: "a".into(),
+                    name: "Write copy".into(),
+                    duration: TaskDuration::days(1),
+                    assignee: Some("Sam".into()),
+                    ..Default::default()
+                },
+                Task {
+                    id: "b".into(),
+                    name: "Go live".into(),
+                    duration: TaskDuration::minutes(0),
+                    dependencies: vec!["a".into()],
+                    is_milestone: true,
+                    ..Default::default()
+                },
+            ],
+            anchors: [("b".into(), "2026-03-01T09:00:00".into())].into(),
+            ..Default::default()
+        }
+    }
+
+    fn feed(filter: FeedFilter) -> CalendarFeed {
+        CalendarFeed {
+            id: "f".into(),
+            name: "Shared".into(),
+            project_ids: vec![],
+            filter,
+            component: IcsComponent::Event,
+        }
+    }
+
+    #[test]
+    fn test_milestones_only_filter() {
+        let filter = FeedFilter {
+            milestones_only: true,
+            ..Default::default()
+        };
+        let ics = render_feed(&feed(filter), &[project()]);
+
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("SUMMARY:Go live\r\n"));
+        assert!(ics.contains("DTSTART:20260301T090000\r\n"));
+    }
+
+    #[test]
+    fn test_assignee_filter() {
+        let filter = FeedFilter {
+            assignee: Some("sam".into()),
+            ..Default::default()
+        };
+        let ics = render_feed(&feed(filter), &[project()]);
+
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("SUMMARY:Write copy\r\n"));
+    }
+
+    #[test]
+    fn test_todos_in_a_named_zone_are_due_in_utc() {
+        let mut project = project();
+        project.timezone = Some("Europe/Paris".into());
+        let result = calculate_backwards_schedule(project.schedule_request()).unwrap();
+
+        let ics = render_components(
+            &project,
+            &result.scheduled_tasks,
+            &FeedFilter::default(),
+            IcsComponent::Todo,
+            "20260101T000000Z",
+        );
+
+        assert_eq!(ics.matches("BEGIN:VTODO").count(), 2);
+        assert!(!ics.contains("VEVENT"));
+        assert!(ics.contains("DUE:20260301T080000Z\r\n"));
+        assert!(ics.contains("STATUS:NEEDS-ACTION\r\n"));
+    }
+
+    #[test]
+    fn test_long_lines_are_folded() {
+        let folded = fold_line(&format!("SUMMARY:{}", "x".repeat(100)));
+        assert!(folded.split("\r\n").all(|l| l.len() <= 75));
+    }
+}
@@ -0,0 +1,1309 @@
+//! File import.
+//!
+//! Turns CSV or TSV task lists, iCalendar files, Microsoft Project XML (MSPDI),
+//! Todoist CSV exports (see [`crate::todoist`]), Trello board exports and
+//! Anchor's own JSON project bundles into a new project. Files dropped on
+//! the main window are imported as a dry run and previewed through the
+//! `import-preview` event; the frontend confirms with [`import_file`]. Task
+//! lists can also be added to an existing project with [`import_csv_tasks`],
+//! and tasks copied from another project with [`import_tasks`].
+
+use crate::history::History;
+use crate::operations::{apply_operations, TaskOperation};
+use crate::project::{load_project, save_project, Project};
+use crate::scheduler::{calculate_backwards_schedule, SubTask, Task, TaskDuration};
+use chrono::{NaiveDate, NaiveDateTime, TimeZone};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    /// Comma- or tab-separated task list.
+    Csv,
+    Ics,
+    Mspdi,
+    /// A project exported as JSON.
+    Bundle,
+    /// A Todoist CSV export, or a project read through the Todoist API.
+    Todoist,
+    /// A Trello board exported as JSON.
+    Trello,
+    /// Issues read through the Jira API, see [`crate::jira`].
+    Jira,
+}
+
+/// What importing a file would create.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportPreview {
+    pub path: String,
+    pub format: ImportFormat,
+    pub project: Project,
+    /// Rows or fields that were skipped or interpreted loosely.
+    pub warnings: Vec<String>,
+    /// Why the imported project can't be scheduled as-is, if it can't.
+    pub schedule_error: Option<String>,
+    /// Tasks the import adds; all of them unless importing into a project.
+    #[serde(default)]
+    pub created_task_ids: Vec<String>,
+    /// Dependencies that name no task and were dropped.
+    #[serde(default)]
+    pub unresolved_dependencies: Vec<UnresolvedDependency>,
+}
+
+/// Which column holds each field, by header, for task lists (such as Notion
+/// database exports) whose headers aren't the ones [`import_csv`] expects.
+/// Unmapped fields are left empty.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ColumnMapping {
+    pub name: String,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub duration: Option<String>,
+    #[serde(default)]
+    pub dependencies: Option<String>,
+    #[serde(default)]
+    pub deadline: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub milestone: Option<String>,
+    #[serde(default)]
+    pub completed: Option<String>,
+    /// Separates dependency references; `;` when unset. Notion uses `,`.
+    #[serde(default)]
+    pub separator: Option<String>,
+}
+
+/// A task list's headers and first rows, for choosing a [`ColumnMapping`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CsvColumns {
+    pub headers: Vec<String>,
+    pub sample_rows: Vec<Vec<String>>,
+}
+
+/// A dependency reference in a task list that matched no task.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UnresolvedDependency {
+    pub task_name: String,
+    pub reference: String,
+}
+
+/// Payload of the `import-failed` event.
+#[derive(Debug, Serialize, Clone)]
+pub struct ImportFailure {
+    pub path: String,
+    pub error: String,
+}
+
+/// Detects the format from the extension, falling back to the contents.
+pub fn detect_format(path: &Path, contents: &str) -> Option<ImportFormat> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    let head = contents.trim_start_matches('\u{feff}').trim_start();
+    if head
+        .get(..12)
+        .is_some_and(|h| h.eq_ignore_ascii_case("TYPE,CONTENT"))
+    {
+        return Some(ImportFormat::Todoist);
+    }
+    match extension.as_deref() {
+        Some("csv") | Some("tsv") | Some("tab") => return Some(ImportFormat::Csv),
+        Some("ics") | Some("ical") => return Some(ImportFormat::Ics),
+        Some("mspdi") => return Some(ImportFormat::Mspdi),
+        Some("json") => return Some(json_format(head)),
+        _ => {}
+    }
+
+    if head.starts_with("BEGIN:VCALENDAR") {
+        Some(ImportFormat::Ics)
+    } else if head.starts_with('<') && head.contains("schemas.microsoft.com/project") {
+        Some(ImportFormat::Mspdi)
+    } else if head.starts_with('{') {
+        Some(json_format(head))
+    } else if extension.as_deref() == Some("txt") && (head.contains(',') || head.contains('\t')) {
+        Some(ImportFormat::Csv)
+    } else {
+        None
+    }
+}
+
+/// A Trello board export has `lists` and `cards`; other JSON is a bundle.
+fn json_format(contents: &str) -> ImportFormat {
+    match serde_json::from_str::<serde_json::Value>(contents) {
+        Ok(value) if value["lists"].is_array() && value["cards"].is_array() => ImportFormat::Trello,
+        _ => ImportFormat::Bundle,
+    }
+}
+
+/// Parses durations like `3d`, `2w`, `4h` or `30m`; a bare number is days.
+pub(crate) fn parse_duration(s: &str) -> Option<TaskDuration> {
+    let s = s.trim().to_ascii_lowercase();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let value: i64 = number.parse().ok()?;
+    match unit.trim() {
+        "" | "d" | "day" | "days" => Some(TaskDuration::days(value)),
+        "w" | "wk" | "week" | "weeks" => Some(TaskDuration::weeks(value)),
+        "h" | "hr" | "hour" | "hours" => Some(TaskDuration::hours(value)),
+        "m" | "min" | "minute" | "minutes" => Some(TaskDuration::minutes(value)),
+        _ => None,
+    }
+}
+
+/// Picks the coarsest unit that represents `minutes` exactly.
+pub(crate) fn duration_from_minutes(minutes: i64) -> TaskDuration {
+    if minutes > 0 && minutes % 60 == 0 {
+        TaskDuration::hours(minutes / 60)
+    } else {
+        TaskDuration::minutes(minutes)
+    }
+}
+
+fn format_date(dt: NaiveDateTime) -> String {
+    dt.format("%Y-%m-%dT%H:%M:%S").to_string()
+}
+
+pub(crate) fn new_task(name: &str) -> Task {
+    Task {
+        id: Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        ..Default::default()
+    }
+}
+
+pub(crate) fn new_project(name: &str) -> Project {
+    let now = chrono::Local::now().to_rfc3339();
+    Project {
+        id: Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        created_at: now.clone(),
+        last_modified: now,
+        ..Default::default()
+    }
+}
+
+// --- CSV ---
+
+/// Splits CSV text into records, honouring quoted fields (RFC 4180).
+pub(crate) fn csv_records(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records.retain(|r| r.iter().any(|f| !f.trim().is_empty()));
+    records
+}
+
+/// What a task list import produced, with its warnings and dropped references.
+type TaskList<T> = (T, Vec<String>, Vec<UnresolvedDependency>);
+
+/// A tab in the header row makes the file tab-separated.
+fn delimiter(text: &str) -> char {
+    match text.lines().next() {
+        Some(header) if header.contains('\t') => '\t',
+        _ => ',',
+    }
+}
+
+/// A deadline as written in a task list: anything [`crate::dates::normalize`]
+/// reads, or Notion's ("March 12, 2026 5:00 PM", or a range whose end counts).
+fn parse_deadline(s: &str) -> Result<String, String> {
+    let normalized = crate::dates::normalize(s, chrono::Local::now().naive_local());
+    if normalized.is_ok() {
+        return normalized;
+    }
+    let end = s.rsplit('→').next().unwrap_or(s).trim();
+    if let Ok(at) = NaiveDateTime::parse_from_str(end, "%B %d, %Y %I:%M %p") {
+        return Ok(format_date(at));
+    }
+    NaiveDate::parse_from_str(end, "%B %d, %Y")
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .or(normalized)
+}
+
+/// A dependency reference without the page link Notion appends to relations,
+/// e.g. `Draft (https://www.notion.so/Draft-1a2b)`.
+fn strip_link(reference: &str) -> &str {
+    match reference.rfind(" (http") {
+        Some(at) if reference.ends_with(')') => reference[..at].trim(),
+        _ => reference,
+    }
+}
+
+/// Imports a task list with a header row. Recognised columns: `name`
+/// (required), `id`, `duration`, `dependencies` (IDs or names separated by
+/// `;`), `deadline`, `notes`, `assignee`, `milestone` and `completed`;
+/// `mapping` names other headers instead.
+///
+/// `known` maps names and IDs of tasks outside the file to task IDs, so rows
+/// can depend on tasks already in a project.
+fn import_csv(
+    name: &str,
+    text: &str,
+    known: &HashMap<String, String>,
+    mapping: Option<&ColumnMapping>,
+) -> Result<TaskList<Project>, String> {
+    let text = text.trim_start_matches('\u{feff}');
+    let mut records = csv_records(text, delimiter(text)).into_iter();
+    let header: Vec<String> = records
+        .next()
+        .ok_or("The file is empty")?
+        .iter()
+        .map(|h| h.trim().to_ascii_lowercase())
+        .collect();
+    let find = |name: &str| {
+        let wanted = name.trim().to_ascii_lowercase();
+        header
+            .iter()
+            .position(|h| *h == wanted)
+            .ok_or_else(|| format!("No '{}' column found", name.trim()))
+    };
+    let column = |mapped: fn(&ColumnMapping) -> &Option<String>, names: &[&str]| match mapping {
+        Some(mapping) => mapped(mapping).as_deref().map(find).transpose(),
+        None => Ok(header.iter().position(|h| names.contains(&h.as_str()))),
+    };
+    let name_col = match mapping {
+        Some(mapping) => find(&mapping.name)?,
+        None => header
+            .iter()
+            .position(|h| ["name", "task", "title"].contains(&h.as_str()))
+            .ok_or("No 'name' column found")?,
+    };
+    let id_col = column(|m| &m.id, &["id"])?;
+    let duration_col = column(|m| &m.duration, &["duration"])?;
+    let deps_col = column(
+        |m| &m.dependencies,
+        &["dependencies", "depends_on", "predecessors"],
+    )?;
+    let deadline_col = column(|m| &m.deadline, &["deadline", "anchor", "due"])?;
+    let notes_col = column(|m| &m.notes, &["notes", "description"])?;
+    let assignee_col = column(|m| &m.assignee, &["assignee", "owner"])?;
+    let milestone_col = column(|m| &m.milestone, &["milestone"])?;
+    let completed_col = column(|m| &m.completed, &["completed", "done"])?;
+    let separator = mapping
+        .and_then(|m| m.separator.as_deref())
+        .filter(|s| !s.is_empty())
+        .unwrap_or(";");
+
+    let mut project = new_project(name);
+    let mut warnings = Vec::new();
+    let mut unresolved = Vec::new();
+    let mut references: HashMap<String, String> = known.clone();
+    let mut raw_deps: Vec<Vec<String>> = Vec::new();
+
+    for (row, record) in records.enumerate() {
+        let line = row + 2;
+        let get = |col: Option<usize>| {
+            col.and_then(|c| record.get(c))
+                .map(|f| f.trim())
+                .filter(|f| !f.is_empty())
+        };
+        let flag = |col| {
+            get(col).is_some_and(|f| {
+                matches!(f.to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "x")
+            })
+        };
+
+        let Some(task_name) = get(Some(name_col)) else {
+            warnings.push(format!("Line {}: no task name, skipped", line));
+            continue;
+        };
+        let mut task = new_task(task_name);
+        task.duration = match get(duration_col).map(|d| (d, parse_duration(d))) {
+            None => TaskDuration::days(1),
+            Some((_, Some(duration))) => duration,
+            Some((raw, None)) => {
+                warnings.push(format!(
+                    "Line {}: unknown duration '{}', using 1 day",
+                    line, raw
+                ));
+                TaskDuration::days(1)
+            }
+        };
+        task.notes = get(notes_col).map(str::to_string);
+        task.assignee = get(assignee_col).map(str::to_string);
+        task.is_milestone = flag(milestone_col);
+        task.completed = flag(completed_col);
+
+        if let Some(deadline) = get(deadline_col) {
+            match parse_deadline(deadline) {
+                Ok(deadline) => {
+                    project.anchors.insert(task.id.clone(), deadline);
+                }
+                Err(e) => warnings.push(format!("Line {}: {}", line, e)),
+            }
+        }
+
+        if let Some(id) = get(id_col) {
+            references.insert(id.to_string(), task.id.clone());
+        }
+        references
+            .entry(task_name.to_string())
+            .or_insert(task.id.clone());
+        raw_deps.push(
+            get(deps_col)
+                .map(|d| {
+                    d.split(separator)
+                        .map(|s| strip_link(s.trim()).to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        );
+        project.tasks.push(task);
+    }
+
+    for (task, deps) in project.tasks.iter_mut().zip(raw_deps) {
+        for dep in deps {
+            match references.get(&dep) {
+                Some(id) => task.dependencies.push(id.clone()),
+                None => {
+                    warnings.push(format!(
+                        "'{}': unknown dependency '{}' dropped",
+                        task.name, dep
+                    ));
+                    unresolved.push(UnresolvedDependency {
+                        task_name: task.name.clone(),
+                        reference: dep,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok((project, warnings, unresolved))
+}
+
+// --- iCalendar ---
+
+fn unescape_ics(value: &str) -> String {
+    value
+        .replace("\\n", "\n")
+        .replace("\\N", "\n")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+/// Parses a DTSTART/DTEND value. Returns the wall-clock time and whether it
+/// was a whole date; UTC times are converted to the local zone.
+fn parse_ics_date(params: &str, value: &str) -> Option<(NaiveDateTime, bool)> {
+    if params.contains("VALUE=DATE") && !params.contains("VALUE=DATE-TIME") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some((date.and_hms_opt(0, 0, 0)?, true));
+    }
+    let (value, utc) = match value.strip_suffix('Z') {
+        Some(v) => (v, true),
+        None => (value, false),
+    };
+    let dt = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    if utc {
+        Some((
+            chrono::Utc
+                .from_utc_datetime(&dt)
+                .with_timezone(&chrono::Local)
+                .naive_local(),
+            false,
+        ))
+    } else {
+        Some((dt, false))
+    }
+}
+
+/// Imports each event as a task anchored at the event's end.
+fn import_ics(name: &str, text: &str) -> Result<(Project, Vec<String>), String> {
+    // Unfold continuation lines (RFC 5545 §3.1)
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')) {
+            Some(rest) if !lines.is_empty() => lines.last_mut().unwrap().push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    let mut project = new_project(name);
+    let mut warnings = Vec::new();
+    let mut event: Option<HashMap<String, (String, String)>> = None;
+
+    for line in &lines {
+        match line.trim_end() {
+            "BEGIN:VEVENT" => event = Some(HashMap::new()),
+            "END:VEVENT" => {
+                let Some(fields) = event.take() else { continue };
+                let summary = fields
+                    .get("SUMMARY")
+                    .map(|(_, v)| unescape_ics(v))
+                    .unwrap_or_else(|| "Untitled event".into());
+                let start = fields
+                    .get("DTSTART")
+                    .and_then(|(p, v)| parse_ics_date(p, v));
+                let end = fields.get("DTEND").and_then(|(p, v)| parse_ics_date(p, v));
+                let Some((start, all_day)) = start else {
+                    warnings.push(format!("'{}': no usable start date, skipped", summary));
+                    continue;
+                };
+                let end = end.map(|(e, _)| e).unwrap_or(start);
+
+                let mut task = new_task(&summary);
+                task.notes = fields.get("DESCRIPTION").map(|(_, v)| unescape_ics(v));
+                task.duration = if all_day {
+                    TaskDuration::days((end - start).num_days())
+                } else {
+                    duration_from_minutes((end - start).num_minutes())
+                };
+                task.is_milestone = end == start;
+                project.anchors.insert(task.id.clone(), format_date(end));
+                project.tasks.push(task);
+            }
+            line => {
+                let (Some(fields), Some((key, value))) = (event.as_mut(), line.split_once(':'))
+                else {
+                    continue;
+                };
+                let (key, params) = key.split_once(';').unwrap_or((key, ""));
+                fields.insert(
+                    key.to_ascii_uppercase(),
+                    (params.to_string(), value.to_string()),
+                );
+            }
+        }
+    }
+
+    if project.tasks.is_empty() && warnings.is_empty() {
+        return Err("No events found".into());
+    }
+    Ok((project, warnings))
+}
+
+// --- Microsoft Project XML ---
+
+/// Minutes in an MSPDI duration such as `PT16H0M0S` or `P1DT2H0M0S`.
+fn parse_mspdi_duration(s: &str) -> Option<i64> {
+    let rest = s.strip_prefix('P')?;
+    let (days, time) = rest.split_once('T').unwrap_or((rest, ""));
+    let mut minutes = match days.strip_suffix('D') {
+        Some(d) => d.parse::<f64>().ok()? * 24.0 * 60.0,
+        None if days.is_empty() => 0.0,
+        None => return None,
+    };
+    let mut number = String::new();
+    for c in time.chars() {
+        match c {
+            'H' | 'M' | 'S' => {
+                let value: f64 = number.parse().ok()?;
+                number.clear();
+                minutes += match c {
+                    'H' => value * 60.0,
+                    'M' => value,
+                    _ => value / 60.0,
+                };
+            }
+            _ => number.push(c),
+        }
+    }
+    Some(minutes.round() as i64)
+}
+
+#[derive(Default)]
+struct MspdiTask {
+    uid: String,
+    name: String,
+    duration: Option<String>,
+    finish: Option<String>,
+    notes: Option<String>,
+    milestone: bool,
+    summary: bool,
+    percent_complete: u32,
+    predecessors: Vec<String>,
+}
+
+/// Imports leaf tasks and their predecessor links. Tasks nothing depends on
+/// are anchored at their planned finish.
+fn import_mspdi(name: &str, text: &str) -> Result<(Project, Vec<String>), String> {
+    let mut reader = Reader::from_str(text);
+    reader.config_mut().trim_text(true);
+
+    let mut path: Vec<String> = Vec::new();
+    let mut title: Option<String> = None;
+    let mut current: Option<MspdiTask> = None;
+    let mut parsed: Vec<MspdiTask> = Vec::new();
+
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(e) => {
+                let tag = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                if tag == "Task" && path.last().map(String::as_str) == Some("Tasks") {
+                    current = Some(MspdiTask::default());
+                }
+                path.push(tag);
+            }
+            Event::End(_) => {
+                let closed = path.pop();
+                if closed.as_deref() == Some("Task")
+                    && path.last().map(String::as_str) == Some("Tasks")
+                {
+                    parsed.extend(current.take());
+                }
+            }
+            Event::Text(t) => {
+                let value = t.unescape().map_err(|e| e.to_string())?.into_owned();
+                let tag = path.last().map(String::as_str).unwrap_or("");
+                match (current.as_mut(), tag) {
+                    // Name is usually the file name; prefer the Title property
+                    (None, "Title") if path.len() == 2 => title = Some(value),
+                    (None, "Name") if path.len() == 2 => {
+                        title.get_or_insert(value);
+                    }
+                    (Some(task), "PredecessorUID") => task.predecessors.push(value),
+                    // Deeper fields belong to baselines, assignments and the like
+                    (Some(_), _) if path.len() != 4 => {}
+                    (Some(task), "UID") => task.uid = value,
+                    (Some(task), "Name") => task.name = value,
+                    (Some(task), "Duration") => task.duration = Some(value),
+                    (Some(task), "Finish") => task.finish = Some(value),
+                    (Some(task), "Notes") => task.notes = Some(value),
+                    (Some(task), "Milestone") => task.milestone = value == "1",
+                    (Some(task), "Summary") => task.summary = value == "1",
+                    (Some(task), "PercentComplete") => {
+                        task.percent_complete = value.parse().unwrap_or(0)
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    let mut project = new_project(title.as_deref().unwrap_or(name));
+    let mut warnings = Vec::new();
+    // Summary rows (and the project row, UID 0) only roll up their children
+    parsed.retain(|t| !t.summary && t.uid != "0" && !t.uid.is_empty());
+    if parsed.is_empty() {
+        return Err("No tasks found".into());
+    }
+
+    let ids: HashMap<&str, String> = parsed
+        .iter()
+        .map(|t| (t.uid.as_str(), Uuid::new_v4().to_string()))
+        .collect();
+    let has_successor: HashSet<&str> = parsed
+        .iter()
+        .flat_map(|t| t.predecessors.iter().map(String::as_str))
+        .collect();
+    let mut working_days = false;
+
+    for source in &parsed {
+        let mut task = new_task(&source.name);
+        task.id = ids[source.uid.as_str()].clone();
+        task.notes = source.notes.clone();
+        task.is_milestone = source.milestone;
+        task.completed = source.percent_complete >= 100;
+
+        let minutes = source.duration.as_deref().and_then(parse_mspdi_duration);
+        task.duration = match minutes {
+            // Project counts 8 working hours as a day
+            Some(m) if m > 0 && m % (8 * 60) == 0 => {
+                working_days = true;
+                TaskDuration::days(m / (8 * 60))
+            }
+            Some(m) => duration_from_minutes(m),
+            None => {
+                warnings.push(format!("'{}': no duration, using 0", source.name));
+                TaskDuration::minutes(0)
+            }
+        };
+
+        for uid in &source.predecessors {
+            match ids.get(uid.as_str()) {
+                Some(id) => task.dependencies.push(id.clone()),
+                None => warnings.push(format!(
+                    "'{}': predecessor {} is a summary or missing task, dropped",
+                    source.name, uid
+                )),
+            }
+        }
+
+        if !has_successor.contains(source.uid.as_str()) {
+            match source
+                .finish
+                .as_deref()
+                .map(crate::scheduler::parse_date_string)
+            {
+                Some(Ok(finish)) => {
+                    project.anchors.insert(task.id.clone(), format_date(finish));
+                }
+                _ => warnings.push(format!("'{}': no finish date to anchor to", source.name)),
+            }
+        }
+        project.tasks.push(task);
+    }
+
+    if working_days {
+        warnings.push("Durations in 8-hour working days were imported as calendar days".into());
+    }
+    Ok((project, warnings))
+}
+
+// --- Trello ---
+
+/// Imports a Trello board export: open lists become phases and open cards
+/// tasks, with due dates as anchors, labels as tags and checklist items as
+/// subtasks. Tasks take a day each; Trello has no dependencies.
+fn import_trello(text: &str) -> Result<(Project, Vec<String>), String> {
+    use serde_json::Value;
+    let board: Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    let text = |v: &Value| v.as_str().map(str::to_string);
+    let open = |v: &&Value| !v["closed"].as_bool().unwrap_or(false);
+    let pos = |v: &Value| v["pos"].as_f64().unwrap_or(0.0);
+    let items = |key: &str| board[key].as_array().cloned().unwrap_or_default();
+
+    let mut lists: Vec<Value> = items("lists").into_iter().filter(|l| open(&l)).collect();
+    lists.sort_by(|a, b| pos(a).total_cmp(&pos(b)));
+    let checklists = items("checklists");
+    let mut project = new_project(&text(&board["name"]).unwrap_or("Trello board".to_string()));
+    let mut warnings = Vec::new();
+
+    for list in &lists {
+        let mut cards: Vec<&Value> = board["cards"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(open)
+            .filter(|c| c["idList"] == list["id"])
+            .collect();
+        cards.sort_by(|a, b| pos(a).total_cmp(&pos(b)));
+        for card in cards {
+            let mut task = new_task(&text(&card["name"]).unwrap_or_default());
+            task.phase = text(&list["name"]);
+            task.notes = text(&card["desc"]).filter(|d| !d.trim().is_empty());
+            task.duration = TaskDuration::days(1);
+            task.completed = card["dueComplete"].as_bool().unwrap_or(false);
+            task.tags = card["labels"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|l| text(&l["name"]).filter(|n| !n.is_empty()))
+                .collect();
+
+            let mut checklist: Vec<&Value> = checklists
+                .iter()
+                .filter(|c| c["idCard"] == card["id"])
+                .collect();
+            checklist.sort_by(|a, b| pos(a).total_cmp(&pos(b)));
+            for list in checklist {
+                let mut entries: Vec<&Value> = list["checkItems"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                entries.sort_by(|a, b| pos(a).total_cmp(&pos(b)));
+                task.subtasks
+                    .extend(entries.into_iter().map(|item| SubTask {
+                        id: Uuid::new_v4().to_string(),
+                        name: text(&item["name"]).unwrap_or_default(),
+                        completed: item["state"] == "complete",
+                    }));
+            }
+
+            if let Some(due) = text(&card["due"]) {
+                match chrono::DateTime::parse_from_rfc3339(&due) {
+                    Ok(at) => {
+                        let local = at.with_timezone(&chrono::Local).naive_local();
+                        project.anchors.insert(task.id.clone(), format_date(local));
+                    }
+                    Err(_) => warnings.push(format!(
+                        "'{}': unreadable due date '{}' skipped",
+                        task.name, due
+                    )),
+                }
+            }
+            project.tasks.push(task);
+        }
+    }
+    if project.tasks.is_empty() {
+        warnings.push("The board has no open cards".to_string());
+    }
+    Ok((project, warnings))
+}
+
+// --- JSON bundle ---
+
+/// Imports an exported project as a copy, so it never overwrites the original.
+fn import_bundle(text: &str) -> Result<(Project, Vec<String>), String> {
+    let mut value: serde_json::Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    // Either `{ "project": {...} }` or the project itself
+    if let Some(project) = value.get_mut("project").filter(|p| p.is_object()) {
+        value = project.take();
+    }
+    let project = crate::migrate::upgrade(value).map_err(|e| e.to_string())?;
+    let fresh = new_project(&project.name);
+    Ok((
+        Project {
+            id: fresh.id,
+            created_at: fresh.created_at,
+            last_modified: fresh.last_modified,
+            ..project
+        },
+        Vec::new(),
+    ))
+}
+
+/// Runs the importer for `path` without saving anything.
+pub fn preview(path: &Path) -> Result<ImportPreview, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let format = detect_format(path, &text).ok_or("Unrecognised file format")?;
+    let name = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Imported project");
+
+    let mut unresolved_dependencies = Vec::new();
+    let (project, warnings) = match format {
+        ImportFormat::Csv => {
+            let (project, warnings, unresolved) = import_csv(name, &text, &HashMap::new(), None)?;
+            unresolved_dependencies = unresolved;
+            (project, warnings)
+        }
+        ImportFormat::Ics => import_ics(name, &text)?,
+        ImportFormat::Mspdi => import_mspdi(name, &text)?,
+        ImportFormat::Bundle => import_bundle(&text)?,
+        ImportFormat::Todoist => crate::todoist::import_csv(name, &text)?,
+        ImportFormat::Trello => import_trello(&text)?,
+        ImportFormat::Jira => return Err("Jira issues are imported from the API".to_string()),
+    };
+    Ok(preview_of(
+        path.display().to_string(),
+        format,
+        project,
+        warnings,
+        unresolved_dependencies,
+    ))
+}
+
+/// The preview of an imported `project`, checking that it can be scheduled.
+pub(crate) fn preview_of(
+    path: String,
+    format: ImportFormat,
+    project: Project,
+    warnings: Vec<String>,
+    unresolved_dependencies: Vec<UnresolvedDependency>,
+) -> ImportPreview {
+    let schedule_error = calculate_backwards_schedule(project.schedule_request())
+        .err()
+        .map(|e| e.to_string());
+    ImportPreview {
+        path,
+        format,
+        created_task_ids: project.tasks.iter().map(|t| t.id.clone()).collect(),
+        project,
+        warnings,
+        schedule_error,
+        unresolved_dependencies,
+    }
+}
+
+/// The task list at `path` as operations adding its tasks to `project`.
+fn csv_operations(project: &Project, path: &Path) -> Result<TaskList<Vec<TaskOperation>>, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    if detect_format(path, &text) != Some(ImportFormat::Csv) {
+        return Err("Expected a CSV or TSV task list".to_string());
+    }
+    let mut known = HashMap::new();
+    for task in &project.tasks {
+        known.insert(task.name.clone(), task.id.clone());
+        known.insert(task.id.clone(), task.id.clone());
+    }
+    let (mut imported, warnings, unresolved) = import_csv(&project.name, &text, &known, None)?;
+    let ops = imported
+        .tasks
+        .into_iter()
+        .map(|task| TaskOperation::Add {
+            anchor: imported.anchors.remove(&task.id),
+            anchor_window: None,
+            task,
+        })
+        .collect();
+    Ok((ops, warnings, unresolved))
+}
+
+/// Adds the tasks in a CSV or TSV file to an existing project. Rows may
+/// depend on the project's tasks by name or ID. With `dry_run` nothing is
+/// saved; otherwise the import can be undone like any task operation.
+#[tauri::command]
+pub fn import_csv_tasks(
+    app: AppHandle,
+    project_id: String,
+    path: String,
+    dry_run: bool,
+) -> Result<ImportPreview, String> {
+    let mut project = load_project(app.clone(), project_id)?;
+    let (ops, warnings, unresolved_dependencies) = csv_operations(&project, Path::new(&path))?;
+    let created_task_ids = ops
+        .iter()
+        .filter_map(|op| match op {
+            TaskOperation::Add { task, .. } => Some(task.id.clone()),
+            _ => None,
+        })
+        .collect();
+    let inverse = apply_operations(&mut project, ops)?;
+    let schedule_error = calculate_backwards_schedule(project.schedule_request())
+        .err()
+        .map(|e| e.to_string());
+
+    if !dry_run {
+        save_project(app.clone(), project.clone())?;
+        app.state::<History>().record(&project.id, inverse);
+    }
+    Ok(ImportPreview {
+        path,
+        format: ImportFormat::Csv,
+        project,
+        warnings,
+        schedule_error,
+        created_task_ids,
+        unresolved_dependencies,
+    })
+}
+
+/// Operations adding copies of `task_ids` from `source` to `target`, without
+/// completion state. Copies keep their IDs unless `target` already uses them;
+/// dependencies on tasks that aren't copied are dropped.
+fn copy_operations(
+    source: &Project,
+    target: &Project,
+    task_ids: &[String],
+) -> Result<Vec<TaskOperation>, String> {
+    if let Some(missing) = task_ids
+        .iter()
+        .find(|id| !source.tasks.iter().any(|t| &t.id == *id))
+    {
+        return Err(format!("Task {} not found in {}", missing, source.name));
+    }
+    let taken: HashSet<&str> = target.tasks.iter().map(|t| t.id.as_str()).collect();
+    let ids: HashMap<&str, String> = source
+        .tasks
+        .iter()
+        .filter(|t| task_ids.contains(&t.id))
+        .map(|t| {
+            let id = if taken.contains(t.id.as_str()) {
+                Uuid::new_v4().to_string()
+            } else {
+                t.id.clone()
+            };
+            (t.id.as_str(), id)
+        })
+        .collect();
+
+    Ok(source
+        .tasks
+        .iter()
+        .filter_map(|task| {
+            let id = ids.get(task.id.as_str())?;
+            let mut copy = Task {
+                id: id.clone(),
+                dependencies: task
+                    .dependencies
+                    .iter()
+                    .filter_map(|d| ids.get(d.as_str()).cloned())
+                    .collect(),
+                start_after_percent: task
+                    .start_after_percent
+                    .iter()
+                    .filter_map(|(d, percent)| Some((ids.get(d.as_str())?.clone(), *percent)))
+                    .collect(),
+                completed: false,
+                completed_at: None,
+                ..task.clone()
+            };
+            for subtask in &mut copy.subtasks {
+                subtask.completed = false;
+            }
+            Some(TaskOperation::Add {
+                task: copy,
+                anchor: source.anchors.get(&task.id).cloned(),
+                anchor_window: source.anchor_windows.get(&task.id).cloned(),
+            })
+        })
+        .collect())
+}
+
+/// Copies tasks, and the dependencies between them, from one project into
+/// another so shared work can be reused. The copy can be undone like any
+/// task operation. Returns the updated project.
+#[tauri::command]
+pub fn import_tasks(
+    app: AppHandle,
+    target_project_id: String,
+    source_project_id: String,
+    task_ids: Vec<String>,
+) -> Result<Project, String> {
+    let source = load_project(app.clone(), source_project_id)?;
+    let mut project = load_project(app.clone(), target_project_id)?;
+    let ops = copy_operations(&source, &project, &task_ids)?;
+    let inverse = apply_operations(&mut project, ops)?;
+    save_project(app.clone(), project.clone())?;
+    app.state::<History>().record(&project.id, inverse);
+    Ok(project)
+}
+
+/// Imports a file as a new project. With `dry_run` nothing is saved.
+#[tauri::command]
+pub fn import_file(app: AppHandle, path: String, dry_run: bool) -> Result<ImportPreview, String> {
+    let preview = preview(Path::new(&path))?;
+    if !dry_run {
+        save_project(app, preview.project.clone())?;
+    }
+    Ok(preview)
+}
+
+/// The headers and first `sample` (default 5) rows of a task list, so the
+/// user can map its columns for [`import_mapped_csv`].
+#[tauri::command]
+pub fn read_csv_columns(path: String, sample: Option<usize>) -> Result<CsvColumns, String> {
+    let text = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let text = text.trim_start_matches('\u{feff}');
+    let mut records = csv_records(text, delimiter(text)).into_iter();
+    let headers = records.next().ok_or("The file is empty")?;
+    Ok(CsvColumns {
+        headers: headers.iter().map(|h| h.trim().to_string()).collect(),
+        sample_rows: records.take(sample.unwrap_or(5)).collect(),
+    })
+}
+
+/// Imports a task list (e.g. a Notion database export) as a new project,
+/// reading fields from the columns `mapping` names. With `dry_run` nothing
+/// is saved, so the result can be previewed first.
+#[tauri::command]
+pub fn import_mapped_csv(
+    app: AppHandle,
+    path: String,
+    mapping: ColumnMapping,
+    dry_run: bool,
+) -> Result<ImportPreview, String> {
+    let file = Path::new(&path);
+    let text = fs::read_to_string(file).map_err(|e| e.to_string())?;
+    let name = file
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Imported project");
+    let (project, warnings, unresolved) = import_csv(name, &text, &HashMap::new(), Some(&mapping))?;
+    let preview = preview_of(path, ImportFormat::Csv, project, warnings, unresolved);
+    if !dry_run {
+        save_project(app, preview.project.clone())?;
+    }
+    Ok(preview)
+}
+
+/// Previews files dropped on the main window, emitting `import-preview` for
+/// each importable file and `import-failed` for the rest.
+pub fn handle_drop(app: &AppHandle, paths: &[PathBuf]) {
+    for path in paths {
+        let _ = match preview(path) {
+            Ok(preview) => app.emit("import-preview", preview),
+            Err(error) => app.emit(
+                "import-failed",
+                ImportFailure {
+                    path: path.display().to_string(),
+                    error,
+                },
+            ),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_format_from_extension_and_contents() {
+        assert_eq!(
+            detect_format(Path::new("plan.csv"), ""),
+            Some(ImportFormat::Csv)
+        );
+        assert_eq!(
+            detect_format(Path::new("export"), "BEGIN:VCALENDAR\r\nVERSION:2.0"),
+            Some(ImportFormat::Ics)
+        );
+        assert_eq!(
+            detect_format(
+                Path::new("plan.xml"),
+                r#"<?xml version="1.0"?><Project xmlns="http://schemas.microsoft.com/project">"#
+            ),
+            Some(ImportFormat::Mspdi)
+        );
+        assert_eq!(detect_format(Path::new("notes.xml"), "<notes/>"), None);
+    }
+
+    #[test]
+    fn test_trello_board_maps_lists_cards_and_checklists() {
+        let board = r#"{
+            "name": "Launch",
+            "lists": [
+                {"id": "l2", "name": "Doing", "closed": false, "pos": 2},
+                {"id": "l1", "name": "To do", "closed": false, "pos": 1},
+                {"id": "l3", "name": "Old", "closed": true, "pos": 3}
+            ],
+            "cards": [
+                {"id": "c1", "name": "Write copy", "desc": "Homepage", "idList": "l1",
+                 "due": "2026-03-12T17:00:00.000Z", "dueComplete": false, "closed": false,
+                 "pos": 1, "labels": [{"name": "content"}, {"name": ""}]},
+                {"id": "c2", "name": "Build site", "desc": "", "idList": "l2",
+                 "due": null, "closed": false, "pos": 1, "labels": []},
+                {"id": "c3", "name": "Archived", "idList": "l1", "closed": true, "pos": 2},
+                {"id": "c4", "name": "In old list", "idList": "l3", "closed": false, "pos": 1}
+            ],
+            "checklists": [
+                {"id": "k1", "idCard": "c2", "pos": 1, "checkItems": [
+                    {"name": "Footer", "state": "complete", "pos": 2},
+                    {"name": "Header", "state": "incomplete", "pos": 1}
+                ]}
+            ]
+        }"#;
+        assert_eq!(
+            detect_format(Path::new("board.json"), board),
+            Some(ImportFormat::Trello)
+        );
+        let (project, warnings) = import_trello(board).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(project.name, "Launch");
+        let names: Vec<_> = project.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Write copy", "Build site"]);
+
+        let copy = &project.tasks[0];
+        assert_eq!(copy.phase.as_deref(), Some("To do"));
+        assert_eq!(copy.notes.as_deref(), Some("Homepage"));
+        assert_eq!(copy.tags, vec!["content"]);
+        assert!(project.anchors.contains_key(&copy.id));
+
+        let site = &project.tasks[1];
+        let items: Vec<_> = site
+            .subtasks
+            .iter()
+            .map(|s| (s.name.as_str(), s.completed))
+            .collect();
+        assert_eq!(items, vec![("Header", false), ("Footer", true)]);
+        assert!(!project.anchors.contains_key(&site.id));
+    }
+
+    #[test]
+    fn test_notion_export_with_column_mapping() {
+        let csv = "Task name,Est.,Blocked by,Due,Status\n\
+                   Outline,2d,,,Done\n\
+                   Draft,1w,Outline (https://www.notion.so/Outline-1a2b),\"March 12, 2026\",\n\
+                   Launch,4h,\"Draft (https://www.notion.so/Draft-3c4d), Outline (https://www.notion.so/Outline-1a2b)\",\"March 20, 2026 5:00 PM\",\n";
+        let mapping = ColumnMapping {
+            name: "Task name".into(),
+            duration: Some("Est.".into()),
+            dependencies: Some("Blocked by".into()),
+            deadline: Some("due".into()),
+            separator: Some(",".into()),
+            ..Default::default()
+        };
+
+        let (project, warnings, unresolved) =
+            import_csv("Notion", csv, &HashMap::new(), Some(&mapping)).unwrap();
+        assert!(warnings.is_empty() && unresolved.is_empty());
+        let [outline, draft, launch] = &project.tasks[..] else {
+            panic!("expected three tasks");
+        };
+        assert_eq!(draft.duration, TaskDuration::weeks(1));
+        assert_eq!(draft.dependencies, vec![outline.id.clone()]);
+        assert_eq!(
+            launch.dependencies,
+            vec![draft.id.clone(), outline.id.clone()]
+        );
+        assert_eq!(project.anchors[&draft.id], "2026-03-12");
+        assert_eq!(project.anchors[&launch.id], "2026-03-20T17:00:00");
+
+        let missing = ColumnMapping {
+            name: "Title".into(),
+            ..Default::default()
+        };
+        assert!(import_csv("Notion", csv, &HashMap::new(), Some(&missing)).is_err());
+    }
+
+    #[test]
+    fn test_csv_resolves_dependencies_by_name() {
+        let csv = "Name,Duration,Dependencies,Deadline\n\
+                   Draft,3d,,\n\
+                   \"Review, final\",4h,Draft,\n\
+                   Ship,1w,\"Review, final;Missing\",2026-03-01\n";
+
+        let (project, warnings, unresolved) =
+            import_csv("Launch", csv, &HashMap::new(), None).unwrap();
+
+        assert_eq!(project.tasks.len(), 3);
+        assert_eq!(project.tasks[1].duration, TaskDuration::hours(4));
+        assert_eq!(project.tasks[2].duration, TaskDuration::weeks(1));
+        assert_eq!(
+            project.tasks[2].dependencies,
+            vec![project.tasks[1].id.clone()]
+        );
+        assert_eq!(project.anchors.len(), 1);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            unresolved,
+            vec![UnresolvedDependency {
+                task_name: "Ship".into(),
+                reference: "Missing".into(),
+            }]
+        );
+        assert!(calculate_backwards_schedule(project.schedule_request()).is_ok());
+    }
+
+    #[test]
+    fn test_tsv_rows_depend_on_existing_tasks() {
+        let tsv = "name\tduration\tdependencies\tnotes\n\
+                   Polish\t2h\tExisting draft\tTabs, not commas\n\
+                   Publish\t30m\tPolish;t-1\t\n";
+        let known: HashMap<String, String> = [
+            ("Existing draft".into(), "t-1".into()),
+            ("t-1".into(), "t-1".into()),
+        ]
+        .into();
+
+        let (project, warnings, unresolved) = import_csv("Blog", tsv, &known, None).unwrap();
+
+        assert!(warnings.is_empty() && unresolved.is_empty());
+        assert_eq!(project.tasks[0].dependencies, vec!["t-1"]);
+        assert_eq!(project.tasks[0].notes.as_deref(), Some("Tabs, not commas"));
+        assert_eq!(project.tasks[1].duration, TaskDuration::minutes(30));
+        assert_eq!(
+            project.tasks[1].dependencies,
+            vec![project.tasks[0].id.clone(), "t-1".into()]
+        );
+    }
+
+    #[test]
+    fn test_ics_events_become_anchored_tasks() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+                   BEGIN:VEVENT\r\n\
+                   SUMMARY:Offsite\\, day one\r\n\
+                   DTSTART;VALUE=DATE:20260310\r\n\
+                   DTEND;VALUE=DATE:20260312\r\n\
+                   END:VEVENT\r\n\
+                   BEGIN:VEVENT\r\n\
+                   SUMMARY:Stand\r\n \
+                   up\r\n\
+                   DTSTART:20260310T090000\r\n\
+                   DTEND:20260310T093000\r\n\
+                   END:VEVENT\r\n\
+                   END:VCALENDAR\r\n";
+
+        let (project, _) = import_ics("Calendar", ics).unwrap();
+
+        assert_eq!(project.tasks[0].name, "Offsite, day one");
+        assert_eq!(project.tasks[0].duration, TaskDuration::days(2));
+        assert_eq!(project.anchors[&project.tasks[0].id], "2026-03-12T00:00:00");
+        assert_eq!(project.tasks[1].name, "Standup");
+        assert_eq!(project.tasks[1].duration, TaskDuration::minutes(30));
+    }
+
+    #[test]
+    fn test_mspdi_skips_summaries_and_anchors_final_tasks() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <Project xmlns="http://schemas.microsoft.com/project">
+              <Name>Fit-out.xml</Name>
+              <Title>Office fit-out</Title>
+              <Tasks>
+                <Task><UID>0</UID><Name>Office fit-out</Name><Summary>1</Summary></Task>
+                <Task><UID>1</UID><Name>Design</Name><Duration>PT16H0M0S</Duration>
+                  <Finish>2026-04-02T17:00:00</Finish><PercentComplete>100</PercentComplete>
+                  <Baseline><Number>0</Number><Duration>PT8H0M0S</Duration></Baseline></Task>
+                <Task><UID>2</UID><Name>Build &amp; paint</Name><Duration>PT4H0M0S</Duration>
+                  <Finish>2026-04-03T12:00:00</Finish>
+                  <PredecessorLink><PredecessorUID>1</PredecessorUID><Type>1</Type></PredecessorLink></Task>
+              </Tasks>
+            </Project>"#;
+
+        let (project, warnings) = import_mspdi("Fit-out", xml).unwrap();
+
+        assert_eq!(project.name, "Office fit-out");
+        assert_eq!(project.tasks.len(), 2);
+        assert_eq!(project.tasks[0].duration, TaskDuration::days(2));
+        assert!(project.tasks[0].completed);
+        assert_eq!(project.tasks[1].name, "Build & paint");
+        assert_eq!(
+            project.tasks[1].dependencies,
+            vec![project.tasks[0].id.clone()]
+        );
+        assert_eq!(project.anchors.len(), 1);
+        assert_eq!(project.anchors[&project.tasks[1].id], "2026-04-03T12:00:00");
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_copied_tasks_keep_their_graph_with_fresh_ids_on_collision() {
+        let task = |id: &str, deps: &[&str]| Task {
+            id: id.into(),
+            name: id.to_uppercase(),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            completed: true,
+            ..new_task(id)
+        };
+        let source = Project {
+            tasks: vec![
+                task("book", &[]),
+                task("brief", &["book"]),
+                task("travel", &["brief"]),
+            ],
+            anchors: [("travel".to_string(), "2026-05-01T09:00:00".to_string())].into(),
+            ..new_project("Offsite")
+        };
+        let mut target = new_project("Retreat");
+        target.tasks.push(task("brief", &[]));
+
+        let ops = copy_operations(
+            &source,
+            &target,
+            &["brief".to_string(), "travel".to_string()],
+        )
+        .unwrap();
+        apply_operations(&mut target, ops).unwrap();
+
+        let brief = &target.tasks[1];
+        let travel = &target.tasks[2];
+        assert_ne!(brief.id, "brief");
+        assert!(brief.dependencies.is_empty() && !brief.completed);
+        assert_eq!(travel.id, "travel");
+        assert_eq!(travel.dependencies, vec![brief.id.clone()]);
+        assert_eq!(target.anchors["travel"], "2026-05-01T09:00:00");
+        assert!(copy_operations(&source, &target, &["nope".to_string()]).is_err());
+    }
+}
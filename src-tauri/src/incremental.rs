@@ -0,0 +1,381 @@
+//! Incremental rescheduling after a single-task edit.
+//!
+//! Editing one task only moves its ancestors' late dates (backward pass) and
+//! its descendants' early dates (forward pass), so only that cone is
+//! re-propagated against the previous result. Anything that could ripple
+//! further falls back to a full recompute, diffed against the previous result.
+
+use crate::datetime::Zone;
+use crate::scheduler::{
+    anchor_conflicts, assess_feasibility, calculate_backwards_schedule, check_anchor_spacing,
+    parse_date_string, phase_summaries, request_clock, scheduled_task, PhaseSummary, ScheduleError,
+    ScheduleFeasibility, ScheduleRequest, ScheduleResult, ScheduleWarning, ScheduledTask, Task,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Rows that changed since the previous schedule.
+///
+/// Warnings, chain buffers and window placements are not refreshed; request a
+/// full schedule where those are displayed.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ScheduleDelta {
+    /// Changed rows, in schedule order.
+    pub updated: Vec<ScheduledTask>,
+    pub feasibility: ScheduleFeasibility,
+    /// Phase aggregates over the whole updated schedule.
+    #[serde(default)]
+    pub phases: Vec<PhaseSummary>,
+    /// True when the edit rippled too far and the whole schedule was recomputed.
+    pub full_recompute: bool,
+}
+
+/// Previous dates of a task: (early start, late start, late finish).
+type Dates = (NaiveDateTime, NaiveDateTime, NaiveDateTime);
+
+fn parse_row(row: &ScheduledTask, zone: Zone) -> Option<Dates> {
+    let ls = NaiveDateTime::parse_from_str(&row.start_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+    let lf = NaiveDateTime::parse_from_str(&row.end_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+    Some((zone.add_minutes(ls, -row.slack_minutes), ls, lf))
+}
+
+/// Diffs a fresh full schedule against the previous rows.
+fn full_delta(
+    request: ScheduleRequest,
+    previous: &ScheduleResult,
+) -> Result<ScheduleDelta, ScheduleError> {
+    let result = calculate_backwards_schedule(request)?;
+    let before: HashMap<&str, &ScheduledTask> = previous
+        .scheduled_tasks
+        .iter()
+        .map(|t| (t.id.as_str(), t))
+        .collect();
+
+    Ok(ScheduleDelta {
+        updated: result
+            .scheduled_tasks
+            .into_iter()
+            .filter(|t| before.get(t.id.as_str()) != Some(&t))
+            .collect(),
+        feasibility: result.feasibility,
+        phases: result.phases,
+        full_recompute: true,
+    })
+}
+
+/// Reschedules after `changed` replaces the task with the same ID in `request`,
+/// given `previous`, the result for `request` as it was.
+pub fn reschedule(
+    request: &ScheduleRequest,
+    previous: &ScheduleResult,
+    changed: Task,
+) -> Result<ScheduleDelta, ScheduleError> {
+    let old_deps = request
+        .tasks
+        .iter()
+        .find(|t| t.id == changed.id)
+        .map(|t| t.dependencies.clone())
+        .ok_or_else(|| ScheduleError::TaskNotFound(changed.id.clone()))?;
+
+    let mut updated_request = request.clone();
+    for task in &mut updated_request.tasks {
+        if task.id == changed.id {
+            *task = changed.clone();
+        }
+    }
+
+    // Exclusion serialization and leveling can move any task, allowed windows
+    // can push a task arbitrarily far, pins cut propagation short,
+    // overlapping dependencies shift the bounds they pass on, a start
+    // floor moves everything behind it, busy slots stretch tasks, and
+    // unanchored tasks are placed forwards rather than from a deadline;
+    // spread slack depends on whole chains
+    let windowed = updated_request.tasks.iter().any(|t| {
+        !t.only_during.is_empty() || t.pinned_start.is_some() || !t.start_after_percent.is_empty()
+    });
+    if windowed
+        || !request.exclusions.is_empty()
+        || request.serialize_work
+        || request.floor_starts
+        || !request.busy.is_empty()
+        || request.spread_slack
+        || previous
+            .warnings
+            .iter()
+            .any(|w| matches!(w, ScheduleWarning::Unanchored { .. }))
+        || previous.scheduled_tasks.len() != request.tasks.len()
+    {
+        return full_delta(updated_request, previous);
+    }
+
+    let (now, zone) = request_clock(request)?;
+    let mut dates: HashMap<&str, Dates> = HashMap::new();
+    for row in &previous.scheduled_tasks {
+        match parse_row(row, zone) {
+            Some(d) => dates.insert(row.id.as_str(), d),
+            None => return full_delta(updated_request, previous),
+        };
+    }
+    let previous_start = dates.values().map(|(_, ls, _)| *ls).min();
+
+    let task_map: HashMap<&str, &Task> = updated_request
+        .tasks
+        .iter()
+        .map(|t| (t.id.as_str(), t))
+        .collect();
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for task in &updated_request.tasks {
+        for dep in &task.dependencies {
+            dependents
+                .entry(dep.as_str())
+                .or_default()
+                .push(task.id.as_str());
+        }
+    }
+
+    // Ancestors through both the old and new dependencies; dropped providers lose a bound
+    let mut ancestors: HashSet<&str> = HashSet::from([changed.id.as_str()]);
+    let mut stack: Vec<&str> = old_deps
+        .iter()
+        .chain(&changed.dependencies)
+        .map(String::as_str)
+        .collect();
+    while let Some(id) = stack.pop() {
+        if id == changed.id {
+            // The edit introduced a cycle; let the full scheduler report it
+            return full_delta(updated_request, previous);
+        }
+        if ancestors.insert(id) {
+            if let Some(task) = task_map.get(id) {
+                stack.extend(task.dependencies.iter().map(String::as_str));
+            }
+        }
+    }
+
+    let mut anchor_limits: HashMap<&str, NaiveDateTime> = HashMap::new();
+    let windows = request
+        .anchor_windows
+        .iter()
+        .map(|(id, w)| (id, w.latest.as_str()));
+    for (id, date) in request
+        .anchors
+        .iter()
+        .map(|(id, d)| (id, d.as_str()))
+        .chain(windows)
+    {
+        let date = zone.normalize(parse_date_string(date).map_err(|e| {
+            ScheduleError::InvalidAnchorDate {
+                task_id: id.clone(),
+                details: e,
+            }
+        })?);
+        let entry = anchor_limits.entry(id.as_str()).or_insert(date);
+        *entry = (*entry).min(date);
+    }
+
+    // --- Backward pass over the ancestor cone, consumers before providers ---
+    let mut pending: HashMap<&str, usize> = ancestors
+        .iter()
+        .map(|id| {
+            let in_cone = dependents
+                .get(id)
+                .map_or(0, |c| c.iter().filter(|c| ancestors.contains(*c)).count());
+            (*id, in_cone)
+        })
+        .collect();
+    let mut queue: Vec<&str> = pending
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(id, _)| *id)
+        .collect();
+    queue.sort_unstable();
+
+    while let Some(id) = queue.pop() {
+        let Some(task) = task_map.get(id).copied() else {
+            return full_delta(updated_request, previous);
+        };
+        let lf = dependents
+            .get(id)
+            .into_iter()
+            .flatten()
+            .filter_map(|c| dates.get(c).map(|(_, ls, _)| *ls))
+            .chain(anchor_limits.get(id).copied())
+            .min();
+        let Some(lf) = lf else {
+            return full_delta(updated_request, previous);
+        };
+        let ls = task.start_for_finish(zone, lf);
+        let es = dates.get(id).map_or(ls, |(es, _, _)| *es);
+        dates.insert(id, (es, ls, lf));
+
+        for dep in &task.dependencies {
+            if let Some(count) = pending.get_mut(dep.as_str()) {
+                *count -= 1;
+                if *count == 0 {
+                    queue.push(dep.as_str());
+                }
+            }
+        }
+    }
+
+    // A new project start shifts every early date
+    let project_start = dates.values().map(|(_, ls, _)| *ls).min();
+    let Some(project_start) = project_start.filter(|s| Some(*s) == previous_start) else {
+        return full_delta(updated_request, previous);
+    };
+
+    // --- Forward pass over the descendant cone, providers before consumers ---
+    let mut descendants: HashSet<&str> = HashSet::new();
+    let mut stack = vec![changed.id.as_str()];
+    while let Some(id) = stack.pop() {
+        if descendants.insert(id) {
+            stack.extend(dependents.get(id).into_iter().flatten().copied());
+        }
+    }
+
+    let mut pending: HashMap<&str, usize> = descendants
+        .iter()
+        .map(|id| {
+            let in_cone = task_map[id]
+                .dependencies
+                .iter()
+                .filter(|d| descendants.contains(d.as_str()))
+                .count();
+            (*id, in_cone)
+        })
+        .collect();
+    let mut queue: Vec<&str> = pending
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(id, _)| *id)
+        .collect();
+    queue.sort_unstable();
+
+    while let Some(id) = queue.pop() {
+        let task = task_map[id];
+        let es = task
+            .dependencies
+            .iter()
+            .filter_map(|d| {
+                let (es, _, _) = dates.get(d.as_str())?;
+                Some(task_map.get(d.as_str())?.finish_for_start(zone, *es))
+            })
+            .fold(project_start, NaiveDateTime::max);
+        if let Some(entry) = dates.get_mut(id) {
+            entry.0 = es;
+        }
+
+        for consumer in dependents.get(id).into_iter().flatten() {
+            if let Some(count) = pending.get_mut(consumer) {
+                *count -= 1;
+                if *count == 0 {
+                    queue.push(consumer);
+                }
+            }
+        }
+    }
+
+    // --- Merge the cone back into the previous rows ---
+    let mut delta = ScheduleDelta::default();
+    let mut merged = Vec::with_capacity(previous.scheduled_tasks.len());
+    for row in &previous.scheduled_tasks {
+        let id = row.id.as_str();
+        if !ancestors.contains(id) && !descendants.contains(id) {
+            merged.push(row.clone());
+            continue;
+        }
+        let (es, ls, lf) = dates[id];
+        let fresh = scheduled_task(task_map[id], es, ls, lf, zone, now);
+        if fresh != *row {
+            delta.updated.push(fresh.clone());
+        }
+        merged.push(fresh);
+    }
+    delta.phases = phase_summaries(&merged);
+    delta.feasibility = assess_feasibility(&merged, now);
+    delta.feasibility.spacing_violations =
+        check_anchor_spacing(&request.anchor_spacing, &merged, zone)?;
+    delta.feasibility.is_feasible &= delta.feasibility.spacing_violations.is_empty();
+    delta.feasibility.anchor_conflicts = anchor_conflicts(&updated_request, zone)?;
+    delta.feasibility.is_feasible &= delta.feasibility.anchor_conflicts.is_empty();
+
+    Ok(delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::TaskDuration;
+
+    fn task(id: &str, days: i64, deps: &[&str]) -> Task {
+        Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(days),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    // prep (3d) drives the project start; a -> b is a shorter side branch into f.
+    fn request() -> ScheduleRequest {
+        ScheduleRequest {
+            tasks: vec![
+                task("prep", 3, &[]),
+                task("a", 1, &[]),
+                task("b", 1, &["a"]),
+                task("f", 1, &["prep", "b"]),
+            ],
+            anchors: [("f".into(), "2026-01-10T00:00:00".into())].into(),
+            reference_time: Some("2026-01-01T00:00:00".into()),
+            ..Default::default()
+        }
+    }
+
+    fn full_rows(request: &ScheduleRequest, changed: &Task) -> Vec<ScheduledTask> {
+        let mut request = request.clone();
+        for t in &mut request.tasks {
+            if t.id == changed.id {
+                *t = changed.clone();
+            }
+        }
+        calculate_backwards_schedule(request)
+            .unwrap()
+            .scheduled_tasks
+    }
+
+    #[test]
+    fn test_edit_within_cone_matches_full_recompute() {
+        let request = request();
+        let previous = calculate_backwards_schedule(request.clone()).unwrap();
+        let changed = task("b", 2, &["a"]);
+
+        let delta = reschedule(&request, &previous, changed.clone()).unwrap();
+        let full = full_rows(&request, &changed);
+
+        assert!(!delta.full_recompute);
+        let ids: Vec<&str> = delta.updated.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        for row in &delta.updated {
+            assert_eq!(Some(row), full.iter().find(|t| t.id == row.id));
+        }
+    }
+
+    #[test]
+    fn test_new_project_start_falls_back_to_full() {
+        let request = request();
+        let previous = calculate_backwards_schedule(request.clone()).unwrap();
+        let changed = task("a", 3, &[]);
+
+        let delta = reschedule(&request, &previous, changed.clone()).unwrap();
+        let full = full_rows(&request, &changed);
+
+        assert!(delta.full_recompute);
+        // prep's slack grows with the earlier project start even though it's outside the cone
+        assert!(delta.updated.iter().any(|t| t.id == "prep"));
+        for row in &delta.updated {
+            assert_eq!(Some(row), full.iter().find(|t| t.id == row.id));
+        }
+    }
+}
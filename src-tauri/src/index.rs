@@ -0,0 +1,334 @@
+//! Project listing index.
+//!
+//! Listing used to parse and schedule every project on each call. The index
+//! (`<app data>/project_index.json`) keeps what `list_projects` needs per
+//! project: deadlines, unfinished scheduled tasks and forecasts. An entry is
+//! recomputed when its project is saved or deleted, when the file changes on
+//! disk (restores, imports, edits by other tools), and once a day so
+//! forecasts keep up with the clock. Everything else is a directory scan.
+
+use crate::cache::ScheduleCache;
+use crate::forecast::{
+    completion_percent, forecast_completion, on_time_probabilities, AnchorProbability,
+    ProgressForecast,
+};
+use crate::project::{get_projects_dir, Project};
+use crate::scheduler::{ScheduledTask, TaskReminder};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+use tauri::{AppHandle, Manager};
+
+/// What listing needs to know about one project.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IndexEntry {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub created_at: String,
+    pub last_modified: String,
+    pub task_count: usize,
+    /// Anchor dates and the latest ends of anchor windows.
+    pub deadlines: Vec<String>,
+    /// Unfinished scheduled tasks; unset without deadlines or when the
+    /// project can't be scheduled.
+    pub upcoming: Option<Vec<ScheduledTask>>,
+    pub forecast: Option<ProgressForecast>,
+    pub on_time: Vec<AnchorProbability>,
+    /// Percentage of the work done, archived tasks included.
+    #[serde(default)]
+    pub completion: Option<f32>,
+    #[serde(default)]
+    pub focus_task_id: Option<String>,
+    /// Minutes before a task is due to remind about it; unset when the
+    /// project's reminders are off.
+    #[serde(default)]
+    pub reminder_lead_minutes: Option<i64>,
+    /// Tasks' own reminders, by task ID.
+    #[serde(default)]
+    pub task_reminders: HashMap<String, Vec<TaskReminder>>,
+    /// Day (YYYY-MM-DD) the entry was computed.
+    pub indexed_on: String,
+    /// The project file's modification time (ms since the epoch) when indexed.
+    pub file_modified: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct IndexFile {
+    entries: HashMap<String, IndexEntry>,
+}
+
+/// The index, loaded from disk on first use.
+#[derive(Default)]
+pub struct ProjectIndex {
+    index: Mutex<Option<IndexFile>>,
+}
+
+fn index_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join("project_index.json"))
+}
+
+fn modified_ms(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+fn compute(
+    app: &AppHandle,
+    project: &Project,
+    file_modified: u64,
+    now: NaiveDateTime,
+) -> IndexEntry {
+    let deadlines: Vec<String> = project
+        .anchors
+        .values()
+        .chain(project.anchor_windows.values().map(|w| &w.latest))
+        .cloned()
+        .collect();
+    let upcoming = if deadlines.is_empty() {
+        None
+    } else {
+        app.state::<ScheduleCache>()
+            .schedule(project, now)
+            .ok()
+            .map(|result| {
+                result
+                    .scheduled_tasks
+                    .into_iter()
+                    .filter(|t| !t.completed)
+                    .collect()
+            })
+    };
+    // Archived completions still count towards the measured pace
+    let history = crate::archive::with_history(app, project).unwrap_or(project.clone());
+
+    IndexEntry {
+        id: project.id.clone(),
+        name: project.name.clone(),
+        color: project.color.clone(),
+        icon: project.icon.clone(),
+        description: project.description.clone(),
+        created_at: project.created_at.clone(),
+        last_modified: project.last_modified.clone(),
+        task_count: project.tasks.len(),
+        deadlines,
+        upcoming,
+        forecast: forecast_completion(&history, now),
+        on_time: on_time_probabilities(project, now),
+        completion: completion_percent(&history.tasks),
+        focus_task_id: project.focus_task_id.clone(),
+        reminder_lead_minutes: project
+            .notification_settings
+            .reminders
+            .then_some(project.notification_settings.deadline_lead_minutes),
+        task_reminders: project
+            .tasks
+            .iter()
+            .filter(|t| !t.reminders.is_empty())
+            .map(|t| (t.id.clone(), t.reminders.clone()))
+            .collect(),
+        indexed_on: now.format("%Y-%m-%d").to_string(),
+        file_modified,
+    }
+}
+
+/// Brings `index` in line with the project files in `dir`, recomputing
+/// entries that are missing, changed on disk or from before `today`.
+/// Returns whether anything changed.
+fn refresh(
+    index: &mut IndexFile,
+    dir: &Path,
+    today: &str,
+    mut compute: impl FnMut(&Project, u64) -> IndexEntry,
+) -> Result<bool, String> {
+    let mut changed = false;
+    let mut seen = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())?.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()).map(String::from) else {
+            continue;
+        };
+        let modified = modified_ms(&path);
+        let fresh = index
+            .entries
+            .get(&id)
+            .is_some_and(|e| e.file_modified == modified && e.indexed_on == today);
+        if !fresh {
+            let project = crate::encryption::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| crate::migrate::parse_project(&json).map_err(|e| e.to_string()));
+            match project {
+                Ok(project) => {
+                    index
+                        .entries
+                        .insert(id.clone(), compute(&project, modified));
+                }
+                // Left for repair_project rather than failing the whole listing
+                Err(e) => {
+                    eprintln!("Skipping unreadable project {}: {}", path.display(), e);
+                    index.entries.remove(&id);
+                    continue;
+                }
+            }
+            changed = true;
+        }
+        seen.push(id);
+    }
+    let before = index.entries.len();
+    index.entries.retain(|id, _| seen.contains(id));
+    Ok(changed || index.entries.len() != before)
+}
+
+impl ProjectIndex {
+    fn with<R>(
+        &self,
+        app: &AppHandle,
+        f: impl FnOnce(&mut IndexFile) -> Result<(R, bool), String>,
+    ) -> Result<R, String> {
+        let mut guard = self.index.lock().unwrap();
+        let index = guard.get_or_insert_with(|| {
+            index_path(app)
+                .and_then(|path| crate::encryption::read_to_string(path).map_err(|e| e.to_string()))
+                .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+                .unwrap_or_default()
+        });
+        let (result, changed) = f(index)?;
+        if changed {
+            let json = serde_json::to_string(&*index).map_err(|e| e.to_string())?;
+            let path = index_path(app)?;
+            let tmp = path.with_extension("json.tmp");
+            crate::encryption::write(&tmp, json).map_err(|e| e.to_string())?;
+            fs::rename(tmp, path).map_err(|e| e.to_string())?;
+        }
+        Ok(result)
+    }
+}
+
+/// Every readable project's entry, refreshed where stale.
+pub(crate) fn entries(app: &AppHandle, now: NaiveDateTime) -> Result<Vec<IndexEntry>, String> {
+    let dir = get_projects_dir(app)?;
+    let today = now.format("%Y-%m-%d").to_string();
+    app.state::<ProjectIndex>().with(app, |index| {
+        let changed = refresh(index, &dir, &today, |project, modified| {
+            compute(app, project, modified, now)
+        })?;
+        Ok((index.entries.values().cloned().collect(), changed))
+    })
+}
+
+/// Drops a project's entry so the next listing recomputes it. Called on save
+/// and delete, since a file's modification time may be too coarse to show
+/// a quick succession of saves.
+pub(crate) fn invalidate(app: &AppHandle, project_id: &str) -> Result<(), String> {
+    app.state::<ProjectIndex>().with(app, |index| {
+        let removed = index.entries.remove(project_id).is_some();
+        Ok(((), removed))
+    })
+}
+
+/// Recomputes every entry. Returns how many projects are indexed.
+#[tauri::command]
+pub fn rebuild_project_index(app: AppHandle) -> Result<usize, String> {
+    app.state::<ProjectIndex>().with(&app, |index| {
+        index.entries.clear();
+        Ok(((), true))
+    })?;
+    let now = chrono::Local::now().naive_local();
+    Ok(entries(&app, now)?.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_only_stale_entries_are_recomputed() {
+        let dir = std::env::temp_dir().join(format!("anchor-index-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let write = |id: &str, name: &str| {
+            let project = Project {
+                id: id.into(),
+                name: name.into(),
+                ..Default::default()
+            };
+            fs::write(
+                dir.join(format!("{}.json", id)),
+                serde_json::to_string(&project).unwrap(),
+            )
+            .unwrap();
+        };
+        write("a", "Alpha");
+        write("b", "Beta");
+        fs::write(dir.join("broken.json"), "{").unwrap();
+
+        let mut computed = Vec::new();
+        let mut index = IndexFile::default();
+        let mut run = |index: &mut IndexFile, today: &str| {
+            refresh(index, &dir, today, |project, modified| {
+                computed.push(project.id.clone());
+                IndexEntry {
+                    id: project.id.clone(),
+                    name: project.name.clone(),
+                    color: None,
+                    icon: None,
+                    description: None,
+                    created_at: String::new(),
+                    last_modified: String::new(),
+                    task_count: 0,
+                    deadlines: Vec::new(),
+                    upcoming: None,
+                    forecast: None,
+                    on_time: Vec::new(),
+                    completion: None,
+                    focus_task_id: None,
+                    reminder_lead_minutes: None,
+                    task_reminders: HashMap::new(),
+                    indexed_on: today.into(),
+                    file_modified: modified,
+                }
+            })
+            .unwrap()
+        };
+
+        assert!(run(&mut index, "2026-03-01"));
+        assert_eq!(index.entries.len(), 2);
+        assert!(!run(&mut index, "2026-03-01"));
+
+        // Changed on disk, removed, and then a new day
+        write("a", "Alpha 2");
+        let later = SystemTime::now() + Duration::from_secs(5);
+        fs::File::options()
+            .write(true)
+            .open(dir.join("a.json"))
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        fs::remove_file(dir.join("b.json")).unwrap();
+        assert!(run(&mut index, "2026-03-01"));
+        assert_eq!(index.entries["a"].name, "Alpha 2");
+        assert!(!index.entries.contains_key("b"));
+        assert!(run(&mut index, "2026-03-02"));
+
+        // Both at first, then "a" after its change and again the next day
+        computed.sort();
+        assert_eq!(computed, vec!["a", "a", "a", "b"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,299 @@
+//! Jira import.
+//!
+//! Reads the issues a JQL query matches through the Jira REST API and turns
+//! them into a project: "blocks" links become dependencies, original
+//! estimates durations and due dates anchors. Each fix version with a release
+//! date gets a release milestone, anchored on that date and depending on the
+//! version's issues, so the release can be planned backwards.
+
+use crate::import::{duration_from_minutes, new_project, new_task, UnresolvedDependency};
+use crate::import::{ImportFormat, ImportPreview};
+use crate::project::{save_project, Project};
+use crate::scheduler::TaskDuration;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use tauri::AppHandle;
+
+/// Issues fetched per request.
+const PAGE_SIZE: &str = "100";
+const FIELDS: &str = "summary,description,issuelinks,fixVersions,timeoriginalestimate,duedate,status,assignee,labels";
+
+/// A Jira site and credentials, used for one import and not stored.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JiraConnection {
+    /// e.g. `https://example.atlassian.net`.
+    pub base_url: String,
+    /// Account email for Jira Cloud API tokens; unset to send `token` as a
+    /// personal access token (Jira Server and Data Center).
+    #[serde(default)]
+    pub email: Option<String>,
+    pub token: String,
+}
+
+impl JiraConnection {
+    fn get(&self, path: &str) -> ureq::Request {
+        let url = format!("{}{}", self.base_url.trim_end_matches('/'), path);
+        let authorization = match &self.email {
+            Some(email) => format!(
+                "Basic {}",
+                STANDARD.encode(format!("{}:{}", email, self.token))
+            ),
+            None => format!("Bearer {}", self.token),
+        };
+        ureq::get(&url)
+            .set("Authorization", &authorization)
+            .set("Accept", "application/json")
+    }
+}
+
+/// Plain text of a description, which API v3 returns as an Atlassian
+/// Document Format tree.
+fn plain_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Object(node) => {
+            let text = node.get("text").and_then(Value::as_str).unwrap_or_default();
+            let children: Vec<String> = node
+                .get("content")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(plain_text)
+                .collect();
+            let separator = if node.get("type").and_then(Value::as_str) == Some("doc") {
+                "\n"
+            } else {
+                ""
+            };
+            format!("{}{}", text, children.join(separator))
+        }
+        _ => String::new(),
+    }
+}
+
+/// Builds a project named `name` from API issues. Links to issues outside
+/// the set are reported rather than kept.
+fn from_issues(name: &str, issues: &[Value]) -> (Project, Vec<String>, Vec<UnresolvedDependency>) {
+    let mut project = new_project(name);
+    let mut warnings = Vec::new();
+    let mut unresolved = Vec::new();
+    let mut ids: HashMap<String, String> = HashMap::new();
+    // Release name to (date, task IDs)
+    let mut releases: BTreeMap<String, (String, Vec<String>)> = BTreeMap::new();
+
+    for issue in issues {
+        let key = issue["key"].as_str().unwrap_or_default();
+        let fields = &issue["fields"];
+        let summary = fields["summary"].as_str().unwrap_or_default();
+        let mut task = new_task(format!("{} {}", key, summary).trim());
+        let notes = plain_text(&fields["description"]);
+        task.notes = Some(notes).filter(|n| !n.trim().is_empty());
+        task.duration = match fields["timeoriginalestimate"].as_i64() {
+            Some(seconds) if seconds > 0 => duration_from_minutes(seconds / 60),
+            _ => TaskDuration::days(1),
+        };
+        task.completed = fields["status"]["statusCategory"]["key"] == "done";
+        task.assignee = fields["assignee"]["displayName"]
+            .as_str()
+            .map(str::to_string);
+        task.tags = fields["labels"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|l| l.as_str().map(str::to_string))
+            .collect();
+        if let Some(due) = fields["duedate"].as_str() {
+            project.anchors.insert(task.id.clone(), due.to_string());
+        }
+        for version in fields["fixVersions"].as_array().into_iter().flatten() {
+            let (Some(version), Some(date)) =
+                (version["name"].as_str(), version["releaseDate"].as_str())
+            else {
+                continue;
+            };
+            releases
+                .entry(version.to_string())
+                .or_insert_with(|| (date.to_string(), Vec::new()))
+                .1
+                .push(task.id.clone());
+        }
+        ids.insert(key.to_string(), task.id.clone());
+        project.tasks.push(task);
+    }
+
+    // "A blocks B" appears on both issues; B depends on A
+    let mut links = Vec::new();
+    for issue in issues {
+        let key = issue["key"].as_str().unwrap_or_default();
+        for link in issue["fields"]["issuelinks"]
+            .as_array()
+            .into_iter()
+            .flatten()
+        {
+            if link["type"]["name"] != "Blocks" {
+                continue;
+            }
+            if let Some(blocker) = link["inwardIssue"]["key"].as_str() {
+                links.push((key.to_string(), blocker.to_string()));
+            }
+            if let Some(blocked) = link["outwardIssue"]["key"].as_str() {
+                links.push((blocked.to_string(), key.to_string()));
+            }
+        }
+    }
+    for (blocked, blocker) in links {
+        let Some(task) = ids
+            .get(&blocked)
+            .and_then(|id| project.tasks.iter_mut().find(|t| &t.id == id))
+        else {
+            continue;
+        };
+        match ids.get(&blocker) {
+            Some(id) if !task.dependencies.contains(id) => task.dependencies.push(id.clone()),
+            Some(_) => {}
+            None => {
+                warnings.push(format!(
+                    "'{}' is blocked by {}, which the query didn't include",
+                    task.name, blocker
+                ));
+                unresolved.push(UnresolvedDependency {
+                    task_name: task.name.clone(),
+                    reference: blocker,
+                });
+            }
+        }
+    }
+
+    for (version, (date, dependencies)) in releases {
+        let mut release = new_task(&format!("Release {}", version));
+        release.duration = TaskDuration::minutes(0);
+        release.is_milestone = true;
+        release.dependencies = dependencies;
+        project.anchors.insert(release.id.clone(), date);
+        project.tasks.push(release);
+    }
+    (project, warnings, unresolved)
+}
+
+/// Every issue `jql` matches.
+fn search(connection: &JiraConnection, jql: &str) -> Result<Vec<Value>, String> {
+    let mut issues = Vec::new();
+    let mut page: Option<String> = None;
+    loop {
+        let mut request = connection
+            .get("/rest/api/3/search/jql")
+            .query("jql", jql)
+            .query("fields", FIELDS)
+            .query("maxResults", PAGE_SIZE);
+        if let Some(page) = &page {
+            request = request.query("nextPageToken", page);
+        }
+        let response: Value = request
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(401 | 403, _) => "Jira rejected the credentials".to_string(),
+                ureq::Error::Status(400, response) => {
+                    format!(
+                        "Jira rejected the query: {}",
+                        response.into_string().unwrap_or_default()
+                    )
+                }
+                e => e.to_string(),
+            })?
+            .into_json()
+            .map_err(|e| e.to_string())?;
+        issues.extend(response["issues"].as_array().cloned().unwrap_or_default());
+        page = response["nextPageToken"].as_str().map(str::to_string);
+        if page.is_none() || response["isLast"].as_bool() == Some(true) {
+            return Ok(issues);
+        }
+    }
+}
+
+/// Imports the issues `jql` matches as a new project called `name`. With
+/// `dry_run` nothing is saved.
+#[tauri::command]
+pub async fn import_jira(
+    app: AppHandle,
+    connection: JiraConnection,
+    jql: String,
+    name: String,
+    dry_run: bool,
+) -> Result<ImportPreview, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let issues = search(&connection, &jql)?;
+        if issues.is_empty() {
+            return Err("The query matched no issues".to_string());
+        }
+        let (project, warnings, unresolved) = from_issues(&name, &issues);
+        let preview = crate::import::preview_of(
+            format!("jira:{}", jql),
+            ImportFormat::Jira,
+            project,
+            warnings,
+            unresolved,
+        );
+        if !dry_run {
+            save_project(app, preview.project.clone())?;
+        }
+        Ok(preview)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_issues_links_and_releases() {
+        let blocks = |direction: &str, key: &str| json!({"type": {"name": "Blocks"}, direction: {"key": key}});
+        let issues = [
+            json!({"key": "APP-1", "fields": {
+                "summary": "Design API",
+                "description": {"type": "doc", "content": [
+                    {"type": "paragraph", "content": [{"type": "text", "text": "REST first"}]}
+                ]},
+                "timeoriginalestimate": 14400,
+                "status": {"statusCategory": {"key": "done"}},
+                "issuelinks": [blocks("outwardIssue", "APP-2")],
+                "fixVersions": [{"name": "1.0", "releaseDate": "2026-04-01"}]
+            }}),
+            json!({"key": "APP-2", "fields": {
+                "summary": "Build API",
+                "labels": ["backend"],
+                "issuelinks": [
+                    blocks("inwardIssue", "APP-1"),
+                    blocks("inwardIssue", "OPS-9"),
+                    {"type": {"name": "Relates"}, "outwardIssue": {"key": "APP-1"}}
+                ],
+                "fixVersions": [{"name": "1.0", "releaseDate": "2026-04-01"}]
+            }}),
+        ];
+
+        let (project, warnings, unresolved) = from_issues("Release", &issues);
+        let [design, build, release] = &project.tasks[..] else {
+            panic!("expected two issues and a release");
+        };
+        assert_eq!(design.name, "APP-1 Design API");
+        assert_eq!(design.notes.as_deref(), Some("REST first"));
+        assert_eq!(design.duration, TaskDuration::hours(4));
+        assert!(design.completed);
+        assert_eq!(build.tags, vec!["backend"]);
+        assert_eq!(build.dependencies, vec![design.id.clone()]);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(unresolved[0].reference, "OPS-9");
+
+        assert_eq!(release.name, "Release 1.0");
+        assert!(release.is_milestone);
+        assert_eq!(
+            release.dependencies,
+            vec![design.id.clone(), build.id.clone()]
+        );
+        assert_eq!(project.anchors[&release.id], "2026-04-01");
+    }
+}
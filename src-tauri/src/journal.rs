@@ -0,0 +1,168 @@
+//! Autosave journal.
+//!
+//! The editor journals every change as it happens (`projects/journal/<id>.json`)
+//! and saves on a debounce. A save that matches the journal clears it, so an
+//! entry still present at launch holds edits a crash kept from being saved.
+//! Those are announced with an `unsaved-changes` event and can be recovered
+//! or discarded.
+
+use crate::project::{get_projects_dir, load_project, save_project, Project};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+/// A project's latest unsaved state.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct JournalEntry {
+    journaled_at: String,
+    project: Project,
+}
+
+/// Summary of a journal entry found at launch.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UnsavedChanges {
+    pub project_id: String,
+    pub project_name: String,
+    pub journaled_at: String,
+}
+
+fn journal_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = get_projects_dir(app)?.join("journal");
+    if !dir.exists() {
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    }
+    Ok(dir)
+}
+
+fn write_entry(dir: &Path, project: Project) -> Result<(), String> {
+    let entry = JournalEntry {
+        journaled_at: chrono::Local::now().to_rfc3339(),
+        project,
+    };
+    let json = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+    // Write then rename so a crash mid-write can't leave a torn entry
+    let tmp = dir.join(format!("{}.json.tmp", entry.project.id));
+    crate::encryption::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, dir.join(format!("{}.json", entry.project.id))).map_err(|e| e.to_string())
+}
+
+fn read_entry(dir: &Path, project_id: &str) -> Option<JournalEntry> {
+    let json = crate::encryption::read_to_string(dir.join(format!("{}.json", project_id))).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+fn remove_entry(dir: &Path, project_id: &str) -> Result<(), String> {
+    let path = dir.join(format!("{}.json", project_id));
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Clears the entry for `project` if it holds nothing newer than `project`.
+fn settle_entry(dir: &Path, project: &Project) -> Result<(), String> {
+    let Some(entry) = read_entry(dir, &project.id) else {
+        return Ok(());
+    };
+    if serde_json::to_value(&entry.project).ok() == serde_json::to_value(project).ok() {
+        remove_entry(dir, &project.id)?;
+    }
+    Ok(())
+}
+
+fn pending_entries(dir: &Path) -> Vec<UnsavedChanges> {
+    let Ok(files) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut pending: Vec<UnsavedChanges> = files
+        .flatten()
+        .filter_map(|file| {
+            let name = file.file_name().into_string().ok()?;
+            let entry = read_entry(dir, name.strip_suffix(".json")?)?;
+            Some(UnsavedChanges {
+                project_id: entry.project.id,
+                project_name: entry.project.name,
+                journaled_at: entry.journaled_at,
+            })
+        })
+        .collect();
+    pending.sort_by(|a, b| a.project_id.cmp(&b.project_id));
+    pending
+}
+
+/// Called from `save_project` with the project as the editor sent it.
+pub(crate) fn settle(app: &AppHandle, project: &Project) -> Result<(), String> {
+    settle_entry(&journal_dir(app)?, project)
+}
+
+/// Called on project deletion.
+pub(crate) fn discard(app: &AppHandle, project_id: &str) -> Result<(), String> {
+    remove_entry(&journal_dir(app)?, project_id)
+}
+
+/// Emits `unsaved-changes` at launch if any project has journaled edits.
+pub(crate) fn announce(app: &AppHandle) {
+    if let Ok(pending) = list_unsaved_changes(app.clone()) {
+        if !pending.is_empty() {
+            let _ = app.emit("unsaved-changes", pending);
+        }
+    }
+}
+
+/// Records the editor's current, not yet saved, state of a project.
+#[tauri::command]
+pub fn journal_project(app: AppHandle, project: Project) -> Result<(), String> {
+    write_entry(&journal_dir(&app)?, project)
+}
+
+/// Projects with journaled edits that were never saved.
+#[tauri::command]
+pub fn list_unsaved_changes(app: AppHandle) -> Result<Vec<UnsavedChanges>, String> {
+    Ok(pending_entries(&journal_dir(&app)?))
+}
+
+/// Saves a project's journaled edits and returns the recovered project.
+#[tauri::command]
+pub fn recover_unsaved_changes(app: AppHandle, project_id: String) -> Result<Project, String> {
+    let entry = read_entry(&journal_dir(&app)?, &project_id)
+        .ok_or_else(|| format!("No unsaved changes for project {}", project_id))?;
+    save_project(app.clone(), entry.project)?;
+    discard(&app, &project_id)?;
+    load_project(app, project_id)
+}
+
+/// Drops a project's journaled edits, keeping the saved version.
+#[tauri::command]
+pub fn discard_unsaved_changes(app: AppHandle, project_id: String) -> Result<(), String> {
+    discard(&app, &project_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_journal_survives_until_a_matching_save() {
+        let dir = std::env::temp_dir().join(format!("anchor-journal-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let saved = Project {
+            id: "p".into(),
+            name: "Launch".into(),
+            ..Default::default()
+        };
+        let mut edited = saved.clone();
+        edited.name = "Launch v2".into();
+        write_entry(&dir, edited.clone()).unwrap();
+
+        // A debounced save of an older state leaves the newer edits journaled
+        settle_entry(&dir, &saved).unwrap();
+        let pending = pending_entries(&dir);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].project_name, "Launch v2");
+
+        settle_entry(&dir, &edited).unwrap();
+        assert!(pending_entries(&dir).is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -3,18 +3,94 @@
 //! A Tauri application that helps you plan projects by working backwards
 //! from deadlines to determine when you need to start.
 
+mod activity;
+mod agenda;
+mod api;
+mod archive;
+mod attachments;
+mod backup;
+mod bundle;
+mod cache;
+mod caldav;
+mod calendar;
+mod capabilities;
+mod capture;
+mod chains;
 mod config;
+mod dates;
+mod datetime;
+mod deeplink;
+mod encryption;
+mod events;
+mod forecast;
+mod gantt;
+mod google;
+mod health;
+mod history;
+mod ics;
+mod import;
+mod incremental;
+mod index;
+mod jira;
+mod journal;
+mod migrate;
+mod monitor;
+mod notifications;
+mod operations;
+mod portfolio;
 mod project;
+mod query;
+mod reminders;
+mod repair;
+mod report;
+mod rollup;
+mod scenario;
 mod scheduler;
+mod search;
+mod stats;
+mod sync;
+mod templates;
+mod timer;
+mod today;
+mod todoist;
+mod trash;
+mod tray;
+mod watcher;
+mod workload;
 
-use scheduler::calculate_backwards_schedule;
-pub use scheduler::{ScheduleRequest, ScheduledTask, Task};
+use incremental::ScheduleDelta;
+use scheduler::{calculate_backwards_schedule, parse_start_date, AnchorSuggestion};
+pub use scheduler::{ScheduleRequest, ScheduleResult, ScheduledTask, Task};
 
 #[tauri::command]
-fn schedule(request: ScheduleRequest) -> Result<Vec<ScheduledTask>, String> {
+fn schedule(request: ScheduleRequest) -> Result<ScheduleResult, String> {
     calculate_backwards_schedule(request).map_err(|e| e.to_string())
 }
 
+/// Earliest achievable finish for each anchor if work starts at `start`
+/// (ISO 8601 DateTime or YYYY-MM-DD), defaulting to now.
+#[tauri::command]
+fn suggest_anchor_dates(
+    request: ScheduleRequest,
+    start: Option<String>,
+) -> Result<Vec<AnchorSuggestion>, String> {
+    let start = match start {
+        Some(s) => parse_start_date(&s)?,
+        None => chrono::Local::now().naive_local(),
+    };
+    scheduler::suggest_anchor_dates(&request, start).map_err(|e| e.to_string())
+}
+
+/// Reschedules a single-task edit against the previous result, returning only changed rows.
+#[tauri::command]
+fn reschedule_task(
+    request: ScheduleRequest,
+    previous: ScheduleResult,
+    changed: Task,
+) -> Result<ScheduleDelta, String> {
+    incremental::reschedule(&request, &previous, changed).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn test_notification(app: tauri::AppHandle) -> Result<String, String> {
     use tauri_plugin_notification::NotificationExt;
@@ -36,9 +112,33 @@ fn show_main_window(window: tauri::Window) {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // Must come first; with deep links, a second launch forwards its URL here
+        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+            use tauri::Manager;
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_desktop_underlay::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, _shortcut, event| {
+                    use tauri_plugin_global_shortcut::ShortcutState;
+                    if event.state() == ShortcutState::Pressed {
+                        capture::show(app);
+                    }
+                })
+                .build(),
+        )
+        .manage(cache::ScheduleCache::default())
+        .manage(history::History::default())
+        .manage(index::ProjectIndex::default())
+        .manage(watcher::ProjectWatcher::default())
+        .manage(sync::SyncBase::default())
         .setup(|app| {
             // let app_handle = app.handle().clone();
 
@@ -78,54 +178,176 @@ pub fn run() {
                 }
             }
 
-            // Tray Setup
-            use tauri::menu::{Menu, MenuItem};
-            use tauri::tray::TrayIconBuilder;
+            // Local API for external schedulers and automation tools, if configured
+            if let Ok(config::AppConfig { api: Some(api), .. }) =
+                config::load_config(app.handle().clone())
+            {
+                let handle = app.handle().clone();
+                let projects = Box::new(move || project::load_all_projects(&handle));
+                if let Err(e) = api::start(api, projects) {
+                    eprintln!("{}", e);
+                }
+            }
 
-            let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let show_i = MenuItem::with_id(app, "show", "Show Anchor", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show_i, &quit_i])?;
+            // Offer to recover edits a crash kept from being saved
+            encryption::init(app.handle());
+            journal::announce(app.handle());
+            trash::purge_expired(app.handle());
+            reminders::start(app.handle());
+            monitor::start(app.handle());
+            if let Err(e) = watcher::start(app.handle()) {
+                eprintln!("Failed to watch projects: {}", e);
+            }
 
-            let _tray = TrayIconBuilder::new()
-                .menu(&menu)
-                .icon(app.default_window_icon().unwrap().clone())
-                .on_menu_event(|app, event| match event.id.as_ref() {
-                    "quit" => {
-                        app.exit(0);
-                    }
-                    "show" => {
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
-                    }
-                    _ => {}
-                })
-                .build(app)?;
+            // anchor:// links, both while running and the one we were launched with
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+                #[cfg(any(windows, target_os = "linux"))]
+                if let Err(e) = app.deep_link().register_all() {
+                    eprintln!("Failed to register anchor:// links: {}", e);
+                }
+                let handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    let urls: Vec<String> = event.urls().iter().map(|u| u.to_string()).collect();
+                    deeplink::handle(&handle, &urls);
+                });
+                if let Ok(Some(urls)) = app.deep_link().get_current() {
+                    let urls: Vec<String> = urls.iter().map(|u| u.to_string()).collect();
+                    deeplink::handle(app.handle(), &urls);
+                }
+            }
+
+            // Quick capture from anywhere
+            capture::register_shortcut(app.handle());
+
+            // Tray with the next task and quick actions
+            tray::init(app.handle())?;
 
             Ok(())
         })
         .on_window_event(|window, event| {
-            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                if window.label() == "main" {
+            if window.label() != "main" {
+                return;
+            }
+            match event {
+                tauri::WindowEvent::CloseRequested { api, .. } => {
                     let _ = window.hide();
                     api.prevent_close();
                 }
+                tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+                    use tauri::Manager;
+                    import::handle_drop(window.app_handle(), paths);
+                }
+                _ => {}
             }
         })
         .invoke_handler(tauri::generate_handler![
             schedule,
+            reschedule_task,
+            suggest_anchor_dates,
+            capabilities::get_capabilities,
             test_notification,
             show_main_window,
             project::create_project,
             project::load_project,
             project::save_project,
             project::list_projects,
+            project::get_dashboard,
+            project::recalculate,
             project::delete_project,
+            project::rename_project,
+            project::set_focus_task,
+            project::set_project_pinned,
+            project::reorder_projects,
             config::load_config,
             config::save_config,
             project::get_next_deadline,
-            project::get_widget_info
+            project::get_widget_info,
+            scenario::evaluate_scenario,
+            scenario::simulate_delay,
+            notifications::notify_project,
+            ics::list_calendar_feeds,
+            ics::save_calendar_feed,
+            ics::delete_calendar_feed,
+            ics::export_calendar_feed,
+            ics::export_ics,
+            caldav::list_caldav_links,
+            caldav::save_caldav_link,
+            caldav::delete_caldav_link,
+            caldav::sync_caldav,
+            calendar::group_calendar,
+            capture::quick_add_task,
+            capture::parse_task_entry,
+            dates::normalize_date,
+            timer::start_timer,
+            timer::stop_timer,
+            timer::active_timer,
+            gantt::export_gantt_svg,
+            report::generate_report_pdf,
+            trash::list_trashed,
+            trash::restore_project,
+            trash::purge_trash,
+            templates::list_templates,
+            templates::create_template,
+            templates::delete_template,
+            templates::instantiate_template,
+            query::query_tasks,
+            search::search,
+            index::rebuild_project_index,
+            sync::get_sync_conflicts,
+            sync::clear_sync_conflicts,
+            encryption::get_encryption_status,
+            encryption::enable_encryption,
+            encryption::unlock_projects,
+            encryption::lock_projects,
+            encryption::encrypt_project_files,
+            encryption::disable_encryption,
+            google::google_begin_auth,
+            google::google_disconnect,
+            google::list_google_calendars,
+            google::link_google_calendar,
+            google::unlink_google_calendar,
+            google::sync_google_calendar,
+            import::import_file,
+            import::import_csv_tasks,
+            import::import_tasks,
+            attachments::add_attachment,
+            attachments::remove_attachment,
+            attachments::open_attachment,
+            activity::add_task_comment,
+            activity::get_task_activity,
+            stats::get_project_stats,
+            bundle::export_project_bundle,
+            bundle::import_project_bundle,
+            todoist::list_todoist_projects,
+            todoist::import_todoist,
+            import::read_csv_columns,
+            import::import_mapped_csv,
+            jira::import_jira,
+            deeplink::take_deep_link,
+            portfolio::get_portfolio,
+            portfolio::get_next_actions,
+            rollup::get_schedule_rollup,
+            workload::get_resource_load,
+            health::check_schedule_health,
+            agenda::generate_agenda,
+            today::get_today,
+            chains::get_chain_slack,
+            operations::apply_task_operations,
+            history::undo,
+            history::redo,
+            journal::journal_project,
+            journal::list_unsaved_changes,
+            journal::recover_unsaved_changes,
+            journal::discard_unsaved_changes,
+            reminders::list_reminders,
+            reminders::snooze_reminder,
+            reminders::dismiss_reminder,
+            repair::repair_project,
+            backup::export_backup,
+            backup::import_backup,
+            archive::load_archive,
+            archive::archive_completed_tasks
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
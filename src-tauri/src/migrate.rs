@@ -0,0 +1,119 @@
+//! Project file schema versions.
+//!
+//! Every saved project records the `schema_version` it was written with.
+//! Older files are upgraded step by step on load, as JSON, before they're
+//! deserialized, so a model change never silently drops fields that only an
+//! old file has. Files from a newer version of Anchor are refused rather
+//! than read lossily and overwritten on the next save.
+
+use crate::project::Project;
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+
+/// The version `save_project` writes.
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// `MIGRATIONS[n]` upgrades a version `n` file to version `n + 1`.
+const MIGRATIONS: [Migration; CURRENT_SCHEMA_VERSION as usize] = [v0_task_durations];
+
+#[derive(Debug, Error)]
+pub enum SchemaError {
+    #[error(
+        "This project was saved by a newer version of Anchor (schema version {found}, \
+         this version reads up to {supported}); update Anchor to open it"
+    )]
+    TooNew { found: u64, supported: u32 },
+    #[error("{0}")]
+    Malformed(#[from] serde_json::Error),
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("not a project file")]
+    NotAnObject,
+}
+
+/// Version 0 files stored durations as `duration_days` or
+/// `duration_minutes` rather than `duration: { value, unit }`.
+fn v0_task_durations(project: &mut Map<String, Value>) {
+    let Some(tasks) = project.get_mut("tasks").and_then(Value::as_array_mut) else {
+        return;
+    };
+    for task in tasks.iter_mut().filter_map(Value::as_object_mut) {
+        let days = task.remove("duration_days");
+        let minutes = task.remove("duration_minutes");
+        if task.contains_key("duration") {
+            continue;
+        }
+        let duration = match (minutes.as_ref().and_then(Value::as_i64), days) {
+            (Some(minutes), _) => json!({ "value": minutes, "unit": "minutes" }),
+            (None, days) => json!({
+                "value": days.as_ref().and_then(Value::as_i64).unwrap_or(0),
+                "unit": "days",
+            }),
+        };
+        task.insert("duration".to_string(), duration);
+    }
+}
+
+/// Upgrades a project's JSON to the current schema and deserializes it.
+pub(crate) fn upgrade(mut value: Value) -> Result<Project, SchemaError> {
+    let project = value.as_object_mut().ok_or(SchemaError::NotAnObject)?;
+    let found = project
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    if found > CURRENT_SCHEMA_VERSION as u64 {
+        return Err(SchemaError::TooNew {
+            found,
+            supported: CURRENT_SCHEMA_VERSION,
+        });
+    }
+    for migrate in &MIGRATIONS[found as usize..] {
+        migrate(project);
+    }
+    project.insert("schema_version".to_string(), CURRENT_SCHEMA_VERSION.into());
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Parses a project file of any supported version.
+pub(crate) fn parse_project(json: &str) -> Result<Project, SchemaError> {
+    upgrade(serde_json::from_str(json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::TaskDuration;
+
+    #[test]
+    fn test_old_files_upgrade_and_newer_files_are_refused() {
+        let project = parse_project(
+            r#"{
+                "id": "p", "name": "Old", "created_at": "", "last_modified": "",
+                "anchors": {},
+                "tasks": [
+                    {"id": "a", "name": "A", "dependencies": [], "duration_days": 3},
+                    {"id": "b", "name": "B", "dependencies": [], "duration_minutes": 90}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(project.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(project.tasks[0].duration, TaskDuration::days(3));
+        assert_eq!(project.tasks[1].duration, TaskDuration::minutes(90));
+
+        // Round-trips unchanged at the current version
+        let saved = serde_json::to_string(&project).unwrap();
+        let reloaded = parse_project(&saved).unwrap();
+        assert_eq!(reloaded.tasks[1].duration, TaskDuration::minutes(90));
+
+        let newer = saved.replace(
+            &format!("\"schema_version\":{}", CURRENT_SCHEMA_VERSION),
+            "\"schema_version\":99",
+        );
+        let err = parse_project(&newer).unwrap_err();
+        assert!(matches!(err, SchemaError::TooNew { found: 99, .. }));
+        assert!(err.to_string().contains("newer version of Anchor"));
+    }
+}
@@ -0,0 +1,370 @@
+//! Deadline monitor.
+//!
+//! Project statuses are worked out when something asks for them, so a
+//! project could turn urgent or overdue without anyone finding out. A
+//! background check re-evaluates every project every few minutes and, when
+//! one becomes urgent or overdue, emits `project-status` with a
+//! [`StatusChange`] and notifies through the project's routing.
+//!
+//! Tasks past their late finish (the date they had to finish by) make
+//! their project overdue in these events straight away. Each is announced
+//! once, then rolled into a daily summary per project, as configured in
+//! [`OverdueSettings`].
+//!
+//! The last statuses seen are kept in `<app data>/project_status.json`, so a
+//! project that fell behind while the app was closed is reported at launch.
+
+use crate::config::OverdueSettings;
+use crate::index::IndexEntry;
+use crate::notifications::{dispatch, Notification};
+use crate::project::{load_project, ProjectMetadata};
+use chrono::{NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+/// Statuses worth interrupting for.
+const ALERT_STATUSES: &[&str] = &["urgent", "overdue"];
+
+/// Payload of `project-status`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StatusChange {
+    pub project_id: String,
+    pub project_name: String,
+    pub from: String,
+    pub to: String,
+    pub next_deadline: Option<String>,
+}
+
+impl StatusChange {
+    fn notification(&self) -> Notification {
+        let deadline = self
+            .next_deadline
+            .as_deref()
+            .and_then(crate::project::parse_date_or_datetime)
+            .map(|d| d.format("%b %-d, %H:%M").to_string());
+        match (self.to.as_str(), deadline) {
+            ("overdue", _) => Notification {
+                title: format!("🚨 {} is overdue", self.project_name),
+                body: "Work that should be done by now isn't finished.".to_string(),
+            },
+            (_, Some(deadline)) => Notification {
+                title: format!("⚠️ {} is due soon", self.project_name),
+                body: format!("Next deadline: {}.", deadline),
+            },
+            (_, None) => Notification {
+                title: format!("⚠️ {} is due soon", self.project_name),
+                body: "Its next deadline is close.".to_string(),
+            },
+        }
+    }
+}
+
+/// An unfinished task past its late finish.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverdueTask {
+    pub project_id: String,
+    pub project_name: String,
+    pub task_id: String,
+    pub task_name: String,
+    pub late_finish: NaiveDateTime,
+}
+
+impl OverdueTask {
+    fn key(&self) -> String {
+        format!("{}:{}", self.project_id, self.task_id)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+struct MonitorState {
+    /// Last status seen per project.
+    statuses: HashMap<String, String>,
+    /// `project:task` for overdue tasks already announced.
+    overdue_notified: Vec<String>,
+    /// Day (YYYY-MM-DD) of the last daily summary.
+    summarized_on: Option<String>,
+}
+
+/// Every project's overdue tasks, most overdue first.
+pub fn overdue_tasks(entries: &[IndexEntry], now: NaiveDateTime) -> Vec<OverdueTask> {
+    let mut overdue: Vec<OverdueTask> = entries
+        .iter()
+        .flat_map(|entry| {
+            let upcoming = entry.upcoming.as_deref().unwrap_or_default();
+            upcoming.iter().filter(|t| !t.completed).filter_map(|t| {
+                let end = NaiveDateTime::parse_from_str(&t.end_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+                (end < now).then(|| OverdueTask {
+                    project_id: entry.id.clone(),
+                    project_name: entry.name.clone(),
+                    task_id: t.id.clone(),
+                    task_name: t.name.clone(),
+                    late_finish: end,
+                })
+            })
+        })
+        .collect();
+    overdue.sort_by(|a, b| (a.late_finish, &a.task_name).cmp(&(b.late_finish, &b.task_name)));
+    overdue
+}
+
+fn days_late(task: &OverdueTask, now: NaiveDateTime) -> String {
+    match (now.date() - task.late_finish.date()).num_days() {
+        0 => "due earlier today".to_string(),
+        1 => "1 day late".to_string(),
+        days => format!("{} days late", days),
+    }
+}
+
+/// What to send about overdue work, by project ID: tasks not announced
+/// before, then each project's summary once a day.
+fn escalate(
+    state: &mut MonitorState,
+    overdue: &[OverdueTask],
+    settings: &OverdueSettings,
+    now: NaiveDateTime,
+) -> Vec<(String, Notification)> {
+    let (known, new): (Vec<&OverdueTask>, Vec<&OverdueTask>) = overdue
+        .iter()
+        .partition(|t| state.overdue_notified.contains(&t.key()));
+    // Finished or rescheduled tasks are announced again if they fall behind
+    state.overdue_notified = overdue.iter().map(OverdueTask::key).collect();
+
+    let mut send = Vec::new();
+    if settings.notify {
+        for task in &new {
+            send.push((
+                task.project_id.clone(),
+                Notification {
+                    title: format!("⏰ {} is overdue · {}", task.task_name, task.project_name),
+                    body: format!(
+                        "It had to be finished by {}.",
+                        task.late_finish.format("%b %-d, %H:%M")
+                    ),
+                },
+            ));
+        }
+    }
+
+    let today = now.format("%Y-%m-%d").to_string();
+    let summary_due = settings
+        .daily_summary_hour
+        .is_some_and(|hour| now.hour() >= hour && state.summarized_on.as_ref() != Some(&today));
+    if summary_due {
+        state.summarized_on = Some(today);
+        let mut by_project: Vec<(&str, Vec<&OverdueTask>)> = Vec::new();
+        for task in known {
+            match by_project.iter_mut().find(|(id, _)| *id == task.project_id) {
+                Some((_, tasks)) => tasks.push(task),
+                None => by_project.push((&task.project_id, vec![task])),
+            }
+        }
+        for (project_id, tasks) in by_project {
+            let mut lines: Vec<String> = tasks
+                .iter()
+                .take(5)
+                .map(|t| format!("• {} ({})", t.task_name, days_late(t, now)))
+                .collect();
+            if tasks.len() > 5 {
+                lines.push(format!("and {} more", tasks.len() - 5));
+            }
+            send.push((
+                project_id.to_string(),
+                Notification {
+                    title: format!(
+                        "📋 {}: {} overdue task(s)",
+                        tasks[0].project_name,
+                        tasks.len()
+                    ),
+                    body: lines.join("\n"),
+                },
+            ));
+        }
+    }
+    send
+}
+
+/// Projects that have just become urgent or overdue. Projects not seen
+/// before only set their baseline.
+pub fn status_changes(
+    previous: &HashMap<String, String>,
+    projects: &[ProjectMetadata],
+) -> Vec<StatusChange> {
+    projects
+        .iter()
+        .filter(|p| ALERT_STATUSES.contains(&p.status.as_str()))
+        .filter_map(|p| {
+            let from = previous.get(&p.id)?;
+            (from != &p.status).then(|| StatusChange {
+                project_id: p.id.clone(),
+                project_name: p.name.clone(),
+                from: from.clone(),
+                to: p.status.clone(),
+                next_deadline: p.next_deadline.clone(),
+            })
+        })
+        .collect()
+}
+
+fn state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join("project_status.json"))
+}
+
+fn load_state(app: &AppHandle) -> MonitorState {
+    state_path(app)
+        .and_then(|path| std::fs::read_to_string(path).map_err(|e| e.to_string()))
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+        .unwrap_or_default()
+}
+
+fn save_state(app: &AppHandle, state: &MonitorState) -> Result<(), String> {
+    let json = serde_json::to_string(state).map_err(|e| e.to_string())?;
+    std::fs::write(state_path(app)?, json).map_err(|e| e.to_string())
+}
+
+/// Sends `notification` through a project's routing.
+fn notify(app: &AppHandle, project_id: &str, notification: &Notification) -> Result<(), String> {
+    let project = load_project(app.clone(), project_id.to_string())?;
+    dispatch(app, &project.notification_settings, notification)
+}
+
+/// Re-evaluates every project, announcing those that fell behind and
+/// following up on overdue tasks.
+fn check(app: &AppHandle, state: &mut MonitorState) -> Result<(), String> {
+    let now = chrono::Local::now().naive_local();
+    let settings = crate::config::load_config(app.clone())?.overdue;
+    let overdue = overdue_tasks(&crate::index::entries(app, now)?, now);
+    let mut projects = crate::project::dashboard(app, None)?;
+    // Overdue work makes its project overdue, whatever comes next
+    for project in &mut projects {
+        if overdue.iter().any(|t| t.project_id == project.id) {
+            project.status = "overdue".to_string();
+        }
+    }
+
+    let changes = status_changes(&state.statuses, &projects);
+    state.statuses = projects.into_iter().map(|p| (p.id, p.status)).collect();
+    let escalations = escalate(state, &overdue, &settings, now);
+    save_state(app, state)?;
+
+    for change in &changes {
+        let _ = app.emit("project-status", change);
+        if let Err(e) = notify(app, &change.project_id, &change.notification()) {
+            eprintln!(
+                "Failed to announce {} as {}: {}",
+                change.project_name, change.to, e
+            );
+        }
+    }
+    for (project_id, notification) in &escalations {
+        if let Err(e) = notify(app, project_id, notification) {
+            eprintln!("Failed to send overdue notice for {}: {}", project_id, e);
+        }
+    }
+    if !changes.is_empty() {
+        crate::tray::refresh(app);
+    }
+    Ok(())
+}
+
+/// Starts the background check.
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut state = load_state(&app);
+        loop {
+            if let Err(e) = check(&app, &mut state) {
+                eprintln!("Failed to check project statuses: {}", e);
+            }
+            std::thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_announces_transitions_into_alert_statuses() {
+        let project = |id: &str, status: &str| ProjectMetadata {
+            id: id.into(),
+            name: id.into(),
+            status: status.into(),
+            next_deadline: Some("2026-03-12T16:00:00".into()),
+            ..Default::default()
+        };
+        let previous = HashMap::from([
+            ("taxes".to_string(), "on_track".to_string()),
+            ("thesis".to_string(), "urgent".to_string()),
+            ("garden".to_string(), "urgent".to_string()),
+        ]);
+        let changes = status_changes(
+            &previous,
+            &[
+                project("taxes", "urgent"),
+                project("thesis", "urgent"),
+                project("garden", "overdue"),
+                project("new", "overdue"),
+            ],
+        );
+
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.project_id.as_str(), c.from.as_str(), c.to.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("taxes", "on_track", "urgent"),
+                ("garden", "urgent", "overdue")
+            ]
+        );
+        assert_eq!(
+            changes[0].notification().body,
+            "Next deadline: Mar 12, 16:00."
+        );
+    }
+
+    #[test]
+    fn test_overdue_tasks_escalate_to_a_daily_summary() {
+        let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap();
+        let task = |id: &str, late_finish: &str| OverdueTask {
+            project_id: "thesis".into(),
+            project_name: "Thesis".into(),
+            task_id: id.into(),
+            task_name: id.into(),
+            late_finish: at(late_finish),
+        };
+        let settings = OverdueSettings::default();
+        let mut state = MonitorState::default();
+        let overdue = vec![task("outline", "2026-03-09T17:00:00")];
+
+        // Announced once, before the summary hour
+        let sent = escalate(&mut state, &overdue, &settings, at("2026-03-10T08:00:00"));
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].1.title, "⏰ outline is overdue · Thesis");
+        assert!(escalate(&mut state, &overdue, &settings, at("2026-03-10T08:05:00")).is_empty());
+
+        // Then summarized once a day
+        let overdue = vec![overdue[0].clone(), task("draft", "2026-03-10T08:30:00")];
+        let sent = escalate(&mut state, &overdue, &settings, at("2026-03-10T09:00:00"));
+        let titles: Vec<_> = sent.iter().map(|(_, n)| n.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "⏰ draft is overdue · Thesis",
+                "📋 Thesis: 1 overdue task(s)"
+            ]
+        );
+        assert_eq!(sent[1].1.body, "• outline (1 day late)");
+        assert!(escalate(&mut state, &overdue, &settings, at("2026-03-10T15:00:00")).is_empty());
+        let sent = escalate(&mut state, &overdue, &settings, at("2026-03-11T09:00:00"));
+        assert_eq!(sent[0].1.title, "📋 Thesis: 2 overdue task(s)");
+    }
+}
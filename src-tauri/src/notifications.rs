@@ -0,0 +1,177 @@
+//! Notification dispatch.
+//!
+//! Every notification goes through [`dispatch`], which resolves a project's
+//! routing settings into concrete channels (desktop, Slack, email) and
+//! delivers to each of them.
+
+use crate::config::{load_config, SmtpSettings};
+use crate::project::load_project;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// A destination a notification can be delivered to.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotificationChannel {
+    Desktop,
+    Slack { webhook_url: String },
+    Email { to: String },
+}
+
+impl NotificationChannel {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Desktop => "desktop",
+            Self::Slack { .. } => "slack",
+            Self::Email { .. } => "email",
+        }
+    }
+}
+
+/// Per-project routing for notifications.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct NotificationSettings {
+    pub channels: Vec<NotificationChannel>,
+    /// Remind when tasks start and before they're due, see
+    /// [`crate::reminders`].
+    #[serde(default = "default_reminders")]
+    pub reminders: bool,
+    /// How long before a task has to finish to remind about it.
+    #[serde(default = "default_deadline_lead_minutes")]
+    pub deadline_lead_minutes: i64,
+}
+
+fn default_reminders() -> bool {
+    true
+}
+
+fn default_deadline_lead_minutes() -> i64 {
+    60
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            channels: vec![NotificationChannel::Desktop],
+            reminders: default_reminders(),
+            deadline_lead_minutes: default_deadline_lead_minutes(),
+        }
+    }
+}
+
+/// A message to deliver.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+}
+
+fn send_desktop(app: &AppHandle, notification: &Notification) -> Result<(), String> {
+    use tauri_plugin_notification::NotificationExt;
+    app.notification()
+        .builder()
+        .title(&notification.title)
+        .body(&notification.body)
+        .show()
+        .map_err(|e| e.to_string())
+}
+
+fn send_slack(webhook_url: &str, notification: &Notification) -> Result<(), String> {
+    let text = format!("*{}*\n{}", notification.title, notification.body);
+    ureq::post(webhook_url)
+        .send_json(serde_json::json!({ "text": text }))
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn send_email(
+    smtp: Option<&SmtpSettings>,
+    to: &str,
+    notification: &Notification,
+) -> Result<(), String> {
+    use lettre::transport::smtp::authentication::Credentials;
+    use lettre::{Message, SmtpTransport, Transport};
+
+    let smtp = smtp.ok_or("Email notifications require SMTP settings in the app config")?;
+    let message = Message::builder()
+        .from(
+            smtp.from
+                .parse()
+                .map_err(|e| format!("Invalid sender: {}", e))?,
+        )
+        .to(to
+            .parse()
+            .map_err(|e| format!("Invalid recipient: {}", e))?)
+        .subject(&notification.title)
+        .body(notification.body.clone())
+        .map_err(|e| e.to_string())?;
+
+    let mailer = SmtpTransport::relay(&smtp.host)
+        .map_err(|e| e.to_string())?
+        .port(smtp.port)
+        .credentials(Credentials::new(
+            smtp.username.clone(),
+            smtp.password.clone(),
+        ))
+        .build();
+    mailer.send(&message).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Delivers `notification` to every channel in `settings`.
+///
+/// A failing channel does not stop delivery to the others; all failures are
+/// reported together.
+pub fn dispatch(
+    app: &AppHandle,
+    settings: &NotificationSettings,
+    notification: &Notification,
+) -> Result<(), String> {
+    let needs_smtp = settings
+        .channels
+        .iter()
+        .any(|c| matches!(c, NotificationChannel::Email { .. }));
+    let smtp = if needs_smtp {
+        load_config(app.clone())?.smtp
+    } else {
+        None
+    };
+
+    let errors: Vec<String> = settings
+        .channels
+        .iter()
+        .filter_map(|channel| {
+            let result = match channel {
+                NotificationChannel::Desktop => send_desktop(app, notification),
+                NotificationChannel::Slack { webhook_url } => send_slack(webhook_url, notification),
+                NotificationChannel::Email { to } => send_email(smtp.as_ref(), to, notification),
+            };
+            result.err().map(|e| format!("{}: {}", channel.label(), e))
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Sends a notification through the routing configured on a project.
+#[tauri::command]
+pub async fn notify_project(
+    app: AppHandle,
+    project_id: String,
+    title: String,
+    body: String,
+) -> Result<(), String> {
+    let project = load_project(app.clone(), project_id)?;
+    let notification = Notification { title, body };
+
+    // Remote channels do network I/O; keep it off the async executor
+    tauri::async_runtime::spawn_blocking(move || {
+        dispatch(&app, &project.notification_settings, &notification)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
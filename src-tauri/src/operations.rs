@@ -0,0 +1,211 @@
+//! Batched task edits.
+//!
+//! Applies many task changes to a project at once: all of them or none, with
+//! a single file write and a single `project-update` event. Every batch
+//! yields the operations that reverse it, which back undo and redo.
+
+use crate::history::History;
+use crate::project::{load_project, save_project, Project};
+use crate::scheduler::{AnchorWindow, Task};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::{AppHandle, Manager};
+
+/// One change in a batch.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TaskOperation {
+    /// Adds a task, optionally anchored. An empty ID gets a fresh one.
+    Add {
+        task: Task,
+        #[serde(default)]
+        anchor: Option<String>,
+        #[serde(default)]
+        anchor_window: Option<AnchorWindow>,
+    },
+    /// Replaces the task with the same ID.
+    Update { task: Task },
+    /// Removes a task along with its anchor and any dependencies on it.
+    Delete { task_id: String },
+    /// Marks a task complete, or incomplete again.
+    Complete {
+        task_id: String,
+        #[serde(default = "yes")]
+        completed: bool,
+    },
+    /// Sets or (with no date) clears a task's anchor.
+    SetAnchor {
+        task_id: String,
+        #[serde(default)]
+        date: Option<String>,
+    },
+}
+
+fn yes() -> bool {
+    true
+}
+
+/// Applies `ops` in order and returns the operations that undo them. On
+/// error `project` may be partly changed, so callers apply to a copy.
+pub fn apply_operations(
+    project: &mut Project,
+    ops: Vec<TaskOperation>,
+) -> Result<Vec<TaskOperation>, String> {
+    let find = |project: &mut Project, id: &str| {
+        project
+            .tasks
+            .iter()
+            .position(|t| t.id == id)
+            .ok_or_else(|| format!("Task {} not found", id))
+    };
+
+    // Each operation's inverse, latest first
+    let mut inverse: Vec<Vec<TaskOperation>> = Vec::new();
+    for op in ops {
+        inverse.push(match op {
+            TaskOperation::Add {
+                mut task,
+                anchor,
+                anchor_window,
+            } => {
+                if task.id.is_empty() {
+                    task.id = uuid::Uuid::new_v4().to_string();
+                }
+                if project.tasks.iter().any(|t| t.id == task.id) {
+                    return Err(format!("Task {} already exists", task.id));
+                }
+                if let Some(date) = anchor {
+                    project.anchors.insert(task.id.clone(), date);
+                }
+                if let Some(window) = anchor_window {
+                    project.anchor_windows.insert(task.id.clone(), window);
+                }
+                let task_id = task.id.clone();
+                project.tasks.push(task);
+                vec![TaskOperation::Delete { task_id }]
+            }
+            TaskOperation::Update { task } => {
+                let i = find(project, &task.id)?;
+                let old = std::mem::replace(&mut project.tasks[i], task);
+                vec![TaskOperation::Update { task: old }]
+            }
+            TaskOperation::Delete { task_id } => {
+                let i = find(project, &task_id)?;
+                let task = project.tasks.remove(i);
+                let mut restore = vec![TaskOperation::Add {
+                    task,
+                    anchor: project.anchors.remove(&task_id),
+                    anchor_window: project.anchor_windows.remove(&task_id),
+                }];
+                for task in &mut project.tasks {
+                    if task.dependencies.contains(&task_id)
+                        || task.start_after_percent.contains_key(&task_id)
+                    {
+                        restore.push(TaskOperation::Update { task: task.clone() });
+                        task.dependencies.retain(|d| *d != task_id);
+                        task.start_after_percent.remove(&task_id);
+                    }
+                }
+                restore
+            }
+            TaskOperation::Complete { task_id, completed } => {
+                let i = find(project, &task_id)?;
+                let was = std::mem::replace(&mut project.tasks[i].completed, completed);
+                vec![TaskOperation::Complete {
+                    task_id,
+                    completed: was,
+                }]
+            }
+            TaskOperation::SetAnchor { task_id, date } => {
+                find(project, &task_id)?;
+                let old = match date {
+                    Some(date) => project.anchors.insert(task_id.clone(), date),
+                    None => project.anchors.remove(&task_id),
+                };
+                vec![TaskOperation::SetAnchor { task_id, date: old }]
+            }
+        });
+    }
+
+    // Adds and updates may name tasks that never arrived
+    let ids: HashSet<&str> = project.tasks.iter().map(|t| t.id.as_str()).collect();
+    for task in &project.tasks {
+        if let Some(missing) = task.dependencies.iter().find(|d| !ids.contains(d.as_str())) {
+            return Err(format!(
+                "Task {} depends on unknown task {}",
+                task.id, missing
+            ));
+        }
+    }
+    Ok(inverse.into_iter().rev().flatten().collect())
+}
+
+/// Applies a batch of task operations to a saved project transactionally
+/// and returns the updated project.
+#[tauri::command]
+pub fn apply_task_operations(
+    app: AppHandle,
+    project_id: String,
+    ops: Vec<TaskOperation>,
+) -> Result<Project, String> {
+    let mut project = load_project(app.clone(), project_id)?;
+    let inverse = apply_operations(&mut project, ops)?;
+    save_project(app.clone(), project.clone())?;
+    app.state::<History>().record(&project.id, inverse);
+    // Reload for the stamps save_project adds
+    load_project(app, project.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::TaskDuration;
+
+    fn task(id: &str, deps: &[&str]) -> Task {
+        Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(1),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_batch_applies_in_order_or_not_at_all() {
+        let mut project = Project {
+            tasks: vec![task("a", &[]), task("b", &["a"])],
+            anchors: [("a".into(), "2026-01-10".into())].into(),
+            ..Default::default()
+        };
+        let ops: Vec<TaskOperation> = serde_json::from_value(serde_json::json!([
+            { "op": "add", "task": task("c", &["b"]), "anchor": "2026-01-20" },
+            { "op": "delete", "task_id": "a" },
+            { "op": "complete", "task_id": "b" },
+        ]))
+        .unwrap();
+
+        let before = project.clone();
+        let inverse = apply_operations(&mut project, ops).unwrap();
+
+        let ids: Vec<&str> = project.tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c"]);
+        assert!(project.tasks[0].dependencies.is_empty());
+        assert!(project.tasks[0].completed);
+        assert!(!project.anchors.contains_key("a"));
+        assert_eq!(project.anchors["c"], "2026-01-20");
+
+        let bad = vec![TaskOperation::Update {
+            task: task("c", &["ghost"]),
+        }];
+        assert!(apply_operations(&mut project.clone(), bad).is_err());
+
+        // Undoing restores b's dependency and a's anchor
+        apply_operations(&mut project, inverse).unwrap();
+        let ids: Vec<&str> = project.tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a"]);
+        assert_eq!(project.tasks[0].dependencies, vec!["a"]);
+        assert!(!project.tasks[0].completed);
+        assert_eq!(project.anchors, before.anchors);
+    }
+}
@@ -0,0 +1,389 @@
+//! Portfolio view across every project: all anchors in date order, weekly
+//! workload, deliverables from different projects due at the same time, and
+//! the single most urgent task overall. Also the cross-project "next actions"
+//! list.
+
+use crate::cache::ScheduleCache;
+use crate::project::{load_all_projects, Project};
+use crate::scheduler::ScheduledTask;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tauri::{AppHandle, Manager};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PortfolioAnchor {
+    pub project_id: String,
+    pub project_name: String,
+    pub task_id: String,
+    pub task_name: String,
+    pub date: String,
+    pub completed: bool,
+}
+
+/// Unfinished work scheduled in the week starting on Monday `week_start`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WeekLoad {
+    pub week_start: String,
+    pub work_minutes: i64,
+    /// Project ID to minutes.
+    pub by_project: HashMap<String, i64>,
+}
+
+/// Two anchors from different projects whose final tasks run at the same time.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeadlineConflict {
+    pub first: PortfolioAnchor,
+    pub second: PortfolioAnchor,
+    pub overlap_minutes: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UrgentTask {
+    pub project_id: String,
+    pub project_name: String,
+    pub task: ScheduledTask,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Portfolio {
+    pub anchors: Vec<PortfolioAnchor>,
+    pub weekly_load: Vec<WeekLoad>,
+    pub conflicts: Vec<DeadlineConflict>,
+    /// The unfinished task with the least slack (earliest start on ties).
+    pub most_urgent: Option<UrgentTask>,
+}
+
+/// An unfinished task that should already have started.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct NextAction {
+    pub project_id: String,
+    pub project_name: String,
+    pub task: ScheduledTask,
+    /// Scheduled end plus slack: the last moment it can finish without
+    /// moving an anchor.
+    pub late_finish: String,
+    /// Minutes from now to `late_finish`; negative once it's too late.
+    pub pressure_minutes: i64,
+}
+
+fn parse(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").ok()
+}
+
+fn week_of(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// The time a task occupies; milestones take up the day they fall on.
+fn span(task: &ScheduledTask) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let (start, end) = (parse(&task.start_date)?, parse(&task.end_date)?);
+    if start < end {
+        return Some((start, end));
+    }
+    let day = end.date().and_time(NaiveTime::MIN);
+    Some((day, day + Duration::days(1)))
+}
+
+/// Aggregates `projects` with their schedules.
+pub fn portfolio(projects: &[(Project, Vec<ScheduledTask>)], now: NaiveDateTime) -> Portfolio {
+    let mut anchors = Vec::new();
+    let mut spans = Vec::new();
+    let mut weeks: BTreeMap<NaiveDate, HashMap<String, i64>> = BTreeMap::new();
+    let mut most_urgent: Option<UrgentTask> = None;
+
+    for (project, schedule) in projects {
+        for (task_id, date) in &project.anchors {
+            let task = schedule.iter().find(|t| &t.id == task_id);
+            let anchor = PortfolioAnchor {
+                project_id: project.id.clone(),
+                project_name: project.name.clone(),
+                task_id: task_id.clone(),
+                task_name: task.map(|t| t.name.clone()).unwrap_or_default(),
+                date: date.clone(),
+                completed: task.is_some_and(|t| t.completed),
+            };
+            if let Some(span) = task.filter(|t| !t.completed).and_then(span) {
+                spans.push((anchor.clone(), span));
+            }
+            anchors.push(anchor);
+        }
+
+        for task in schedule.iter().filter(|t| !t.completed) {
+            let Some((start, end)) = span(task) else {
+                continue;
+            };
+            // Remaining work only: what's already behind us is prorated away
+            let from = start.max(now);
+            let length = (end - start).num_minutes().max(1) as f64;
+            let mut week = week_of(from.date());
+            while week.and_time(NaiveTime::MIN) < end {
+                let week_end = (week + Duration::days(7)).and_time(NaiveTime::MIN);
+                let overlap = (end.min(week_end) - from.max(week.and_time(NaiveTime::MIN)))
+                    .num_minutes()
+                    .max(0);
+                let minutes = (task.estimate_minutes as f64 * overlap as f64 / length).round();
+                if minutes > 0.0 {
+                    *weeks
+                        .entry(week)
+                        .or_default()
+                        .entry(project.id.clone())
+                        .or_default() += minutes as i64;
+                }
+                week += Duration::days(7);
+            }
+
+            let more_urgent = most_urgent.as_ref().is_none_or(|current| {
+                (task.slack_minutes, &task.start_date)
+                    < (current.task.slack_minutes, &current.task.start_date)
+            });
+            if more_urgent {
+                most_urgent = Some(UrgentTask {
+                    project_id: project.id.clone(),
+                    project_name: project.name.clone(),
+                    task: task.clone(),
+                });
+            }
+        }
+    }
+    anchors.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then(a.project_name.cmp(&b.project_name))
+    });
+
+    let mut conflicts = Vec::new();
+    for (i, (a, (a_start, a_end))) in spans.iter().enumerate() {
+        for (b, (b_start, b_end)) in &spans[i + 1..] {
+            let overlap = ((*a_end).min(*b_end) - (*a_start).max(*b_start)).num_minutes();
+            if a.project_id != b.project_id && overlap > 0 {
+                let (first, second) = if a.date <= b.date { (a, b) } else { (b, a) };
+                conflicts.push(DeadlineConflict {
+                    first: first.clone(),
+                    second: second.clone(),
+                    overlap_minutes: overlap,
+                });
+            }
+        }
+    }
+    conflicts.sort_by(|a, b| a.first.date.cmp(&b.first.date));
+
+    Portfolio {
+        anchors,
+        weekly_load: weeks
+            .into_iter()
+            .map(|(week, by_project)| WeekLoad {
+                week_start: week.to_string(),
+                work_minutes: by_project.values().sum(),
+                by_project,
+            })
+            .collect(),
+        conflicts,
+        most_urgent,
+    }
+}
+
+/// Up to `limit` unfinished tasks across `projects` whose scheduled start is
+/// at or before `now`, the nearest late finish first.
+pub fn next_actions(
+    projects: &[(Project, Vec<ScheduledTask>)],
+    now: NaiveDateTime,
+    limit: usize,
+) -> Vec<NextAction> {
+    let mut actions: Vec<NextAction> = projects
+        .iter()
+        .flat_map(|(project, schedule)| schedule.iter().map(move |task| (project, task)))
+        .filter(|(_, task)| !task.completed)
+        .filter_map(|(project, task)| {
+            let start = parse(&task.start_date)?;
+            if start > now {
+                return None;
+            }
+            let late_finish = parse(&task.end_date)? + Duration::minutes(task.slack_minutes);
+            Some(NextAction {
+                project_id: project.id.clone(),
+                project_name: project.name.clone(),
+                task: task.clone(),
+                late_finish: late_finish.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                pressure_minutes: (late_finish - now).num_minutes(),
+            })
+        })
+        .collect();
+    actions.sort_by(|a, b| {
+        a.pressure_minutes
+            .cmp(&b.pressure_minutes)
+            .then_with(|| a.task.start_date.cmp(&b.task.start_date))
+    });
+    actions.truncate(limit);
+    actions
+}
+
+/// Every project with its schedule; projects that can't be scheduled are
+/// left out.
+fn scheduled_projects(
+    app: &AppHandle,
+    now: NaiveDateTime,
+) -> Result<Vec<(Project, Vec<ScheduledTask>)>, String> {
+    let cache = app.state::<ScheduleCache>();
+    Ok(load_all_projects(app)?
+        .into_iter()
+        .filter_map(|project| {
+            let schedule = cache.schedule(&project, now).ok()?.scheduled_tasks;
+            Some((project, schedule))
+        })
+        .collect())
+}
+
+/// Anchors, weekly workload, deadline conflicts and the most urgent task
+/// across all projects. Projects that can't be scheduled are left out.
+#[tauri::command]
+pub fn get_portfolio(app: AppHandle) -> Result<Portfolio, String> {
+    let now = chrono::Local::now().naive_local();
+    Ok(portfolio(&scheduled_projects(&app, now)?, now))
+}
+
+/// The `limit` (default 10) most pressing tasks that should be under way,
+/// across all projects.
+#[tauri::command]
+pub fn get_next_actions(app: AppHandle, limit: Option<usize>) -> Result<Vec<NextAction>, String> {
+    let now = chrono::Local::now().naive_local();
+    Ok(next_actions(
+        &scheduled_projects(&app, now)?,
+        now,
+        limit.unwrap_or(10),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_portfolio_spans_projects() {
+        let task = |id: &str, start: &str, end: &str, slack: i64| ScheduledTask {
+            id: id.into(),
+            name: id.to_uppercase(),
+            start_date: format!("2026-03-{}:00:00", start),
+            end_date: format!("2026-03-{}:00:00", end),
+            estimate_minutes: 600,
+            slack_minutes: slack,
+            ..Default::default()
+        };
+        let project = |id: &str, anchor: &str, date: &str| Project {
+            id: id.into(),
+            name: id.to_uppercase(),
+            anchors: [(anchor.to_string(), date.to_string())].into(),
+            ..Default::default()
+        };
+        let projects = vec![
+            (
+                project("talk", "slides", "2026-03-13T17:00:00"),
+                vec![
+                    task("outline", "09T09", "10T09", 0),
+                    task("slides", "12T09", "13T17", 0),
+                ],
+            ),
+            (
+                project("report", "submit", "2026-03-12T17:00:00"),
+                vec![task("submit", "11T09", "12T17", 120)],
+            ),
+            (
+                project("garden", "plant", "2026-03-20T12:00:00"),
+                vec![task("plant", "20T12", "20T12", 5000)],
+            ),
+        ];
+        let now = parse("2026-03-09T09:00:00").unwrap();
+
+        let result = portfolio(&projects, now);
+        let dates: Vec<_> = result.anchors.iter().map(|a| a.task_id.as_str()).collect();
+        assert_eq!(dates, vec!["submit", "slides", "plant"]);
+
+        // The report and the talk both need 12 March
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].first.task_id, "submit");
+        assert_eq!(result.conflicts[0].overlap_minutes, 8 * 60);
+
+        let weeks: Vec<_> = result
+            .weekly_load
+            .iter()
+            .map(|w| (w.week_start.as_str(), w.work_minutes))
+            .collect();
+        assert_eq!(weeks, vec![("2026-03-09", 1800), ("2026-03-16", 600)]);
+
+        let urgent = result.most_urgent.unwrap();
+        assert_eq!(
+            (urgent.project_id.as_str(), urgent.task.id.as_str()),
+            ("talk", "outline")
+        );
+    }
+
+    #[test]
+    fn test_next_actions_by_late_finish() {
+        let task = |id: &str, start: &str, end: &str, slack: i64, completed: bool| ScheduledTask {
+            id: id.into(),
+            start_date: start.into(),
+            end_date: end.into(),
+            slack_minutes: slack,
+            completed,
+            ..Default::default()
+        };
+        let project = |id: &str| Project {
+            id: id.into(),
+            ..Default::default()
+        };
+        let projects = vec![
+            (
+                project("a"),
+                vec![
+                    task(
+                        "roomy",
+                        "2026-03-09T09:00:00",
+                        "2026-03-10T17:00:00",
+                        2880,
+                        false,
+                    ),
+                    task(
+                        "done",
+                        "2026-03-09T09:00:00",
+                        "2026-03-09T12:00:00",
+                        0,
+                        true,
+                    ),
+                    task(
+                        "later",
+                        "2026-03-11T09:00:00",
+                        "2026-03-11T17:00:00",
+                        0,
+                        false,
+                    ),
+                ],
+            ),
+            (
+                project("b"),
+                vec![task(
+                    "tight",
+                    "2026-03-08T09:00:00",
+                    "2026-03-11T09:00:00",
+                    0,
+                    false,
+                )],
+            ),
+        ];
+        let now = parse("2026-03-10T09:00:00").unwrap();
+
+        let actions = next_actions(&projects, now, 10);
+        let ids: Vec<_> = actions
+            .iter()
+            .map(|a| {
+                (
+                    a.project_id.as_str(),
+                    a.task.id.as_str(),
+                    a.pressure_minutes,
+                )
+            })
+            .collect();
+        assert_eq!(ids, vec![("b", "tight", 1440), ("a", "roomy", 3360)]);
+        assert_eq!(actions[1].late_finish, "2026-03-12T17:00:00");
+        assert_eq!(next_actions(&projects, now, 1).len(), 1);
+    }
+}
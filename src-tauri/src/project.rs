@@ -1,4 +1,16 @@
-use crate::scheduler::Task;
+use crate::activity::ActivityEntry;
+use crate::cache::ScheduleCache;
+use crate::calendar::{group_by_week, CalendarWeek};
+use crate::config::WidgetSettings;
+use crate::events::ChangeKind;
+use crate::forecast::{AnchorProbability, ProgressForecast};
+use crate::index::IndexEntry;
+use crate::migrate::SchemaError;
+use crate::notifications::NotificationSettings;
+use crate::scheduler::{
+    AnchorSpacing, AnchorWindow, ExclusionGroup, ScheduleRequest, ScheduleResult, ScheduledTask,
+    Task,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -7,26 +19,114 @@ use tauri::AppHandle;
 use tauri::Manager;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Below this chance of meeting an upcoming anchor, a project is "at_risk".
+const AT_RISK_PROBABILITY: f64 = 0.8;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Project {
+    /// File format version, see [`crate::migrate`].
+    #[serde(default)]
+    pub schema_version: u32,
     pub id: String,
     pub name: String,
+    /// CSS color (e.g. `#3b82f6`) used to tell projects apart.
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Emoji or icon name shown beside the name.
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
     pub created_at: String,
     pub last_modified: String,
     pub tasks: Vec<Task>,
     pub anchors: HashMap<String, String>,
+    #[serde(default)]
+    pub anchor_windows: HashMap<String, AnchorWindow>,
+    #[serde(default)]
+    pub notification_settings: NotificationSettings,
+    /// IANA time zone the project's dates are in; the local zone when unset.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub exclusions: Vec<ExclusionGroup>,
+    #[serde(default)]
+    pub anchor_spacing: Vec<AnchorSpacing>,
+    /// Single-user mode: never schedule two tasks at once.
+    #[serde(default)]
+    pub serialize_work: bool,
+    /// Spread slack along chains instead of planning everything as late as possible.
+    #[serde(default)]
+    pub spread_slack: bool,
+    /// Append-only log of task changes and comments, see [`crate::activity`].
+    #[serde(default)]
+    pub activity: Vec<ActivityEntry>,
+    /// Task chosen as the current focus, shown instead of the next one due
+    /// while it's unfinished. See [`set_focus_task`].
+    #[serde(default)]
+    pub focus_task_id: Option<String>,
+}
+
+impl Project {
+    /// Builds the scheduler input for this project's tasks and anchors.
+    pub fn schedule_request(&self) -> ScheduleRequest {
+        ScheduleRequest {
+            tasks: self.tasks.clone(),
+            anchors: self.anchors.clone(),
+            anchor_windows: self.anchor_windows.clone(),
+            timezone: self.timezone.clone(),
+            exclusions: self.exclusions.clone(),
+            anchor_spacing: self.anchor_spacing.clone(),
+            serialize_work: self.serialize_work,
+            spread_slack: self.spread_slack,
+            ..Default::default()
+        }
+    }
 }
 
+/// A stored project as listed, without anything derived from its schedule.
 #[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectListing {
+    pub id: String,
+    pub name: String,
+    pub pinned: bool,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub last_modified: String,
+    pub task_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ProjectMetadata {
     pub id: String,
     pub name: String,
+    pub pinned: bool,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub description: Option<String>,
     pub created_at: String,
     pub last_modified: String,
     pub task_count: usize,
     pub next_deadline: Option<String>,
     pub current_focus: Option<String>,
     pub status: String,
+    pub forecast: Option<ProgressForecast>,
+    /// Chance of meeting each anchor, by anchor ID.
+    #[serde(default)]
+    pub on_time: Vec<AnchorProbability>,
+    /// Percentage of the work done, counting checked-off subtasks.
+    #[serde(default)]
+    pub completion: Option<f32>,
+}
+
+/// The soonest upcoming deadline across all projects.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NextDeadline {
+    pub next: Option<ProjectMetadata>,
+    /// The projects due after it, soonest first.
+    pub runners_up: Vec<ProjectMetadata>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -38,32 +138,86 @@ pub struct WidgetTask {
     pub completed: bool,
     pub is_milestone: bool,
     pub status: String, // "active", "future", "overdue"
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The task's project, in the all-projects view.
+    #[serde(default)]
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub project_name: Option<String>,
+    #[serde(default)]
+    pub project_color: Option<String>,
+    /// Fraction of its subtasks checked off; unset without subtasks.
+    #[serde(default)]
+    pub subtask_progress: Option<f32>,
+    /// Whole days past the date it had to finish by, in `overdue_tasks`.
+    #[serde(default)]
+    pub days_overdue: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProjectSummary {
     pub id: String,
     pub name: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct WidgetInfo {
     pub project_id: String,
     pub project_name: String,
+    pub project_color: Option<String>,
+    pub project_icon: Option<String>,
+    pub project_description: Option<String>,
     pub next_deadline: Option<String>,
     pub status: String,
     pub current_focus: Option<String>,
     pub upcoming_tasks: Vec<WidgetTask>,
     pub calendar_tasks: Vec<WidgetTask>,
+    /// `calendar_tasks` by week and day.
+    pub calendar_weeks: Vec<CalendarWeek>,
     pub all_projects: Vec<ProjectSummary>,
     pub task_progress: Option<f32>,
     pub active_task: Option<WidgetTask>,
+    pub forecast: Option<ProgressForecast>,
+    /// Time left to each unfinished anchor, soonest first.
+    pub countdowns: Vec<AnchorCountdown>,
+    /// Unfinished work past its required finish, most overdue first. The
+    /// other lists show it rescheduled from now.
+    pub overdue_tasks: Vec<WidgetTask>,
+    /// Percentage of the work done, across every project in the merged view.
+    pub project_completion: Option<f32>,
+}
+
+/// Time left to one anchor, for "T-minus" counters.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnchorCountdown {
+    pub task_id: String,
+    pub name: String,
+    /// The anchor as stored, a date or date-time.
+    pub date: String,
+    /// Whole days and whole hours left; negative once passed. A date-only
+    /// anchor is due at the end of its day.
+    pub days_remaining: i64,
+    pub hours_remaining: i64,
+    /// How much of the time from the project's start to the anchor has
+    /// passed, 0 to 100.
+    pub elapsed_percent: f32,
+    /// The anchor's project, in the all-projects view.
+    pub project_id: Option<String>,
+    pub project_name: Option<String>,
 }
 
 // Helper to get projects directory: app_data_dir/projects
-fn get_projects_dir(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let projects_dir = app_data_dir.join("projects");
+pub(crate) fn get_projects_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let projects_dir = match crate::config::load_config(app.clone())?.projects_dir {
+        Some(dir) if !dir.trim().is_empty() => PathBuf::from(dir),
+        _ => {
+            let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+            app_data_dir.join("projects")
+        }
+    };
     if !projects_dir.exists() {
         fs::create_dir_all(&projects_dir).map_err(|e| e.to_string())?;
     }
@@ -80,6 +234,7 @@ pub fn create_project(app: AppHandle, name: String) -> Result<Project, String> {
         last_modified: now,
         tasks: vec![],
         anchors: HashMap::new(),
+        ..Default::default()
     };
 
     save_project(app, project.clone())?;
@@ -87,16 +242,73 @@ pub fn create_project(app: AppHandle, name: String) -> Result<Project, String> {
 }
 
 #[tauri::command]
-pub fn save_project(app: AppHandle, mut project: Project) -> Result<(), String> {
-    let dir = get_projects_dir(&app)?;
-    project.last_modified = chrono::Local::now().to_rfc3339();
+pub fn save_project(app: AppHandle, project: Project) -> Result<(), String> {
+    store_project(&app, project, Vec::new())
+}
+
+/// Saves `project`, appending `log` to its activity log after the changes
+/// the save itself records.
+pub(crate) fn store_project(
+    app: &AppHandle,
+    mut project: Project,
+    log: Vec<ActivityEntry>,
+) -> Result<(), String> {
+    let dir = get_projects_dir(app)?;
+    // As the editor sent it, to settle the journal once it's on disk
+    let sent = project.clone();
+    let now = chrono::Local::now().to_rfc3339();
     let path = dir.join(format!("{}.json", project.id));
+    crate::dates::normalize_project(&mut project, chrono::Local::now().naive_local());
+
+    // Merge rather than overwrite a save made elsewhere since we last looked
+    let previous = crate::encryption::read_to_string(&path)
+        .ok()
+        .and_then(|json| crate::migrate::parse_project(&json).ok());
+    // The activity log only grows through this function
+    project.activity = previous
+        .as_ref()
+        .map(|p| p.activity.clone())
+        .unwrap_or_default();
+    let sync = app.state::<crate::sync::SyncBase>();
+    let mut merged = false;
+    if let (Some(base), Some(theirs)) = (sync.get(&project.id), &previous) {
+        if theirs.last_modified != base.last_modified {
+            let conflicts;
+            (project, conflicts) = crate::sync::merge_projects(&base, &project, theirs, &now)?;
+            crate::sync::record_conflicts(app, conflicts)?;
+            merged = true;
+        }
+    }
+    crate::sync::stamp_updates(&mut project, previous.as_ref(), &now);
+    crate::activity::record_changes(previous.as_ref(), &mut project, &now);
+    project.activity.extend(log);
+    project.last_modified = now.clone();
+    project.schema_version = crate::migrate::CURRENT_SCHEMA_VERSION;
+
+    // Stamp completion times so progress can be measured later
+    for task in &mut project.tasks {
+        if !task.completed {
+            task.completed_at = None;
+        } else if task.completed_at.is_none() {
+            task.completed_at = Some(now.clone());
+        }
+    }
     let json = serde_json::to_string_pretty(&project).map_err(|e| e.to_string())?;
-    fs::write(path, json).map_err(|e| e.to_string())?;
+    let json = crate::encryption::seal(json).map_err(|e| e.to_string())?;
+    app.state::<crate::watcher::ProjectWatcher>()
+        .write(&path, || crate::repair::write_atomic(&path, &json))?;
+    crate::journal::settle(app, &sent)?;
+    crate::index::invalidate(app, &project.id)?;
+    sync.remember(&project);
 
-    // Emit update event
-    use tauri::Emitter;
-    let _ = app.emit("project-update", ());
+    // Merges tell the editor to reload
+    let kind = match (&previous, merged) {
+        (None, _) => ChangeKind::Created,
+        (_, true) => ChangeKind::Merged,
+        (_, false) => ChangeKind::Saved,
+    };
+    let widget = crate::events::affects_widget(previous.as_ref(), &project);
+    crate::events::project_changed(app, Some(&project.id), kind, widget);
 
     Ok(())
 }
@@ -110,12 +322,54 @@ pub fn load_project(app: AppHandle, id: String) -> Result<Project, String> {
         return Err(format!("Project {} not found", id));
     }
 
-    let json = fs::read_to_string(path).map_err(|e| e.to_string())?;
-    let project: Project = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+    let json = crate::encryption::read_to_string(path).map_err(|e| e.to_string())?;
+    let project = crate::migrate::parse_project(&json).map_err(|e| match e {
+        SchemaError::TooNew { .. } => e.to_string(),
+        _ => format!("Project {} is unreadable ({}); try repairing it", id, e),
+    })?;
+    app.state::<crate::sync::SyncBase>().remember(&project);
     Ok(project)
 }
 
-fn parse_date_or_datetime(s: &str) -> Option<chrono::NaiveDateTime> {
+/// The unfinished task due soonest that hasn't ended yet, with its parsed
+/// start and end.
+pub(crate) fn next_up(
+    schedule: &[ScheduledTask],
+    now: chrono::NaiveDateTime,
+) -> Option<(chrono::NaiveDateTime, chrono::NaiveDateTime, &ScheduledTask)> {
+    schedule
+        .iter()
+        .filter(|t| !t.completed)
+        .filter_map(|t| {
+            let start =
+                chrono::NaiveDateTime::parse_from_str(&t.start_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+            let end =
+                chrono::NaiveDateTime::parse_from_str(&t.end_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+            // Include if it ends now or in future
+            (end >= now).then_some((start, end, t))
+        })
+        .min_by_key(|(_, end, _)| *end)
+}
+
+/// "Current focus" text for the task returned by [`next_up`].
+pub(crate) fn focus_label(
+    start: chrono::NaiveDateTime,
+    end: chrono::NaiveDateTime,
+    task: &ScheduledTask,
+    now: chrono::NaiveDateTime,
+) -> String {
+    if now >= start && now <= end {
+        return task.name.clone();
+    }
+    let until = start - now;
+    if until.num_days() > 0 {
+        format!("{} (starts in {} days)", task.name, until.num_days())
+    } else {
+        format!("{} (starts in {} hours)", task.name, until.num_hours())
+    }
+}
+
+pub(crate) fn parse_date_or_datetime(s: &str) -> Option<chrono::NaiveDateTime> {
     // Try DateTime first
     if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S") {
         return Some(dt);
@@ -127,173 +381,655 @@ fn parse_date_or_datetime(s: &str) -> Option<chrono::NaiveDateTime> {
     None
 }
 
-#[tauri::command]
-pub fn list_projects(app: AppHandle) -> Result<Vec<ProjectMetadata>, String> {
-    let dir = get_projects_dir(&app)?;
+/// Reads every parseable project in the projects directory.
+pub(crate) fn load_all_projects(app: &AppHandle) -> Result<Vec<Project>, String> {
+    let dir = get_projects_dir(app)?;
     let mut projects = Vec::new();
-    let now = chrono::Local::now().naive_local();
 
     for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
         let entry = entry.map_err(|e| e.to_string())?;
         let path = entry.path();
         if path.extension().and_then(|s| s.to_str()) == Some("json") {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if let Ok(project) = serde_json::from_str::<Project>(&content) {
-                    // Calculate derived metadata
-                    let mut next_deadline = None;
-                    let mut current_focus = None;
-                    let mut status = "empty".to_string();
-
-                    if !project.anchors.is_empty() {
-                        // Default to Anchor for deadline/status
-                        let mut anchors: Vec<chrono::NaiveDateTime> = project
-                            .anchors
-                            .values()
-                            .filter_map(|d| parse_date_or_datetime(d))
-                            .filter(|d| *d >= now)
-                            .collect();
-                        anchors.sort();
-
-                        // Default to nearest anchor
-                        if let Some(anchor) = anchors.first() {
-                            next_deadline = Some(anchor.format("%Y-%m-%dT%H:%M:%S").to_string());
-                            let duration = *anchor - now;
-                            let days = duration.num_days();
-                            status = if duration.num_seconds() < 0 {
-                                "overdue".to_string()
-                            } else if days <= 5 {
-                                "urgent".to_string()
-                            } else {
-                                "on_track".to_string()
-                            };
-                        } else {
-                            status = "overdue".to_string(); // All anchors passed
-                        }
-
-                        // Try to find a better "Next Deadline" from the schedule (Next Task)
-                        let req = crate::scheduler::ScheduleRequest {
-                            tasks: project.tasks.clone(),
-                            anchors: project.anchors.clone(),
-                        };
-
-                        if let Ok(schedule) = crate::scheduler::calculate_backwards_schedule(req) {
-                            // Find active or next upcoming task (excluding completed ones)
-                            let mut active_or_upcoming = schedule
-                                .iter()
-                                .filter(|t| !t.completed)
-                                .filter_map(|t| {
-                                    let start = chrono::NaiveDateTime::parse_from_str(
-                                        &t.start_date,
-                                        "%Y-%m-%dT%H:%M:%S",
-                                    )
-                                    .ok()?;
-                                    let end = chrono::NaiveDateTime::parse_from_str(
-                                        &t.end_date,
-                                        "%Y-%m-%dT%H:%M:%S",
-                                    )
-                                    .ok()?;
-                                    // Include if it ends now or in future
-                                    if end >= now {
-                                        Some((start, end, t))
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect::<Vec<_>>();
-
-                            // Sort by end date (deadline)
-                            active_or_upcoming.sort_by_key(|(_, end, _)| *end);
-
-                            if let Some((start, end, task)) = active_or_upcoming.first() {
-                                // Update Next Deadline to this task's deadline
-                                next_deadline = Some(end.format("%Y-%m-%dT%H:%M:%S").to_string());
-
-                                // Update Status based on THIS deadline
-                                let duration = *end - now;
-                                let days = duration.num_days();
-                                status = if duration.num_seconds() < 0 {
-                                    "overdue".to_string()
-                                } else if days <= 2 {
-                                    "urgent".to_string()
-                                } else {
-                                    "on_track".to_string()
-                                };
-
-                                // Set Current Focus text
-                                if now >= *start && now <= *end {
-                                    current_focus = Some(task.name.clone());
-                                } else {
-                                    let start_duration = *start - now;
-                                    let start_days = start_duration.num_days();
-                                    let start_hours = start_duration.num_hours();
-
-                                    if start_days > 0 {
-                                        current_focus = Some(format!(
-                                            "{} (starts in {} days)",
-                                            task.name, start_days
-                                        ));
-                                    } else {
-                                        current_focus = Some(format!(
-                                            "{} (starts in {} hours)",
-                                            task.name, start_hours
-                                        ));
-                                    }
-                                }
-                            } else {
-                                current_focus = Some("All tasks completed".to_string());
-                            }
-                        }
-                    }
-
-                    projects.push(ProjectMetadata {
-                        id: project.id,
-                        name: project.name,
-                        created_at: project.created_at,
-                        last_modified: project.last_modified,
-                        task_count: project.tasks.len(),
-                        next_deadline,
-                        current_focus,
-                        status,
-                    });
-                }
+            match crate::encryption::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| {
+                    crate::migrate::parse_project(&content).map_err(|e| e.to_string())
+                }) {
+                Ok(project) => projects.push(project),
+                // Left for repair_project rather than failing the whole listing
+                Err(e) => eprintln!("Skipping unreadable project {}: {}", path.display(), e),
             }
         }
     }
 
-    // Sort by last modified desc
-    projects.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+    Ok(projects)
+}
+
+/// Schedules a saved project as of now. Used when recalculation isn't
+/// triggered by every edit (see `RecalculationTrigger`).
+#[tauri::command]
+pub fn recalculate(app: AppHandle, project_id: String) -> Result<ScheduleResult, String> {
+    let project = load_project(app.clone(), project_id)?;
+    let now = chrono::Local::now().naive_local();
+    app.state::<ScheduleCache>()
+        .schedule(&project, now)
+        .map_err(|e| e.to_string())
+}
+
+/// Listing metadata for an indexed project, as of `now`.
+fn project_metadata(entry: &IndexEntry, now: chrono::NaiveDateTime) -> ProjectMetadata {
+    let mut next_deadline = None;
+    let mut current_focus = None;
+    let mut status = "empty".to_string();
+
+    if !entry.deadlines.is_empty() {
+        // Default to Anchor for deadline/status
+        let mut anchors: Vec<chrono::NaiveDateTime> = entry
+            .deadlines
+            .iter()
+            .filter_map(|d| parse_date_or_datetime(d))
+            .filter(|d| *d >= now)
+            .collect();
+        anchors.sort();
+
+        // Default to nearest anchor
+        if let Some(anchor) = anchors.first() {
+            next_deadline = Some(anchor.format("%Y-%m-%dT%H:%M:%S").to_string());
+            let duration = *anchor - now;
+            let days = duration.num_days();
+            status = if duration.num_seconds() < 0 {
+                "overdue".to_string()
+            } else if days <= 5 {
+                "urgent".to_string()
+            } else {
+                "on_track".to_string()
+            };
+        } else {
+            status = "overdue".to_string(); // All anchors passed
+        }
+
+        // Try to find a better "Next Deadline" from the schedule (Next Task)
+        if let Some(upcoming) = &entry.upcoming {
+            if let Some((start, end, task)) = next_up(upcoming, now) {
+                // Update Next Deadline to this task's deadline
+                next_deadline = Some(end.format("%Y-%m-%dT%H:%M:%S").to_string());
+
+                // Update Status based on THIS deadline
+                let duration = end - now;
+                let days = duration.num_days();
+                status = if duration.num_seconds() < 0 {
+                    "overdue".to_string()
+                } else if days <= 2 {
+                    "urgent".to_string()
+                } else {
+                    "on_track".to_string()
+                };
+
+                current_focus = Some(focus_label(start, end, task, now));
+            } else {
+                current_focus = Some("All tasks completed".to_string());
+            }
+        }
+    }
+
+    // A chosen focus wins while it's unfinished
+    let chosen = entry.upcoming.as_deref().and_then(|upcoming| {
+        let id = entry.focus_task_id.as_deref()?;
+        let task = upcoming.iter().find(|t| t.id == id && !t.completed)?;
+        let start = parse_date_or_datetime(&task.start_date)?;
+        let end = parse_date_or_datetime(&task.end_date)?;
+        // Past its end it's overdue, not upcoming
+        Some(focus_label(start, end.max(now), task, now))
+    });
+    if chosen.is_some() {
+        current_focus = chosen;
+    }
+
+    let at_risk = entry.on_time.iter().any(|p| {
+        p.probability < AT_RISK_PROBABILITY
+            && parse_date_or_datetime(&p.anchor_date).is_some_and(|d| d >= now)
+    });
+    if at_risk && (status == "on_track" || status == "urgent") {
+        status = "at_risk".to_string();
+    }
+
+    ProjectMetadata {
+        id: entry.id.clone(),
+        name: entry.name.clone(),
+        pinned: false,
+        color: entry.color.clone(),
+        icon: entry.icon.clone(),
+        description: entry.description.clone(),
+        created_at: entry.created_at.clone(),
+        last_modified: entry.last_modified.clone(),
+        task_count: entry.task_count,
+        next_deadline,
+        current_focus,
+        status,
+        forecast: entry.forecast.clone(),
+        on_time: entry.on_time.clone(),
+        completion: entry.completion,
+    }
+}
+
+/// Sorts projects pinned first, then in the manual order, then most recently
+/// modified first. `key` gives a project's ID, pinned flag and last modified.
+fn arrange<T>(projects: &mut [T], order: &[String], key: impl Fn(&T) -> (&str, bool, &str)) {
+    projects.sort_by_cached_key(|project| {
+        let (id, pinned, last_modified) = key(project);
+        let position = order.iter().position(|p| p == id);
+        (
+            !pinned,
+            position.unwrap_or(usize::MAX),
+            std::cmp::Reverse(last_modified.to_string()),
+        )
+    });
+}
 
+/// Stored projects: pinned first, then in the manual order, then most
+/// recently modified. Deadlines, focus and status come from [`get_dashboard`].
+#[tauri::command]
+pub fn list_projects(app: AppHandle) -> Result<Vec<ProjectListing>, String> {
+    let now = chrono::Local::now().naive_local();
+    let config = crate::config::load_config(app.clone())?;
+    let mut projects: Vec<ProjectListing> = crate::index::entries(&app, now)?
+        .into_iter()
+        .map(|entry| ProjectListing {
+            pinned: config.pinned_projects.contains(&entry.id),
+            id: entry.id,
+            name: entry.name,
+            color: entry.color,
+            icon: entry.icon,
+            description: entry.description,
+            created_at: entry.created_at,
+            last_modified: entry.last_modified,
+            task_count: entry.task_count,
+        })
+        .collect();
+
+    arrange(&mut projects, &config.project_order, |p| {
+        (&p.id, p.pinned, &p.last_modified)
+    });
     Ok(projects)
 }
 
+pub(crate) fn dashboard(
+    app: &AppHandle,
+    project_ids: Option<&[String]>,
+) -> Result<Vec<ProjectMetadata>, String> {
+    let now = chrono::Local::now().naive_local();
+    let config = crate::config::load_config(app.clone())?;
+    let mut projects: Vec<ProjectMetadata> = crate::index::entries(app, now)?
+        .iter()
+        .filter(|entry| project_ids.is_none_or(|ids| ids.contains(&entry.id)))
+        .map(|entry| ProjectMetadata {
+            pinned: config.pinned_projects.contains(&entry.id),
+            ..project_metadata(entry, now)
+        })
+        .collect();
+
+    arrange(&mut projects, &config.project_order, |p| {
+        (&p.id, p.pinned, &p.last_modified)
+    });
+    Ok(projects)
+}
+
+/// Deadline, focus and status for the given projects (all when unset), in
+/// [`list_projects`] order. Built from the project index's cached schedules
+/// rather than by rescheduling.
+#[tauri::command]
+pub fn get_dashboard(
+    app: AppHandle,
+    project_ids: Option<Vec<String>>,
+) -> Result<Vec<ProjectMetadata>, String> {
+    dashboard(&app, project_ids.as_deref())
+}
+
 #[tauri::command]
 pub fn delete_project(app: AppHandle, id: String) -> Result<(), String> {
     let dir = get_projects_dir(&app)?;
     let path = dir.join(format!("{}.json", id));
     if path.exists() {
-        fs::remove_file(path).map_err(|e| e.to_string())?;
+        // Kept in the trash (with its archive) until purged
+        crate::trash::move_to_trash(&dir, &id)?;
+        crate::repair::remove_sidecars(&path);
+        crate::index::invalidate(&app, &id)?;
+        app.state::<ScheduleCache>().invalidate(&id);
+        app.state::<crate::history::History>().forget(&id);
+        crate::journal::discard(&app, &id)?;
+
+        crate::events::project_changed(&app, Some(&id), ChangeKind::Deleted, true);
+    }
+    Ok(())
+}
+
+/// Changes one project-level field in place, when `change` says it changed
+/// anything. Only that field and `last_modified` change, and the editor is
+/// told (with `kind`) so an open copy can pick the change up.
+fn update_in_place(
+    app: &AppHandle,
+    id: &str,
+    kind: ChangeKind,
+    change: impl FnOnce(&mut Project) -> Result<bool, String>,
+) -> Result<(), String> {
+    let path = get_projects_dir(app)?.join(format!("{}.json", id));
+    if !path.exists() {
+        return Err(format!("Project {} not found", id));
+    }
+    let json = crate::encryption::read_to_string(&path).map_err(|e| e.to_string())?;
+    let mut project = crate::migrate::parse_project(&json).map_err(|e| e.to_string())?;
+    if !change(&mut project)? {
+        return Ok(());
+    }
+    project.last_modified = chrono::Local::now().to_rfc3339();
+
+    // Not remembered as the sync base: an editor holding the old value merges
+    // the change into its next save rather than overwriting it
+    let json = serde_json::to_string_pretty(&project).map_err(|e| e.to_string())?;
+    let json = crate::encryption::seal(json).map_err(|e| e.to_string())?;
+    app.state::<crate::watcher::ProjectWatcher>()
+        .write(&path, || crate::repair::write_atomic(&path, &json))?;
+    crate::index::invalidate(app, id)?;
+
+    crate::events::project_changed(app, Some(id), kind, true);
+    Ok(())
+}
+
+/// Renames a project in place. Only the name and `last_modified` change, and
+/// the editor gets the project's ID so an open copy can pick the name up
+/// (or merge it into its next save).
+#[tauri::command]
+pub fn rename_project(app: AppHandle, id: String, new_name: String) -> Result<(), String> {
+    let name = new_name.trim();
+    if name.is_empty() {
+        return Err("Project name is empty".to_string());
+    }
+    update_in_place(&app, &id, ChangeKind::Renamed, |project| {
+        if project.name == name {
+            return Ok(false);
+        }
+        project.name = name.to_string();
+        Ok(true)
+    })
+}
+
+/// Makes a task the project's current focus in listings and the widget, in
+/// place of the next one due; `None` goes back to the automatic choice.
+#[tauri::command]
+pub fn set_focus_task(
+    app: AppHandle,
+    project_id: String,
+    task_id: Option<String>,
+) -> Result<(), String> {
+    update_in_place(&app, &project_id, ChangeKind::Focused, |project| {
+        if let Some(id) = &task_id {
+            if !project.tasks.iter().any(|t| &t.id == id) {
+                return Err(format!("Task {} not found", id));
+            }
+        }
+        if project.focus_task_id == task_id {
+            return Ok(false);
+        }
+        project.focus_task_id = task_id;
+        Ok(true)
+    })
+}
 
-        // Emit update event
-        use tauri::Emitter;
-        let _ = app.emit("project-update", ());
+/// Marks a task done in place, for quick actions outside the editor.
+pub(crate) fn complete_task(
+    app: &AppHandle,
+    project_id: &str,
+    task_id: &str,
+) -> Result<(), String> {
+    update_in_place(app, project_id, ChangeKind::Completed, |project| {
+        let before = project.clone();
+        let now = chrono::Local::now().to_rfc3339();
+        let task = project
+            .tasks
+            .iter_mut()
+            .find(|t| t.id == task_id)
+            .ok_or_else(|| format!("Task {} not found", task_id))?;
+        if task.completed {
+            return Ok(false);
+        }
+        task.completed = true;
+        task.completed_at = Some(now.clone());
+        task.updated_at = Some(now.clone());
+        crate::activity::record_changes(Some(&before), project, &now);
+        Ok(true)
+    })
+}
+
+/// Changes one task in place, when `change` says it changed anything.
+pub(crate) fn update_task(
+    app: &AppHandle,
+    project_id: &str,
+    task_id: &str,
+    kind: ChangeKind,
+    change: impl FnOnce(&mut Task) -> bool,
+) -> Result<(), String> {
+    update_in_place(app, project_id, kind, |project| {
+        let task = project
+            .tasks
+            .iter_mut()
+            .find(|t| t.id == task_id)
+            .ok_or_else(|| format!("Task {} not found", task_id))?;
+        if !change(task) {
+            return Ok(false);
+        }
+        task.updated_at = Some(chrono::Local::now().to_rfc3339());
+        Ok(true)
+    })
+}
+
+/// Appends `task` to a project in place, anchored to `anchor` if given.
+pub(crate) fn add_task(
+    app: &AppHandle,
+    project_id: &str,
+    mut task: Task,
+    anchor: Option<String>,
+) -> Result<(), String> {
+    update_in_place(app, project_id, ChangeKind::TaskAdded, |project| {
+        let before = project.clone();
+        let now = chrono::Local::now().to_rfc3339();
+        task.updated_at = Some(now.clone());
+        if let Some(anchor) = anchor {
+            project.anchors.insert(task.id.clone(), anchor);
+        }
+        project.tasks.push(task);
+        crate::activity::record_changes(Some(&before), project, &now);
+        Ok(true)
+    })
+}
+
+/// Pins a project to the top of listings, or unpins it.
+#[tauri::command]
+pub fn set_project_pinned(app: AppHandle, id: String, pinned: bool) -> Result<(), String> {
+    let mut config = crate::config::load_config(app.clone())?;
+    config.pinned_projects.retain(|p| p != &id);
+    if pinned {
+        config.pinned_projects.push(id);
+    }
+    crate::config::save_config(app.clone(), config)?;
+
+    crate::events::project_changed(&app, None, ChangeKind::Reordered, true);
+    Ok(())
+}
+
+/// Sets the manual project order; projects not in `ids` follow, most
+/// recently modified first. Pinned projects stay on top in this order.
+#[tauri::command]
+pub fn reorder_projects(app: AppHandle, ids: Vec<String>) -> Result<(), String> {
+    let mut config = crate::config::load_config(app.clone())?;
+    config.project_order.clear();
+    for id in ids {
+        if !config.project_order.contains(&id) {
+            config.project_order.push(id);
+        }
     }
+    crate::config::save_config(app.clone(), config)?;
+
+    crate::events::project_changed(&app, None, ChangeKind::Reordered, true);
     Ok(())
 }
 
+/// Projects with a deadline, soonest first, split into the first and up to
+/// `runners_up` more.
+fn nearest_deadlines(projects: Vec<ProjectMetadata>, runners_up: usize) -> NextDeadline {
+    let mut due: Vec<(chrono::NaiveDateTime, ProjectMetadata)> = projects
+        .into_iter()
+        .filter_map(|p| Some((parse_date_or_datetime(p.next_deadline.as_deref()?)?, p)))
+        .collect();
+    due.sort_by_key(|(deadline, _)| *deadline);
+    let mut projects = due.into_iter().map(|(_, p)| p).take(runners_up + 1);
+    NextDeadline {
+        next: projects.next(),
+        runners_up: projects.collect(),
+    }
+}
+
+/// The project whose next deadline comes soonest, plus the `runners_up`
+/// (default 3) after it.
 #[tauri::command]
-pub fn get_next_deadline(app: AppHandle) -> Result<Option<ProjectMetadata>, String> {
-    let projects = list_projects(app)?;
-    // Return the first project since list_projects sorts by last_modified
-    Ok(projects.first().cloned())
+pub fn get_next_deadline(
+    app: AppHandle,
+    runners_up: Option<usize>,
+) -> Result<NextDeadline, String> {
+    let projects = dashboard(&app, None)?;
+    Ok(nearest_deadlines(projects, runners_up.unwrap_or(3)))
+}
+
+/// Widget project ID for the merged view of every project.
+pub const ALL_PROJECTS: &str = "all";
+
+/// A project's schedule as the widget shows it, where overdue work starts
+/// now rather than in the past, along with the schedule it was required to
+/// follow. Both come from the [`ScheduleCache`].
+fn widget_schedule(
+    app: &AppHandle,
+    id: &str,
+    now: chrono::NaiveDateTime,
+) -> Result<(Project, ScheduleResult, Vec<ScheduledTask>), String> {
+    let project = load_project(app.clone(), id.to_string())?;
+    let (required, schedule) = app
+        .state::<ScheduleCache>()
+        .schedule_from_now(&project, now)
+        .map_err(|e| e.to_string())?;
+    Ok((project, required, schedule))
+}
+
+/// Countdowns to a project's unfinished anchors (and the latest ends of
+/// anchor windows), soonest first. Progress is measured from the earliest
+/// scheduled start, or the project's creation when nothing is scheduled.
+fn anchor_countdowns(
+    project: &Project,
+    schedule: &[ScheduledTask],
+    now: chrono::NaiveDateTime,
+    label: Option<&ProjectMetadata>,
+) -> Vec<AnchorCountdown> {
+    let start = schedule
+        .iter()
+        .filter_map(|t| parse_date_or_datetime(&t.start_date))
+        .min()
+        .or_else(|| parse_date_or_datetime(project.created_at.get(..19)?))
+        .unwrap_or(now);
+    let mut countdowns: Vec<(chrono::NaiveDateTime, AnchorCountdown)> = project
+        .anchors
+        .iter()
+        .chain(project.anchor_windows.iter().map(|(id, w)| (id, &w.latest)))
+        .filter_map(|(id, date)| {
+            let task = project.tasks.iter().find(|t| &t.id == id && !t.completed)?;
+            let deadline = parse_date_or_datetime(date)?;
+            let remaining = deadline - now;
+            let span = (deadline - start).num_seconds().max(1) as f32;
+            let elapsed = (now - start).num_seconds() as f32 / span;
+            Some((
+                deadline,
+                AnchorCountdown {
+                    task_id: task.id.clone(),
+                    name: task.name.clone(),
+                    date: date.clone(),
+                    days_remaining: remaining.num_days(),
+                    hours_remaining: remaining.num_hours(),
+                    elapsed_percent: (elapsed * 100.0).clamp(0.0, 100.0),
+                    project_id: label.map(|p| p.id.clone()),
+                    project_name: label.map(|p| p.name.clone()),
+                },
+            ))
+        })
+        .collect();
+    countdowns.sort_by_key(|(deadline, _)| *deadline);
+    countdowns.into_iter().map(|(_, c)| c).collect()
+}
+
+fn widget_task(
+    task: &ScheduledTask,
+    start: chrono::NaiveDateTime,
+    end: chrono::NaiveDateTime,
+    now: chrono::NaiveDateTime,
+    project: Option<&ProjectMetadata>,
+    subtask_progress: Option<f32>,
+) -> WidgetTask {
+    let status = if end < now {
+        "overdue".to_string()
+    } else if start <= now && end >= now {
+        "active".to_string()
+    } else {
+        "future".to_string()
+    };
+    WidgetTask {
+        id: task.id.clone(),
+        name: task.name.clone(),
+        start_date: task.start_date.clone(),
+        end_date: task.end_date.clone(),
+        completed: task.completed,
+        is_milestone: task.is_milestone,
+        status,
+        tags: task.tags.clone(),
+        project_id: project.map(|p| p.id.clone()),
+        project_name: project.map(|p| p.name.clone()),
+        project_color: project.and_then(|p| p.color.clone()),
+        subtask_progress,
+        days_overdue: None,
+    }
+}
+
+/// Unfinished tasks whose required finish has passed, most overdue first.
+/// `required` is the schedule as planned back from the anchors, before
+/// overdue work is moved to start now.
+fn overdue_tasks(
+    required: &[ScheduledTask],
+    now: chrono::NaiveDateTime,
+    project: Option<&ProjectMetadata>,
+    subtasks: &HashMap<String, f32>,
+) -> Vec<(chrono::NaiveDateTime, WidgetTask)> {
+    required
+        .iter()
+        .filter(|t| !t.completed)
+        .filter_map(|t| {
+            let start =
+                chrono::NaiveDateTime::parse_from_str(&t.start_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+            let end =
+                chrono::NaiveDateTime::parse_from_str(&t.end_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+            (end < now).then(|| {
+                let mut task =
+                    widget_task(t, start, end, now, project, subtasks.get(&t.id).copied());
+                task.days_overdue = Some((now - end).num_days());
+                (end, task)
+            })
+        })
+        .collect()
+}
+
+/// Unfinished tasks ending now or later, soonest start first, and the task
+/// in progress (or next up, or the chosen `focus` while unfinished) with how
+/// far along it is: its share of subtasks
+/// done, from `subtasks` by task ID, or else of its time passed. Tasks are
+/// labelled with their project when one is given.
+fn widget_tasks(
+    schedule: &[(ScheduledTask, Option<&ProjectMetadata>)],
+    subtasks: &HashMap<String, f32>,
+    focus: Option<&str>,
+    now: chrono::NaiveDateTime,
+) -> (Vec<WidgetTask>, Option<WidgetTask>, Option<f32>) {
+    let mut dated = schedule
+        .iter()
+        .filter_map(|(t, project)| {
+            let start =
+                chrono::NaiveDateTime::parse_from_str(&t.start_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+            let end =
+                chrono::NaiveDateTime::parse_from_str(&t.end_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+
+            Some((start, end, t, *project))
+        })
+        .collect::<Vec<_>>();
+
+    // "Up Next": only tasks that end now or in the future
+    dated.sort_by(|a, b| a.2.start_date.cmp(&b.2.start_date));
+    let upcoming_tasks = dated
+        .iter()
+        .filter(|(_, end, t, _)| !t.completed && *end >= now)
+        .map(|(start, end, t, project)| {
+            widget_task(t, *start, *end, now, *project, subtasks.get(&t.id).copied())
+        })
+        .collect();
+
+    // The task in progress, or else the next to start, for the focus view
+    dated.sort_by_key(|(_, end, _, _)| *end);
+    let chosen = dated
+        .iter()
+        .find(|(_, _, t, _)| !t.completed && Some(t.id.as_str()) == focus);
+    let target = chosen.or_else(|| {
+        dated
+            .iter()
+            .find(|(_, end, t, _)| !t.completed && now <= *end)
+    });
+
+    match target {
+        Some((start, end, task, project)) => {
+            let active = widget_task(
+                task,
+                *start,
+                *end,
+                now,
+                *project,
+                subtasks.get(&task.id).copied(),
+            );
+            let progress = active.subtask_progress.unwrap_or_else(|| {
+                let total_seconds = (*end - *start).num_seconds().max(1) as f32;
+                let elapsed = (now - *start).num_seconds().max(0) as f32;
+                (elapsed / total_seconds).clamp(0.0f32, 1.0f32)
+            });
+            (upcoming_tasks, Some(active), Some(progress))
+        }
+        // Maybe all tasks are completed? Check if there's ANY task
+        None if !schedule.is_empty() && schedule.iter().all(|(t, _)| t.completed) => {
+            (upcoming_tasks, None, Some(1.0f32)) // Project done
+        }
+        None => (upcoming_tasks, None, Some(0.0f32)), // Start of project
+    }
 }
 
+/// Applies the widget settings to upcoming tasks, giving the "Up Next" list
+/// and the month view's tasks.
+fn limit_widget_tasks(
+    tasks: Vec<WidgetTask>,
+    settings: &WidgetSettings,
+    now: chrono::NaiveDateTime,
+) -> (Vec<WidgetTask>, Vec<WidgetTask>) {
+    let starts_within = |task: &WidgetTask, days: Option<u32>| {
+        let Some(days) = days else {
+            return true;
+        };
+        chrono::NaiveDateTime::parse_from_str(&task.start_date, "%Y-%m-%dT%H:%M:%S")
+            .map_or(true, |start| {
+                start <= now + chrono::Duration::days(days as i64)
+            })
+    };
+    let calendar_tasks: Vec<WidgetTask> = tasks
+        .into_iter()
+        .filter(|t| t.is_milestone || !settings.milestones_only)
+        .filter(|t| starts_within(t, settings.lookahead_days))
+        .collect();
+    let upcoming_tasks = calendar_tasks
+        .iter()
+        .filter(|t| t.status != "future" || starts_within(t, settings.hide_future_after_days))
+        .take(settings.max_tasks)
+        .cloned()
+        .collect();
+    (upcoming_tasks, calendar_tasks)
+}
+
+/// Widget info for one project, or for every project merged when
+/// `project_id` is [`ALL_PROJECTS`].
 #[tauri::command]
 pub fn get_widget_info(
     app: AppHandle,
     project_id: Option<String>,
 ) -> Result<Option<WidgetInfo>, String> {
     // 1. Get all projects
-    let projects = list_projects(app.clone())?;
+    let projects = dashboard(&app, None)?;
+    let all = project_id.as_deref() == Some(ALL_PROJECTS);
 
     // 2. Determine target project
     let target_metadata = if let Some(id) = project_id {
@@ -313,154 +1049,437 @@ pub fn get_widget_info(
         }
     };
 
-    // 3. Prepare summary list for switching
-    let all_projects = projects
+    // 3. Prepare summary list for switching, with the merged view first
+    let mut all_projects: Vec<ProjectSummary> = projects
         .iter()
         .map(|p| ProjectSummary {
             id: p.id.clone(),
             name: p.name.clone(),
+            color: p.color.clone(),
+            icon: p.icon.clone(),
         })
         .collect();
+    if projects.len() > 1 {
+        all_projects.insert(
+            0,
+            ProjectSummary {
+                id: ALL_PROJECTS.to_string(),
+                name: "All projects".to_string(),
+                color: None,
+                icon: None,
+            },
+        );
+    }
 
-    // 4. Load full project for scheduling
-    let project = load_project(app, metadata.id.clone())?;
-
-    // 5. Calculate schedule
-    let req = crate::scheduler::ScheduleRequest {
-        tasks: project.tasks.clone(),
-        anchors: project.anchors.clone(),
+    // 4. Schedule the project, or every project with its tasks labelled
+    let now = chrono::Local::now().naive_local();
+    let targets: Vec<(&ProjectMetadata, Option<&ProjectMetadata>)> = if all {
+        projects.iter().map(|p| (p, Some(p))).collect()
+    } else {
+        vec![(&metadata, None)]
     };
+    let mut schedule: Vec<(ScheduledTask, Option<&ProjectMetadata>)> = Vec::new();
+    let mut countdowns = Vec::new();
+    let mut subtasks = HashMap::new();
+    let mut overdue = Vec::new();
+    let mut all_tasks = Vec::new();
+    // Only a single project's chosen focus applies
+    let mut focus = None;
+    for (target, label) in targets {
+        match widget_schedule(&app, &target.id, now) {
+            Ok((project, required, tasks)) => {
+                countdowns.extend(anchor_countdowns(&project, &tasks, now, label));
+                if !all {
+                    focus = project.focus_task_id.clone();
+                }
+                subtasks.extend(
+                    project.tasks.iter().filter_map(|t| {
+                        Some((t.id.clone(), crate::forecast::subtask_progress(t)?))
+                    }),
+                );
+                overdue.extend(overdue_tasks(
+                    &required.scheduled_tasks,
+                    now,
+                    label,
+                    &subtasks,
+                ));
+                if all {
+                    let history = crate::archive::with_history(&app, &project);
+                    all_tasks.extend(history.unwrap_or(project).tasks);
+                }
+                schedule.extend(tasks.into_iter().map(|t| (t, label)));
+            }
+            // One unschedulable project shouldn't blank the merged view
+            Err(e) if all => eprintln!("Skipping {} in the widget: {}", target.name, e),
+            Err(e) => return Err(e),
+        }
+    }
+    // Merged countdowns are ordered by time left
+    countdowns.sort_by_key(|c| c.hours_remaining);
+    overdue.sort_by(|a, b| (a.0, &a.1.name).cmp(&(b.0, &b.1.name)));
+    let overdue_tasks: Vec<WidgetTask> = overdue.into_iter().map(|(_, t)| t).collect();
 
-    let schedule =
-        crate::scheduler::calculate_backwards_schedule(req).map_err(|e| e.to_string())?;
+    // 5. "Up Next" list and the active/next task's progress
+    let (tasks, active_task, task_progress) =
+        widget_tasks(&schedule, &subtasks, focus.as_deref(), now);
+    let settings = crate::config::load_config(app.clone())?.widget;
+    let (top_tasks, calendar_tasks) = limit_widget_tasks(tasks, &settings, now);
+    let calendar_weeks = group_by_week(calendar_tasks.iter().map(|t| {
+        (
+            t.id.as_str(),
+            t.start_date.as_str(),
+            t.end_date.as_str(),
+            t.is_milestone,
+        )
+    }));
 
-    let now = chrono::Local::now().naive_local();
+    if all {
+        // Deadline and status come from whichever project is due first
+        let soonest = nearest_deadlines(projects.clone(), 0).next;
+        let soonest = soonest.as_ref().unwrap_or(&metadata);
+        return Ok(Some(WidgetInfo {
+            project_id: ALL_PROJECTS.to_string(),
+            project_name: "All projects".to_string(),
+            project_color: None,
+            project_icon: None,
+            project_description: None,
+            next_deadline: soonest.next_deadline.clone(),
+            status: soonest.status.clone(),
+            current_focus: active_task.as_ref().map(|t| t.name.clone()),
+            upcoming_tasks: top_tasks,
+            calendar_tasks,
+            calendar_weeks,
+            all_projects,
+            task_progress,
+            active_task,
+            forecast: None,
+            countdowns,
+            overdue_tasks,
+            project_completion: crate::forecast::completion_percent(&all_tasks),
+        }));
+    }
+
+    Ok(Some(WidgetInfo {
+        project_id: metadata.id.clone(),
+        project_name: metadata.name.clone(),
+        project_color: metadata.color.clone(),
+        project_icon: metadata.icon.clone(),
+        project_description: metadata.description.clone(),
+        next_deadline: metadata.next_deadline.clone(),
+        status: metadata.status.clone(),
+        current_focus: metadata.current_focus.clone(),
+        upcoming_tasks: top_tasks,
+        calendar_tasks,
+        calendar_weeks,
+        all_projects,
+        task_progress,
+        active_task,
+        forecast: metadata.forecast.clone(),
+        countdowns,
+        overdue_tasks,
+        project_completion: metadata.completion,
+    }))
+}
 
-    // 6. Process tasks for "Up Next" list
-    let mut upcoming_tasks = Vec::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Filter and sort tasks
-    let mut sorted_tasks = schedule.clone();
-    sorted_tasks.sort_by(|a, b| a.start_date.cmp(&b.start_date)); // Sort by start date
+    #[test]
+    fn test_dashboard_metadata_comes_from_the_cached_schedule() {
+        let task = |id: &str, start: &str, end: &str| ScheduledTask {
+            id: id.into(),
+            name: id.to_uppercase(),
+            start_date: start.into(),
+            end_date: end.into(),
+            ..Default::default()
+        };
+        let mut entry = IndexEntry {
+            id: "p".into(),
+            name: "Thesis".into(),
+            color: Some("#3b82f6".into()),
+            icon: Some("🎓".into()),
+            description: None,
+            created_at: String::new(),
+            last_modified: String::new(),
+            task_count: 2,
+            deadlines: vec!["2026-03-31".into()],
+            upcoming: Some(vec![
+                task("draft", "2026-03-09T09:00:00", "2026-03-11T17:00:00"),
+                task("submit", "2026-03-30T09:00:00", "2026-03-31T17:00:00"),
+            ]),
+            forecast: None,
+            on_time: Vec::new(),
+            completion: Some(25.0),
+            focus_task_id: None,
+            reminder_lead_minutes: None,
+            task_reminders: HashMap::new(),
+            indexed_on: String::new(),
+            file_modified: 0,
+        };
+        let now = chrono::NaiveDateTime::parse_from_str("2026-03-10T12:00:00", "%Y-%m-%dT%H:%M:%S")
+            .unwrap();
 
-    for task in sorted_tasks {
-        if task.completed {
-            continue;
-        }
+        let metadata = project_metadata(&entry, now);
+        assert_eq!(
+            metadata.next_deadline.as_deref(),
+            Some("2026-03-11T17:00:00")
+        );
+        assert_eq!(metadata.current_focus.as_deref(), Some("DRAFT"));
+        entry.focus_task_id = Some("submit".into());
+        assert_eq!(
+            project_metadata(&entry, now).current_focus.as_deref(),
+            Some("SUBMIT (starts in 19 days)")
+        );
+        entry.focus_task_id = None;
+        assert_eq!(metadata.status, "urgent");
+        assert_eq!(metadata.color.as_deref(), Some("#3b82f6"));
+        assert_eq!(metadata.icon.as_deref(), Some("🎓"));
 
-        if let (Ok(start), Ok(end)) = (
-            chrono::NaiveDateTime::parse_from_str(&task.start_date, "%Y-%m-%dT%H:%M:%S"),
-            chrono::NaiveDateTime::parse_from_str(&task.end_date, "%Y-%m-%dT%H:%M:%S"),
-        ) {
-            // Only show tasks that end now or in the future
-            if end >= now {
-                let status = if end < now {
-                    "overdue".to_string()
-                } else if start <= now && end >= now {
-                    "active".to_string()
-                } else {
-                    "future".to_string()
-                };
+        entry.upcoming = Some(Vec::new());
+        let metadata = project_metadata(&entry, now);
+        assert_eq!(
+            metadata.next_deadline.as_deref(),
+            Some("2026-03-31T23:59:59")
+        );
+        assert_eq!(
+            metadata.current_focus.as_deref(),
+            Some("All tasks completed")
+        );
+        assert_eq!(metadata.status, "on_track");
+    }
 
-                upcoming_tasks.push(WidgetTask {
-                    id: task.id,
-                    name: task.name,
-                    start_date: task.start_date,
-                    end_date: task.end_date,
-                    completed: task.completed,
-                    is_milestone: task.is_milestone,
-                    status,
-                });
-            }
-        }
+    #[test]
+    fn test_pinned_then_manual_order_then_recent() {
+        let mut projects = vec![
+            ("old", false, "2026-01-01"),
+            ("recent", false, "2026-03-01"),
+            ("ordered", false, "2026-02-01"),
+            ("pinned", true, "2025-12-01"),
+            ("first", false, "2025-11-01"),
+        ];
+        let order = vec!["first".to_string(), "ordered".to_string()];
+        arrange(&mut projects, &order, |p| (p.0, p.1, p.2));
+        let ids: Vec<_> = projects.iter().map(|p| p.0).collect();
+        assert_eq!(ids, vec!["pinned", "first", "ordered", "recent", "old"]);
     }
 
-    let calendar_tasks = upcoming_tasks.clone();
-    let top_tasks = upcoming_tasks.into_iter().take(5).collect();
+    #[test]
+    fn test_nearest_deadline_across_projects() {
+        let project = |id: &str, deadline: Option<&str>| ProjectMetadata {
+            id: id.into(),
+            next_deadline: deadline.map(String::from),
+            ..Default::default()
+        };
+        // Listed most recently modified first, as the dashboard may return them
+        let projects = vec![
+            project("edited", Some("2026-04-01T17:00:00")),
+            project("none", None),
+            project("soonest", Some("2026-03-12T09:00:00")),
+            project("next", Some("2026-03-20T17:00:00")),
+        ];
+        let result = nearest_deadlines(projects.clone(), 1);
+        assert_eq!(result.next.unwrap().id, "soonest");
+        let runners_up: Vec<_> = result.runners_up.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(runners_up, vec!["next"]);
 
-    // Calculate Task Progress for the active/next task
+        assert!(nearest_deadlines(vec![projects[1].clone()], 3)
+            .next
+            .is_none());
+    }
 
-    // logic from list_projects reused partly here to find the "current" task for progress
-    // We need to re-find the "active" task from the full schedule
-    let mut active_or_next = schedule
-        .iter()
-        .filter_map(|t| {
-            let start =
-                chrono::NaiveDateTime::parse_from_str(&t.start_date, "%Y-%m-%dT%H:%M:%S").ok()?;
-            let end =
-                chrono::NaiveDateTime::parse_from_str(&t.end_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+    #[test]
+    fn test_widget_tasks_merge_projects() {
+        let project = |id: &str, name: &str| ProjectMetadata {
+            id: id.into(),
+            name: name.into(),
+            ..Default::default()
+        };
+        let (thesis, house) = (project("p1", "Thesis"), project("p2", "House"));
+        let task = |id: &str, start: &str, end: &str, completed: bool| ScheduledTask {
+            id: id.into(),
+            name: id.into(),
+            start_date: start.into(),
+            end_date: end.into(),
+            completed,
+            ..Default::default()
+        };
+        let schedule = vec![
+            (
+                task("write", "2026-03-10T09:00:00", "2026-03-12T17:00:00", false),
+                Some(&thesis),
+            ),
+            (
+                task("read", "2026-03-02T09:00:00", "2026-03-06T17:00:00", true),
+                Some(&thesis),
+            ),
+            (
+                task("paint", "2026-03-09T09:00:00", "2026-03-10T17:00:00", false),
+                Some(&house),
+            ),
+        ];
+        let now = chrono::NaiveDateTime::parse_from_str("2026-03-10T13:00:00", "%Y-%m-%dT%H:%M:%S")
+            .unwrap();
 
-            Some((start, end, t))
-        })
-        .collect::<Vec<_>>();
-    active_or_next.sort_by_key(|(_, end, _)| *end);
-
-    // Find the task that matches "current_focus" name if possible, or just the first non-completed
-    let target_task_tuple = active_or_next.iter().find(|(start, end, t)| {
-        if t.completed {
-            return false;
-        } // prioritized uncompleted
-          // If now is in range, this is definitely it
-        if now >= *start && now <= *end {
-            return true;
-        }
-        // If now is before start, this is the upcoming one
-        if now < *start {
-            return true;
-        }
-        false
-    });
+        let (upcoming, active, progress) = widget_tasks(&schedule, &HashMap::new(), None, now);
+        let labels: Vec<_> = upcoming
+            .iter()
+            .map(|t| (t.id.as_str(), t.project_name.as_deref(), t.status.as_str()))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("paint", Some("House"), "active"),
+                ("write", Some("Thesis"), "active")
+            ]
+        );
+        // Ends first, so it's the focus
+        let active = active.unwrap();
+        assert_eq!(active.id, "paint");
+        assert_eq!(active.project_id.as_deref(), Some("p2"));
+        assert!(progress.unwrap() > 0.5);
 
-    let mut active_task = None;
+        // With subtasks, progress is their share done
+        let subtasks = HashMap::from([("paint".to_string(), 0.25)]);
+        let (_, active, progress) = widget_tasks(&schedule, &subtasks, None, now);
+        assert_eq!(active.unwrap().subtask_progress, Some(0.25));
+        assert_eq!(progress, Some(0.25));
 
-    let task_progress = if let Some((start, end, task)) = target_task_tuple {
-        // Construct WidgetTask for the active task
-        let status = if *end < now {
-            "overdue".to_string()
-        } else if *start <= now && *end >= now {
-            "active".to_string()
-        } else {
-            "future".to_string()
+        // A chosen focus overrides the next task due
+        let (_, active, _) = widget_tasks(&schedule, &subtasks, Some("write"), now);
+        assert_eq!(active.unwrap().id, "write");
+        let (_, active, _) = widget_tasks(&schedule, &subtasks, Some("read"), now);
+        assert_eq!(active.unwrap().id, "paint");
+
+        let done = vec![(schedule[1].0.clone(), None)];
+        let (upcoming, active, progress) = widget_tasks(&done, &HashMap::new(), None, now);
+        assert!(upcoming.is_empty() && active.is_none());
+        assert_eq!(progress, Some(1.0));
+    }
+
+    #[test]
+    fn test_widget_settings_limit_tasks() {
+        let task = |id: &str, start: &str, status: &str, is_milestone: bool| WidgetTask {
+            id: id.into(),
+            name: id.into(),
+            start_date: start.into(),
+            end_date: start.into(),
+            completed: false,
+            is_milestone,
+            status: status.into(),
+            tags: Vec::new(),
+            project_id: None,
+            project_name: None,
+            project_color: None,
+            subtask_progress: None,
+            days_overdue: None,
         };
+        let tasks = vec![
+            task("late", "2026-03-01T09:00:00", "overdue", false),
+            task("soon", "2026-03-12T09:00:00", "future", true),
+            task("later", "2026-03-25T09:00:00", "future", false),
+            task("far", "2026-06-01T09:00:00", "future", true),
+        ];
+        let now = chrono::NaiveDateTime::parse_from_str("2026-03-10T12:00:00", "%Y-%m-%dT%H:%M:%S")
+            .unwrap();
+        let ids = |tasks: &[WidgetTask]| tasks.iter().map(|t| t.id.clone()).collect::<Vec<_>>();
 
-        active_task = Some(WidgetTask {
-            id: task.id.clone(),
-            name: task.name.clone(),
-            start_date: task.start_date.clone(),
-            end_date: task.end_date.clone(),
-            completed: task.completed,
-            is_milestone: task.is_milestone,
-            status,
-        });
+        let (upcoming, calendar) =
+            limit_widget_tasks(tasks.clone(), &WidgetSettings::default(), now);
+        assert_eq!(ids(&upcoming), ids(&calendar));
+        assert_eq!(calendar.len(), 4);
 
-        if task.completed {
-            Some(1.0f32)
-        } else {
-            let total_seconds = (*end - *start).num_seconds().max(1) as f32;
-            let elapsed = (now - *start).num_seconds().max(0) as f32;
+        let settings = WidgetSettings {
+            max_tasks: 2,
+            lookahead_days: Some(30),
+            hide_future_after_days: Some(7),
+            ..Default::default()
+        };
+        let (upcoming, calendar) = limit_widget_tasks(tasks.clone(), &settings, now);
+        assert_eq!(ids(&upcoming), vec!["late", "soon"]);
+        assert_eq!(ids(&calendar), vec!["late", "soon", "later"]);
 
-            let p = elapsed / total_seconds;
-            Some(p.clamp(0.0f32, 1.0f32))
-        }
-    } else {
-        // Maybe all tasks are completed? Check if there's ANY task
-        if !schedule.is_empty() && schedule.iter().all(|t| t.completed) {
-            Some(1.0f32) // Project done
-        } else {
-            Some(0.0f32) // Start of project
-        }
-    };
+        let settings = WidgetSettings {
+            milestones_only: true,
+            ..Default::default()
+        };
+        let (upcoming, _) = limit_widget_tasks(tasks, &settings, now);
+        assert_eq!(ids(&upcoming), vec!["soon", "far"]);
+    }
 
-    Ok(Some(WidgetInfo {
-        project_id: metadata.id.clone(),
-        project_name: metadata.name.clone(),
-        next_deadline: metadata.next_deadline.clone(),
-        status: metadata.status.clone(),
-        current_focus: metadata.current_focus.clone(),
-        upcoming_tasks: top_tasks,
-        calendar_tasks,
-        all_projects,
-        task_progress,
-        active_task,
-    }))
+    #[test]
+    fn test_anchor_countdowns() {
+        let task = |id: &str, completed: bool| Task {
+            id: id.into(),
+            name: id.to_uppercase(),
+            completed,
+            ..Default::default()
+        };
+        let project = Project {
+            tasks: vec![
+                task("launch", false),
+                task("beta", false),
+                task("done", true),
+            ],
+            anchors: HashMap::from([
+                ("launch".to_string(), "2026-03-31".to_string()),
+                ("beta".to_string(), "2026-03-15T09:00:00".to_string()),
+                ("done".to_string(), "2026-03-12".to_string()),
+            ]),
+            created_at: "2026-01-01T00:00:00".into(),
+            ..Default::default()
+        };
+        let schedule = vec![ScheduledTask {
+            start_date: "2026-03-05T09:00:00".into(),
+            ..Default::default()
+        }];
+        let now = chrono::NaiveDateTime::parse_from_str("2026-03-10T09:00:00", "%Y-%m-%dT%H:%M:%S")
+            .unwrap();
+
+        let countdowns = anchor_countdowns(&project, &schedule, now, None);
+        let [beta, launch] = &countdowns[..] else {
+            panic!("expected two unfinished anchors");
+        };
+        assert_eq!(beta.name, "BETA");
+        assert_eq!((beta.days_remaining, beta.hours_remaining), (5, 120));
+        assert_eq!(beta.elapsed_percent, 50.0);
+        // Due at the end of the day
+        assert_eq!((launch.days_remaining, launch.hours_remaining), (21, 518));
+        assert!(launch.project_id.is_none());
+
+        // Nothing scheduled: measured from creation
+        let countdowns = anchor_countdowns(&project, &[], now, None);
+        assert!(countdowns[0].elapsed_percent > 90.0);
+    }
+
+    #[test]
+    fn test_overdue_tasks() {
+        let task = |id: &str, end: &str, completed: bool| ScheduledTask {
+            id: id.into(),
+            name: id.into(),
+            start_date: "2026-03-01T09:00:00".into(),
+            end_date: end.into(),
+            completed,
+            ..Default::default()
+        };
+        let required = vec![
+            task("late", "2026-03-07T17:00:00", false),
+            task("later", "2026-03-03T17:00:00", false),
+            task("done", "2026-03-02T17:00:00", true),
+            task("ahead", "2026-03-20T17:00:00", false),
+        ];
+        let now = chrono::NaiveDateTime::parse_from_str("2026-03-10T12:00:00", "%Y-%m-%dT%H:%M:%S")
+            .unwrap();
+
+        let mut overdue = overdue_tasks(&required, now, None, &HashMap::new());
+        overdue.sort_by_key(|(end, _)| *end);
+        let days: Vec<_> = overdue
+            .iter()
+            .map(|(_, t)| (t.id.as_str(), t.status.as_str(), t.days_overdue))
+            .collect();
+        assert_eq!(
+            days,
+            vec![("later", "overdue", Some(6)), ("late", "overdue", Some(2))]
+        );
+    }
 }
@@ -0,0 +1,192 @@
+//! Filtered task queries, e.g. "only #writing tasks due this week".
+
+use crate::cache::ScheduleCache;
+use crate::project::load_project;
+use crate::scheduler::ScheduledTask;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+/// Where a task stands relative to now, as shown in the widget.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Active,
+    Future,
+    Overdue,
+    Completed,
+}
+
+impl TaskStatus {
+    pub(crate) fn of(
+        task: &ScheduledTask,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        now: NaiveDateTime,
+    ) -> Self {
+        if task.completed {
+            TaskStatus::Completed
+        } else if end < now {
+            TaskStatus::Overdue
+        } else if start <= now {
+            TaskStatus::Active
+        } else {
+            TaskStatus::Future
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TaskFilter {
+    /// Tasks carrying all of these tags. Case-insensitive; a leading `#` is ignored.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Tasks in any of these states; all states when empty.
+    #[serde(default)]
+    pub status: Vec<TaskStatus>,
+    /// Tasks due at or after this (ISO 8601 DateTime, or YYYY-MM-DD for the
+    /// start of that day).
+    #[serde(default)]
+    pub due_from: Option<String>,
+    /// Tasks due at or before this (ISO 8601 DateTime, or YYYY-MM-DD for the
+    /// end of that day).
+    #[serde(default)]
+    pub due_to: Option<String>,
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+fn parse_bound(s: &str, end_of_day: bool) -> Result<NaiveDateTime, String> {
+    if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S") {
+        return Ok(dt);
+    }
+    let date =
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|_| format!("Invalid date: {}", s))?;
+    let time = if end_of_day { (23, 59, 59) } else { (0, 0, 0) };
+    Ok(date.and_hms_opt(time.0, time.1, time.2).unwrap_or_default())
+}
+
+/// The tasks in `schedule` matching `filter`, ordered by start.
+pub fn filter_tasks(
+    schedule: &[ScheduledTask],
+    filter: &TaskFilter,
+    now: NaiveDateTime,
+) -> Result<Vec<ScheduledTask>, String> {
+    let from = filter
+        .due_from
+        .as_deref()
+        .map(|s| parse_bound(s, false))
+        .transpose()?;
+    let to = filter
+        .due_to
+        .as_deref()
+        .map(|s| parse_bound(s, true))
+        .transpose()?;
+    let tags: Vec<String> = filter.tags.iter().map(|t| normalize_tag(t)).collect();
+
+    let mut matches: Vec<ScheduledTask> = schedule
+        .iter()
+        .filter(|task| {
+            let Ok(start) = NaiveDateTime::parse_from_str(&task.start_date, "%Y-%m-%dT%H:%M:%S")
+            else {
+                return false;
+            };
+            let Ok(end) = NaiveDateTime::parse_from_str(&task.end_date, "%Y-%m-%dT%H:%M:%S") else {
+                return false;
+            };
+            let task_tags: Vec<String> = task.tags.iter().map(|t| normalize_tag(t)).collect();
+            tags.iter().all(|tag| task_tags.contains(tag))
+                && (filter.status.is_empty()
+                    || filter
+                        .status
+                        .contains(&TaskStatus::of(task, start, end, now)))
+                && from.is_none_or(|from| end >= from)
+                && to.is_none_or(|to| end <= to)
+        })
+        .cloned()
+        .collect();
+    matches.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+    Ok(matches)
+}
+
+/// A project's scheduled tasks matching `filter`, ordered by start.
+#[tauri::command]
+pub fn query_tasks(
+    app: AppHandle,
+    project_id: String,
+    filter: Option<TaskFilter>,
+) -> Result<Vec<ScheduledTask>, String> {
+    let project = load_project(app.clone(), project_id)?;
+    let now = chrono::Local::now().naive_local();
+    let result = app
+        .state::<ScheduleCache>()
+        .schedule(&project, now)
+        .map_err(|e| e.to_string())?;
+    filter_tasks(&result.scheduled_tasks, &filter.unwrap_or_default(), now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filters_by_tag_status_and_due_date() {
+        let rows = [
+            ("late", "03-02", "03-06", &["Writing"][..], false),
+            ("now", "03-09", "03-11", &["writing", "review"], false),
+            ("next", "03-12", "03-13", &["writing"], false),
+            ("later", "03-16", "03-20", &["writing"], false),
+            ("done", "03-09", "03-10", &["writing"], true),
+            ("other", "03-09", "03-12", &[], false),
+        ];
+        let schedule: Vec<ScheduledTask> = rows
+            .iter()
+            .map(|(id, start, end, tags, completed)| ScheduledTask {
+                id: id.to_string(),
+                start_date: format!("2026-{}T09:00:00", start),
+                end_date: format!("2026-{}T17:00:00", end),
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                completed: *completed,
+                ..Default::default()
+            })
+            .collect();
+        let now =
+            NaiveDateTime::parse_from_str("2026-03-10T12:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        let ids = |filter: TaskFilter| -> Vec<String> {
+            filter_tasks(&schedule, &filter, now)
+                .unwrap()
+                .into_iter()
+                .map(|t| t.id)
+                .collect()
+        };
+
+        // "#writing tasks due this week" that still need doing
+        let this_week = TaskFilter {
+            tags: vec!["#writing".into()],
+            status: vec![TaskStatus::Active, TaskStatus::Future, TaskStatus::Overdue],
+            due_from: Some("2026-03-09".into()),
+            due_to: Some("2026-03-15".into()),
+        };
+        assert_eq!(ids(this_week), vec!["now", "next"]);
+
+        let overdue = TaskFilter {
+            status: vec![TaskStatus::Overdue],
+            ..Default::default()
+        };
+        assert_eq!(ids(overdue), vec!["late"]);
+        let both_tags = TaskFilter {
+            tags: vec!["writing".into(), "REVIEW".into()],
+            ..Default::default()
+        };
+        assert_eq!(ids(both_tags), vec!["now"]);
+        assert_eq!(ids(TaskFilter::default()).len(), 6);
+
+        let bad = TaskFilter {
+            due_to: Some("next week".into()),
+            ..Default::default()
+        };
+        assert!(filter_tasks(&schedule, &bad, now).is_err());
+    }
+}
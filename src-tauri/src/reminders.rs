@@ -0,0 +1,489 @@
+//! Task reminders.
+//!
+//! A background check once a minute against each project's cached schedule
+//! (the project index's unfinished tasks). By default a task is reminded of
+//! when its scheduled start arrives and `deadline_lead_minutes` before it has
+//! to finish; a task's own [`TaskReminder`]s replace those. Reminders go
+//! through the project's notification routing, and can be turned off per
+//! project in its [`NotificationSettings`].
+//!
+//! The time of the last check is kept in `<app data>/reminders.json`, so
+//! reminders that fell due while the app was closed are sent at launch, as
+//! long as what they remind of is still ahead. Reminders that went off are
+//! kept there too, as [`FiredReminder`]s the user can snooze or dismiss;
+//! each is also sent to the frontend as a `reminder` event.
+//!
+//! [`NotificationSettings`]: crate::notifications::NotificationSettings
+
+use crate::notifications::{dispatch, Notification};
+use crate::project::load_project;
+use crate::scheduler::{ReminderKind, ScheduledTask, TaskReminder};
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How late a reminder for something already started or due is still
+/// sent, so waking from sleep doesn't replay everything missed overnight.
+const MAX_CATCH_UP_MINUTES: i64 = 5;
+
+/// Days fired and dismissed reminders are remembered for.
+const KEEP_DAYS: i64 = 7;
+
+/// Reminders are held back until then, see [`snooze`].
+static SNOOZED_UNTIL: Mutex<Option<NaiveDateTime>> = Mutex::new(None);
+
+/// Serializes changes to the state file between the check and commands.
+static STATE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone)]
+pub struct Reminder {
+    pub task_id: String,
+    pub kind: ReminderKind,
+    pub minutes_before: i64,
+    /// When it falls due.
+    pub at: NaiveDateTime,
+    pub notification: Notification,
+}
+
+/// A reminder that went off, kept so it can be snoozed or dismissed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FiredReminder {
+    /// Names this occurrence: project, task, what it counts back from and
+    /// how far, and when it fell due.
+    pub id: String,
+    pub project_id: String,
+    pub task_id: String,
+    /// When it fell due.
+    pub at: String,
+    pub title: String,
+    pub body: String,
+    /// Goes off again then.
+    #[serde(default)]
+    pub snoozed_until: Option<String>,
+}
+
+impl FiredReminder {
+    fn new(project_id: &str, reminder: Reminder) -> Self {
+        let at = reminder.at.format("%Y-%m-%dT%H:%M:%S").to_string();
+        Self {
+            id: format!(
+                "{}:{}:{:?}:{}@{}",
+                project_id, reminder.task_id, reminder.kind, reminder.minutes_before, at
+            ),
+            project_id: project_id.to_string(),
+            task_id: reminder.task_id,
+            at,
+            title: reminder.notification.title,
+            body: reminder.notification.body,
+            snoozed_until: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct ReminderState {
+    /// Reminders up to here have been sent.
+    checked_until: Option<String>,
+    #[serde(default)]
+    fired: Vec<FiredReminder>,
+    /// IDs of dismissed reminders, never sent again.
+    #[serde(default)]
+    dismissed: Vec<String>,
+}
+
+fn state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join("reminders.json"))
+}
+
+/// Runs `f` on the stored state and saves it.
+fn with_state<R>(app: &AppHandle, f: impl FnOnce(&mut ReminderState) -> R) -> Result<R, String> {
+    let _guard = STATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = state_path(app)?;
+    let mut state: ReminderState = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    let result = f(&mut state);
+    let json = serde_json::to_string(&state).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())?;
+    Ok(result)
+}
+
+fn parse(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").ok()
+}
+
+/// Records newly due reminders, returning those to send: new ones not
+/// dismissed or already sent, and snoozed ones whose time has come.
+fn record(
+    state: &mut ReminderState,
+    due: Vec<FiredReminder>,
+    now: NaiveDateTime,
+) -> Vec<FiredReminder> {
+    let mut send = Vec::new();
+    for reminder in due {
+        if state.dismissed.contains(&reminder.id) || state.fired.iter().any(|f| f.id == reminder.id)
+        {
+            continue;
+        }
+        state.fired.push(reminder.clone());
+        send.push(reminder);
+    }
+    for reminder in &mut state.fired {
+        if reminder
+            .snoozed_until
+            .as_deref()
+            .and_then(parse)
+            .is_some_and(|until| until <= now)
+        {
+            reminder.snoozed_until = None;
+            send.push(reminder.clone());
+        }
+    }
+
+    let cutoff = now - Duration::days(KEEP_DAYS);
+    let recent = |at: &str| parse(at).is_some_and(|at| at >= cutoff);
+    state
+        .fired
+        .retain(|f| f.snoozed_until.is_some() || recent(&f.at));
+    state
+        .dismissed
+        .retain(|id| id.rsplit_once('@').is_some_and(|(_, at)| recent(at)));
+    state.checked_until = Some(now.format("%Y-%m-%dT%H:%M:%S").to_string());
+    send
+}
+
+/// The project's reminders for tasks without their own.
+pub fn default_reminders(lead_minutes: i64) -> Vec<TaskReminder> {
+    vec![
+        TaskReminder {
+            minutes_before: 0,
+            relative_to: ReminderKind::Start,
+        },
+        TaskReminder {
+            minutes_before: lead_minutes,
+            relative_to: ReminderKind::Deadline,
+        },
+    ]
+}
+
+fn ahead(minutes: i64) -> String {
+    match minutes {
+        m if m >= 1440 && m % 1440 == 0 => format!("{} day(s)", m / 1440),
+        m if m >= 60 && m % 60 == 0 => format!("{} hour(s)", m / 60),
+        m => format!("{} minute(s)", m),
+    }
+}
+
+fn notification(
+    project_name: &str,
+    task: &ScheduledTask,
+    reminder: &TaskReminder,
+    target: NaiveDateTime,
+) -> Notification {
+    let when = target.format("%b %-d, %H:%M");
+    match (reminder.relative_to, reminder.minutes_before) {
+        (ReminderKind::Start, 0) => Notification {
+            title: format!("⚓ Time to start · {}", project_name),
+            body: format!("\"{}\" is scheduled to start now.", task.name),
+        },
+        (ReminderKind::Start, minutes) => Notification {
+            title: format!("🔔 Starting in {} · {}", ahead(minutes), project_name),
+            body: format!("\"{}\" is scheduled to start {}.", task.name, when),
+        },
+        (ReminderKind::Deadline, _) => Notification {
+            title: format!("⏰ Due soon · {}", project_name),
+            body: format!("\"{}\" has to be finished by {}.", task.name, when),
+        },
+    }
+}
+
+/// Reminders falling due after `since`, up to and including `now`. Ones
+/// for a start or deadline already more than [`MAX_CATCH_UP_MINUTES`] past
+/// are dropped. Milestones have no start, so only deadline reminders.
+pub fn due_reminders(
+    project_name: &str,
+    tasks: &[ScheduledTask],
+    defaults: &[TaskReminder],
+    own: &HashMap<String, Vec<TaskReminder>>,
+    since: NaiveDateTime,
+    now: NaiveDateTime,
+) -> Vec<Reminder> {
+    let catch_up = now - Duration::minutes(MAX_CATCH_UP_MINUTES);
+    let mut reminders = Vec::new();
+    for task in tasks.iter().filter(|t| !t.completed) {
+        let (Ok(start), Ok(end)) = (
+            NaiveDateTime::parse_from_str(&task.start_date, "%Y-%m-%dT%H:%M:%S"),
+            NaiveDateTime::parse_from_str(&task.end_date, "%Y-%m-%dT%H:%M:%S"),
+        ) else {
+            continue;
+        };
+        let offsets = own.get(&task.id).map_or(defaults, Vec::as_slice);
+        for reminder in offsets {
+            let target = match reminder.relative_to {
+                ReminderKind::Start if task.is_milestone => continue,
+                ReminderKind::Start => start,
+                ReminderKind::Deadline => end,
+            };
+            let at = target - Duration::minutes(reminder.minutes_before);
+            if since < at && at <= now && target.max(at) > catch_up {
+                reminders.push(Reminder {
+                    task_id: task.id.clone(),
+                    kind: reminder.relative_to,
+                    minutes_before: reminder.minutes_before,
+                    at,
+                    notification: notification(project_name, task, reminder, target),
+                });
+            }
+        }
+    }
+    reminders
+}
+
+/// Sends the reminders due in every project since the last check, and
+/// snoozed ones whose time has come.
+fn check(app: &AppHandle, now: NaiveDateTime) -> Result<(), String> {
+    let since = with_state(app, |state| state.checked_until.as_deref().and_then(parse))?;
+    let Some(since) = since else {
+        // First run: nothing to catch up on
+        return with_state(app, |state| record(state, Vec::new(), now)).map(|_| ());
+    };
+    let mut due = Vec::new();
+    for entry in crate::index::entries(app, now)? {
+        let (Some(lead), Some(upcoming)) = (entry.reminder_lead_minutes, &entry.upcoming) else {
+            continue;
+        };
+        let defaults = default_reminders(lead);
+        let reminders = due_reminders(
+            &entry.name,
+            upcoming,
+            &defaults,
+            &entry.task_reminders,
+            since,
+            now,
+        );
+        due.extend(
+            reminders
+                .into_iter()
+                .map(|r| FiredReminder::new(&entry.id, r)),
+        );
+    }
+
+    // Sent while the state is held, so it's saved once everything went out;
+    // a failure is logged and the rest are still sent
+    with_state(app, |state| {
+        let mut projects = HashMap::new();
+        for reminder in record(state, due, now) {
+            let _ = app.emit("reminder", &reminder);
+            if !projects.contains_key(&reminder.project_id) {
+                match load_project(app.clone(), reminder.project_id.clone()) {
+                    Ok(project) => {
+                        projects.insert(reminder.project_id.clone(), project);
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to send reminder {}: {}", reminder.id, e);
+                        continue;
+                    }
+                }
+            }
+            let notification = Notification {
+                title: reminder.title.clone(),
+                body: reminder.body.clone(),
+            };
+            let settings = &projects[&reminder.project_id].notification_settings;
+            if let Err(e) = dispatch(app, settings, &notification) {
+                eprintln!("Failed to send reminder {}: {}", reminder.id, e);
+            }
+        }
+    })
+}
+
+/// Reminders that went off in the last week, and snoozed ones.
+#[tauri::command]
+pub fn list_reminders(app: AppHandle) -> Result<Vec<FiredReminder>, String> {
+    with_state(&app, |state| state.fired.clone())
+}
+
+/// Sends a reminder that went off again in `minutes`.
+#[tauri::command]
+pub fn snooze_reminder(app: AppHandle, id: String, minutes: i64) -> Result<FiredReminder, String> {
+    if minutes <= 0 {
+        return Err("Snooze for at least a minute".to_string());
+    }
+    let until = chrono::Local::now().naive_local() + Duration::minutes(minutes);
+    with_state(&app, |state| {
+        let reminder = state
+            .fired
+            .iter_mut()
+            .find(|f| f.id == id)
+            .ok_or_else(|| format!("Reminder {} not found", id))?;
+        reminder.snoozed_until = Some(until.format("%Y-%m-%dT%H:%M:%S").to_string());
+        Ok(reminder.clone())
+    })?
+}
+
+/// Puts a reminder away for good, snoozed or not.
+#[tauri::command]
+pub fn dismiss_reminder(app: AppHandle, id: String) -> Result<(), String> {
+    with_state(&app, |state| {
+        state.fired.retain(|f| f.id != id);
+        if !state.dismissed.contains(&id) {
+            state.dismissed.push(id);
+        }
+    })
+}
+
+/// Holds reminders back until `until`, or lets them through again when
+/// unset. Ones falling due meanwhile are sent afterwards if still ahead.
+pub fn snooze(until: Option<NaiveDateTime>) {
+    *SNOOZED_UNTIL.lock().unwrap_or_else(|e| e.into_inner()) = until;
+}
+
+/// When reminders resume, while snoozed.
+pub fn snoozed_until(now: NaiveDateTime) -> Option<NaiveDateTime> {
+    SNOOZED_UNTIL
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .filter(|until| *until > now)
+}
+
+/// Starts the background check, first catching up on reminders that fell
+/// due since the app last checked.
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        let now = chrono::Local::now().naive_local();
+        if snoozed_until(now).is_none() {
+            if let Err(e) = check(&app, now) {
+                eprintln!("Failed to check reminders: {}", e);
+            }
+        }
+        std::thread::sleep(CHECK_INTERVAL);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reminders_fall_due_once() {
+        let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap();
+        let task = |id: &str, start: &str, end: &str| ScheduledTask {
+            id: id.into(),
+            name: id.into(),
+            start_date: start.into(),
+            end_date: end.into(),
+            ..Default::default()
+        };
+        let tasks = vec![
+            task("draft", "2026-03-10T09:00:00", "2026-03-10T12:00:00"),
+            ScheduledTask {
+                is_milestone: true,
+                ..task("submit", "2026-03-10T10:00:00", "2026-03-10T10:00:00")
+            },
+            ScheduledTask {
+                completed: true,
+                ..task("done", "2026-03-10T09:00:00", "2026-03-10T09:30:00")
+            },
+            task("review", "2026-03-11T09:00:00", "2026-03-11T10:00:00"),
+        ];
+        // A day ahead of the review's start, instead of the defaults
+        let own = HashMap::from([(
+            "review".to_string(),
+            vec![TaskReminder {
+                minutes_before: 1440,
+                relative_to: ReminderKind::Start,
+            }],
+        )]);
+        let remind = |since: &str, now: &str| {
+            due_reminders(
+                "Thesis",
+                &tasks,
+                &default_reminders(60),
+                &own,
+                at(since),
+                at(now),
+            )
+        };
+        let summary = |since: &str, now: &str| -> Vec<(String, ReminderKind)> {
+            remind(since, now)
+                .into_iter()
+                .map(|r| (r.task_id, r.kind))
+                .collect()
+        };
+
+        assert_eq!(
+            summary("2026-03-10T08:59:00", "2026-03-10T09:00:00"),
+            vec![
+                ("draft".to_string(), ReminderKind::Start),
+                ("submit".to_string(), ReminderKind::Deadline),
+                ("review".to_string(), ReminderKind::Start)
+            ]
+        );
+        // The next check doesn't repeat them
+        assert!(summary("2026-03-10T09:00:00", "2026-03-10T09:01:00").is_empty());
+        let due = remind("2026-03-10T10:59:00", "2026-03-10T11:00:00");
+        assert_eq!(due.len(), 1);
+        assert_eq!(
+            due[0].notification.body,
+            "\"draft\" has to be finished by Mar 10, 12:00."
+        );
+
+        // At launch after a night away, only what's still ahead
+        assert_eq!(
+            summary("2026-03-09T18:00:00", "2026-03-10T11:30:00"),
+            vec![
+                ("draft".to_string(), ReminderKind::Deadline),
+                ("review".to_string(), ReminderKind::Start)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snoozed_and_dismissed_reminders() {
+        let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap();
+        let reminder = |task_id: &str| {
+            FiredReminder::new(
+                "thesis",
+                Reminder {
+                    task_id: task_id.into(),
+                    kind: ReminderKind::Start,
+                    minutes_before: 0,
+                    at: at("2026-03-10T09:00:00"),
+                    notification: Notification {
+                        title: "Time to start".into(),
+                        body: task_id.into(),
+                    },
+                },
+            )
+        };
+        let mut state = ReminderState::default();
+        let now = at("2026-03-10T09:00:00");
+        let sent = record(&mut state, vec![reminder("draft"), reminder("review")], now);
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].id, "thesis:draft:Start:0@2026-03-10T09:00:00");
+
+        // Dismissed and snoozed ones don't come back on their own
+        state.dismissed.push(sent[0].id.clone());
+        state.fired.retain(|f| f.id != sent[0].id);
+        state.fired[0].snoozed_until = Some("2026-03-10T09:30:00".into());
+        let again = vec![reminder("draft"), reminder("review")];
+        assert!(record(&mut state, again, at("2026-03-10T09:01:00")).is_empty());
+
+        let sent = record(&mut state, Vec::new(), at("2026-03-10T09:30:00"));
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].task_id, "review");
+        assert_eq!(state.fired[0].snoozed_until, None);
+
+        // Forgotten after a week
+        record(&mut state, Vec::new(), at("2026-03-18T09:00:00"));
+        assert!(state.fired.is_empty() && state.dismissed.is_empty());
+    }
+}
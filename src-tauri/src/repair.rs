@@ -0,0 +1,188 @@
+//! Project file integrity.
+//!
+//! Project files are written to `<id>.json.tmp` and renamed into place, with
+//! the previous version kept as `<id>.json.bak`, so a crash mid-write leaves
+//! either the old or the new file intact. [`repair_project`] checks files and
+//! restores unreadable ones from whichever sidecar still parses.
+
+use crate::migrate::{parse_project, SchemaError};
+use crate::project::{get_projects_dir, Project};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// What a check found for one project file.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairStatus {
+    Ok,
+    /// Replaced by a readable backup or unfinished write; the damaged file
+    /// is kept as `<id>.json.corrupt`.
+    Restored,
+    /// Unreadable with no readable copy to fall back on.
+    Unrecoverable,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RepairReport {
+    pub project_id: String,
+    pub status: RepairStatus,
+    /// Why the file couldn't be read, when it couldn't.
+    #[serde(default)]
+    pub error: Option<String>,
+    /// The sidecar the project was restored from.
+    #[serde(default)]
+    pub restored_from: Option<String>,
+}
+
+fn sidecar(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn read(path: &Path) -> Result<Project, SchemaError> {
+    parse_project(&crate::encryption::read_to_string(path)?)
+}
+
+/// Replaces `path` with `contents` without ever leaving it half-written,
+/// keeping the previous contents as `.bak`.
+pub(crate) fn write_atomic(path: &Path, contents: &str) -> Result<(), String> {
+    let tmp = sidecar(path, ".tmp");
+    let mut file = fs::File::create(&tmp).map_err(|e| e.to_string())?;
+    file.write_all(contents.as_bytes())
+        .and_then(|_| file.sync_all())
+        .map_err(|e| e.to_string())?;
+    // Only a readable file is worth keeping as the backup
+    if read(path).is_ok() {
+        fs::copy(path, sidecar(path, ".bak")).map_err(|e| e.to_string())?;
+    }
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+/// Removes a project file's sidecars along with it.
+pub(crate) fn remove_sidecars(path: &Path) {
+    for suffix in [".tmp", ".bak", ".corrupt"] {
+        let _ = fs::remove_file(sidecar(path, suffix));
+    }
+}
+
+fn check(path: &Path, project_id: &str) -> RepairReport {
+    let mut report = RepairReport {
+        project_id: project_id.to_string(),
+        status: RepairStatus::Ok,
+        error: None,
+        restored_from: None,
+    };
+    // Can't tell a sealed file from a damaged one without the key
+    if crate::encryption::is_locked() {
+        report.error = Some(crate::encryption::EncryptionError::Locked.to_string());
+        return report;
+    }
+    let error = match read(path) {
+        Ok(_) => return report,
+        // Intact, just written by a newer version of Anchor
+        Err(e @ SchemaError::TooNew { .. }) => {
+            report.error = Some(e.to_string());
+            return report;
+        }
+        Err(e) => e,
+    };
+    report.error = Some(error.to_string());
+
+    // An unfinished write that parses is newer than the backup
+    let candidate = [".tmp", ".bak"]
+        .into_iter()
+        .map(|suffix| sidecar(path, suffix))
+        .filter_map(|p| read(&p).ok().map(|project| (p, project)))
+        .max_by(|(_, a), (_, b)| a.last_modified.cmp(&b.last_modified));
+    let restored = candidate.and_then(|(from, _)| {
+        if path.exists() {
+            fs::rename(path, sidecar(path, ".corrupt")).ok()?;
+        }
+        fs::copy(&from, path).ok()?;
+        from.file_name()?.to_str().map(String::from)
+    });
+    match restored {
+        Some(from) => {
+            report.status = RepairStatus::Restored;
+            report.restored_from = Some(from);
+        }
+        None => report.status = RepairStatus::Unrecoverable,
+    }
+    report
+}
+
+fn check_dir(dir: &Path, project_id: Option<&str>) -> Result<Vec<RepairReport>, String> {
+    let mut ids: Vec<String> = match project_id {
+        Some(id) => vec![id.to_string()],
+        None => fs::read_dir(dir)
+            .map_err(|e| e.to_string())?
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                // A file that only survives as a sidecar is worth checking too
+                let id = [".json", ".json.tmp", ".json.bak"]
+                    .iter()
+                    .find_map(|ext| name.strip_suffix(ext))?;
+                Some(id.to_string())
+            })
+            .collect(),
+    };
+    ids.sort();
+    ids.dedup();
+    Ok(ids
+        .iter()
+        .map(|id| check(&dir.join(format!("{}.json", id)), id))
+        .collect())
+}
+
+/// Checks one project file, or all of them, restoring unreadable files from
+/// a readable backup where possible.
+#[tauri::command]
+pub fn repair_project(
+    app: tauri::AppHandle,
+    project_id: Option<String>,
+) -> Result<Vec<RepairReport>, String> {
+    let reports = check_dir(&get_projects_dir(&app)?, project_id.as_deref())?;
+    if reports.iter().any(|r| r.status == RepairStatus::Restored) {
+        crate::events::project_changed(&app, None, crate::events::ChangeKind::Restored, true);
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corrupt_file_is_restored_from_backup() {
+        let dir = std::env::temp_dir().join(format!("anchor-repair-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("p.json");
+        let mut project = Project {
+            id: "p".into(),
+            name: "First".into(),
+            ..Default::default()
+        };
+        write_atomic(&path, &serde_json::to_string(&project).unwrap()).unwrap();
+        project.name = "Second".into();
+        write_atomic(&path, &serde_json::to_string(&project).unwrap()).unwrap();
+
+        // A torn write that bypassed the rename
+        fs::write(&path, "{\"id\": \"p\", \"na").unwrap();
+
+        let reports = check_dir(&dir, None).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].status, RepairStatus::Restored);
+        assert_eq!(reports[0].restored_from.as_deref(), Some("p.json.bak"));
+        assert_eq!(read(&path).unwrap().name, "First");
+        assert!(sidecar(&path, ".corrupt").exists());
+        assert_eq!(
+            check_dir(&dir, Some("p")).unwrap()[0].status,
+            RepairStatus::Ok
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
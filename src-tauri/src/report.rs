@@ -0,0 +1,513 @@
+//! One-page PDF status reports.
+//!
+//! Lays out the next deadline, current focus, upcoming tasks, the critical
+//! path and a small Gantt chart on a single A4 page. The PDF is written by
+//! hand using the standard Helvetica fonts, so nothing needs embedding.
+
+use crate::cache::ScheduleCache;
+use crate::project::{focus_label, load_project, next_up, Project};
+use crate::scheduler::ScheduledTask;
+use chrono::{Duration, NaiveDateTime};
+use std::fmt::Write;
+use std::fs;
+use tauri::{AppHandle, Manager};
+
+const PAGE_WIDTH: f64 = 595.0;
+const PAGE_HEIGHT: f64 = 842.0;
+const MARGIN: f64 = 40.0;
+const UPCOMING_ROWS: usize = 12;
+
+type Rgb = (f64, f64, f64);
+const INK: Rgb = (0.13, 0.13, 0.13);
+const MUTED: Rgb = (0.45, 0.45, 0.45);
+const RULE: Rgb = (0.85, 0.85, 0.85);
+const BAR: Rgb = (0.31, 0.5, 0.85);
+const CRITICAL: Rgb = (0.85, 0.33, 0.31);
+const DONE: Rgb = (0.71, 0.71, 0.71);
+const TODAY: Rgb = (0.88, 0.54, 0.0);
+
+#[derive(Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+/// Drawing operations for one page, positioned from the top-left corner.
+#[derive(Default)]
+struct Page {
+    ops: String,
+}
+
+impl Page {
+    fn text(&mut self, x: f64, top: f64, size: f64, font: Font, color: Rgb, s: &str) {
+        let font = match font {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        };
+        let _ = writeln!(
+            self.ops,
+            "BT {:.2} {:.2} {:.2} rg /{} {} Tf {:.1} {:.1} Td ({}) Tj ET",
+            color.0,
+            color.1,
+            color.2,
+            font,
+            size,
+            x,
+            PAGE_HEIGHT - top,
+            pdf_text(s)
+        );
+    }
+
+    fn rect(&mut self, x: f64, top: f64, width: f64, height: f64, color: Rgb) {
+        let _ = writeln!(
+            self.ops,
+            "{:.2} {:.2} {:.2} rg {:.1} {:.1} {:.1} {:.1} re f",
+            color.0,
+            color.1,
+            color.2,
+            x,
+            PAGE_HEIGHT - top - height,
+            width,
+            height
+        );
+    }
+
+    fn line(&mut self, x1: f64, top1: f64, x2: f64, top2: f64, color: Rgb, dashed: bool) {
+        let dash = if dashed { "[3 2] 0 d" } else { "[] 0 d" };
+        let _ = writeln!(
+            self.ops,
+            "{:.2} {:.2} {:.2} RG 0.75 w {} {:.1} {:.1} m {:.1} {:.1} l S",
+            color.0,
+            color.1,
+            color.2,
+            dash,
+            x1,
+            PAGE_HEIGHT - top1,
+            x2,
+            PAGE_HEIGHT - top2
+        );
+    }
+}
+
+/// Escapes a string for a PDF literal in WinAnsiEncoding, replacing
+/// characters the standard fonts can't show.
+fn pdf_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        let byte = match c {
+            '(' | ')' | '\\' => {
+                out.push('\\');
+                out.push(c);
+                continue;
+            }
+            ' '..='~' => {
+                out.push(c);
+                continue;
+            }
+            '\u{a0}'..='\u{ff}' => c as u32,
+            '€' => 0x80,
+            '…' => 0x85,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            _ => '?' as u32,
+        };
+        let _ = write!(out, "\\{:03o}", byte);
+    }
+    out
+}
+
+/// Shortens `s` to roughly fit `width` points at `size`. Helvetica averages
+/// a little over half an em per character.
+fn fit(s: &str, width: f64, size: f64) -> String {
+    let max = (width / (size * 0.55)) as usize;
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+    let mut short: String = s.chars().take(max.saturating_sub(1)).collect();
+    short.push('…');
+    short
+}
+
+/// Splits `s` into lines of roughly `width` points at `size`.
+fn wrap(s: &str, width: f64, size: f64) -> Vec<String> {
+    let max = (width / (size * 0.55)) as usize;
+    let mut lines = vec![String::new()];
+    for word in s.split(' ') {
+        let line = lines.last_mut().unwrap();
+        if !line.is_empty() && line.chars().count() + word.chars().count() >= max {
+            lines.push(word.to_string());
+        } else {
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+    }
+    lines
+}
+
+fn parse(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").ok()
+}
+
+fn slack_label(task: &ScheduledTask) -> String {
+    if task.is_critical {
+        "Critical".to_string()
+    } else if task.slack_minutes >= 24 * 60 {
+        format!("{}d", task.slack_minutes / (24 * 60))
+    } else {
+        format!("{}h", task.slack_minutes / 60)
+    }
+}
+
+/// Wraps drawing operations into a single-page PDF document.
+fn finish(title: &str, content: &str) -> Vec<u8> {
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 4 0 R /F2 5 0 R >> >> /Contents 6 0 R >>",
+            PAGE_WIDTH, PAGE_HEIGHT
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ),
+        format!("<< /Title ({}) /Producer (Anchor) >>", pdf_text(title)),
+    ];
+
+    let mut pdf = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+    }
+    let xref = pdf.len();
+    let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(trailer, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        trailer,
+        "trailer\n<< /Size {} /Root 1 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        objects.len(),
+        xref
+    );
+    pdf.extend_from_slice(trailer.as_bytes());
+    pdf
+}
+
+/// Renders a one-page status report for `project`.
+pub fn render_report(project: &Project, schedule: &[ScheduledTask], now: NaiveDateTime) -> Vec<u8> {
+    let mut page = Page::default();
+    let content_width = PAGE_WIDTH - 2.0 * MARGIN;
+    let tasks: Vec<(&ScheduledTask, NaiveDateTime, NaiveDateTime)> = schedule
+        .iter()
+        .filter_map(|t| Some((t, parse(&t.start_date)?, parse(&t.end_date)?)))
+        .collect();
+
+    // Header
+    page.text(
+        MARGIN,
+        58.0,
+        20.0,
+        Font::Bold,
+        INK,
+        &fit(&project.name, content_width, 20.0),
+    );
+    page.text(
+        MARGIN,
+        76.0,
+        10.0,
+        Font::Regular,
+        MUTED,
+        &format!("Status report, {}", now.format("%-d %B %Y %H:%M")),
+    );
+    page.line(MARGIN, 88.0, PAGE_WIDTH - MARGIN, 88.0, RULE, false);
+
+    // Summary
+    let next = next_up(schedule, now);
+    let done = schedule.iter().filter(|t| t.completed).count();
+    let summary = [
+        (
+            "Next deadline",
+            next.map(|(_, end, _)| end.format("%a %-d %b %Y, %H:%M").to_string())
+                .unwrap_or_else(|| "None".to_string()),
+        ),
+        (
+            "Current focus",
+            next.map(|(start, end, task)| focus_label(start, end, task, now))
+                .unwrap_or_else(|| "All tasks completed".to_string()),
+        ),
+        (
+            "Progress",
+            format!("{} of {} tasks done", done, schedule.len()),
+        ),
+    ];
+    let cell = content_width / 3.0;
+    for (i, (label, value)) in summary.iter().enumerate() {
+        let x = MARGIN + cell * i as f64;
+        page.text(x, 108.0, 8.0, Font::Regular, MUTED, &label.to_uppercase());
+        page.text(
+            x,
+            124.0,
+            11.0,
+            Font::Bold,
+            INK,
+            &fit(value, cell - 10.0, 11.0),
+        );
+    }
+
+    // Upcoming tasks
+    let mut top = 160.0;
+    page.text(MARGIN, top, 13.0, Font::Bold, INK, "Upcoming tasks");
+    top += 18.0;
+    let columns = [MARGIN, MARGIN + 270.0, MARGIN + 370.0, MARGIN + 470.0];
+    for (x, heading) in columns.iter().zip(["Task", "Start", "Due", "Slack"]) {
+        page.text(*x, top, 8.0, Font::Bold, MUTED, &heading.to_uppercase());
+    }
+    page.line(
+        MARGIN,
+        top + 4.0,
+        PAGE_WIDTH - MARGIN,
+        top + 4.0,
+        RULE,
+        false,
+    );
+    let mut upcoming: Vec<_> = tasks
+        .iter()
+        .filter(|(t, _, end)| !t.completed && *end >= now)
+        .collect();
+    upcoming.sort_by_key(|(_, start, _)| *start);
+    if upcoming.is_empty() {
+        top += 16.0;
+        page.text(
+            MARGIN,
+            top,
+            9.0,
+            Font::Regular,
+            MUTED,
+            "Nothing left to do.",
+        );
+    }
+    for (task, start, end) in upcoming.iter().take(UPCOMING_ROWS) {
+        top += 15.0;
+        let color = if task.is_critical { CRITICAL } else { INK };
+        page.text(
+            columns[0],
+            top,
+            9.0,
+            Font::Regular,
+            INK,
+            &fit(&task.name, 260.0, 9.0),
+        );
+        page.text(
+            columns[1],
+            top,
+            9.0,
+            Font::Regular,
+            INK,
+            &start.format("%a %-d %b").to_string(),
+        );
+        page.text(
+            columns[2],
+            top,
+            9.0,
+            Font::Regular,
+            INK,
+            &end.format("%a %-d %b").to_string(),
+        );
+        page.text(
+            columns[3],
+            top,
+            9.0,
+            Font::Regular,
+            color,
+            &slack_label(task),
+        );
+    }
+    if upcoming.len() > UPCOMING_ROWS {
+        top += 15.0;
+        let more = format!("and {} more", upcoming.len() - UPCOMING_ROWS);
+        page.text(MARGIN, top, 9.0, Font::Regular, MUTED, &more);
+    }
+
+    // Critical path
+    top += 34.0;
+    page.text(MARGIN, top, 13.0, Font::Bold, INK, "Critical path");
+    let critical: Vec<_> = upcoming.iter().filter(|(t, _, _)| t.is_critical).collect();
+    let summary = match (critical.first(), critical.last()) {
+        (Some((_, start, _)), Some((_, _, end))) => format!(
+            "{} unfinished critical task{}, running {} to {}. Any delay here moves the deadline.",
+            critical.len(),
+            if critical.len() == 1 { "" } else { "s" },
+            start.format("%-d %b"),
+            end.format("%-d %b"),
+        ),
+        _ => "No unfinished tasks are on the critical path.".to_string(),
+    };
+    top += 16.0;
+    page.text(MARGIN, top, 9.0, Font::Regular, INK, &summary);
+    let chain = critical
+        .iter()
+        .map(|(t, _, _)| t.name.as_str())
+        .collect::<Vec<_>>()
+        .join(" > ");
+    for line in wrap(&chain, content_width, 9.0)
+        .iter()
+        .filter(|l| !l.is_empty())
+        .take(3)
+    {
+        top += 13.0;
+        page.text(MARGIN, top, 9.0, Font::Regular, CRITICAL, line);
+    }
+
+    // Timeline
+    top += 34.0;
+    page.text(MARGIN, top, 13.0, Font::Bold, INK, "Timeline");
+    top += 10.0;
+    let row = 12.0;
+    let rows = (((PAGE_HEIGHT - MARGIN - top - 14.0) / row) as usize).min(tasks.len());
+    let shown = &tasks[..rows];
+    if let (Some(first), Some(last)) = (
+        shown.iter().map(|r| r.1).min(),
+        shown.iter().map(|r| r.2).max(),
+    ) {
+        let label_width = 150.0;
+        let left = MARGIN + label_width;
+        let width = content_width - label_width;
+        let t0 = first.date().and_hms_opt(0, 0, 0).unwrap_or(first);
+        let t1 = (last + Duration::days(1)).max(t0 + Duration::days(1));
+        let span = (t1 - t0).num_minutes() as f64;
+        let x = |t: NaiveDateTime| left + (t - t0).num_minutes() as f64 / span * width;
+
+        page.text(
+            left,
+            top + 8.0,
+            7.0,
+            Font::Regular,
+            MUTED,
+            &t0.format("%-d %b").to_string(),
+        );
+        let end_label = t1.format("%-d %b").to_string();
+        page.text(
+            left + width - 24.0,
+            top + 8.0,
+            7.0,
+            Font::Regular,
+            MUTED,
+            &end_label,
+        );
+        top += 14.0;
+        let bottom = top + row * rows as f64;
+        page.line(left, top, left, bottom, RULE, false);
+
+        for (i, (task, start, end)) in shown.iter().enumerate() {
+            let y = top + row * i as f64;
+            let color = if task.completed {
+                DONE
+            } else if task.is_critical {
+                CRITICAL
+            } else {
+                BAR
+            };
+            page.text(
+                MARGIN,
+                y + 8.5,
+                7.5,
+                Font::Regular,
+                INK,
+                &fit(&task.name, label_width - 8.0, 7.5),
+            );
+            if task.is_milestone || start == end {
+                page.rect(x(*end) - 3.0, y + 3.0, 6.0, 6.0, color);
+            } else {
+                let bar = (x(*end) - x(*start)).max(1.5);
+                page.rect(x(*start), y + 2.5, bar, row - 5.0, color);
+            }
+        }
+        if now >= t0 && now <= t1 {
+            page.line(x(now), top - 4.0, x(now), bottom, TODAY, true);
+        }
+        if tasks.len() > rows {
+            let more = format!("and {} more", tasks.len() - rows);
+            page.text(MARGIN, bottom + 10.0, 7.5, Font::Regular, MUTED, &more);
+        }
+    }
+
+    finish(&project.name, &page.ops)
+}
+
+/// Writes a one-page PDF status report for a project to `path`.
+#[tauri::command]
+pub fn generate_report_pdf(app: AppHandle, project_id: String, path: String) -> Result<(), String> {
+    let project = load_project(app.clone(), project_id)?;
+    let now = chrono::Local::now().naive_local();
+    let result = app
+        .state::<ScheduleCache>()
+        .schedule(&project, now)
+        .map_err(|e| e.to_string())?;
+    fs::write(path, render_report(&project, &result.scheduled_tasks, now))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{calculate_backwards_schedule, Task, TaskDuration};
+
+    #[test]
+    fn test_report_is_a_valid_single_page_pdf() {
+        let task = |id: &str, name: &str, days: i64, deps: &[&str]| Task {
+            id: id.into(),
+            name: name.into(),
+            duration: TaskDuration::days(days),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let project = Project {
+            name: "Client (v2)".into(),
+            tasks: vec![
+                task("a", "Draft – intro", 2, &[]),
+                task("b", "Review", 1, &["a"]),
+                task("c", "Deliver", 1, &["b"]),
+            ],
+            anchors: [("c".into(), "2026-03-10T17:00:00".into())].into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+        let schedule = calculate_backwards_schedule(project.schedule_request())
+            .unwrap()
+            .scheduled_tasks;
+        let now = parse("2026-03-05T12:00:00").unwrap();
+
+        let pdf = render_report(&project, &schedule, now);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(pdf.starts_with(b"%PDF-1.4") && text.ends_with("%%EOF\n"));
+        assert!(text.contains("(Client \\(v2\\)) Tj"));
+        assert!(text.contains("(Draft \\226 intro) Tj"));
+        assert!(text.contains("(Upcoming tasks) Tj") && text.contains("(Critical) Tj"));
+        assert!(text.contains("(3 unfinished critical tasks"));
+
+        // Every xref entry points at the start of its object
+        let xref = text.rfind("xref\n").unwrap();
+        for (i, entry) in text[xref..].lines().skip(3).take(7).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            let header = format!("{} 0 obj", i + 1);
+            assert_eq!(&pdf[offset..offset + header.len()], header.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! Zoomed-out timeline rollups.
+//!
+//! Aggregates a schedule into contiguous month or quarter buckets so long
+//! projects can be drawn without shipping every task row to the frontend.
+
+use crate::project::load_project;
+use crate::scheduler::{calculate_backwards_schedule, ScheduledTask};
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RollupPeriod {
+    Month,
+    Quarter,
+}
+
+impl RollupPeriod {
+    fn months(self) -> u32 {
+        match self {
+            Self::Month => 1,
+            Self::Quarter => 3,
+        }
+    }
+
+    /// First day of the period containing `date`.
+    fn start_of(self, date: NaiveDate) -> NaiveDate {
+        let month0 = date.month0() / self.months() * self.months();
+        NaiveDate::from_ymd_opt(date.year(), month0 + 1, 1).unwrap_or(date)
+    }
+
+    fn label(self, start: NaiveDate) -> String {
+        match self {
+            Self::Month => start.format("%Y-%m").to_string(),
+            Self::Quarter => format!("{}-Q{}", start.year(), start.month0() / 3 + 1),
+        }
+    }
+}
+
+/// Aggregate figures for one month or quarter.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RollupBucket {
+    pub label: String,
+    pub period_start: String,
+    pub period_end: String,
+    /// Tasks scheduled to be in progress at any point in the period.
+    pub task_count: usize,
+    pub completed_count: usize,
+    pub critical_count: usize,
+    /// Milestones falling due in the period.
+    pub milestone_count: usize,
+    /// Estimated work in the period; tasks spanning several periods are prorated.
+    pub work_minutes: i64,
+}
+
+/// Buckets `schedule` into contiguous periods from its first start to its last end.
+pub fn rollup(schedule: &[ScheduledTask], period: RollupPeriod) -> Vec<RollupBucket> {
+    let spans: Vec<(NaiveDateTime, NaiveDateTime, &ScheduledTask)> = schedule
+        .iter()
+        .filter_map(|t| {
+            let start = NaiveDateTime::parse_from_str(&t.start_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+            let end = NaiveDateTime::parse_from_str(&t.end_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+            Some((start, end, t))
+        })
+        .collect();
+
+    let (Some(first), Some(last)) = (
+        spans.iter().map(|(s, _, _)| *s).min(),
+        spans.iter().map(|(_, e, _)| *e).max(),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut buckets = Vec::new();
+    let mut bucket_start = period.start_of(first.date());
+    while bucket_start <= last.date() {
+        let Some(next_start) =
+            bucket_start.checked_add_months(chrono::Months::new(period.months()))
+        else {
+            break;
+        };
+        let from = bucket_start.and_time(chrono::NaiveTime::MIN);
+        let until = next_start.and_time(chrono::NaiveTime::MIN);
+
+        let mut bucket = RollupBucket {
+            label: period.label(bucket_start),
+            period_start: from.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            period_end: until.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            ..Default::default()
+        };
+
+        for (start, end, task) in &spans {
+            if task.is_milestone && *end >= from && *end < until {
+                bucket.milestone_count += 1;
+            }
+
+            let overlaps = if start == end {
+                *start >= from && *start < until
+            } else {
+                *start < until && *end > from
+            };
+            if !overlaps {
+                continue;
+            }
+
+            bucket.task_count += 1;
+            if task.completed {
+                bucket.completed_count += 1;
+            }
+            if task.is_critical {
+                bucket.critical_count += 1;
+            }
+
+            let span = (*end - *start).num_minutes();
+            bucket.work_minutes += if span <= 0 {
+                task.estimate_minutes
+            } else {
+                let overlap = ((*end).min(until) - (*start).max(from)).num_minutes();
+                (task.estimate_minutes as f64 * overlap as f64 / span as f64).round() as i64
+            };
+        }
+
+        buckets.push(bucket);
+        bucket_start = next_start;
+    }
+
+    buckets
+}
+
+#[tauri::command]
+pub fn get_schedule_rollup(
+    app: AppHandle,
+    project_id: String,
+    period: RollupPeriod,
+) -> Result<Vec<RollupBucket>, String> {
+    let project = load_project(app, project_id)?;
+    let result =
+        calculate_backwards_schedule(project.schedule_request()).map_err(|e| e.to_string())?;
+    Ok(rollup(&result.scheduled_tasks, period))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheduled(id: &str, start: &str, end: &str, minutes: i64) -> ScheduledTask {
+        ScheduledTask {
+            id: id.into(),
+            name: id.into(),
+            start_date: start.into(),
+            end_date: end.into(),
+            estimate_minutes: minutes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_work_is_prorated_across_months() {
+        // Four days of work, one in March and three in April, plus an empty May gap before June.
+        let schedule = vec![
+            scheduled("a", "2026-03-31T00:00:00", "2026-04-04T00:00:00", 4 * 1440),
+            scheduled("b", "2026-06-01T00:00:00", "2026-06-02T00:00:00", 1440),
+        ];
+
+        let buckets = rollup(&schedule, RollupPeriod::Month);
+        let labels: Vec<&str> = buckets.iter().map(|b| b.label.as_str()).collect();
+
+        assert_eq!(labels, vec!["2026-03", "2026-04", "2026-05", "2026-06"]);
+        assert_eq!(buckets[0].work_minutes, 1440);
+        assert_eq!(buckets[1].work_minutes, 3 * 1440);
+        assert_eq!(buckets[2].task_count, 0);
+        assert_eq!(buckets[3].task_count, 1);
+    }
+
+    #[test]
+    fn test_quarter_labels() {
+        let schedule = vec![scheduled(
+            "a",
+            "2026-02-10T00:00:00",
+            "2026-05-10T00:00:00",
+            1440,
+        )];
+
+        let buckets = rollup(&schedule, RollupPeriod::Quarter);
+
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].label, "2026-Q1");
+        assert_eq!(buckets[1].period_start, "2026-04-01T00:00:00");
+    }
+}
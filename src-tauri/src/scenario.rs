@@ -0,0 +1,450 @@
+//! What-if scenario evaluation.
+//!
+//! Applies ephemeral overrides (durations, delays, extra dependencies, moved
+//! anchors) to a stored project and schedules the result without persisting
+//! anything.
+
+use crate::project::{load_project, Project};
+use crate::scheduler::{
+    calculate_backwards_schedule, ScheduleError, ScheduleResult, ScheduledTask, Task, TaskDuration,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tauri::AppHandle;
+
+/// Temporary changes to apply on top of a stored project.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ScenarioOverrides {
+    /// Map of TaskID → new duration in minutes.
+    #[serde(default)]
+    pub durations: HashMap<String, i64>,
+    /// Map of TaskID → extra provider IDs that must complete before it.
+    #[serde(default)]
+    pub added_dependencies: HashMap<String, Vec<String>>,
+    /// Map of TaskID → new anchor date (ISO 8601 DateTime or YYYY-MM-DD).
+    #[serde(default)]
+    pub anchors: HashMap<String, String>,
+    /// Map of TaskID → minutes the task runs over its estimate, e.g. a late delivery.
+    #[serde(default)]
+    pub delays: HashMap<String, i64>,
+}
+
+/// How a single anchor's chain changes under the scenario.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnchorImpact {
+    pub task_id: String,
+    pub task_name: String,
+    pub baseline_end: Option<String>,
+    pub scenario_end: Option<String>,
+    /// Earliest start among the anchor and everything upstream of it.
+    pub baseline_required_start: Option<String>,
+    pub scenario_required_start: Option<String>,
+    /// Positive when the chain may start later than before, negative when earlier.
+    pub start_shift_minutes: Option<i64>,
+}
+
+/// Result of evaluating a scenario.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScenarioResult {
+    pub schedule: ScheduleResult,
+    pub anchor_impacts: Vec<AnchorImpact>,
+}
+
+/// Returns a copy of `project` with the overrides applied.
+fn apply_overrides(
+    project: &Project,
+    overrides: &ScenarioOverrides,
+) -> Result<Project, ScheduleError> {
+    let mut scenario = project.clone();
+    let known: HashSet<String> = scenario.tasks.iter().map(|t| t.id.clone()).collect();
+
+    let referenced = overrides
+        .durations
+        .keys()
+        .chain(overrides.added_dependencies.keys())
+        .chain(overrides.added_dependencies.values().flatten())
+        .chain(overrides.anchors.keys())
+        .chain(overrides.delays.keys());
+    for id in referenced {
+        if !known.contains(id) {
+            return Err(ScheduleError::TaskNotFound(id.clone()));
+        }
+    }
+
+    for task in &mut scenario.tasks {
+        if let Some(&minutes) = overrides.durations.get(&task.id) {
+            task.duration = TaskDuration::minutes(minutes);
+        }
+        if let Some(&delay) = overrides.delays.get(&task.id) {
+            task.duration = TaskDuration::minutes(task.duration().num_minutes() + delay);
+        }
+        if let Some(extra) = overrides.added_dependencies.get(&task.id) {
+            for dep in extra {
+                if !task.dependencies.contains(dep) {
+                    task.dependencies.push(dep.clone());
+                }
+            }
+        }
+    }
+
+    for (task_id, date) in &overrides.anchors {
+        scenario.anchors.insert(task_id.clone(), date.clone());
+    }
+
+    Ok(scenario)
+}
+
+/// `task_id` and all of its (transitive) providers.
+fn upstream_of<'a>(tasks: &'a [Task], task_id: &'a str) -> HashSet<&'a str> {
+    let deps: HashMap<&str, &Vec<String>> = tasks
+        .iter()
+        .map(|t| (t.id.as_str(), &t.dependencies))
+        .collect();
+
+    let mut upstream = HashSet::new();
+    let mut stack = vec![task_id];
+    while let Some(id) = stack.pop() {
+        if upstream.insert(id) {
+            if let Some(providers) = deps.get(id) {
+                stack.extend(providers.iter().map(|p| p.as_str()));
+            }
+        }
+    }
+    upstream
+}
+
+/// `task_id` and all of its (transitive) consumers.
+fn downstream_of<'a>(tasks: &'a [Task], task_id: &'a str) -> HashSet<&'a str> {
+    let mut downstream = HashSet::from([task_id]);
+    // Tasks are few; sweep until no consumer is added
+    let mut grew = true;
+    while grew {
+        grew = false;
+        for task in tasks {
+            if !downstream.contains(task.id.as_str())
+                && task
+                    .dependencies
+                    .iter()
+                    .any(|d| downstream.contains(d.as_str()))
+            {
+                downstream.insert(task.id.as_str());
+                grew = true;
+            }
+        }
+    }
+    downstream
+}
+
+/// Earliest start among `anchor_id` and all of its (transitive) providers.
+fn required_start(
+    tasks: &[Task],
+    schedule: &[ScheduledTask],
+    anchor_id: &str,
+) -> Option<NaiveDateTime> {
+    let upstream = upstream_of(tasks, anchor_id);
+    schedule
+        .iter()
+        .filter(|t| upstream.contains(t.id.as_str()))
+        .filter_map(|t| NaiveDateTime::parse_from_str(&t.start_date, "%Y-%m-%dT%H:%M:%S").ok())
+        .min()
+}
+
+fn end_of(schedule: &[ScheduledTask], task_id: &str) -> Option<String> {
+    schedule
+        .iter()
+        .find(|t| t.id == task_id)
+        .map(|t| t.end_date.clone())
+}
+
+/// Schedules `project` with `overrides` applied and compares each anchor to the baseline.
+pub fn evaluate(
+    project: &Project,
+    overrides: &ScenarioOverrides,
+) -> Result<ScenarioResult, ScheduleError> {
+    let scenario = apply_overrides(project, overrides)?;
+    let schedule = calculate_backwards_schedule(scenario.schedule_request())?;
+
+    // The stored project may itself be unschedulable; impacts then only carry scenario values.
+    let baseline = calculate_backwards_schedule(project.schedule_request())
+        .map(|r| r.scheduled_tasks)
+        .unwrap_or_default();
+    let scheduled = &schedule.scheduled_tasks;
+
+    let mut anchor_ids: Vec<&String> = scenario.anchors.keys().collect();
+    anchor_ids.sort();
+
+    let anchor_impacts = anchor_ids
+        .into_iter()
+        .map(|anchor_id| {
+            let task_name = scenario
+                .tasks
+                .iter()
+                .find(|t| &t.id == anchor_id)
+                .map(|t| t.name.clone())
+                .unwrap_or_default();
+            let before = required_start(&project.tasks, &baseline, anchor_id);
+            let after = required_start(&scenario.tasks, scheduled, anchor_id);
+            let fmt = |d: NaiveDateTime| d.format("%Y-%m-%dT%H:%M:%S").to_string();
+
+            AnchorImpact {
+                task_id: anchor_id.clone(),
+                task_name,
+                baseline_end: end_of(&baseline, anchor_id),
+                scenario_end: end_of(scheduled, anchor_id),
+                baseline_required_start: before.map(fmt),
+                scenario_required_start: after.map(fmt),
+                start_shift_minutes: before.zip(after).map(|(b, a)| (a - b).num_minutes()),
+            }
+        })
+        .collect();
+
+    Ok(ScenarioResult {
+        schedule,
+        anchor_impacts,
+    })
+}
+
+/// Where a task sits relative to the delayed one.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DelayRelation {
+    Delayed,
+    Upstream,
+    Downstream,
+}
+
+/// A task whose required dates or slack change because of a delay.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DateShift {
+    pub task_id: String,
+    pub relation: DelayRelation,
+    pub baseline_start: String,
+    pub baseline_end: String,
+    pub new_start: String,
+    pub new_end: String,
+    /// Negative when the task now has to start earlier.
+    pub start_shift_minutes: i64,
+    /// Negative when the task lost slack.
+    pub slack_change_minutes: i64,
+}
+
+/// What a task running late does to the rest of the plan.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DelayImpact {
+    pub task_id: String,
+    pub delay_minutes: i64,
+    /// Upstream tasks have to start earlier to absorb the delay; downstream
+    /// tasks keep their dates but lose slack.
+    pub shifts: Vec<DateShift>,
+    /// Anchors that were achievable but now need work to start before now.
+    pub infeasible_anchors: Vec<String>,
+    pub critical_chains: Vec<Vec<String>>,
+    pub anchor_impacts: Vec<AnchorImpact>,
+}
+
+/// Anchors whose chain requires starting before now in `schedule`.
+fn anchors_in_past(project: &Project, schedule: &[ScheduledTask]) -> HashSet<String> {
+    let late: HashSet<&str> = schedule
+        .iter()
+        .filter(|t| t.starts_in_past)
+        .map(|t| t.id.as_str())
+        .collect();
+    project
+        .anchors
+        .keys()
+        .chain(project.anchor_windows.keys())
+        .filter(|anchor| {
+            upstream_of(&project.tasks, anchor)
+                .iter()
+                .any(|id| late.contains(id))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Schedules `project` as of `now` with `task_id` running `delay_minutes`
+/// over its estimate and reports what moves.
+pub fn simulate(
+    project: &Project,
+    task_id: &str,
+    delay_minutes: i64,
+    now: NaiveDateTime,
+) -> Result<DelayImpact, ScheduleError> {
+    let overrides = ScenarioOverrides {
+        delays: [(task_id.to_string(), delay_minutes)].into(),
+        ..Default::default()
+    };
+    let scenario = apply_overrides(project, &overrides)?;
+    let at_now = |p: &Project| {
+        let mut request = p.schedule_request();
+        request.reference_time = Some(now.format("%Y-%m-%dT%H:%M:%S").to_string());
+        calculate_backwards_schedule(request)
+    };
+    let baseline = at_now(project)?;
+    let delayed = at_now(&scenario)?;
+
+    let upstream = upstream_of(&project.tasks, task_id);
+    let downstream = downstream_of(&project.tasks, task_id);
+    let before: HashMap<&str, &ScheduledTask> = baseline
+        .scheduled_tasks
+        .iter()
+        .map(|t| (t.id.as_str(), t))
+        .collect();
+    let parse = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").ok();
+
+    let shifts = delayed
+        .scheduled_tasks
+        .iter()
+        .filter_map(|after| {
+            let id = after.id.as_str();
+            let relation = if id == task_id {
+                DelayRelation::Delayed
+            } else if upstream.contains(id) {
+                DelayRelation::Upstream
+            } else if downstream.contains(id) {
+                DelayRelation::Downstream
+            } else {
+                return None;
+            };
+            let before = before.get(id)?;
+            let start_shift =
+                (parse(&after.start_date)? - parse(&before.start_date)?).num_minutes();
+            let slack_change = after.slack_minutes - before.slack_minutes;
+            if start_shift == 0 && slack_change == 0 && after.end_date == before.end_date {
+                return None;
+            }
+            Some(DateShift {
+                task_id: after.id.clone(),
+                relation,
+                baseline_start: before.start_date.clone(),
+                baseline_end: before.end_date.clone(),
+                new_start: after.start_date.clone(),
+                new_end: after.end_date.clone(),
+                start_shift_minutes: start_shift,
+                slack_change_minutes: slack_change,
+            })
+        })
+        .collect();
+
+    let was_late = anchors_in_past(project, &baseline.scheduled_tasks);
+    let mut infeasible_anchors: Vec<String> = anchors_in_past(&scenario, &delayed.scheduled_tasks)
+        .into_iter()
+        .filter(|anchor| !was_late.contains(anchor))
+        .collect();
+    infeasible_anchors.sort();
+
+    Ok(DelayImpact {
+        task_id: task_id.to_string(),
+        delay_minutes,
+        shifts,
+        infeasible_anchors,
+        critical_chains: delayed.critical_chains,
+        anchor_impacts: evaluate(project, &overrides)?.anchor_impacts,
+    })
+}
+
+/// "What if this task is `delay_minutes` late?" for a saved project, as of now.
+#[tauri::command]
+pub fn simulate_delay(
+    app: AppHandle,
+    project_id: String,
+    task_id: String,
+    delay_minutes: i64,
+) -> Result<DelayImpact, String> {
+    let project = load_project(app, project_id)?;
+    let now = chrono::Local::now().naive_local();
+    simulate(&project, &task_id, delay_minutes, now).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn evaluate_scenario(
+    app: AppHandle,
+    project_id: String,
+    overrides: ScenarioOverrides,
+) -> Result<ScenarioResult, String> {
+    let project = load_project(app, project_id)?;
+    evaluate(&project, &overrides).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, minutes: i64, deps: &[&str]) -> Task {
+        Task {
+            id: id.into(),
+            name: id.to_uppercase(),
+            duration: TaskDuration::minutes(minutes),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn project() -> Project {
+        Project {
+            id: "p".into(),
+            name: "Scenario".into(),
+            created_at: String::new(),
+            last_modified: String::new(),
+            tasks: vec![task("a", 60, &[]), task("b", 60, &["a"])],
+            anchors: [("b".into(), "2026-01-15T10:00:00".into())].into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_longer_duration_pulls_chain_earlier() {
+        let project = project();
+        let overrides = ScenarioOverrides {
+            durations: [("a".into(), 180)].into(),
+            ..Default::default()
+        };
+
+        let result = evaluate(&project, &overrides).expect("Scenario should schedule");
+        let impact = &result.anchor_impacts[0];
+
+        assert_eq!(impact.task_id, "b");
+        assert_eq!(impact.start_shift_minutes, Some(-120));
+        // Nothing was persisted on the source project.
+        assert_eq!(project.tasks[0].duration, TaskDuration::minutes(60));
+    }
+
+    #[test]
+    fn test_unknown_task_is_rejected() {
+        let overrides = ScenarioOverrides {
+            anchors: [("missing".into(), "2026-01-15".into())].into(),
+            ..Default::default()
+        };
+
+        match evaluate(&project(), &overrides) {
+            Err(ScheduleError::TaskNotFound(id)) => assert_eq!(id, "missing"),
+            other => panic!(
+                "Expected TaskNotFound, got {:?}",
+                other.map(|r| r.anchor_impacts)
+            ),
+        }
+    }
+
+    #[test]
+    fn test_delay_pulls_upstream_and_flags_anchor() {
+        // c runs alongside the a -> b chain into anchor b
+        let mut project = project();
+        project.tasks.push(task("c", 30, &["a"]));
+        project.tasks[1].dependencies.push("c".into());
+        let now =
+            NaiveDateTime::parse_from_str("2026-01-15T07:30:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+
+        let impact = simulate(&project, "c", 60, now).expect("Delay should schedule");
+        let shift = |id: &str| impact.shifts.iter().find(|s| s.task_id == id).unwrap();
+
+        // c now takes 90 minutes, so a has to start an hour earlier
+        assert_eq!(shift("c").relation, DelayRelation::Delayed);
+        assert_eq!(shift("a").relation, DelayRelation::Upstream);
+        assert_eq!(shift("a").start_shift_minutes, -60);
+        assert_eq!(shift("a").new_start, "2026-01-15T06:30:00");
+        // ...which is before now
+        assert_eq!(impact.infeasible_anchors, vec!["b"]);
+        assert_eq!(impact.critical_chains, vec![vec!["a", "c", "b"]]);
+    }
+}
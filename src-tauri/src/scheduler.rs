@@ -3,9 +3,11 @@
 //! Implements the core scheduling algorithm that works backwards from anchor dates
 //! to determine when predecessor tasks must start.
 
+use crate::datetime::Zone;
 use chrono::{Duration, NaiveDateTime};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap};
 use thiserror::Error;
 
 /// A subtask within a larger task.
@@ -16,27 +18,304 @@ pub struct SubTask {
     pub completed: bool,
 }
 
+/// A file or link kept on a task, e.g. the brief or the ticket.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Attachment {
+    pub id: String,
+    /// Shown in place of the file name or URL.
+    pub name: String,
+    /// Web link; unset for files.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Copy of the file, relative to `projects/attachments`; unset for links.
+    #[serde(default)]
+    pub file: Option<String>,
+    pub added_at: String,
+}
+
+/// What a reminder counts back from.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderKind {
+    Start,
+    /// The scheduled finish.
+    Deadline,
+}
+
+/// A reminder some time before a task starts or has to finish.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TaskReminder {
+    pub minutes_before: i64,
+    pub relative_to: ReminderKind,
+}
+
 /// A task definition with dependencies.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Task {
     pub id: String,
     pub name: String,
-    pub duration_days: i64,
-    pub duration_minutes: Option<i64>, // New field for minute precision
+    /// Planned duration. Also read from the legacy `duration_days` and
+    /// `duration_minutes` fields.
+    #[serde(flatten, with = "legacy_duration")]
+    pub duration: TaskDuration,
     /// IDs of tasks that must complete before this one can start.
     pub dependencies: Vec<String>,
+    /// Dependencies this task may overlap: how far along each must be, as a
+    /// percentage (50.0 = half done), before this task can start.
+    #[serde(default)]
+    pub start_after_percent: HashMap<String, f64>,
     #[serde(default)]
     pub completed: bool,
     #[serde(default)]
     pub notes: Option<String>,
     #[serde(default)]
     pub is_milestone: bool,
+    /// Runs on its own clock (paint drying, shipping) rather than on working
+    /// time: day and week durations are exact 24-hour periods, and the time
+    /// can't be made up by working harder.
+    #[serde(default)]
+    pub is_elapsed: bool,
     #[serde(default)]
     pub subtasks: Vec<SubTask>,
+    /// When the task was marked complete (RFC 3339), stamped on save.
+    #[serde(default)]
+    pub completed_at: Option<String>,
+    /// Person responsible for the task.
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Phase or group the task belongs to, e.g. "Design".
+    #[serde(default)]
+    pub phase: Option<String>,
+    /// Free-form labels, e.g. "writing", for filtering with `query_tasks`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Contingency added on top of the estimate, as a percentage (20.0 = 20%).
+    #[serde(default)]
+    pub buffer_percent: f64,
+    /// Best case for a three-point estimate; `duration` is the most likely.
+    #[serde(default)]
+    pub optimistic: Option<TaskDuration>,
+    /// Worst case for a three-point estimate.
+    #[serde(default)]
+    pub pessimistic: Option<TaskDuration>,
+    /// When non-empty, the task must run entirely inside one of these periods
+    /// (e.g. concrete pours only April–October).
+    #[serde(default)]
+    pub only_during: Vec<AllowedWindow>,
+    /// Manually scheduled start (ISO 8601 DateTime, or YYYY-MM-DD for the
+    /// start of that day). The task stays there and is planned around.
+    #[serde(default)]
+    pub pinned_start: Option<String>,
+    /// When the task was last edited (RFC 3339), used to settle conflicting
+    /// edits from synced copies. Stamped on save if the editor didn't.
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    /// Replaces the project's default reminders for this task when set.
+    #[serde(default)]
+    pub reminders: Vec<TaskReminder>,
+    /// Time actually worked, from the task timer. See [`crate::timer`].
+    #[serde(default)]
+    pub sessions: Vec<WorkSession>,
+}
+
+/// A stretch of work on a task, in local time (`%Y-%m-%dT%H:%M:%S`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorkSession {
+    pub started_at: String,
+    /// Unset while the timer runs.
+    #[serde(default)]
+    pub ended_at: Option<String>,
+}
+
+impl WorkSession {
+    /// Minutes worked; none while running or when unreadable.
+    pub fn minutes(&self) -> i64 {
+        let parse = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").ok();
+        match (
+            parse(&self.started_at),
+            self.ended_at.as_deref().and_then(parse),
+        ) {
+            (Some(start), Some(end)) => (end - start).num_minutes().max(0),
+            _ => 0,
+        }
+    }
+}
+
+/// A period a task may run in (ISO 8601 DateTime or YYYY-MM-DD). A bare start
+/// date means the start of that day and a bare end date its end.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AllowedWindow {
+    pub start: String,
+    pub end: String,
+}
+
+/// Unit a task duration is expressed in.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DurationUnit {
+    Minutes,
+    Hours,
+    #[default]
+    Days,
+    Weeks,
+}
+
+/// A task duration. Minutes and hours are elapsed time; days and weeks are
+/// civil days, so they keep the clock time across DST changes.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TaskDuration {
+    pub value: i64,
+    pub unit: DurationUnit,
+}
+
+impl TaskDuration {
+    pub fn minutes(value: i64) -> Self {
+        Self {
+            value,
+            unit: DurationUnit::Minutes,
+        }
+    }
+
+    pub fn hours(value: i64) -> Self {
+        Self {
+            value,
+            unit: DurationUnit::Hours,
+        }
+    }
+
+    pub fn days(value: i64) -> Self {
+        Self {
+            value,
+            unit: DurationUnit::Days,
+        }
+    }
+
+    pub fn weeks(value: i64) -> Self {
+        Self {
+            value,
+            unit: DurationUnit::Weeks,
+        }
+    }
+
+    /// The duration as a fixed length, counting calendar days as 24 hours.
+    pub fn as_duration(self) -> Duration {
+        let (days, minutes) = self.parts();
+        Duration::days(days) + Duration::minutes(minutes)
+    }
+
+    /// Splits the duration into (civil days, elapsed minutes).
+    fn parts(self) -> (i64, i64) {
+        match self.unit {
+            DurationUnit::Minutes => (0, self.value),
+            DurationUnit::Hours => (0, self.value * 60),
+            DurationUnit::Days => (self.value, 0),
+            DurationUnit::Weeks => (self.value * 7, 0),
+        }
+    }
+}
+
+/// Serializes a task's duration as `duration: { value, unit }`, while still
+/// accepting files written with `duration_days` / `duration_minutes`.
+mod legacy_duration {
+    use super::TaskDuration;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Fields {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duration: Option<TaskDuration>,
+        #[serde(default, skip_serializing)]
+        duration_days: Option<i64>,
+        #[serde(default, skip_serializing)]
+        duration_minutes: Option<i64>,
+    }
+
+    pub fn serialize<S: Serializer>(duration: &TaskDuration, s: S) -> Result<S::Ok, S::Error> {
+        Fields {
+            duration: Some(*duration),
+            duration_days: None,
+            duration_minutes: None,
+        }
+        .serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<TaskDuration, D::Error> {
+        let fields = Fields::deserialize(d)?;
+        Ok(match (fields.duration, fields.duration_minutes) {
+            (Some(duration), _) => duration,
+            (None, Some(minutes)) => TaskDuration::minutes(minutes),
+            (None, None) => TaskDuration::days(fields.duration_days.unwrap_or(0)),
+        })
+    }
+}
+
+impl Task {
+    /// The task's planned duration.
+    pub fn duration(&self) -> Duration {
+        self.duration.as_duration()
+    }
+
+    /// Contingency time derived from `buffer_percent`.
+    pub fn buffer(&self) -> Duration {
+        let minutes = self.duration().num_minutes() as f64 * self.buffer_percent.max(0.0) / 100.0;
+        Duration::minutes(minutes.round() as i64)
+    }
+
+    /// Minutes worked in finished timer sessions.
+    pub fn actual_minutes(&self) -> i64 {
+        self.sessions.iter().map(WorkSession::minutes).sum()
+    }
+
+    /// Splits estimate plus contingency (the span the scheduler actually
+    /// reserves) into civil days and elapsed minutes. Once work has been
+    /// tracked on an unfinished task, only what's left of it is reserved.
+    fn span(&self) -> (i64, i64) {
+        if self.is_elapsed {
+            return (0, (self.duration() + self.buffer()).num_minutes());
+        }
+        let actual = self.actual_minutes();
+        if actual > 0 && !self.completed && !self.is_milestone {
+            let planned = (self.duration() + self.buffer()).num_minutes();
+            return (0, (planned - actual).max(0));
+        }
+        let (days, minutes) = self.duration.parts();
+        (days, minutes + self.buffer().num_minutes())
+    }
+
+    /// Minutes of the task's span still to run once it is `percent` done.
+    fn remaining_minutes(&self, percent: f64) -> i64 {
+        let fraction = 1.0 - percent.clamp(0.0, 100.0) / 100.0;
+        ((self.duration() + self.buffer()).num_minutes() as f64 * fraction).round() as i64
+    }
+
+    /// When the task must start to finish at `finish` in `zone`.
+    pub(crate) fn start_for_finish(&self, zone: Zone, finish: NaiveDateTime) -> NaiveDateTime {
+        let (days, minutes) = self.span();
+        zone.add_minutes(zone.add_days(finish, -days), -minutes)
+    }
+
+    /// Whether the task is work measured on the clock, which busy slots
+    /// interrupt.
+    fn works_around_busy(&self) -> bool {
+        !self.is_elapsed
+            && !self.is_milestone
+            && matches!(
+                self.duration.unit,
+                DurationUnit::Minutes | DurationUnit::Hours
+            )
+    }
+
+    /// When the task finishes if it starts at `start` in `zone`.
+    pub(crate) fn finish_for_start(&self, zone: Zone, start: NaiveDateTime) -> NaiveDateTime {
+        let (days, minutes) = self.span();
+        zone.add_minutes(zone.add_days(start, days), minutes)
+    }
 }
 
 /// A scheduled task with computed start and end dates.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct ScheduledTask {
     pub id: String,
     pub name: String,
@@ -47,14 +326,262 @@ pub struct ScheduledTask {
     pub is_critical: bool,
     pub slack_minutes: i64, // Changed from slack_days
     pub is_milestone: bool,
+    #[serde(default)]
+    pub is_elapsed: bool,
+    #[serde(default)]
+    pub is_pinned: bool,
+    #[serde(default)]
+    pub phase: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// True when the task is not completed and its required start is before "now".
+    pub starts_in_past: bool,
+    /// The pure estimate, without contingency.
+    pub estimate_minutes: i64,
+    /// Contingency included in the scheduled span.
+    pub buffer_minutes: i64,
+    /// Time tracked on the task so far, to set against the estimate.
+    #[serde(default)]
+    pub actual_minutes: i64,
 }
 
 /// Request to calculate a backwards schedule.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ScheduleRequest {
     pub tasks: Vec<Task>,
     /// Map of TaskID → EndDate (ISO 8601 DateTime or YYYY-MM-DD) for anchor tasks.
     pub anchors: HashMap<String, String>,
+    /// Map of TaskID → acceptable finish range for anchor tasks with flexible deadlines.
+    #[serde(default)]
+    pub anchor_windows: HashMap<String, AnchorWindow>,
+    /// Moment treated as "now" for feasibility checks (ISO 8601 DateTime).
+    /// Defaults to the local clock.
+    #[serde(default)]
+    pub reference_time: Option<String>,
+    /// IANA time zone the dates are wall-clock times in. Defaults to the local zone.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Groups of tasks that must not overlap.
+    #[serde(default)]
+    pub exclusions: Vec<ExclusionGroup>,
+    /// Minimum gaps between anchored milestones.
+    #[serde(default)]
+    pub anchor_spacing: Vec<AnchorSpacing>,
+    /// Single-user mode: level all incomplete work into one sequence with no
+    /// two tasks at once. Elapsed tasks run alongside.
+    #[serde(default)]
+    pub serialize_work: bool,
+    /// Never plan an incomplete task to start before `start_floor`; tasks
+    /// that would are pushed later along with their dependents.
+    #[serde(default)]
+    pub floor_starts: bool,
+    /// ISO 8601 DateTime or YYYY-MM-DD. Defaults to the reference time.
+    #[serde(default)]
+    pub start_floor: Option<String>,
+    /// Times no one can work (meetings, appointments). Minute- and
+    /// hour-based tasks are split around them; other tasks ignore them.
+    #[serde(default)]
+    pub busy: Vec<BusySlot>,
+    /// Plan tasks between their early and late dates instead of as late as
+    /// possible, sharing each chain's slack out along it. Reported slack is
+    /// then what's left after the planned start.
+    #[serde(default)]
+    pub spread_slack: bool,
+}
+
+/// A stretch of unavailable time (ISO 8601 DateTime).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BusySlot {
+    pub start: String,
+    pub end: String,
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// Requires `before` to finish at least `min_gap_days` (civil days) before
+/// `after` finishes, e.g. dress rehearsal three days before opening night.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AnchorSpacing {
+    pub before: String,
+    pub after: String,
+    pub min_gap_days: i64,
+}
+
+/// An [`AnchorSpacing`] the schedule doesn't satisfy.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SpacingViolation {
+    pub before: String,
+    pub after: String,
+    pub min_gap_days: i64,
+    /// How much closer together than allowed the two finishes are.
+    pub shortfall_minutes: i64,
+}
+
+/// Tasks that can't run at the same time because they share a physical
+/// resource (one oven, one meeting room).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ExclusionGroup {
+    pub resource: String,
+    pub task_ids: Vec<String>,
+}
+
+/// An acceptable finish range (ISO 8601 DateTime or YYYY-MM-DD) for an anchor task.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AnchorWindow {
+    pub earliest: String,
+    pub latest: String,
+}
+
+/// Where an anchor window's task landed.
+///
+/// The task finishes as late as its window and consumers allow, which leaves
+/// the most room for everything upstream of it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnchorWindowPlacement {
+    pub task_id: String,
+    pub earliest: String,
+    pub latest: String,
+    pub placed_at: String,
+    /// 0.0 at the earliest acceptable finish, 1.0 at the latest.
+    pub position: f64,
+    /// False when consumer constraints forced the finish before the window opens.
+    pub within_window: bool,
+}
+
+/// Whether the remaining work still fits between "now" and its deadlines.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ScheduleFeasibility {
+    pub is_feasible: bool,
+    /// Incomplete tasks whose required start is already in the past.
+    pub late_task_ids: Vec<String>,
+    /// How far before "now" the earliest incomplete task should have started.
+    pub shortfall_minutes: i64,
+    /// Extra pace needed to finish the remaining work on time, as a fraction
+    /// (0.25 = 25% more work per day). `None` when the last deadline has already
+    /// passed or the elapsed-time tasks alone no longer fit before it.
+    pub required_daily_overrun: Option<f64>,
+    #[serde(default)]
+    pub spacing_violations: Vec<SpacingViolation>,
+    #[serde(default)]
+    pub anchor_conflicts: Vec<AnchorConflict>,
+}
+
+/// Two anchors too close together for the work between them: finishing
+/// `upstream` on its date leaves `downstream` no way to finish on its own.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AnchorConflict {
+    pub upstream: String,
+    pub downstream: String,
+    pub upstream_anchor: String,
+    pub downstream_anchor: String,
+    /// Least time the work after `upstream` needs before `downstream` can finish.
+    pub required_gap_minutes: i64,
+    /// How much closer together than that the anchors are.
+    pub shortfall_minutes: i64,
+}
+
+/// Contingency carried by the chain of tasks that drives an anchor's start.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChainBuffer {
+    pub anchor_id: String,
+    /// Driving chain, ordered from its first task to the anchor.
+    pub task_ids: Vec<String>,
+    pub estimate_minutes: i64,
+    pub buffer_minutes: i64,
+}
+
+/// Dates spanned by the tasks in one phase.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PhaseSummary {
+    pub phase: String,
+    /// Earliest task start in the phase.
+    pub start_date: String,
+    /// Latest task finish in the phase.
+    pub end_date: String,
+    /// Least slack of any task in the phase.
+    pub slack_minutes: i64,
+    pub is_critical: bool,
+    pub task_count: usize,
+    pub completed_count: usize,
+}
+
+/// A non-fatal issue found while scheduling.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScheduleWarning {
+    /// An anchored task must finish before its anchor date to leave room for a consumer.
+    AnchorOverridden {
+        task_id: String,
+        anchor_date: String,
+        scheduled_end: String,
+        /// How much earlier than the anchor the task has to finish.
+        overridden_by_minutes: i64,
+        /// The consumer (or exclusive-resource neighbour) whose start imposed the earlier finish.
+        constrained_by: Option<String>,
+    },
+    /// None of the task's allowed windows fits it before its required finish;
+    /// it's left at its unconstrained slot.
+    NoAllowedWindow {
+        task_id: String,
+        required_finish: String,
+    },
+    /// Tasks sharing an exclusive resource overlap and there isn't enough slack to serialize them.
+    ExclusionConflict {
+        resource: String,
+        task_ids: Vec<String>,
+        overlap_minutes: i64,
+    },
+    /// With `serialize_work`, a pinned task overlaps the next task in the sequence.
+    SerialWorkOverlap {
+        task_ids: Vec<String>,
+        overlap_minutes: i64,
+    },
+    /// With `serialize_work`, the sequence has to start before now to meet its anchors.
+    SerialWorkStartsInPast {
+        task_id: String,
+        required_start: String,
+        shortfall_minutes: i64,
+    },
+    /// With `floor_starts`, work that can't start before the floor pushes an
+    /// anchor past its date.
+    FloorOverrun {
+        task_id: String,
+        anchor_date: String,
+        projected_finish: String,
+        overrun_minutes: i64,
+    },
+    /// A pinned task finishes after a dependent task has to start, or after
+    /// its own anchor when `constrained_by` is `None`.
+    PinConflict {
+        task_id: String,
+        constrained_by: Option<String>,
+        overlap_minutes: i64,
+    },
+    /// No anchor or pin depends on these tasks, so they have no deadline;
+    /// they're placed as soon as their dependencies allow instead.
+    Unanchored { task_ids: Vec<String> },
+    /// An incomplete task's latest start comes before its earliest start,
+    /// e.g. because a pin or exclusion squeezed it.
+    NegativeSlack { task_id: String, slack_minutes: i64 },
+}
+
+/// Output of the scheduler.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ScheduleResult {
+    /// Tasks in topological order (providers before consumers). Tasks that
+    /// become ready together are ordered by start date, then by ID.
+    pub scheduled_tasks: Vec<ScheduledTask>,
+    pub feasibility: ScheduleFeasibility,
+    pub chain_buffers: Vec<ChainBuffer>,
+    /// Per critical anchor (by ID), its critical tasks in order from the
+    /// first one through to the anchor.
+    #[serde(default)]
+    pub critical_chains: Vec<Vec<String>>,
+    /// Per phase, ordered by start date.
+    #[serde(default)]
+    pub phases: Vec<PhaseSummary>,
+    pub anchor_windows: Vec<AnchorWindowPlacement>,
+    pub warnings: Vec<ScheduleWarning>,
 }
 
 /// Errors that can occur during schedule calculation.
@@ -63,6 +590,24 @@ pub enum ScheduleError {
     #[error("Invalid date format for anchor task '{task_id}': {details}")]
     InvalidAnchorDate { task_id: String, details: String },
 
+    #[error("Invalid reference time: {0}")]
+    InvalidReferenceTime(String),
+
+    #[error("Invalid time zone: {0}")]
+    InvalidTimezone(String),
+
+    #[error("Invalid start floor: {0}")]
+    InvalidStartFloor(String),
+
+    #[error("Invalid allowed window for task '{task_id}': {details}")]
+    InvalidAllowedWindow { task_id: String, details: String },
+
+    #[error("Invalid busy slot: {0}")]
+    InvalidBusySlot(String),
+
+    #[error("Invalid pinned start for task '{task_id}': {details}")]
+    InvalidPinnedStart { task_id: String, details: String },
+
     #[error("Anchor task '{0}' not found in task list")]
     AnchorTaskNotFound(String),
 
@@ -72,15 +617,23 @@ pub enum ScheduleError {
     #[error("No end date computed for task '{0}' - check for disconnected dependencies")]
     NoEndDateComputed(String),
 
-    #[allow(dead_code)]
     #[error("Cycle detected in task dependencies")]
     CycleDetected,
 }
 
-fn parse_date_string(s: &str) -> Result<NaiveDateTime, String> {
-    // Try ISO 8601 DateTime first
-    if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S") {
-        return Ok(dt);
+/// Reads a stored date. Typed dates in other forms go through
+/// [`crate::dates::normalize`] first.
+pub(crate) fn parse_date_string(s: &str) -> Result<NaiveDateTime, String> {
+    // Try ISO 8601 DateTime first, then with a space or without seconds
+    for format in [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(s, format) {
+            return Ok(dt);
+        }
     }
     // Try YYYY-MM-DD and assume end of day (23:59:59)
     if let Ok(d) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
@@ -94,232 +647,1453 @@ fn parse_date_string(s: &str) -> Result<NaiveDateTime, String> {
     ))
 }
 
+/// Index-based view of a request's tasks.
+///
+/// IDs are interned to positions in `tasks` once so both passes run on
+/// `Vec`-backed structures; strings only reappear in the output.
+struct TaskGraph<'a> {
+    tasks: &'a [Task],
+    index: HashMap<&'a str, usize>,
+    /// Each task's providers (its dependencies).
+    providers: Vec<Vec<usize>>,
+    /// Per provider, minutes of its span the consumer may overlap.
+    leads: Vec<Vec<i64>>,
+    /// Each task's consumers (the tasks depending on it).
+    consumers: Vec<Vec<usize>>,
+}
+
+impl<'a> TaskGraph<'a> {
+    fn new(tasks: &'a [Task]) -> Result<Self, ScheduleError> {
+        let index: HashMap<&str, usize> = tasks
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.id.as_str(), i))
+            .collect();
+
+        let mut providers = vec![Vec::new(); tasks.len()];
+        let mut leads = vec![Vec::new(); tasks.len()];
+        let mut consumers = vec![Vec::new(); tasks.len()];
+        for (i, task) in tasks.iter().enumerate() {
+            for dep_id in &task.dependencies {
+                let p = *index
+                    .get(dep_id.as_str())
+                    .ok_or_else(|| ScheduleError::TaskNotFound(dep_id.clone()))?;
+                providers[i].push(p);
+                leads[i].push(
+                    task.start_after_percent
+                        .get(dep_id)
+                        .map_or(0, |&percent| tasks[p].remaining_minutes(percent)),
+                );
+                consumers[p].push(i);
+            }
+        }
+
+        Ok(Self {
+            tasks,
+            index,
+            providers,
+            leads,
+            consumers,
+        })
+    }
+
+    fn lookup(&self, id: &str) -> Option<usize> {
+        self.index.get(id).copied()
+    }
+
+    /// Task `i`'s providers, each with the latest it may finish when `i`
+    /// starts at `start`.
+    fn provider_deadlines(
+        &self,
+        i: usize,
+        start: NaiveDateTime,
+        zone: Zone,
+    ) -> impl Iterator<Item = (usize, NaiveDateTime)> + '_ {
+        self.providers[i]
+            .iter()
+            .zip(&self.leads[i])
+            .map(move |(&p, &lead)| (p, zone.add_minutes(start, lead)))
+    }
+}
+
 /// Calculate a backwards schedule with critical path analysis.
 pub fn calculate_backwards_schedule(
     request: ScheduleRequest,
-) -> Result<Vec<ScheduledTask>, ScheduleError> {
-    let task_map: HashMap<String, Task> = request
-        .tasks
-        .iter()
-        .map(|t| (t.id.clone(), t.clone()))
-        .collect();
+) -> Result<ScheduleResult, ScheduleError> {
+    let (now, zone) = request_clock(&request)?;
 
     if request.tasks.is_empty() {
-        return Ok(Vec::new());
+        return Ok(ScheduleResult {
+            scheduled_tasks: Vec::new(),
+            feasibility: assess_feasibility(&[], now),
+            ..Default::default()
+        });
     }
 
-    // --- Backward Pass (Calculate Late Start/Finish) ---
-    // Build reverse dependency map: provider -> consumers (to find roots for backward pass)
-    let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
-    for task in &request.tasks {
-        for dep_id in &task.dependencies {
-            dependents
-                .entry(dep_id.clone())
-                .or_default()
-                .push(task.id.clone());
+    let graph = TaskGraph::new(&request.tasks)?;
+    let tasks = graph.tasks;
+    let n = tasks.len();
+
+    // Allowed windows per task; empty means unconstrained
+    let mut allowed: Vec<Vec<(NaiveDateTime, NaiveDateTime)>> = vec![Vec::new(); n];
+    for (i, task) in tasks.iter().enumerate() {
+        if !task.only_during.is_empty() {
+            allowed[i] = allowed_windows(task, zone)?;
         }
     }
+    let pinned: Vec<Option<(NaiveDateTime, NaiveDateTime)>> = tasks
+        .iter()
+        .map(|task| pinned_dates(task, zone))
+        .collect::<Result<_, _>>()?;
+    let busy = busy_slots(&request.busy, zone)?;
+    // Minutes of work for tasks that flow around busy slots
+    let work_minutes: Vec<Option<i64>> = tasks
+        .iter()
+        .zip(&allowed)
+        .zip(&pinned)
+        .map(|((task, allowed), pin)| {
+            let minutes = (task.duration() + task.buffer()).num_minutes();
+            (!busy.is_empty()
+                && task.works_around_busy()
+                && allowed.is_empty()
+                && pin.is_none()
+                && minutes > 0)
+                .then_some(minutes)
+        })
+        .collect();
+
+    // --- Backward Pass (Calculate Late Start/Finish) ---
 
     // Initialize end dates from anchors
-    let mut late_finish: HashMap<String, NaiveDateTime> = HashMap::new();
+    let mut late_finish: Vec<Option<NaiveDateTime>> = vec![None; n];
     for (task_id, date_str) in &request.anchors {
-        if !task_map.contains_key(task_id) {
-            return Err(ScheduleError::AnchorTaskNotFound(task_id.clone()));
-        }
+        let i = graph
+            .lookup(task_id)
+            .ok_or_else(|| ScheduleError::AnchorTaskNotFound(task_id.clone()))?;
 
         let date = parse_date_string(date_str).map_err(|e| ScheduleError::InvalidAnchorDate {
             task_id: task_id.clone(),
             details: e,
         })?;
 
-        late_finish.insert(task_id.clone(), date);
+        late_finish[i] = Some(zone.normalize(date));
     }
 
-    let mut unscheduled_consumers: HashMap<String, usize> = dependents
-        .iter()
-        .map(|(id, consumers)| (id.clone(), consumers.len()))
+    // Windows seed their latest acceptable finish; a point anchor on the same task still wins if tighter
+    let mut windows: Vec<(usize, NaiveDateTime, NaiveDateTime)> = Vec::new();
+    for (task_id, window) in &request.anchor_windows {
+        let i = graph
+            .lookup(task_id)
+            .ok_or_else(|| ScheduleError::AnchorTaskNotFound(task_id.clone()))?;
+
+        let parse = |s: &str| {
+            parse_date_string(s).map_err(|e| ScheduleError::InvalidAnchorDate {
+                task_id: task_id.clone(),
+                details: e,
+            })
+        };
+        let earliest = zone.normalize(parse(&window.earliest)?);
+        let latest = zone.normalize(parse(&window.latest)?);
+        if earliest > latest {
+            return Err(ScheduleError::InvalidAnchorDate {
+                task_id: task_id.clone(),
+                details: format!(
+                    "window opens ({}) after it closes ({})",
+                    window.earliest, window.latest
+                ),
+            });
+        }
+
+        late_finish[i] = Some(late_finish[i].map_or(latest, |lf| lf.min(latest)));
+        windows.push((i, earliest, latest));
+    }
+    let anchored = late_finish.clone();
+    let anchor_conflicts = check_anchor_conflicts(&graph, &anchored, zone);
+
+    // Tasks an anchor or pin depends on; the rest have no deadline to work back from
+    let mut reachable = vec![false; n];
+    let mut stack: Vec<usize> = (0..n)
+        .filter(|&i| anchored[i].is_some() || pinned[i].is_some())
         .collect();
+    while let Some(i) = stack.pop() {
+        if !reachable[i] {
+            reachable[i] = true;
+            stack.extend(&graph.providers[i]);
+        }
+    }
 
-    let mut queue: Vec<String> = request
-        .tasks
+    // Topological order: a task is ready once all of its consumers are scheduled
+    let mut unscheduled_consumers: Vec<usize> = graph
+        .consumers
         .iter()
-        .filter(|t| !dependents.contains_key(&t.id))
-        .map(|t| t.id.clone())
+        .map(|consumers| consumers.iter().filter(|&&c| reachable[c]).count())
         .collect();
-    let mut visited_backward = HashSet::new();
+    let mut queue: Vec<usize> = (0..n)
+        .filter(|&i| reachable[i] && unscheduled_consumers[i] == 0)
+        .collect();
+
+    let mut unplaceable: Vec<(&String, NaiveDateTime)> = Vec::new();
 
-    // We need to capture the results of the backward pass
-    let mut backward_schedule: HashMap<String, (NaiveDateTime, NaiveDateTime)> = HashMap::new(); // id -> (start, end)
+    // Consumer that set each provider's late finish, for explaining overridden anchors
+    let mut binding_consumer: Vec<Option<usize>> = vec![None; n];
 
-    // Using a proper topological sort based on unscheduled_consumers count
-    while let Some(task_id) = queue.pop() {
-        if visited_backward.contains(&task_id) {
+    let mut backward: Vec<Option<(NaiveDateTime, NaiveDateTime)>> = vec![None; n]; // (start, end)
+
+    while let Some(i) = queue.pop() {
+        if backward[i].is_some() {
             continue;
         }
+        let task = &tasks[i];
 
-        let task = task_map
-            .get(&task_id)
-            .ok_or_else(|| ScheduleError::TaskNotFound(task_id.clone()))?;
-
-        // Late Finish is already set either by Anchor or by successors
-        let lf = *late_finish
-            .get(&task_id)
-            .ok_or_else(|| ScheduleError::NoEndDateComputed(task.name.clone()))?;
+        // Late Finish is already set either by Anchor or by successors; pins need neither
+        let lf = match (pinned[i], late_finish[i]) {
+            (Some((_, pinned_finish)), _) => pinned_finish,
+            (None, Some(lf)) => lf,
+            (None, None) => return Err(ScheduleError::NoEndDateComputed(task.name.clone())),
+        };
 
-        // Calculate duration logic
-        let duration = if let Some(mins) = task.duration_minutes {
-            Duration::minutes(mins)
+        let (ls, lf) = if let Some(pin) = pinned[i] {
+            pin
+        } else if let Some(minutes) = work_minutes[i] {
+            work_backward(zone, minutes, lf, &busy)
+        } else if allowed[i].is_empty() {
+            (task.start_for_finish(zone, lf), lf)
         } else {
-            Duration::days(task.duration_days)
+            fit_backward(task, zone, lf, &allowed[i]).unwrap_or_else(|| {
+                unplaceable.push((&task.id, lf));
+                (task.start_for_finish(zone, lf), lf)
+            })
         };
+        backward[i] = Some((ls, lf));
 
-        let ls = lf - duration;
-        backward_schedule.insert(task.id.clone(), (ls, lf));
-        visited_backward.insert(task_id.clone());
+        // Propagate to providers: each must end by this task's start, or
+        // only reach its overlap point by then
+        for (p, deadline) in graph.provider_deadlines(i, ls, zone) {
+            match late_finish[p] {
+                Some(existing) if existing < deadline => {}
+                Some(existing) if existing == deadline => {
+                    // Ties credit the lowest consumer ID, independent of visiting order
+                    if binding_consumer[p].is_some_and(|b| task.id < tasks[b].id) {
+                        binding_consumer[p] = Some(i);
+                    }
+                }
+                _ => {
+                    late_finish[p] = Some(deadline);
+                    binding_consumer[p] = Some(i);
+                }
+            }
 
-        // Propagate to dependencies (providers)
-        for provider_id in &task.dependencies {
-            // Provider must end by this task's start (Late Finish of provider <= Late Start of consumer)
-            let entry = late_finish
-                .entry(provider_id.clone())
-                .or_insert(NaiveDateTime::MAX);
-            if ls < *entry {
-                *entry = ls;
+            unscheduled_consumers[p] -= 1;
+            if unscheduled_consumers[p] == 0 {
+                queue.push(p);
             }
+        }
+    }
 
-            // Decrement consumer count
-            if let Some(count) = unscheduled_consumers.get_mut(provider_id) {
-                *count -= 1;
-                if *count == 0 {
-                    queue.push(provider_id.clone());
-                }
+    // Unanchored tasks start as soon as their providers finish, from the
+    // start of the anchored work (or now, when nothing is anchored)
+    let fallback_start = backward
+        .iter()
+        .flatten()
+        .map(|(start, _)| *start)
+        .min()
+        .unwrap_or(now);
+    let mut waiting: Vec<usize> = graph
+        .providers
+        .iter()
+        .map(|providers| providers.iter().filter(|&&p| !reachable[p]).count())
+        .collect();
+    let mut ready: Vec<usize> = (0..n)
+        .filter(|&i| !reachable[i] && waiting[i] == 0)
+        .collect();
+    let mut unanchored: Vec<String> = Vec::new();
+    while let Some(i) = ready.pop() {
+        let task = &tasks[i];
+        let start = graph.providers[i]
+            .iter()
+            .zip(&graph.leads[i])
+            .filter_map(|(&p, &lead)| Some(zone.add_minutes(backward[p]?.1, -lead)))
+            .fold(fallback_start, NaiveDateTime::max);
+        backward[i] = Some(if let Some(minutes) = work_minutes[i] {
+            work_forward(zone, minutes, start, &busy)
+        } else {
+            let start = fit_forward(task, zone, start, &allowed[i]).unwrap_or(start);
+            (start, task.finish_for_start(zone, start))
+        });
+        unanchored.push(task.id.clone());
+
+        for &c in &graph.consumers[i] {
+            waiting[c] -= 1;
+            if waiting[c] == 0 {
+                ready.push(c);
             }
         }
     }
 
     // Verify all tasks were scheduled
-    if backward_schedule.len() != request.tasks.len() {
-        // Find which tasks are missing
-        let scheduled_ids: HashSet<_> = backward_schedule.keys().collect();
-        let missing_tasks: Vec<String> = request
-            .tasks
-            .iter()
-            .filter(|t| !scheduled_ids.contains(&t.id))
-            .map(|t| t.name.clone())
-            .collect();
+    let missing_tasks: Vec<String> = tasks
+        .iter()
+        .zip(&backward)
+        .filter(|(_, dates)| dates.is_none())
+        .map(|(t, _)| t.name.clone())
+        .collect();
+    if !missing_tasks.is_empty() {
+        return Err(ScheduleError::NoEndDateComputed(format!(
+            "Tasks not processing from anchors (cycle?): {:?}",
+            missing_tasks
+        )));
+    }
+    let mut backward: Vec<(NaiveDateTime, NaiveDateTime)> =
+        backward.into_iter().flatten().collect();
 
-        if !missing_tasks.is_empty() {
-            return Err(ScheduleError::NoEndDateComputed(format!(
-                "Tasks not processing from anchors (disconnected?): {:?}",
-                missing_tasks
-            )));
+    // Task each task follows in the single-user sequence, if leveled
+    let (follows, leveling_warnings) = if request.serialize_work {
+        level_work(
+            zone,
+            now,
+            &graph,
+            &pinned,
+            &mut backward,
+            &mut binding_consumer,
+        )
+    } else {
+        (vec![None; n], Vec::new())
+    };
+    let mut followed_by: Vec<Option<usize>> = vec![None; n];
+    for (i, prev) in follows.iter().enumerate() {
+        if let Some(prev) = *prev {
+            followed_by[prev] = Some(i);
         }
     }
 
     // --- Forward Pass (Calculate Early Start/Finish) ---
 
     // Project start is the earliest start date from the backward pass
-    let project_start = backward_schedule
-        .values()
+    let project_start = backward
+        .iter()
         .map(|(start, _)| *start)
         .min()
         .ok_or(ScheduleError::CycleDetected)?; // Should not be empty if tasks exist
 
-    let mut early_finish: HashMap<String, NaiveDateTime> = HashMap::new();
-    let mut early_start: HashMap<String, NaiveDateTime> = HashMap::new();
-
-    // In-degrees for Forward Pass are simply the number of dependencies
-    let mut in_degree: HashMap<String, usize> = request
-        .tasks
-        .iter()
-        .map(|t| (t.id.clone(), t.dependencies.len()))
-        .collect();
+    let mut early_start: Vec<Option<NaiveDateTime>> = vec![None; n];
+    let mut early_finish: Vec<Option<NaiveDateTime>> = vec![None; n];
 
-    // Queue for forward pass (Tasks with 0 dependencies)
-    let mut forward_queue: Vec<String> = request
-        .tasks
-        .iter()
-        .filter(|t| t.dependencies.is_empty())
-        .map(|t| t.id.clone())
+    // A task is ready once all of its providers (and the task it follows) are scheduled
+    let mut in_degree: Vec<usize> = (0..n)
+        .map(|i| graph.providers[i].len() + usize::from(follows[i].is_some()))
         .collect();
+    let mut forward_queue: Vec<usize> = (0..n).filter(|&i| in_degree[i] == 0).collect();
 
-    let mut visited_forward = HashSet::new();
-
-    while let Some(task_id) = forward_queue.pop() {
-        visited_forward.insert(task_id.clone());
-        let task = task_map.get(&task_id).unwrap();
-
-        // Calculate Early Start (ES)
-        // ES = max(EF of dependencies), else Project Start
-        let es = if task.dependencies.is_empty() {
-            project_start
-        } else {
-            let mut max_ef = project_start; // Fallback
-            for dep in &task.dependencies {
-                if let Some(&ef) = early_finish.get(dep) {
-                    if ef > max_ef {
-                        max_ef = ef;
-                    }
-                }
-            }
-            max_ef
-        };
+    while let Some(i) = forward_queue.pop() {
+        let task = &tasks[i];
 
-        let duration = if let Some(mins) = task.duration_minutes {
-            Duration::minutes(mins)
+        // ES = max(EF of dependencies, less any overlap), else Project Start
+        let es = graph.providers[i]
+            .iter()
+            .zip(&graph.leads[i])
+            .filter_map(|(&p, &lead)| Some(zone.add_minutes(early_finish[p]?, -lead)))
+            .chain(follows[i].and_then(|prev| early_finish[prev]))
+            .fold(project_start, NaiveDateTime::max);
+        let (es, ef) = if let Some(minutes) = work_minutes[i] {
+            work_forward(zone, minutes, es, &busy)
         } else {
-            Duration::days(task.duration_days)
+            let es = if let Some((pinned_start, _)) = pinned[i] {
+                pinned_start
+            } else if allowed[i].is_empty() {
+                es
+            } else {
+                fit_forward(task, zone, es, &allowed[i]).unwrap_or(es)
+            };
+            (es, task.finish_for_start(zone, es))
         };
 
-        let ef = es + duration;
-        early_start.insert(task_id.clone(), es);
-        early_finish.insert(task_id.clone(), ef);
+        early_start[i] = Some(es);
+        early_finish[i] = Some(ef);
 
-        // Propagate to consumers (dependents)
-        if let Some(consumers) = dependents.get(&task_id) {
-            for consumer in consumers {
-                if let Some(degree) = in_degree.get_mut(consumer) {
-                    *degree -= 1;
-                    if *degree == 0 {
-                        forward_queue.push(consumer.clone());
-                    }
-                }
+        for &c in graph.consumers[i].iter().chain(&followed_by[i]) {
+            in_degree[c] -= 1;
+            if in_degree[c] == 0 {
+                forward_queue.push(c);
             }
         }
     }
 
+    // Fallback if forward pass missed a task
+    let mut early_start: Vec<NaiveDateTime> = early_start
+        .iter()
+        .zip(&backward)
+        .map(|(es, (ls, _))| es.unwrap_or(*ls))
+        .collect();
+
+    let exclusion_warnings = serialize_exclusions(
+        &request.exclusions,
+        zone,
+        &graph,
+        &early_start,
+        &pinned,
+        &mut backward,
+        &mut binding_consumer,
+    )?;
+    let pin_warnings = pin_conflicts(&pinned, &anchored, zone, &graph, &backward);
+    let floor_warnings = if request.floor_starts {
+        let floor = match &request.start_floor {
+            Some(s) => {
+                zone.normalize(parse_start_date(s).map_err(ScheduleError::InvalidStartFloor)?)
+            }
+            None => now,
+        };
+        apply_start_floor(
+            floor,
+            zone,
+            &graph,
+            &pinned,
+            &anchored,
+            &mut backward,
+            &mut early_start,
+        )
+    } else {
+        Vec::new()
+    };
+
     // --- Combine & Result ---
 
-    let mut final_schedule = Vec::new();
-
-    for task in &request.tasks {
-        if let Some((ls, lf)) = backward_schedule.get(&task.id) {
-            let es = early_start.get(&task.id).unwrap_or(ls); // Fallback if forward pass missed it (disconnected?)
-
-            // Slack = LS - ES
-            let slack_minutes = (*ls - *es).num_minutes();
-            let is_critical = slack_minutes <= 0; // Float precision or tight constraints
-
-            final_schedule.push(ScheduledTask {
-                id: task.id.clone(),
-                name: task.name.clone(),
-                start_date: ls.format("%Y-%m-%dT%H:%M:%S").to_string(),
-                end_date: lf.format("%Y-%m-%dT%H:%M:%S").to_string(),
-                completed: task.completed,
-                notes: task.notes.clone(),
-                is_critical,
-                slack_minutes,
-                is_milestone: task.is_milestone,
+    let order = topological_order(&graph, &backward);
+    let planned = request.spread_slack.then(|| {
+        spread_slack(
+            zone,
+            &graph,
+            &order,
+            &backward,
+            &early_start,
+            &binding_consumer,
+            |i, start| match work_minutes[i] {
+                Some(minutes) => work_forward(zone, minutes, start, &busy).1,
+                None => tasks[i].finish_for_start(zone, start),
+            },
+        )
+    });
+    let final_schedule: Vec<ScheduledTask> = order
+        .iter()
+        .map(|&i| {
+            let (ls, lf) = backward[i];
+            let mut row = scheduled_task(&tasks[i], early_start[i], ls, lf, zone, now);
+            // Windows and pins keep their late placement
+            if let Some((start, finish)) = planned
+                .as_ref()
+                .map(|p| p[i])
+                .filter(|_| allowed[i].is_empty() && pinned[i].is_none())
+            {
+                row.start_date = start.format("%Y-%m-%dT%H:%M:%S").to_string();
+                row.end_date = finish.format("%Y-%m-%dT%H:%M:%S").to_string();
+                row.slack_minutes = zone.minutes_between(start, ls);
+                row.starts_in_past = !row.completed && start < now;
+            }
+            row
+        })
+        .collect();
+
+    let phases = phase_summaries(&final_schedule);
+    let mut feasibility = assess_feasibility(&final_schedule, now);
+    feasibility.spacing_violations =
+        check_anchor_spacing(&request.anchor_spacing, &final_schedule, zone)?;
+    feasibility.is_feasible &= feasibility.spacing_violations.is_empty();
+    feasibility.anchor_conflicts = anchor_conflicts;
+    feasibility.is_feasible &= feasibility.anchor_conflicts.is_empty();
+    feasibility.is_feasible &= floor_warnings.is_empty();
+
+    let anchor_ids: BTreeSet<&String> = request
+        .anchors
+        .keys()
+        .chain(request.anchor_windows.keys())
+        .collect();
+    let anchors: Vec<usize> = anchor_ids
+        .into_iter()
+        .filter_map(|anchor_id| graph.lookup(anchor_id))
+        .collect();
+    let critical: Vec<bool> = (0..n)
+        .map(|i| zone.minutes_between(early_start[i], backward[i].0) <= 0)
+        .collect();
+    let critical_chains = anchors
+        .iter()
+        .filter(|&&anchor| critical[anchor])
+        .map(|&anchor| {
+            driving_chain(anchor, &graph, zone, &backward, |p| critical[p])
+                .into_iter()
+                .map(|i| tasks[i].id.clone())
+                .collect()
+        })
+        .collect();
+    let chain_buffers = anchors
+        .iter()
+        .map(|&anchor| {
+            let chain = driving_chain(anchor, &graph, zone, &backward, |_| true);
+            let (estimate, buffer) = chain.iter().map(|&i| &tasks[i]).fold((0, 0), |(e, b), t| {
+                (e + t.duration().num_minutes(), b + t.buffer().num_minutes())
+            });
+            ChainBuffer {
+                anchor_id: tasks[anchor].id.clone(),
+                task_ids: chain.iter().map(|&i| tasks[i].id.clone()).collect(),
+                estimate_minutes: estimate,
+                buffer_minutes: buffer,
+            }
+        })
+        .collect();
+
+    // Anchors pulled earlier by their consumers; windows only count once pulled out of the window
+    let mut warnings = leveling_warnings;
+    warnings.extend(exclusion_warnings);
+    warnings.extend(pin_warnings);
+    warnings.extend(floor_warnings);
+    if !unanchored.is_empty() {
+        unanchored.sort();
+        warnings.push(ScheduleWarning::Unanchored {
+            task_ids: unanchored,
+        });
+    }
+    unplaceable.sort();
+    warnings.extend(unplaceable.into_iter().map(|(task_id, lf)| {
+        ScheduleWarning::NoAllowedWindow {
+            task_id: task_id.clone(),
+            required_finish: lf.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        }
+    }));
+    let mut anchor_limits: Vec<(&String, usize, NaiveDateTime)> = request
+        .anchors
+        .iter()
+        .filter_map(|(id, date)| Some((id, graph.lookup(id)?, parse_date_string(date).ok()?)))
+        .chain(
+            windows
+                .iter()
+                .map(|(i, earliest, _)| (&tasks[*i].id, *i, *earliest)),
+        )
+        .collect();
+    anchor_limits.sort();
+    for (task_id, i, anchor_date) in anchor_limits {
+        let (_, scheduled_end) = backward[i];
+        // A pin placing the task early is the user's choice, not an override
+        if scheduled_end < anchor_date && pinned[i].is_none() {
+            warnings.push(ScheduleWarning::AnchorOverridden {
+                task_id: task_id.clone(),
+                anchor_date: anchor_date.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                scheduled_end: scheduled_end.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                overridden_by_minutes: (anchor_date - scheduled_end).num_minutes(),
+                constrained_by: binding_consumer[i].map(|c| tasks[c].id.clone()),
             });
         }
     }
+    warnings.extend(
+        final_schedule
+            .iter()
+            .filter(|t| !t.completed && t.slack_minutes < 0)
+            .map(|t| ScheduleWarning::NegativeSlack {
+                task_id: t.id.clone(),
+                slack_minutes: t.slack_minutes,
+            }),
+    );
+
+    windows.sort_by(|a, b| tasks[a.0].id.cmp(&tasks[b.0].id));
+    let anchor_windows = windows
+        .into_iter()
+        .map(|(i, earliest, latest)| {
+            let (_, placed) = backward[i];
+            let span = (latest - earliest).num_seconds();
+            let position = if span == 0 {
+                1.0
+            } else {
+                ((placed - earliest).num_seconds() as f64 / span as f64).clamp(0.0, 1.0)
+            };
+            AnchorWindowPlacement {
+                task_id: tasks[i].id.clone(),
+                earliest: earliest.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                latest: latest.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                placed_at: placed.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                position,
+                within_window: placed >= earliest,
+            }
+        })
+        .collect();
 
-    Ok(final_schedule)
+    Ok(ScheduleResult {
+        scheduled_tasks: final_schedule,
+        feasibility,
+        chain_buffers,
+        critical_chains,
+        phases,
+        anchor_windows,
+        warnings,
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// The earliest an anchor can finish, compared with its current date.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AnchorSuggestion {
+    pub task_id: String,
+    /// Suggested anchor date: the earliest achievable finish.
+    pub earliest_finish: String,
+    /// The anchor's current date (a window's latest date).
+    pub current_anchor: Option<String>,
+    /// Room between the earliest finish and the current anchor; negative when
+    /// the current anchor can't be met from the start date.
+    pub slack_minutes: Option<i64>,
+}
+
+/// Runs the schedule forwards from `start`: every incomplete task begins as
+/// soon as its dependencies allow, and each anchor gets its earliest
+/// achievable finish. Completed tasks take no time; pins and allowed windows
+/// still apply, exclusions and single-user leveling don't.
+pub fn suggest_anchor_dates(
+    request: &ScheduleRequest,
+    start: NaiveDateTime,
+) -> Result<Vec<AnchorSuggestion>, ScheduleError> {
+    let (_, zone) = request_clock(request)?;
+    let graph = TaskGraph::new(&request.tasks)?;
+    let tasks = graph.tasks;
+    let n = tasks.len();
+    let start = zone.normalize(start);
+
+    let mut early_finish: Vec<Option<NaiveDateTime>> = vec![None; n];
+    let mut in_degree: Vec<usize> = graph.providers.iter().map(Vec::len).collect();
+    let mut queue: Vec<usize> = (0..n).filter(|&i| in_degree[i] == 0).collect();
+    while let Some(i) = queue.pop() {
+        let task = &tasks[i];
+        let es = graph.providers[i]
+            .iter()
+            .zip(&graph.leads[i])
+            .filter_map(|(&p, &lead)| Some(zone.add_minutes(early_finish[p]?, -lead)))
+            .fold(start, NaiveDateTime::max);
+
+        early_finish[i] = Some(if task.completed {
+            es
+        } else if let Some((_, pinned_finish)) = pinned_dates(task, zone)? {
+            pinned_finish
+        } else if task.only_during.is_empty() {
+            task.finish_for_start(zone, es)
+        } else {
+            let windows = allowed_windows(task, zone)?;
+            task.finish_for_start(zone, fit_forward(task, zone, es, &windows).unwrap_or(es))
+        });
+
+        for &c in &graph.consumers[i] {
+            in_degree[c] -= 1;
+            if in_degree[c] == 0 {
+                queue.push(c);
+            }
+        }
+    }
+
+    let mut anchors: Vec<(&String, Option<&String>)> = request
+        .anchors
+        .iter()
+        .map(|(id, date)| (id, Some(date)))
+        .chain(
+            request
+                .anchor_windows
+                .iter()
+                .map(|(id, window)| (id, Some(&window.latest))),
+        )
+        .collect();
+    anchors.sort();
+    anchors.dedup_by(|a, b| a.0 == b.0);
+
+    anchors
+        .into_iter()
+        .map(|(task_id, current)| {
+            let i = graph
+                .lookup(task_id)
+                .ok_or_else(|| ScheduleError::AnchorTaskNotFound(task_id.clone()))?;
+            let finish = early_finish[i].ok_or(ScheduleError::CycleDetected)?;
+            let current = current
+                .map(|date| {
+                    parse_date_string(date).map_err(|details| ScheduleError::InvalidAnchorDate {
+                        task_id: task_id.clone(),
+                        details,
+                    })
+                })
+                .transpose()?
+                .map(|date| zone.normalize(date));
+            Ok(AnchorSuggestion {
+                task_id: task_id.clone(),
+                earliest_finish: finish.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                current_anchor: current.map(|d| d.format("%Y-%m-%dT%H:%M:%S").to_string()),
+                slack_minutes: current.map(|d| zone.minutes_between(finish, d)),
+            })
+        })
+        .collect()
+}
+
+/// Parses a task's allowed windows, sorted by start.
+fn allowed_windows(
+    task: &Task,
+    zone: Zone,
+) -> Result<Vec<(NaiveDateTime, NaiveDateTime)>, ScheduleError> {
+    let invalid = |details: String| ScheduleError::InvalidAllowedWindow {
+        task_id: task.id.clone(),
+        details,
+    };
+    let mut windows = Vec::new();
+    for window in &task.only_during {
+        let start = parse_start_date(&window.start).map_err(invalid)?;
+        let end = parse_date_string(&window.end).map_err(invalid)?;
+        if start >= end {
+            return Err(invalid(format!(
+                "window starts ({}) after it ends ({})",
+                window.start, window.end
+            )));
+        }
+        windows.push((zone.normalize(start), zone.normalize(end)));
+    }
+    windows.sort();
+    Ok(windows)
+}
+
+/// Busy slots sorted by start, with overlapping slots merged.
+fn busy_slots(
+    slots: &[BusySlot],
+    zone: Zone,
+) -> Result<Vec<(NaiveDateTime, NaiveDateTime)>, ScheduleError> {
+    let mut parsed = Vec::new();
+    for slot in slots {
+        let start = parse_start_date(&slot.start).map_err(ScheduleError::InvalidBusySlot)?;
+        let end = parse_date_string(&slot.end).map_err(ScheduleError::InvalidBusySlot)?;
+        if start >= end {
+            return Err(ScheduleError::InvalidBusySlot(format!(
+                "slot starts ({}) after it ends ({})",
+                slot.start, slot.end
+            )));
+        }
+        parsed.push((zone.normalize(start), zone.normalize(end)));
+    }
+    parsed.sort();
+
+    let mut merged: Vec<(NaiveDateTime, NaiveDateTime)> = Vec::new();
+    for (start, end) in parsed {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = (*last_end).max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    Ok(merged)
+}
+
+/// Latest (start, finish) for `minutes` of work done by `lf` in the free
+/// time between busy slots. The finish is where the last free stretch ends.
+fn work_backward(
+    zone: Zone,
+    minutes: i64,
+    lf: NaiveDateTime,
+    busy: &[(NaiveDateTime, NaiveDateTime)],
+) -> (NaiveDateTime, NaiveDateTime) {
+    let mut cursor = lf;
+    let mut remaining = minutes;
+    let mut finish = None;
+    for &(start, end) in busy.iter().rev() {
+        if start >= cursor {
+            continue;
+        }
+        if end < cursor {
+            let free = zone.minutes_between(end, cursor);
+            let finish = *finish.get_or_insert(cursor);
+            if free >= remaining {
+                return (zone.add_minutes(cursor, -remaining), finish);
+            }
+            remaining -= free;
+        }
+        cursor = start;
+    }
+    let finish = finish.unwrap_or(cursor);
+    (zone.add_minutes(cursor, -remaining), finish)
+}
+
+/// Earliest (start, finish) for `minutes` of work starting no sooner than
+/// `es`, in the free time between busy slots.
+fn work_forward(
+    zone: Zone,
+    minutes: i64,
+    es: NaiveDateTime,
+    busy: &[(NaiveDateTime, NaiveDateTime)],
+) -> (NaiveDateTime, NaiveDateTime) {
+    let mut cursor = es;
+    let mut remaining = minutes;
+    let mut begin = None;
+    for &(start, end) in busy {
+        if end <= cursor {
+            continue;
+        }
+        if start > cursor {
+            let free = zone.minutes_between(cursor, start);
+            let begin = *begin.get_or_insert(cursor);
+            if free >= remaining {
+                return (begin, zone.add_minutes(cursor, remaining));
+            }
+            remaining -= free;
+        }
+        cursor = end;
+    }
+    let begin = begin.unwrap_or(cursor);
+    (begin, zone.add_minutes(cursor, remaining))
+}
+
+/// Like [`parse_date_string`], but a bare date means the start of that day.
+pub(crate) fn parse_start_date(s: &str) -> Result<NaiveDateTime, String> {
+    match chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        Ok(d) => Ok(d.and_time(chrono::NaiveTime::MIN)),
+        Err(_) => parse_date_string(s),
+    }
+}
+
+/// A pinned task's fixed (start, finish).
+fn pinned_dates(
+    task: &Task,
+    zone: Zone,
+) -> Result<Option<(NaiveDateTime, NaiveDateTime)>, ScheduleError> {
+    let Some(pin) = &task.pinned_start else {
+        return Ok(None);
+    };
+    let start = parse_start_date(pin).map_err(|details| ScheduleError::InvalidPinnedStart {
+        task_id: task.id.clone(),
+        details,
+    })?;
+    let start = zone.normalize(start);
+    Ok(Some((start, task.finish_for_start(zone, start))))
+}
+
+/// Pinned tasks finishing after a consumer's start or their own anchor.
+fn pin_conflicts(
+    pinned: &[Option<(NaiveDateTime, NaiveDateTime)>],
+    anchored: &[Option<NaiveDateTime>],
+    zone: Zone,
+    graph: &TaskGraph,
+    backward: &[(NaiveDateTime, NaiveDateTime)],
+) -> Vec<ScheduleWarning> {
+    let mut warnings = Vec::new();
+    for (i, pin) in pinned.iter().enumerate() {
+        let Some((_, finish)) = *pin else { continue };
+        let task_id = &graph.tasks[i].id;
+
+        let mut limits: Vec<(Option<&String>, NaiveDateTime)> = graph.consumers[i]
+            .iter()
+            .flat_map(|&c| {
+                graph
+                    .provider_deadlines(c, backward[c].0, zone)
+                    .filter(move |&(p, _)| p == i)
+                    .map(move |(_, deadline)| (Some(&graph.tasks[c].id), deadline))
+            })
+            .collect();
+        limits.extend(anchored[i].map(|anchor| (None, anchor)));
+        limits.sort();
+
+        for (constrained_by, limit) in limits {
+            if finish > limit {
+                warnings.push(ScheduleWarning::PinConflict {
+                    task_id: task_id.clone(),
+                    constrained_by: constrained_by.cloned(),
+                    overlap_minutes: zone.minutes_between(limit, finish),
+                });
+            }
+        }
+    }
+    warnings
+}
+
+/// Latest placement finishing by `lf` that lies entirely inside one window.
+fn fit_backward(
+    task: &Task,
+    zone: Zone,
+    lf: NaiveDateTime,
+    windows: &[(NaiveDateTime, NaiveDateTime)],
+) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    windows.iter().rev().find_map(|(start, end)| {
+        let finish = lf.min(*end);
+        let begin = task.start_for_finish(zone, finish);
+        (begin >= *start).then_some((begin, finish))
+    })
+}
+
+/// Earliest start at or after `es` that lies entirely inside one window.
+fn fit_forward(
+    task: &Task,
+    zone: Zone,
+    es: NaiveDateTime,
+    windows: &[(NaiveDateTime, NaiveDateTime)],
+) -> Option<NaiveDateTime> {
+    windows.iter().find_map(|(start, end)| {
+        let begin = es.max(*start);
+        (task.finish_for_start(zone, begin) <= *end).then_some(begin)
+    })
+}
+
+/// Resolves the request's "now" and time zone.
+pub(crate) fn request_clock(
+    request: &ScheduleRequest,
+) -> Result<(NaiveDateTime, Zone), ScheduleError> {
+    let now = match &request.reference_time {
+        Some(s) => parse_date_string(s).map_err(ScheduleError::InvalidReferenceTime)?,
+        None => chrono::Local::now().naive_local(),
+    };
+    let zone = Zone::parse(request.timezone.as_deref()).map_err(ScheduleError::InvalidTimezone)?;
+    Ok((now, zone))
+}
+
+/// Builds the output row for a task from its early start and late start/finish.
+pub(crate) fn scheduled_task(
+    task: &Task,
+    es: NaiveDateTime,
+    ls: NaiveDateTime,
+    lf: NaiveDateTime,
+    zone: Zone,
+    now: NaiveDateTime,
+) -> ScheduledTask {
+    // Slack = LS - ES
+    let slack_minutes = zone.minutes_between(es, ls);
+    let is_critical = slack_minutes <= 0; // Float precision or tight constraints
+
+    ScheduledTask {
+        id: task.id.clone(),
+        name: task.name.clone(),
+        start_date: ls.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        end_date: lf.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        completed: task.completed,
+        notes: task.notes.clone(),
+        is_critical,
+        slack_minutes,
+        is_milestone: task.is_milestone,
+        is_elapsed: task.is_elapsed,
+        is_pinned: task.pinned_start.is_some(),
+        phase: task.phase.clone(),
+        tags: task.tags.clone(),
+        starts_in_past: !task.completed && ls < now,
+        estimate_minutes: task.duration().num_minutes(),
+        buffer_minutes: task.buffer().num_minutes(),
+        actual_minutes: task.actual_minutes(),
+    }
+}
+
+/// Planned (start, finish) per task with slack shared out along each chain.
+///
+/// A task's chain runs through the consumers that bound its late finish to
+/// an anchor. Each task starts the same fraction of its slack after its
+/// early start as the fraction of that chain's early span already behind it,
+/// so the first task starts early, the anchor finishes before its deadline,
+/// and the gaps between grow in proportion to the work. `finish_for` gives a
+/// task's finish for a given start.
+fn spread_slack(
+    zone: Zone,
+    graph: &TaskGraph,
+    order: &[usize],
+    backward: &[(NaiveDateTime, NaiveDateTime)],
+    early_start: &[NaiveDateTime],
+    binding_consumer: &[Option<usize>],
+    finish_for: impl Fn(usize, NaiveDateTime) -> NaiveDateTime,
+) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+    let n = order.len();
+    let Some(project_start) = early_start.iter().min().copied() else {
+        return Vec::new();
+    };
+
+    // Early finish of the anchor at the end of each task's chain
+    let mut chain_end: Vec<Option<NaiveDateTime>> = vec![None; n];
+    for &i in order.iter().rev() {
+        let mut end = i;
+        for _ in 0..n {
+            match binding_consumer[end] {
+                Some(c) if c != i => end = c,
+                _ => break,
+            }
+        }
+        chain_end[i] = Some(finish_for(end, early_start[end]));
+    }
+
+    let mut planned = vec![(project_start, project_start); n];
+    for &i in order {
+        let (ls, _) = backward[i];
+        let es = early_start[i];
+        let slack = zone.minutes_between(es, ls).max(0);
+        let span = chain_end[i].map_or(0, |end| zone.minutes_between(project_start, end));
+        let behind = zone.minutes_between(project_start, es);
+        let share = if span > 0 {
+            (slack as f64 * behind as f64 / span as f64).round() as i64
+        } else {
+            0
+        };
+
+        let start = graph.providers[i]
+            .iter()
+            .zip(&graph.leads[i])
+            .map(|(&p, &lead)| zone.add_minutes(planned[p].1, -lead))
+            .fold(zone.add_minutes(es, share), NaiveDateTime::max)
+            .min(ls);
+        planned[i] = (start, finish_for(i, start));
+    }
+    planned
+}
+
+/// Orders tasks so every provider precedes its consumers. Among tasks that
+/// are ready at the same time, the earlier start goes first, then the lower ID.
+fn topological_order(graph: &TaskGraph, backward: &[(NaiveDateTime, NaiveDateTime)]) -> Vec<usize> {
+    let key = |i: usize| Reverse((backward[i].0, graph.tasks[i].id.as_str(), i));
+    let mut in_degree: Vec<usize> = graph.providers.iter().map(Vec::len).collect();
+    let mut ready: BinaryHeap<_> = (0..in_degree.len())
+        .filter(|&i| in_degree[i] == 0)
+        .map(key)
+        .collect();
+
+    let mut order = Vec::with_capacity(in_degree.len());
+    while let Some(Reverse((_, _, i))) = ready.pop() {
+        order.push(i);
+        for &c in &graph.consumers[i] {
+            in_degree[c] -= 1;
+            if in_degree[c] == 0 {
+                ready.push(key(c));
+            }
+        }
+    }
+    order
+}
+
+/// Serializes each exclusion group, latest task first: a task overlapping the
+/// next one on the same resource is pulled earlier, along with its providers,
+/// as long as that stays within its slack. Overlaps that don't fit are warned about.
+fn serialize_exclusions(
+    exclusions: &[ExclusionGroup],
+    zone: Zone,
+    graph: &TaskGraph,
+    early_start: &[NaiveDateTime],
+    pinned: &[Option<(NaiveDateTime, NaiveDateTime)>],
+    backward: &mut [(NaiveDateTime, NaiveDateTime)],
+    binding_consumer: &mut [Option<usize>],
+) -> Result<Vec<ScheduleWarning>, ScheduleError> {
+    let mut warnings = Vec::new();
+
+    for group in exclusions {
+        let mut members = Vec::new();
+        for id in &group.task_ids {
+            let i = graph
+                .lookup(id)
+                .ok_or_else(|| ScheduleError::TaskNotFound(id.clone()))?;
+            let (ls, lf) = backward[i];
+            members.push((lf, zone.minutes_between(early_start[i], ls), id, i));
+        }
+        // Latest finish keeps its slot; on ties the task with less slack stays put
+        members.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(b.2)));
+        members.dedup_by(|a, b| a.3 == b.3);
+
+        for pair in members.windows(2) {
+            let (later, i) = (pair[0].3, pair[1].3);
+            let (later_start, later_finish) = backward[later];
+            let (start, finish) = backward[i];
+            if finish <= later_start || start >= later_finish {
+                continue;
+            }
+
+            let pulled_start = graph.tasks[i].start_for_finish(zone, later_start);
+            if pulled_start < early_start[i] || pinned[i].is_some() {
+                warnings.push(ScheduleWarning::ExclusionConflict {
+                    resource: group.resource.clone(),
+                    task_ids: vec![pair[1].2.clone(), pair[0].2.clone()],
+                    overlap_minutes: zone.minutes_between(later_start, finish.min(later_finish)),
+                });
+                continue;
+            }
+
+            binding_consumer[i] = Some(later);
+            pull_earlier(i, later_start, zone, graph, pinned, backward);
+        }
+    }
+
+    Ok(warnings)
+}
+
+/// Levels incomplete, non-elapsed work into one sequence, latest finish first:
+/// each task is pulled (with its providers) to finish by the time the task
+/// after it starts. Returns the task each one follows, plus warnings where a
+/// pin breaks the sequence or the sequence has to start before `now`.
+fn level_work(
+    zone: Zone,
+    now: NaiveDateTime,
+    graph: &TaskGraph,
+    pinned: &[Option<(NaiveDateTime, NaiveDateTime)>],
+    backward: &mut [(NaiveDateTime, NaiveDateTime)],
+    binding_consumer: &mut [Option<usize>],
+) -> (Vec<Option<usize>>, Vec<ScheduleWarning>) {
+    let tasks = graph.tasks;
+    let mut follows = vec![None; tasks.len()];
+    let mut warnings = Vec::new();
+
+    // Topological position breaks finish ties so zero-length consumers go after their providers
+    let mut position = vec![0; tasks.len()];
+    for (pos, i) in topological_order(graph, backward).into_iter().enumerate() {
+        position[i] = pos;
+    }
+    let mut queue: BinaryHeap<(NaiveDateTime, usize, usize)> = (0..tasks.len())
+        .filter(|&i| !tasks[i].completed && !tasks[i].is_elapsed)
+        .map(|i| (backward[i].1, position[i], i))
+        .collect();
+
+    // Most recently placed task and the start the next one has to finish by
+    let mut next: Option<(usize, NaiveDateTime)> = None;
+    while let Some((finish, _, i)) = queue.pop() {
+        // Pulled earlier along with a consumer since it was queued
+        if backward[i].1 != finish {
+            queue.push((backward[i].1, position[i], i));
+            continue;
+        }
+
+        let mut limit = backward[i].0;
+        if let Some((after, deadline)) = next {
+            if finish <= deadline {
+                follows[after] = Some(i);
+            } else if pinned[i].is_some() {
+                warnings.push(ScheduleWarning::SerialWorkOverlap {
+                    task_ids: vec![tasks[i].id.clone(), tasks[after].id.clone()],
+                    overlap_minutes: zone.minutes_between(deadline, finish.min(backward[after].1)),
+                });
+                limit = limit.min(deadline);
+            } else {
+                binding_consumer[i] = Some(after);
+                pull_earlier(i, deadline, zone, graph, pinned, backward);
+                follows[after] = Some(i);
+                limit = backward[i].0;
+            }
+        }
+        next = Some((i, limit));
+    }
+
+    if let Some((first, start)) = next.filter(|&(_, start)| start < now) {
+        warnings.push(ScheduleWarning::SerialWorkStartsInPast {
+            task_id: tasks[first].id.clone(),
+            required_start: start.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            shortfall_minutes: zone.minutes_between(start, now),
+        });
+    }
+
+    (follows, warnings)
+}
+
+/// Pushes incomplete, unpinned tasks that start before `floor` to start at
+/// it, and their dependents after them. Returns the anchors now finishing
+/// after their date.
+fn apply_start_floor(
+    floor: NaiveDateTime,
+    zone: Zone,
+    graph: &TaskGraph,
+    pinned: &[Option<(NaiveDateTime, NaiveDateTime)>],
+    anchored: &[Option<NaiveDateTime>],
+    backward: &mut [(NaiveDateTime, NaiveDateTime)],
+    early_start: &mut [NaiveDateTime],
+) -> Vec<ScheduleWarning> {
+    let tasks = graph.tasks;
+    for i in topological_order(graph, backward) {
+        if tasks[i].completed || pinned[i].is_some() {
+            continue;
+        }
+        let start = graph.providers[i]
+            .iter()
+            .zip(&graph.leads[i])
+            .map(|(&p, &lead)| zone.add_minutes(backward[p].1, -lead))
+            .fold(backward[i].0.max(floor), NaiveDateTime::max);
+        if start > backward[i].0 {
+            backward[i] = (start, tasks[i].finish_for_start(zone, start));
+        }
+        early_start[i] = early_start[i].max(floor);
+    }
+
+    let mut overrun: Vec<usize> = (0..tasks.len())
+        .filter(|&i| anchored[i].is_some_and(|anchor| backward[i].1 > anchor))
+        .collect();
+    overrun.sort_by_key(|&i| &tasks[i].id);
+    overrun
+        .into_iter()
+        .filter_map(|i| {
+            let (anchor, finish) = (anchored[i]?, backward[i].1);
+            Some(ScheduleWarning::FloorOverrun {
+                task_id: tasks[i].id.clone(),
+                anchor_date: anchor.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                projected_finish: finish.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                overrun_minutes: zone.minutes_between(anchor, finish),
+            })
+        })
+        .collect()
+}
+
+/// Moves task `i` to finish by `finish`, then pulls any provider that would
+/// now finish after its consumer starts. Pinned providers stay put.
+fn pull_earlier(
+    i: usize,
+    finish: NaiveDateTime,
+    zone: Zone,
+    graph: &TaskGraph,
+    pinned: &[Option<(NaiveDateTime, NaiveDateTime)>],
+    backward: &mut [(NaiveDateTime, NaiveDateTime)],
+) {
+    let mut stack = vec![(i, finish)];
+    while let Some((i, finish)) = stack.pop() {
+        if backward[i].1 <= finish || pinned[i].is_some() {
+            continue;
+        }
+        let start = graph.tasks[i].start_for_finish(zone, finish);
+        backward[i] = (start, finish);
+        stack.extend(graph.provider_deadlines(i, start, zone));
+    }
+}
+
+/// Walks back from `anchor` through the providers that bind each task's
+/// start (provider finish == its deadline for the task's start) and pass
+/// `follow`, preferring the earliest-starting one, and returns the chain
+/// ordered from its first task to the anchor.
+fn driving_chain(
+    anchor: usize,
+    graph: &TaskGraph,
+    zone: Zone,
+    backward: &[(NaiveDateTime, NaiveDateTime)],
+    follow: impl Fn(usize) -> bool,
+) -> Vec<usize> {
+    let mut chain = vec![anchor];
+    let mut current = anchor;
+
+    loop {
+        let (start, _) = backward[current];
+        let next = graph
+            .provider_deadlines(current, start, zone)
+            .filter(|&(p, deadline)| backward[p].1 == deadline && follow(p))
+            .map(|(p, _)| p)
+            .min_by(|&a, &b| {
+                backward[a]
+                    .0
+                    .cmp(&backward[b].0)
+                    .then_with(|| graph.tasks[a].id.cmp(&graph.tasks[b].id))
+            });
+        match next {
+            Some(p) if !chain.contains(&p) => {
+                chain.push(p);
+                current = p;
+            }
+            _ => break,
+        }
+    }
+
+    chain.reverse();
+    chain
+}
+
+/// Aggregates the scheduled tasks of each phase.
+pub(crate) fn phase_summaries(schedule: &[ScheduledTask]) -> Vec<PhaseSummary> {
+    let mut phases: Vec<PhaseSummary> = Vec::new();
+    for task in schedule {
+        let Some(phase) = &task.phase else { continue };
+        let Some(summary) = phases.iter_mut().find(|p| &p.phase == phase) else {
+            phases.push(PhaseSummary {
+                phase: phase.clone(),
+                start_date: task.start_date.clone(),
+                end_date: task.end_date.clone(),
+                slack_minutes: task.slack_minutes,
+                is_critical: task.is_critical,
+                task_count: 1,
+                completed_count: usize::from(task.completed),
+            });
+            continue;
+        };
+        // Dates share one fixed format, so they compare as strings
+        if task.start_date < summary.start_date {
+            summary.start_date = task.start_date.clone();
+        }
+        if task.end_date > summary.end_date {
+            summary.end_date = task.end_date.clone();
+        }
+        summary.slack_minutes = summary.slack_minutes.min(task.slack_minutes);
+        summary.is_critical |= task.is_critical;
+        summary.task_count += 1;
+        summary.completed_count += usize::from(task.completed);
+    }
+    phases.sort_by(|a, b| a.start_date.cmp(&b.start_date).then(a.phase.cmp(&b.phase)));
+    phases
+}
+
+/// Checks the scheduled finishes against each spacing constraint.
+pub(crate) fn check_anchor_spacing(
+    spacing: &[AnchorSpacing],
+    schedule: &[ScheduledTask],
+    zone: Zone,
+) -> Result<Vec<SpacingViolation>, ScheduleError> {
+    let finish = |id: &String| {
+        schedule
+            .iter()
+            .find(|t| &t.id == id)
+            .and_then(|t| NaiveDateTime::parse_from_str(&t.end_date, "%Y-%m-%dT%H:%M:%S").ok())
+            .ok_or_else(|| ScheduleError::TaskNotFound(id.clone()))
+    };
+
+    let mut violations = Vec::new();
+    for rule in spacing {
+        let required = zone.add_days(finish(&rule.before)?, rule.min_gap_days);
+        let after = finish(&rule.after)?;
+        if after < required {
+            violations.push(SpacingViolation {
+                before: rule.before.clone(),
+                after: rule.after.clone(),
+                min_gap_days: rule.min_gap_days,
+                shortfall_minutes: zone.minutes_between(after, required),
+            });
+        }
+    }
+    Ok(violations)
+}
+
+/// Anchors downstream of another anchor that the work in between can't
+/// reach in time, for a request's tasks and anchors.
+pub(crate) fn anchor_conflicts(
+    request: &ScheduleRequest,
+    zone: Zone,
+) -> Result<Vec<AnchorConflict>, ScheduleError> {
+    let graph = TaskGraph::new(&request.tasks)?;
+    let mut anchored = vec![None; graph.tasks.len()];
+    let windows = request
+        .anchor_windows
+        .iter()
+        .map(|(id, window)| (id, &window.latest));
+    for (task_id, date) in request.anchors.iter().chain(windows) {
+        let i = graph
+            .lookup(task_id)
+            .ok_or_else(|| ScheduleError::AnchorTaskNotFound(task_id.clone()))?;
+        let date = parse_date_string(date).map_err(|e| ScheduleError::InvalidAnchorDate {
+            task_id: task_id.clone(),
+            details: e,
+        })?;
+        let date = zone.normalize(date);
+        anchored[i] = Some(anchored[i].map_or(date, |d: NaiveDateTime| d.min(date)));
+    }
+    Ok(check_anchor_conflicts(&graph, &anchored, zone))
+}
+
+/// Runs forwards from each anchor finishing on its date and reports the
+/// downstream anchors that can't then be met. Pins, windows and busy slots
+/// aren't considered, so this is the plain work each gap has to hold.
+fn check_anchor_conflicts(
+    graph: &TaskGraph,
+    anchored: &[Option<NaiveDateTime>],
+    zone: Zone,
+) -> Vec<AnchorConflict> {
+    let fmt = |d: NaiveDateTime| d.format("%Y-%m-%dT%H:%M:%S").to_string();
+    let mut conflicts = Vec::new();
+    for (u, upstream_date) in anchored.iter().enumerate() {
+        let Some(upstream_date) = *upstream_date else {
+            continue;
+        };
+
+        let mut downstream = vec![false; anchored.len()];
+        let mut stack = graph.consumers[u].clone();
+        while let Some(i) = stack.pop() {
+            if !downstream[i] {
+                downstream[i] = true;
+                stack.extend(&graph.consumers[i]);
+            }
+        }
+        if downstream[u] {
+            continue; // Cycle; reported by the main passes
+        }
+
+        let mut waiting: Vec<usize> = graph
+            .providers
+            .iter()
+            .map(|providers| providers.iter().filter(|&&p| downstream[p]).count())
+            .collect();
+        let mut finish: Vec<Option<NaiveDateTime>> = vec![None; anchored.len()];
+        finish[u] = Some(upstream_date);
+        let mut ready: Vec<usize> = (0..anchored.len())
+            .filter(|&i| downstream[i] && waiting[i] == 0)
+            .collect();
+        while let Some(i) = ready.pop() {
+            let start = graph.providers[i]
+                .iter()
+                .zip(&graph.leads[i])
+                .filter_map(|(&p, &lead)| Some(zone.add_minutes(finish[p]?, -lead)))
+                .max()
+                .unwrap_or(upstream_date);
+            let end = graph.tasks[i].finish_for_start(zone, start);
+            finish[i] = Some(end);
+
+            if let Some(anchor) = anchored[i].filter(|&anchor| end > anchor) {
+                conflicts.push(AnchorConflict {
+                    upstream: graph.tasks[u].id.clone(),
+                    downstream: graph.tasks[i].id.clone(),
+                    upstream_anchor: fmt(upstream_date),
+                    downstream_anchor: fmt(anchor),
+                    required_gap_minutes: zone.minutes_between(upstream_date, end),
+                    shortfall_minutes: zone.minutes_between(anchor, end),
+                });
+            }
+            for &c in &graph.consumers[i] {
+                waiting[c] -= 1;
+                if waiting[c] == 0 {
+                    ready.push(c);
+                }
+            }
+        }
+    }
+    conflicts.sort_by(|a, b| (&a.upstream, &a.downstream).cmp(&(&b.upstream, &b.downstream)));
+    conflicts
+}
+
+/// Compares the remaining (incomplete) work against the time left before its deadlines.
+pub(crate) fn assess_feasibility(
+    schedule: &[ScheduledTask],
+    now: NaiveDateTime,
+) -> ScheduleFeasibility {
+    let remaining: Vec<(NaiveDateTime, NaiveDateTime, bool)> = schedule
+        .iter()
+        .filter(|t| !t.completed)
+        .filter_map(|t| {
+            let start = NaiveDateTime::parse_from_str(&t.start_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+            let end = NaiveDateTime::parse_from_str(&t.end_date, "%Y-%m-%dT%H:%M:%S").ok()?;
+            Some((start, end, t.is_elapsed))
+        })
+        .collect();
+
+    let late_task_ids: Vec<String> = schedule
+        .iter()
+        .filter(|t| t.starts_in_past)
+        .map(|t| t.id.clone())
+        .collect();
+
+    let (Some(first_start), Some(last_end)) = (
+        remaining.iter().map(|(s, _, _)| *s).min(),
+        remaining.iter().map(|(_, e, _)| *e).max(),
+    ) else {
+        return ScheduleFeasibility {
+            is_feasible: true,
+            ..Default::default()
+        };
+    };
+
+    let shortfall_minutes = (now - first_start).num_minutes().max(0);
+    let required_daily_overrun = if shortfall_minutes == 0 {
+        Some(0.0)
+    } else if last_end > now {
+        // Elapsed tasks take as long however hard anyone works, so only the
+        // rest of the window can absorb the catch-up
+        let fixed: i64 = remaining
+            .iter()
+            .filter(|(_, _, elapsed)| *elapsed)
+            .map(|(s, e, _)| (*e - *s).num_minutes())
+            .sum();
+        let planned = (last_end - first_start).num_minutes() - fixed;
+        let available = (last_end - now).num_minutes() - fixed;
+        (planned > 0 && available > 0).then(|| planned as f64 / available as f64 - 1.0)
+    } else {
+        None
+    };
+
+    ScheduleFeasibility {
+        is_feasible: late_task_ids.is_empty(),
+        late_task_ids,
+        shortfall_minutes,
+        required_daily_overrun,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_simple_chain_with_days() {
@@ -328,31 +2102,24 @@ mod tests {
                 Task {
                     id: "a".into(),
                     name: "Task A".into(),
-                    duration_days: 5,
-                    duration_minutes: None,
+                    duration: TaskDuration::days(5),
                     dependencies: vec![],
-                    completed: false,
-                    notes: None,
-                    is_milestone: false,
-                    subtasks: vec![],
+                    ..Default::default()
                 },
                 Task {
                     id: "b".into(),
                     name: "Task B".into(),
-                    duration_days: 3,
-                    duration_minutes: None,
+                    duration: TaskDuration::days(3),
                     dependencies: vec!["a".into()],
-                    completed: false,
-                    notes: None,
-                    is_milestone: false,
-                    subtasks: vec![],
+                    ..Default::default()
                 },
             ],
             anchors: [("b".into(), "2026-01-15".into())].into(),
+            ..Default::default()
         };
 
         let result = calculate_backwards_schedule(request).expect("Should work with days");
-        assert_eq!(result.len(), 2);
+        assert_eq!(result.scheduled_tasks.len(), 2);
     }
 
     #[test]
@@ -364,30 +2131,25 @@ mod tests {
                 Task {
                     id: "a".into(),
                     name: "Task A".into(),
-                    duration_days: 0,
-                    duration_minutes: Some(30),
+                    duration: TaskDuration::minutes(30),
                     dependencies: vec![],
-                    completed: false,
-                    notes: None,
-                    is_milestone: false,
-                    subtasks: vec![],
+                    ..Default::default()
                 },
                 Task {
                     id: "b".into(),
                     name: "Task B".into(),
-                    duration_days: 0,
-                    duration_minutes: Some(60),
+                    duration: TaskDuration::minutes(60),
                     dependencies: vec!["a".into()],
-                    completed: false,
-                    notes: None,
-                    is_milestone: false,
-                    subtasks: vec![],
+                    ..Default::default()
                 },
             ],
             anchors: [("b".into(), "2026-01-15T10:00:00".into())].into(),
+            ..Default::default()
         };
 
-        let result = calculate_backwards_schedule(request).expect("Should work with minutes");
+        let result = calculate_backwards_schedule(request)
+            .expect("Should work with minutes")
+            .scheduled_tasks;
 
         let task_a = result.iter().find(|t| t.id == "a").unwrap();
         let task_b = result.iter().find(|t| t.id == "b").unwrap();
@@ -405,37 +2167,33 @@ mod tests {
                 Task {
                     id: "a".into(),
                     name: "Task A".into(),
-                    duration_days: 5,
-                    duration_minutes: None,
+                    duration: TaskDuration::days(5),
                     dependencies: vec![],
-                    completed: false,
-                    notes: None,
-                    is_milestone: false,
-                    subtasks: vec![],
+                    ..Default::default()
                 },
                 Task {
                     id: "b".into(),
                     name: "Task B".into(),
-                    duration_days: 3,
-                    duration_minutes: None,
+                    duration: TaskDuration::days(3),
                     dependencies: vec!["a".into()],
-                    completed: false,
-                    notes: None,
-                    is_milestone: false,
-                    subtasks: vec![],
+                    ..Default::default()
                 },
             ],
             anchors: [("a".into(), "2026-01-15".into())].into(),
+            ..Default::default()
         };
 
-        let result = calculate_backwards_schedule(request);
-        assert!(result.is_err());
-        match result {
-            Err(ScheduleError::NoEndDateComputed(msg)) => {
-                assert!(msg.contains("Task B"));
-            }
-            _ => panic!("Expected NoEndDateComputed error"),
-        }
+        // B has no deadline, so it follows A instead of failing the schedule
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+        let b = result.scheduled_tasks.iter().find(|t| t.id == "b").unwrap();
+        assert_eq!(b.start_date, "2026-01-15T23:59:59");
+        assert_eq!(b.end_date, "2026-01-18T23:59:59");
+        assert_eq!(
+            result.warnings,
+            vec![ScheduleWarning::Unanchored {
+                task_ids: vec!["b".into()]
+            }]
+        );
     }
 
     #[test]
@@ -455,24 +2213,16 @@ mod tests {
                 Task {
                     id: "a".into(),
                     name: "Task A".into(),
-                    duration_days: 1,
-                    duration_minutes: None,
+                    duration: TaskDuration::days(1),
                     dependencies: vec![],
-                    completed: false,
-                    notes: None,
-                    is_milestone: false,
-                    subtasks: vec![],
+                    ..Default::default()
                 },
                 Task {
                     id: "b".into(),
                     name: "Task B".into(),
-                    duration_days: 1,
-                    duration_minutes: None,
+                    duration: TaskDuration::days(1),
                     dependencies: vec!["a".into()],
-                    completed: false,
-                    notes: None,
-                    is_milestone: false,
-                    subtasks: vec![],
+                    ..Default::default()
                 },
             ],
             anchors: [
@@ -480,6 +2230,7 @@ mod tests {
                 ("b".into(), "2026-01-10T00:00:00".into()),
             ]
             .into(),
+            ..Default::default()
         };
 
         // Run multiple times to catch potential hashmap randomness
@@ -487,10 +2238,11 @@ mod tests {
             let result = calculate_backwards_schedule(ScheduleRequest {
                 tasks: request.tasks.clone(),
                 anchors: request.anchors.clone(),
+                ..Default::default()
             })
             .expect("Schedule failed");
 
-            let task_a = result.iter().find(|t| t.id == "a").unwrap();
+            let task_a = result.scheduled_tasks.iter().find(|t| t.id == "a").unwrap();
 
             // Check if it respected the tighter constraint
             assert!(
@@ -498,6 +2250,18 @@ mod tests {
                 "Task A end_date was {}, expected 2026-01-09",
                 task_a.end_date
             );
+
+            // And that the override is reported rather than silent
+            assert_eq!(
+                result.warnings,
+                vec![ScheduleWarning::AnchorOverridden {
+                    task_id: "a".into(),
+                    anchor_date: "2026-01-20T00:00:00".into(),
+                    scheduled_end: "2026-01-09T00:00:00".into(),
+                    overridden_by_minutes: 11 * 24 * 60,
+                    constrained_by: Some("b".into()),
+                }]
+            );
         }
     }
 
@@ -506,9 +2270,924 @@ mod tests {
         let request = ScheduleRequest {
             tasks: vec![],
             anchors: HashMap::new(),
+            ..Default::default()
         };
 
         let result = calculate_backwards_schedule(request).expect("Should handle empty project");
-        assert!(result.is_empty());
+        assert!(result.scheduled_tasks.is_empty());
+        assert!(result.feasibility.is_feasible);
+    }
+
+    #[test]
+    fn test_buffer_is_scheduled_but_reported_separately() {
+        // A (1 day, +50%) -> B (2 days, +25%), anchored Jan 10.
+        // B reserves 2.5 days, A 1.5 days: A starts Jan 6 00:00.
+        let request = ScheduleRequest {
+            tasks: vec![
+                Task {
+                    id: "a".into(),
+                    name: "Task A".into(),
+                    duration: TaskDuration::days(1),
+                    buffer_percent: 50.0,
+                    ..Default::default()
+                },
+                Task {
+                    id: "b".into(),
+                    name: "Task B".into(),
+                    duration: TaskDuration::days(2),
+                    dependencies: vec!["a".into()],
+                    buffer_percent: 25.0,
+                    ..Default::default()
+                },
+            ],
+            anchors: [("b".into(), "2026-01-10T00:00:00".into())].into(),
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+        let task_a = result.scheduled_tasks.iter().find(|t| t.id == "a").unwrap();
+
+        assert_eq!(task_a.start_date, "2026-01-06T00:00:00");
+        assert_eq!(task_a.estimate_minutes, 24 * 60);
+        assert_eq!(task_a.buffer_minutes, 12 * 60);
+
+        let chain = &result.chain_buffers[0];
+        assert_eq!(chain.anchor_id, "b");
+        assert_eq!(chain.task_ids, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(chain.estimate_minutes, 3 * 24 * 60);
+        assert_eq!(chain.buffer_minutes, 24 * 60);
+    }
+
+    #[test]
+    fn test_anchor_window_placement() {
+        // A (1 day) -> B (1 day). A may finish Jan 5–Jan 12, B is anchored Jan 10.
+        // B pulls A to finish Jan 9, i.e. 4/7 of the way into its window.
+        let request = ScheduleRequest {
+            tasks: vec![
+                Task {
+                    id: "a".into(),
+                    name: "Task A".into(),
+                    duration: TaskDuration::days(1),
+                    ..Default::default()
+                },
+                Task {
+                    id: "b".into(),
+                    name: "Task B".into(),
+                    duration: TaskDuration::days(1),
+                    dependencies: vec!["a".into()],
+                    ..Default::default()
+                },
+            ],
+            anchors: [("b".into(), "2026-01-10T00:00:00".into())].into(),
+            anchor_windows: [(
+                "a".into(),
+                AnchorWindow {
+                    earliest: "2026-01-05T00:00:00".into(),
+                    latest: "2026-01-12T00:00:00".into(),
+                },
+            )]
+            .into(),
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+        let placement = &result.anchor_windows[0];
+
+        assert_eq!(placement.task_id, "a");
+        assert_eq!(placement.placed_at, "2026-01-09T00:00:00");
+        assert!(placement.within_window);
+        assert!((placement.position - 4.0 / 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_starts_in_past_is_flagged() {
+        // A (2 days) -> B (2 days), anchored Jan 10. A must start Jan 6 but "now" is Jan 7.
+        let request = ScheduleRequest {
+            tasks: vec![
+                Task {
+                    id: "a".into(),
+                    name: "Task A".into(),
+                    duration: TaskDuration::days(2),
+                    dependencies: vec![],
+                    ..Default::default()
+                },
+                Task {
+                    id: "b".into(),
+                    name: "Task B".into(),
+                    duration: TaskDuration::days(2),
+                    dependencies: vec!["a".into()],
+                    ..Default::default()
+                },
+            ],
+            anchors: [("b".into(), "2026-01-10T00:00:00".into())].into(),
+            reference_time: Some("2026-01-07T00:00:00".into()),
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+        let task_a = result.scheduled_tasks.iter().find(|t| t.id == "a").unwrap();
+        let task_b = result.scheduled_tasks.iter().find(|t| t.id == "b").unwrap();
+
+        assert!(task_a.starts_in_past);
+        assert!(!task_b.starts_in_past);
+        assert!(!result.feasibility.is_feasible);
+        assert_eq!(result.feasibility.late_task_ids, vec!["a".to_string()]);
+        assert_eq!(result.feasibility.shortfall_minutes, 24 * 60);
+        // Four days of planned work must now fit into three.
+        let overrun = result.feasibility.required_daily_overrun.unwrap();
+        assert!((overrun - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_durations_across_dst_change() {
+        // New York clocks spring forward at 02:00 on 2026-03-08.
+        let request = ScheduleRequest {
+            tasks: vec![
+                Task {
+                    id: "prep".into(),
+                    name: "Prep".into(),
+                    duration: TaskDuration::days(2),
+                    ..Default::default()
+                },
+                Task {
+                    id: "call".into(),
+                    name: "Call".into(),
+                    duration: TaskDuration::minutes(60),
+                    ..Default::default()
+                },
+            ],
+            anchors: [
+                ("prep".into(), "2026-03-09T09:00:00".into()),
+                ("call".into(), "2026-03-08T03:30:00".into()),
+            ]
+            .into(),
+            timezone: Some("America/New_York".into()),
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+        let prep = result
+            .scheduled_tasks
+            .iter()
+            .find(|t| t.id == "prep")
+            .unwrap();
+        let call = result
+            .scheduled_tasks
+            .iter()
+            .find(|t| t.id == "call")
+            .unwrap();
+
+        // Whole days keep the clock time; minutes are real elapsed time.
+        assert_eq!(prep.start_date, "2026-03-07T09:00:00");
+        assert_eq!(call.start_date, "2026-03-08T01:30:00");
+    }
+
+    #[test]
+    fn test_elapsed_days_are_real_time() {
+        // A 2-day cure across the spring-forward change is 48 real hours.
+        let request = ScheduleRequest {
+            tasks: vec![Task {
+                id: "cure".into(),
+                name: "Cure".into(),
+                duration: TaskDuration::days(2),
+                is_elapsed: true,
+                ..Default::default()
+            }],
+            anchors: [("cure".into(), "2026-03-09T09:00:00".into())].into(),
+            timezone: Some("America/New_York".into()),
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+
+        assert_eq!(result.scheduled_tasks[0].start_date, "2026-03-07T08:00:00");
+    }
+
+    #[test]
+    fn test_elapsed_time_cannot_absorb_overrun() {
+        // Prep (2d work) -> cure (2d elapsed), a day behind: the lost day must
+        // come out of prep alone.
+        let request = ScheduleRequest {
+            tasks: vec![
+                Task {
+                    id: "prep".into(),
+                    name: "Prep".into(),
+                    duration: TaskDuration::days(2),
+                    ..Default::default()
+                },
+                Task {
+                    id: "cure".into(),
+                    name: "Cure".into(),
+                    duration: TaskDuration::days(2),
+                    is_elapsed: true,
+                    dependencies: vec!["prep".into()],
+                    ..Default::default()
+                },
+            ],
+            anchors: [("cure".into(), "2026-01-10T00:00:00".into())].into(),
+            reference_time: Some("2026-01-07T00:00:00".into()),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+        let overrun = result.feasibility.required_daily_overrun.unwrap();
+
+        assert!((overrun - 1.0).abs() < 1e-9);
+    }
+
+    fn oven_request(driver_days: i64) -> ScheduleRequest {
+        // Roast and bake share the oven and both feed dinner; "shop" sets how much slack they get.
+        let task = |id: &str, days: i64, deps: &[&str]| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(days),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        ScheduleRequest {
+            tasks: vec![
+                task("roast", 1, &[]),
+                task("bake", 1, &[]),
+                task("shop", driver_days, &[]),
+                task("dinner", 1, &["roast", "bake", "shop"]),
+            ],
+            anchors: [("dinner".into(), "2026-01-10T00:00:00".into())].into(),
+            exclusions: vec![ExclusionGroup {
+                resource: "oven".into(),
+                task_ids: vec!["roast".into(), "bake".into()],
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_exclusive_tasks_are_serialized_within_slack() {
+        let result = calculate_backwards_schedule(oven_request(3)).expect("Schedule failed");
+        let roast = result
+            .scheduled_tasks
+            .iter()
+            .find(|t| t.id == "roast")
+            .unwrap();
+        let bake = result
+            .scheduled_tasks
+            .iter()
+            .find(|t| t.id == "bake")
+            .unwrap();
+
+        // Equal slack, so the tie goes by ID: bake keeps its slot and roast moves.
+        assert_eq!(bake.start_date, "2026-01-08T00:00:00");
+        assert_eq!(roast.start_date, "2026-01-07T00:00:00");
+        assert_eq!(roast.end_date, "2026-01-08T00:00:00");
+        assert_eq!(roast.slack_minutes, 24 * 60);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_exclusive_tasks_without_slack_warn() {
+        let result = calculate_backwards_schedule(oven_request(1)).expect("Schedule failed");
+        let roast = result
+            .scheduled_tasks
+            .iter()
+            .find(|t| t.id == "roast")
+            .unwrap();
+
+        assert_eq!(roast.start_date, "2026-01-08T00:00:00");
+        assert_eq!(
+            result.warnings,
+            vec![ScheduleWarning::ExclusionConflict {
+                resource: "oven".into(),
+                task_ids: vec!["roast".into(), "bake".into()],
+                overlap_minutes: 24 * 60,
+            }]
+        );
+    }
+
+    fn pour_request(window_end: &str) -> ScheduleRequest {
+        ScheduleRequest {
+            tasks: vec![
+                Task {
+                    id: "pour".into(),
+                    name: "Pour concrete".into(),
+                    duration: TaskDuration::days(2),
+                    only_during: vec![AllowedWindow {
+                        start: "2026-04-01".into(),
+                        end: window_end.into(),
+                    }],
+                    ..Default::default()
+                },
+                Task {
+                    id: "handover".into(),
+                    name: "Handover".into(),
+                    duration: TaskDuration::days(1),
+                    dependencies: vec!["pour".into()],
+                    ..Default::default()
+                },
+            ],
+            anchors: [("handover".into(), "2027-01-15T00:00:00".into())].into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_task_is_placed_inside_allowed_window() {
+        let result = calculate_backwards_schedule(pour_request("2026-10-31T17:00:00"))
+            .expect("Schedule failed");
+        let pour = result
+            .scheduled_tasks
+            .iter()
+            .find(|t| t.id == "pour")
+            .unwrap();
+
+        assert_eq!(pour.start_date, "2026-10-29T17:00:00");
+        assert_eq!(pour.end_date, "2026-10-31T17:00:00");
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_window_too_short_is_reported() {
+        let result = calculate_backwards_schedule(pour_request("2026-04-01T12:00:00"))
+            .expect("Schedule failed");
+
+        assert_eq!(
+            result.warnings,
+            vec![ScheduleWarning::NoAllowedWindow {
+                task_id: "pour".into(),
+                required_finish: "2027-01-14T00:00:00".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_anchor_spacing_violation_is_infeasible() {
+        let milestone = |id: &str| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::minutes(0),
+            is_milestone: true,
+            ..Default::default()
+        };
+        let request = ScheduleRequest {
+            tasks: vec![milestone("rehearsal"), milestone("opening")],
+            anchors: [
+                ("rehearsal".into(), "2026-01-08T19:00:00".into()),
+                ("opening".into(), "2026-01-10T19:00:00".into()),
+            ]
+            .into(),
+            anchor_spacing: vec![AnchorSpacing {
+                before: "rehearsal".into(),
+                after: "opening".into(),
+                min_gap_days: 3,
+            }],
+            reference_time: Some("2026-01-01T00:00:00".into()),
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+
+        assert!(!result.feasibility.is_feasible);
+        assert_eq!(
+            result.feasibility.spacing_violations,
+            vec![SpacingViolation {
+                before: "rehearsal".into(),
+                after: "opening".into(),
+                min_gap_days: 3,
+                shortfall_minutes: 24 * 60,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_large_chain() {
+        // 5,000 one-hour tasks in a single chain ending at the anchor.
+        let tasks: Vec<Task> = (0..5000)
+            .map(|i| Task {
+                id: format!("t{}", i),
+                name: format!("Task {}", i),
+                duration: TaskDuration::minutes(60),
+                dependencies: if i == 0 {
+                    vec![]
+                } else {
+                    vec![format!("t{}", i - 1)]
+                },
+                ..Default::default()
+            })
+            .collect();
+        let request = ScheduleRequest {
+            tasks,
+            anchors: [("t4999".into(), "2026-12-31T00:00:00".into())].into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+
+        // 5,000 hours is 208 days and 8 hours.
+        assert_eq!(result.scheduled_tasks[0].start_date, "2026-06-05T16:00:00");
+        assert!(result.scheduled_tasks.iter().all(|t| t.is_critical));
+    }
+
+    #[test]
+    fn test_unknown_dependency() {
+        let request = ScheduleRequest {
+            tasks: vec![Task {
+                id: "a".into(),
+                name: "Task A".into(),
+                duration: TaskDuration::days(1),
+                dependencies: vec!["ghost".into()],
+                ..Default::default()
+            }],
+            anchors: [("a".into(), "2026-01-10T00:00:00".into())].into(),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            calculate_backwards_schedule(request),
+            Err(ScheduleError::TaskNotFound(id)) if id == "ghost"
+        ));
+    }
+
+    #[test]
+    fn test_results_are_in_topological_order() {
+        // Listed consumers-first; "b" and "c" both start at 08:00 and tie on ID.
+        let task = |id: &str, minutes: i64, deps: &[&str]| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::minutes(minutes),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let request = ScheduleRequest {
+            tasks: vec![
+                task("d", 60, &["c", "b", "a"]),
+                task("c", 60, &[]),
+                task("b", 60, &[]),
+                task("a", 120, &[]),
+            ],
+            anchors: [("d".into(), "2026-01-10T10:00:00".into())].into(),
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+        let ids: Vec<&str> = result
+            .scheduled_tasks
+            .iter()
+            .map(|t| t.id.as_str())
+            .collect();
+
+        assert_eq!(ids, vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_duration_units() {
+        let task = |duration: TaskDuration| Task {
+            duration,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            task(TaskDuration::hours(3)).duration(),
+            Duration::minutes(180)
+        );
+        assert_eq!(task(TaskDuration::weeks(2)).duration(), Duration::days(14));
+    }
+
+    #[test]
+    fn test_legacy_duration_fields_still_load() {
+        let days: Task = serde_json::from_str(
+            r#"{"id": "a", "name": "A", "duration_days": 2, "duration_minutes": null, "dependencies": []}"#,
+        )
+        .unwrap();
+        let minutes: Task = serde_json::from_str(
+            r#"{"id": "b", "name": "B", "duration_days": 0, "duration_minutes": 45, "dependencies": []}"#,
+        )
+        .unwrap();
+
+        assert_eq!(days.duration, TaskDuration::days(2));
+        assert_eq!(minutes.duration, TaskDuration::minutes(45));
+        // Saved back in the new shape only.
+        let json = serde_json::to_value(&minutes).unwrap();
+        assert_eq!(json["duration"]["unit"], "minutes");
+        assert!(json.get("duration_minutes").is_none());
+    }
+
+    fn pinned_request(pin: &str) -> ScheduleRequest {
+        // design -> meeting (pinned, 1h) -> ship (anchored)
+        let task = |id: &str, duration: TaskDuration, deps: &[&str]| Task {
+            id: id.into(),
+            name: id.into(),
+            duration,
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let mut meeting = task("meeting", TaskDuration::minutes(60), &["design"]);
+        meeting.pinned_start = Some(pin.into());
+        ScheduleRequest {
+            tasks: vec![
+                task("design", TaskDuration::days(1), &[]),
+                meeting,
+                task("ship", TaskDuration::days(1), &["meeting"]),
+            ],
+            anchors: [("ship".into(), "2026-01-10T00:00:00".into())].into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pinned_task_stays_put_and_bounds_neighbours() {
+        let result = calculate_backwards_schedule(pinned_request("2026-01-05T10:00:00"))
+            .expect("Schedule failed");
+        let find = |id: &str| result.scheduled_tasks.iter().find(|t| t.id == id).unwrap();
+
+        assert_eq!(find("meeting").start_date, "2026-01-05T10:00:00");
+        assert!(find("meeting").is_pinned);
+        assert_eq!(find("design").end_date, "2026-01-05T10:00:00");
+        // ship can start as soon as the meeting ends
+        assert_eq!(find("ship").start_date, "2026-01-09T00:00:00");
+        assert_eq!(find("ship").slack_minutes, (3 * 24 + 13) * 60);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_pin_after_dependent_start_is_flagged() {
+        let result = calculate_backwards_schedule(pinned_request("2026-01-09T12:00:00"))
+            .expect("Schedule failed");
+
+        assert_eq!(
+            result.warnings,
+            vec![
+                ScheduleWarning::PinConflict {
+                    task_id: "meeting".into(),
+                    constrained_by: Some("ship".into()),
+                    overlap_minutes: 13 * 60,
+                },
+                // ship can't start until the meeting ends
+                ScheduleWarning::NegativeSlack {
+                    task_id: "ship".into(),
+                    slack_minutes: -13 * 60,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_partial_overlap_dependency() {
+        // "paint" can start once "plaster" is half done
+        let task: Task = serde_json::from_str(
+            r#"{"id": "paint", "name": "Paint", "duration": {"value": 2, "unit": "days"},
+                "dependencies": ["plaster"], "start_after_percent": {"plaster": 50.0}}"#,
+        )
+        .unwrap();
+        let request = ScheduleRequest {
+            tasks: vec![
+                Task {
+                    id: "plaster".into(),
+                    name: "Plaster".into(),
+                    duration: TaskDuration::days(4),
+                    ..Default::default()
+                },
+                task,
+            ],
+            anchors: [("paint".into(), "2026-01-10T00:00:00".into())].into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+        let find = |id: &str| result.scheduled_tasks.iter().find(|t| t.id == id).unwrap();
+
+        assert_eq!(find("plaster").start_date, "2026-01-06T00:00:00");
+        assert_eq!(find("plaster").end_date, "2026-01-10T00:00:00");
+        assert_eq!(find("paint").start_date, "2026-01-08T00:00:00");
+        // Both passes agree, so the overlapping chain is critical end to end
+        assert_eq!(find("plaster").slack_minutes, 0);
+        assert_eq!(find("paint").slack_minutes, 0);
+        assert_eq!(result.chain_buffers[0].task_ids, vec!["plaster", "paint"]);
+    }
+
+    #[test]
+    fn test_critical_chains_run_from_source_to_anchor() {
+        let task = |id: &str, days: i64, deps: &[&str]| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(days),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let request = ScheduleRequest {
+            tasks: vec![
+                task("build", 2, &[]),
+                task("test", 1, &["build"]),
+                task("launch", 1, &["test"]),
+                task("draft", 1, &[]),
+                task("docs", 1, &["draft"]),
+            ],
+            anchors: [
+                ("launch".into(), "2026-01-10T00:00:00".into()),
+                ("docs".into(), "2026-01-20T00:00:00".into()),
+            ]
+            .into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+
+        // docs has slack, so only the launch chain is critical
+        assert_eq!(
+            result.critical_chains,
+            vec![vec!["build", "test", "launch"]]
+        );
+    }
+
+    #[test]
+    fn test_serialized_work_never_overlaps() {
+        let task = |id: &str| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(1),
+            ..Default::default()
+        };
+        let request = |now: &str| ScheduleRequest {
+            tasks: vec![task("a"), task("b"), task("c")],
+            anchors: ["a", "b", "c"]
+                .map(|id| (id.to_string(), "2026-01-10T00:00:00".to_string()))
+                .into(),
+            reference_time: Some(now.into()),
+            timezone: Some("UTC".into()),
+            serialize_work: true,
+            ..Default::default()
+        };
+
+        let result =
+            calculate_backwards_schedule(request("2026-01-01T00:00:00")).expect("Schedule failed");
+        let dates: Vec<(&str, &str)> = result
+            .scheduled_tasks
+            .iter()
+            .map(|t| (t.start_date.as_str(), t.end_date.as_str()))
+            .collect();
+        assert_eq!(
+            dates,
+            vec![
+                ("2026-01-07T00:00:00", "2026-01-08T00:00:00"),
+                ("2026-01-08T00:00:00", "2026-01-09T00:00:00"),
+                ("2026-01-09T00:00:00", "2026-01-10T00:00:00"),
+            ]
+        );
+        // The sequence has no slack
+        assert!(result.scheduled_tasks.iter().all(|t| t.is_critical));
+
+        let result =
+            calculate_backwards_schedule(request("2026-01-08T00:00:00")).expect("Schedule failed");
+        assert!(result
+            .warnings
+            .contains(&ScheduleWarning::SerialWorkStartsInPast {
+                task_id: "a".into(),
+                required_start: "2026-01-07T00:00:00".into(),
+                shortfall_minutes: 24 * 60,
+            }));
+    }
+
+    #[test]
+    fn test_phase_summaries() {
+        let task = |id: &str, days: i64, deps: &[&str], phase: &str| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(days),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            phase: Some(phase.into()),
+            ..Default::default()
+        };
+        let request = ScheduleRequest {
+            tasks: vec![
+                task("sketch", 2, &[], "Design"),
+                task("mockup", 1, &["sketch"], "Design"),
+                task("code", 3, &["mockup"], "Build"),
+            ],
+            anchors: [("code".into(), "2026-05-10T00:00:00".into())].into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+
+        assert_eq!(result.scheduled_tasks[0].phase.as_deref(), Some("Design"));
+        let phases: Vec<(&str, &str, &str, usize)> = result
+            .phases
+            .iter()
+            .map(|p| {
+                (
+                    p.phase.as_str(),
+                    p.start_date.as_str(),
+                    p.end_date.as_str(),
+                    p.task_count,
+                )
+            })
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                ("Design", "2026-05-04T00:00:00", "2026-05-07T00:00:00", 2),
+                ("Build", "2026-05-07T00:00:00", "2026-05-10T00:00:00", 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_suggested_anchor_is_earliest_finish() {
+        let task = |id: &str, days: i64, deps: &[&str]| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(days),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let mut done = task("research", 5, &[]);
+        done.completed = true;
+        let request = ScheduleRequest {
+            tasks: vec![
+                done,
+                task("write", 3, &["research"]),
+                task("edit", 2, &["write"]),
+                task("cover", 1, &[]),
+                task("print", 1, &["edit", "cover"]),
+            ],
+            anchors: [("print".into(), "2026-02-10".into())].into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+        let start =
+            NaiveDateTime::parse_from_str("2026-02-02T09:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+
+        let suggestions = suggest_anchor_dates(&request, start).unwrap();
+
+        assert_eq!(
+            suggestions,
+            vec![AnchorSuggestion {
+                task_id: "print".into(),
+                earliest_finish: "2026-02-08T09:00:00".into(),
+                current_anchor: Some("2026-02-10T23:59:59".into()),
+                slack_minutes: Some(2 * 1440 + 14 * 60 + 59),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_start_floor_pushes_late_work_and_flags_anchor() {
+        let task = |id: &str, days: i64, deps: &[&str]| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(days),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let request = ScheduleRequest {
+            tasks: vec![task("a", 2, &[]), task("b", 2, &["a"])],
+            anchors: [("b".into(), "2026-01-10T00:00:00".into())].into(),
+            reference_time: Some("2026-01-07T00:00:00".into()),
+            timezone: Some("UTC".into()),
+            floor_starts: true,
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+        let find = |id: &str| result.scheduled_tasks.iter().find(|t| t.id == id).unwrap();
+
+        // a should have started on the 6th; it can't start before now
+        assert_eq!(find("a").start_date, "2026-01-07T00:00:00");
+        assert_eq!(find("b").start_date, "2026-01-09T00:00:00");
+        assert!(result.scheduled_tasks.iter().all(|t| !t.starts_in_past));
+        assert!(!result.feasibility.is_feasible);
+        assert_eq!(
+            result.warnings,
+            vec![ScheduleWarning::FloorOverrun {
+                task_id: "b".into(),
+                anchor_date: "2026-01-10T00:00:00".into(),
+                projected_finish: "2026-01-11T00:00:00".into(),
+                overrun_minutes: 24 * 60,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_clock_work_flows_around_busy_slots() {
+        let task = |id: &str, deps: &[&str], hours: i64| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::hours(hours),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let slot = |start: &str, end: &str| BusySlot {
+            start: start.into(),
+            end: end.into(),
+            label: None,
+        };
+        let request = ScheduleRequest {
+            tasks: vec![task("outline", &[], 1), task("slides", &["outline"], 3)],
+            anchors: [("slides".into(), "2026-03-05T14:00:00".into())].into(),
+            timezone: Some("UTC".into()),
+            // Overlapping meetings merge into 12:00-13:00
+            busy: vec![
+                slot("2026-03-05T12:30:00", "2026-03-05T13:00:00"),
+                slot("2026-03-05T10:30:00", "2026-03-05T11:00:00"),
+                slot("2026-03-05T12:00:00", "2026-03-05T12:45:00"),
+            ],
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+        let find = |id: &str| result.scheduled_tasks.iter().find(|t| t.id == id).unwrap();
+
+        // 13-14, 11-12 and 9:30-10:30 make three free hours
+        assert_eq!(find("slides").start_date, "2026-03-05T09:30:00");
+        assert_eq!(find("slides").end_date, "2026-03-05T14:00:00");
+        assert_eq!(find("outline").end_date, "2026-03-05T09:30:00");
+        assert!(find("slides").is_critical);
+        assert_eq!(find("slides").slack_minutes, 0);
+    }
+
+    #[test]
+    fn test_anchors_too_close_for_the_work_between_are_reported() {
+        let task = |id: &str, days: i64, deps: &[&str]| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(days),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        let request = ScheduleRequest {
+            tasks: vec![
+                task("design", 2, &[]),
+                task("build", 3, &["design"]),
+                task("launch", 1, &["build"]),
+            ],
+            // Four days of work after design, but only two days between anchors
+            anchors: [
+                ("design".into(), "2026-01-10T00:00:00".into()),
+                ("launch".into(), "2026-01-12T00:00:00".into()),
+            ]
+            .into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+
+        assert!(!result.feasibility.is_feasible);
+        assert_eq!(
+            result.feasibility.anchor_conflicts,
+            vec![AnchorConflict {
+                upstream: "design".into(),
+                downstream: "launch".into(),
+                upstream_anchor: "2026-01-10T00:00:00".into(),
+                downstream_anchor: "2026-01-12T00:00:00".into(),
+                required_gap_minutes: 4 * 24 * 60,
+                shortfall_minutes: 2 * 24 * 60,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_spread_slack_leaves_room_along_the_chain() {
+        let task = |id: &str, days: i64, deps: &[&str]| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::days(days),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        };
+        // x is critical; b -> c -> y has two days of slack
+        let request = ScheduleRequest {
+            tasks: vec![
+                task("a", 4, &[]),
+                task("x", 1, &["a"]),
+                task("b", 1, &[]),
+                task("c", 1, &["b"]),
+                task("y", 1, &["c"]),
+            ],
+            anchors: [
+                ("x".into(), "2026-01-10T00:00:00".into()),
+                ("y".into(), "2026-01-10T00:00:00".into()),
+            ]
+            .into(),
+            timezone: Some("UTC".into()),
+            spread_slack: true,
+            ..Default::default()
+        };
+
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+        let find = |id: &str| result.scheduled_tasks.iter().find(|t| t.id == id).unwrap();
+
+        assert_eq!(find("a").start_date, "2026-01-05T00:00:00");
+        assert_eq!(find("b").start_date, "2026-01-05T00:00:00");
+        assert_eq!(find("c").start_date, "2026-01-06T16:00:00");
+        assert_eq!(find("y").start_date, "2026-01-08T08:00:00");
+        assert_eq!(find("y").end_date, "2026-01-09T08:00:00");
+        // What's left after the planned start
+        assert_eq!(find("y").slack_minutes, 16 * 60);
+        assert!(find("x").is_critical);
+        assert!(!find("b").is_critical);
     }
 }
@@ -0,0 +1,268 @@
+//! Full-text search across all stored projects.
+//!
+//! Matches every query word (case-insensitive, anywhere in a word) against
+//! project names and each task's name, tags, subtasks and notes. Tasks must
+//! contain every word across those fields; hits are ranked by where the
+//! words were found, whole-word matches and an exact phrase.
+
+use crate::project::{load_all_projects, Project};
+use crate::scheduler::Task;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const DEFAULT_LIMIT: usize = 50;
+const SNIPPET_CONTEXT: usize = 40;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchField {
+    ProjectName,
+    TaskName,
+    Tag,
+    Subtask,
+    Notes,
+}
+
+impl SearchField {
+    fn weight(self) -> f64 {
+        match self {
+            SearchField::ProjectName | SearchField::TaskName => 3.0,
+            SearchField::Tag => 2.0,
+            SearchField::Subtask => 1.5,
+            SearchField::Notes => 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SearchHit {
+    pub project_id: String,
+    pub project_name: String,
+    /// Unset when the project itself matched.
+    pub task_id: Option<String>,
+    pub task_name: Option<String>,
+    /// Where the best match was found.
+    pub field: SearchField,
+    /// The matching text, trimmed to the match for long notes.
+    pub snippet: String,
+    pub score: f64,
+}
+
+fn is_word_start(text: &str, at: usize) -> bool {
+    text[..at]
+        .chars()
+        .next_back()
+        .is_none_or(|c| !c.is_alphanumeric())
+}
+
+/// Score for one term in one piece of text, or None when it's absent.
+fn term_score(text: &str, term: &str) -> Option<f64> {
+    let at = text.find(term)?;
+    Some(if is_word_start(text, at) { 1.0 } else { 0.5 })
+}
+
+/// Trims long text to the first match with some context on either side.
+fn snippet(text: &str, terms: &[String]) -> String {
+    let lower = text.to_lowercase();
+    // Lowercasing can change byte lengths; only trim when offsets line up
+    if lower.len() != text.len() || text.chars().count() <= 2 * SNIPPET_CONTEXT {
+        return text.to_string();
+    }
+    let at = terms
+        .iter()
+        .filter_map(|t| lower.find(t))
+        .min()
+        .unwrap_or(0);
+    let floor = |mut i: usize| {
+        while !text.is_char_boundary(i) {
+            i -= 1;
+        }
+        i
+    };
+    let start = floor(at.saturating_sub(SNIPPET_CONTEXT));
+    let end = floor((at + 2 * SNIPPET_CONTEXT).min(text.len()));
+    format!(
+        "{}{}{}",
+        if start > 0 { "…" } else { "" },
+        text[start..end].trim(),
+        if end < text.len() { "…" } else { "" }
+    )
+}
+
+fn task_fields(task: &Task) -> Vec<(SearchField, &str)> {
+    let mut fields = vec![(SearchField::TaskName, task.name.as_str())];
+    fields.extend(task.tags.iter().map(|t| (SearchField::Tag, t.as_str())));
+    fields.extend(
+        task.subtasks
+            .iter()
+            .map(|s| (SearchField::Subtask, s.name.as_str())),
+    );
+    if let Some(notes) = &task.notes {
+        fields.push((SearchField::Notes, notes.as_str()));
+    }
+    fields
+}
+
+/// Scores `fields` against `terms`, requiring every term somewhere. Returns
+/// the score and the field (with its text) that matched the most.
+fn score<'a>(
+    fields: &[(SearchField, &'a str)],
+    terms: &[String],
+    phrase: &str,
+) -> Option<(f64, SearchField, &'a str)> {
+    let lower: Vec<String> = fields.iter().map(|(_, text)| text.to_lowercase()).collect();
+    let weighted = || {
+        fields
+            .iter()
+            .zip(&lower)
+            .map(|((field, text), lower)| (*field, *text, lower))
+    };
+
+    let mut total = 0.0;
+    for term in terms {
+        total += weighted()
+            .filter_map(|(field, _, lower)| Some(field.weight() * term_score(lower, term)?))
+            .max_by(f64::total_cmp)?;
+    }
+    if terms.len() > 1 {
+        total += weighted()
+            .filter(|(_, _, lower)| lower.contains(phrase))
+            .map(|(field, _, _)| field.weight())
+            .fold(0.0, f64::max);
+    }
+    // Ties go to the earlier field
+    let (field, text, _) = weighted()
+        .rev()
+        .map(|(field, text, lower)| {
+            let found: f64 = terms.iter().filter_map(|t| term_score(lower, t)).sum();
+            (field, text, field.weight() * found)
+        })
+        .max_by(|a, b| a.2.total_cmp(&b.2))?;
+    Some((total, field, text))
+}
+
+/// Ranked hits for `query` in `projects`, best first.
+pub fn search_projects(projects: &[Project], query: &str, limit: usize) -> Vec<SearchHit> {
+    let phrase = query.trim().to_lowercase();
+    let terms: Vec<String> = phrase.split_whitespace().map(String::from).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let phrase = terms.join(" ");
+
+    let mut hits = Vec::new();
+    for project in projects {
+        let name = [(SearchField::ProjectName, project.name.as_str())];
+        if let Some((score, field, _)) = score(&name, &terms, &phrase) {
+            hits.push(SearchHit {
+                project_id: project.id.clone(),
+                project_name: project.name.clone(),
+                task_id: None,
+                task_name: None,
+                field,
+                snippet: project.name.clone(),
+                score,
+            });
+        }
+        for task in &project.tasks {
+            let Some((mut score, field, text)) = score(&task_fields(task), &terms, &phrase) else {
+                continue;
+            };
+            // Open work is more likely what's being looked for
+            if task.completed {
+                score *= 0.8;
+            }
+            hits.push(SearchHit {
+                project_id: project.id.clone(),
+                project_name: project.name.clone(),
+                task_id: Some(task.id.clone()),
+                task_name: Some(task.name.clone()),
+                field,
+                snippet: snippet(text, &terms),
+                score,
+            });
+        }
+    }
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.project_name.cmp(&b.project_name))
+    });
+    hits.truncate(limit);
+    hits
+}
+
+/// Searches project names and task names, tags, subtasks and notes across
+/// all stored projects.
+#[tauri::command]
+pub fn search(
+    app: AppHandle,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<SearchHit>, String> {
+    let projects = load_all_projects(&app)?;
+    Ok(search_projects(
+        &projects,
+        &query,
+        limit.unwrap_or(DEFAULT_LIMIT),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::SubTask;
+
+    #[test]
+    fn test_hits_are_ranked_across_fields_and_projects() {
+        let task = |id: &str, name: &str, notes: Option<&str>| Task {
+            id: id.into(),
+            name: name.into(),
+            notes: notes.map(String::from),
+            ..Default::default()
+        };
+        let thesis = Project {
+            id: "thesis".into(),
+            name: "Thesis".into(),
+            tasks: vec![
+                task("a", "Ethics approval", None),
+                task(
+                    "b",
+                    "Recruit participants",
+                    Some(&format!(
+                        "{} needs the ethics approval number first",
+                        "x".repeat(90)
+                    )),
+                ),
+                Task {
+                    subtasks: vec![SubTask {
+                        id: "s".into(),
+                        name: "Submit ethics form".into(),
+                        completed: false,
+                    }],
+                    ..task("c", "Paperwork", None)
+                },
+                task("d", "Unrelated", Some("bioethics reading")),
+            ],
+            ..Default::default()
+        };
+        let ethics = Project {
+            id: "club".into(),
+            name: "Ethics club".into(),
+            ..Default::default()
+        };
+
+        let hits = search_projects(&[thesis.clone(), ethics], "ethics approval", 10);
+        let ids: Vec<_> = hits.iter().map(|h| h.task_id.as_deref()).collect();
+        assert_eq!(ids, vec![Some("a"), Some("b")]);
+        assert_eq!(hits[1].field, SearchField::Notes);
+        assert!(hits[1].snippet.starts_with('…') && hits[1].snippet.contains("ethics approval"));
+
+        let hits = search_projects(&[thesis], "ETHICS", 10);
+        let ids: Vec<_> = hits.iter().map(|h| h.task_id.as_deref().unwrap()).collect();
+        // Name, then subtask, then notes; a mid-word match ranks last
+        assert_eq!(ids, vec!["a", "c", "b", "d"]);
+        assert_eq!(hits[1].field, SearchField::Subtask);
+        assert!(search_projects(&[], "  ", 10).is_empty());
+    }
+}
@@ -0,0 +1,304 @@
+//! Project statistics: progress over time, burndown toward each anchor,
+//! slip against the plan and task counts by status. Computed here so every
+//! frontend (app, widget, API) reports the same numbers.
+
+use crate::cache::ScheduleCache;
+use crate::project::{load_project, Project};
+use crate::query::TaskStatus;
+use crate::scheduler::{parse_date_string, ScheduledTask, Task};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::{AppHandle, Manager};
+
+/// Longer series are sampled down to about this many points.
+const MAX_POINTS: i64 = 120;
+
+/// Planned work completed and remaining at the end of a day.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProgressPoint {
+    pub date: String,
+    pub completed_minutes: i64,
+    pub remaining_minutes: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BurndownPoint {
+    pub date: String,
+    /// Work left that day; unset for days still to come.
+    pub remaining_minutes: Option<i64>,
+    /// Work that would be left burning down evenly to the anchor.
+    pub ideal_minutes: f64,
+}
+
+/// Burndown of the work an anchor depends on.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AnchorBurndown {
+    pub task_id: String,
+    pub task_name: String,
+    pub anchor_date: String,
+    pub total_minutes: i64,
+    pub points: Vec<BurndownPoint>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct StatusCounts {
+    pub active: usize,
+    pub future: usize,
+    pub overdue: usize,
+    pub completed: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectStats {
+    pub progress: Vec<ProgressPoint>,
+    pub burndowns: Vec<AnchorBurndown>,
+    /// Mean days between each completed task's scheduled end and when it
+    /// was actually completed; negative when work finishes early.
+    pub average_slip_days: Option<f64>,
+    pub status_counts: StatusCounts,
+}
+
+fn completed_on(task: &Task) -> Option<NaiveDate> {
+    let at = DateTime::parse_from_rfc3339(task.completed_at.as_deref()?).ok()?;
+    Some(at.naive_local().date())
+}
+
+/// Days from `from` to `to` inclusive, sampled so there are at most about
+/// [`MAX_POINTS`], always ending on `to`.
+fn days(from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+    let span = (to - from).num_days().max(0);
+    let step = (span + MAX_POINTS - 1) / MAX_POINTS;
+    let mut days: Vec<NaiveDate> = (0..=span)
+        .rev()
+        .step_by(step.max(1) as usize)
+        .map(|offset| from + Duration::days(offset))
+        .collect();
+    days.reverse();
+    days
+}
+
+/// Minutes of `tasks` completed by the end of `day`.
+fn completed_by(tasks: &[&Task], day: NaiveDate) -> i64 {
+    tasks
+        .iter()
+        .filter(|t| t.completed && completed_on(t).is_none_or(|on| on <= day))
+        .map(|t| t.duration().num_minutes())
+        .sum()
+}
+
+/// The task and everything it transitively depends on.
+fn upstream<'a>(tasks: &'a [Task], id: &str) -> Vec<&'a Task> {
+    let mut seen = HashSet::new();
+    let mut stack = vec![id];
+    while let Some(id) = stack.pop() {
+        if !seen.insert(id) {
+            continue;
+        }
+        if let Some(task) = tasks.iter().find(|t| t.id == id) {
+            stack.extend(task.dependencies.iter().map(String::as_str));
+        }
+    }
+    tasks
+        .iter()
+        .filter(|t| seen.contains(t.id.as_str()))
+        .collect()
+}
+
+/// Statistics for `project`. `history` is the project with its archived
+/// tasks, which still count as completed work.
+pub fn project_stats(
+    project: &Project,
+    history: &Project,
+    schedule: &[ScheduledTask],
+    now: NaiveDateTime,
+) -> ProjectStats {
+    let today = now.date();
+    let created = DateTime::parse_from_rfc3339(&project.created_at)
+        .map(|d| d.naive_local().date())
+        .unwrap_or(today);
+    let start = history
+        .tasks
+        .iter()
+        .filter_map(completed_on)
+        .fold(created.min(today), NaiveDate::min);
+
+    let all: Vec<&Task> = history.tasks.iter().collect();
+    let total: i64 = all.iter().map(|t| t.duration().num_minutes()).sum();
+    let progress = days(start, today)
+        .into_iter()
+        .map(|day| {
+            let completed = completed_by(&all, day);
+            ProgressPoint {
+                date: day.to_string(),
+                completed_minutes: completed,
+                remaining_minutes: total - completed,
+            }
+        })
+        .collect();
+
+    let mut burndowns: Vec<AnchorBurndown> = project
+        .anchors
+        .iter()
+        .filter_map(|(id, date)| {
+            let anchor = parse_date_string(date).ok()?.date();
+            let tasks = upstream(&history.tasks, id);
+            let total: i64 = tasks.iter().map(|t| t.duration().num_minutes()).sum();
+            let span = (anchor - start).num_days().max(1) as f64;
+            let points = days(start, anchor.max(start))
+                .into_iter()
+                .map(|day| BurndownPoint {
+                    date: day.to_string(),
+                    remaining_minutes: (day <= today).then(|| total - completed_by(&tasks, day)),
+                    ideal_minutes: total as f64 * (1.0 - (day - start).num_days() as f64 / span),
+                })
+                .collect();
+            Some(AnchorBurndown {
+                task_id: id.clone(),
+                task_name: tasks
+                    .iter()
+                    .find(|t| &t.id == id)
+                    .map(|t| t.name.clone())
+                    .unwrap_or_default(),
+                anchor_date: date.clone(),
+                total_minutes: total,
+                points,
+            })
+        })
+        .collect();
+    burndowns.sort_by(|a, b| a.anchor_date.cmp(&b.anchor_date));
+
+    let mut status_counts = StatusCounts::default();
+    let mut slips = Vec::new();
+    for scheduled in schedule {
+        let (Ok(start), Ok(end)) = (
+            NaiveDateTime::parse_from_str(&scheduled.start_date, "%Y-%m-%dT%H:%M:%S"),
+            NaiveDateTime::parse_from_str(&scheduled.end_date, "%Y-%m-%dT%H:%M:%S"),
+        ) else {
+            continue;
+        };
+        match TaskStatus::of(scheduled, start, end, now) {
+            TaskStatus::Active => status_counts.active += 1,
+            TaskStatus::Future => status_counts.future += 1,
+            TaskStatus::Overdue => status_counts.overdue += 1,
+            TaskStatus::Completed => status_counts.completed += 1,
+        }
+        let completed_at = project
+            .tasks
+            .iter()
+            .find(|t| t.id == scheduled.id)
+            .and_then(|t| DateTime::parse_from_rfc3339(t.completed_at.as_deref()?).ok());
+        if let Some(at) = completed_at {
+            slips.push((at.naive_local() - end).num_minutes() as f64 / 1440.0);
+        }
+    }
+    let average_slip_days =
+        (!slips.is_empty()).then(|| slips.iter().sum::<f64>() / slips.len() as f64);
+
+    ProjectStats {
+        progress,
+        burndowns,
+        average_slip_days,
+        status_counts,
+    }
+}
+
+/// Progress, burndowns, slip and status counts for a project.
+#[tauri::command]
+pub fn get_project_stats(app: AppHandle, project_id: String) -> Result<ProjectStats, String> {
+    let project = load_project(app.clone(), project_id)?;
+    let history = crate::archive::with_history(&app, &project)?;
+    let now = chrono::Local::now().naive_local();
+    let result = app
+        .state::<ScheduleCache>()
+        .schedule(&project, now)
+        .map_err(|e| e.to_string())?;
+    Ok(project_stats(
+        &project,
+        &history,
+        &result.scheduled_tasks,
+        now,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::TaskDuration;
+
+    #[test]
+    fn test_progress_burndown_slip_and_counts() {
+        let task = |id: &str, deps: &[&str], completed_at: Option<&str>| Task {
+            id: id.into(),
+            name: id.into(),
+            duration: TaskDuration::hours(8),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            completed: completed_at.is_some(),
+            completed_at: completed_at.map(String::from),
+            ..Default::default()
+        };
+        let project = Project {
+            created_at: "2026-03-01T09:00:00+00:00".into(),
+            tasks: vec![
+                task("a", &[], Some("2026-03-02T17:00:00+00:00")),
+                task("b", &["a"], Some("2026-03-04T12:00:00+00:00")),
+                task("c", &["b"], None),
+                task("side", &[], None),
+            ],
+            anchors: [("c".to_string(), "2026-03-11T17:00:00".to_string())].into(),
+            ..Default::default()
+        };
+        let scheduled = |id: &str, end: &str, completed: bool| ScheduledTask {
+            id: id.into(),
+            start_date: "2026-03-01T09:00:00".into(),
+            end_date: end.into(),
+            completed,
+            ..Default::default()
+        };
+        let schedule = vec![
+            scheduled("a", "2026-03-02T17:00:00", true),
+            scheduled("b", "2026-03-03T12:00:00", true),
+            scheduled("c", "2026-03-11T17:00:00", false),
+            scheduled("side", "2026-03-04T17:00:00", false),
+        ];
+        let now =
+            NaiveDateTime::parse_from_str("2026-03-05T12:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+
+        let stats = project_stats(&project, &project, &schedule, now);
+        let progress: Vec<_> = stats
+            .progress
+            .iter()
+            .map(|p| (p.date.as_str(), p.completed_minutes / 60))
+            .collect();
+        assert_eq!(
+            progress,
+            vec![
+                ("2026-03-01", 0),
+                ("2026-03-02", 8),
+                ("2026-03-03", 8),
+                ("2026-03-04", 16),
+                ("2026-03-05", 16)
+            ]
+        );
+
+        // "c" depends on three tasks' worth of work, not the side task
+        let burndown = &stats.burndowns[0];
+        assert_eq!(burndown.total_minutes, 24 * 60);
+        assert_eq!(burndown.points.len(), 11);
+        assert_eq!(burndown.points[4].remaining_minutes, Some(8 * 60));
+        assert_eq!(burndown.points[5].remaining_minutes, None);
+        assert_eq!(burndown.points[10].ideal_minutes, 0.0);
+
+        // On time, and a day late
+        assert_eq!(stats.average_slip_days, Some(0.5));
+        assert_eq!(
+            stats.status_counts,
+            StatusCounts {
+                active: 1,
+                future: 0,
+                overdue: 1,
+                completed: 2
+            }
+        );
+    }
+}
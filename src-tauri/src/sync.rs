@@ -0,0 +1,356 @@
+//! Sync-folder support.
+//!
+//! With `AppConfig::projects_dir` pointing into Dropbox, iCloud Drive or
+//! Syncthing, another machine may save a project while it's open here. Each
+//! project is remembered as it was last loaded or saved; when a save finds
+//! the file changed since then, the two versions are merged field by field
+//! against that base instead of overwriting. Where both sides changed the
+//! same task field, the task with the later `updated_at` wins and the other
+//! value is kept in a conflict report.
+
+use crate::project::Project;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// Oldest reports are dropped beyond this.
+const MAX_CONFLICTS: usize = 200;
+
+/// Fields that describe the file rather than the plan; taken from the save.
+const UNMERGED_FIELDS: [&str; 2] = ["last_modified", "schema_version"];
+
+/// Each project as this app last loaded or saved it.
+#[derive(Default)]
+pub struct SyncBase {
+    bases: Mutex<HashMap<String, Project>>,
+}
+
+impl SyncBase {
+    pub(crate) fn remember(&self, project: &Project) {
+        self.bases
+            .lock()
+            .unwrap()
+            .insert(project.id.clone(), project.clone());
+    }
+
+    pub(crate) fn get(&self, project_id: &str) -> Option<Project> {
+        self.bases.lock().unwrap().get(project_id).cloned()
+    }
+}
+
+/// A field both machines changed, and the value that lost.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SyncConflict {
+    pub project_id: String,
+    /// Unset for project-level fields such as the name or anchors.
+    pub task_id: Option<String>,
+    pub task_name: Option<String>,
+    /// The field, or "task" when one side deleted a task the other edited.
+    pub field: String,
+    /// Value in the saved project (null when deleted).
+    pub kept: Value,
+    /// Value that was overwritten (null when deleted).
+    pub discarded: Value,
+    /// When the merge happened (RFC 3339).
+    pub detected_at: String,
+}
+
+fn is_newer(a: Option<&Value>, b: Option<&Value>) -> bool {
+    let time = |v: Option<&Value>| {
+        v.and_then(Value::as_str)
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+    };
+    time(a) >= time(b)
+}
+
+/// Three-way merge of two objects' fields. `conflict` is called with the
+/// field, kept and discarded values where both sides changed a field
+/// differently; `prefer_ours` decides which side is kept.
+fn merge_fields(
+    base: &Map<String, Value>,
+    ours: &Map<String, Value>,
+    theirs: &Map<String, Value>,
+    prefer_ours: bool,
+    mut conflict: impl FnMut(&str, &Value, &Value),
+) -> Map<String, Value> {
+    let keys = ours
+        .keys()
+        .chain(theirs.keys().filter(|k| !ours.contains_key(*k)));
+    let mut merged = Map::new();
+    for key in keys {
+        let (b, o, t) = (base.get(key), ours.get(key), theirs.get(key));
+        let value = if o == t || t == b {
+            o
+        } else if o == b {
+            t
+        } else {
+            let (kept, discarded) = if prefer_ours { (o, t) } else { (t, o) };
+            conflict(
+                key,
+                kept.unwrap_or(&Value::Null),
+                discarded.unwrap_or(&Value::Null),
+            );
+            kept
+        };
+        if let Some(value) = value {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+    merged
+}
+
+fn tasks_by_id(project: &Value) -> Vec<(String, Map<String, Value>)> {
+    project["tasks"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|t| Some((t["id"].as_str()?.to_string(), t.as_object()?.clone())))
+        .collect()
+}
+
+/// Merges `ours` (being saved) and `theirs` (on disk), both edited from
+/// `base`. Project-level conflicts go to `ours`.
+pub fn merge_projects(
+    base: &Project,
+    ours: &Project,
+    theirs: &Project,
+    now: &str,
+) -> Result<(Project, Vec<SyncConflict>), String> {
+    let to_object = |p: &Project| match serde_json::to_value(p) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err("Project is not an object".to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    let (base, ours, theirs) = (to_object(base)?, to_object(ours)?, to_object(theirs)?);
+    let mut conflicts = Vec::new();
+    let mut report =
+        |task: Option<&Map<String, Value>>, field: &str, kept: &Value, discarded: &Value| {
+            conflicts.push(SyncConflict {
+                project_id: ours["id"].as_str().unwrap_or_default().to_string(),
+                task_id: task.and_then(|t| t["id"].as_str()).map(String::from),
+                task_name: task.and_then(|t| t["name"].as_str()).map(String::from),
+                field: field.to_string(),
+                kept: kept.clone(),
+                discarded: discarded.clone(),
+                detected_at: now.to_string(),
+            })
+        };
+
+    let mut merged = merge_fields(&base, &ours, &theirs, true, |field, kept, discarded| {
+        if field != "tasks" && !UNMERGED_FIELDS.contains(&field) {
+            report(None, field, kept, discarded)
+        }
+    });
+    for field in UNMERGED_FIELDS {
+        if let Some(value) = ours.get(field) {
+            merged.insert(field.into(), value.clone());
+        }
+    }
+
+    let (base_value, ours_value, theirs_value) = (
+        Value::Object(base),
+        Value::Object(ours.clone()),
+        Value::Object(theirs),
+    );
+    let base_tasks: HashMap<_, _> = tasks_by_id(&base_value).into_iter().collect();
+    let our_tasks = tasks_by_id(&ours_value);
+    let their_tasks = tasks_by_id(&theirs_value);
+    let ids = our_tasks.iter().map(|(id, _)| id).chain(
+        their_tasks
+            .iter()
+            .map(|(id, _)| id)
+            .filter(|id| !our_tasks.iter().any(|(ours, _)| ours == *id)),
+    );
+    let find = |tasks: &[(String, Map<String, Value>)], id: &str| {
+        tasks
+            .iter()
+            .find(|(t, _)| t == id)
+            .map(|(_, task)| task.clone())
+    };
+
+    let mut tasks = Vec::new();
+    for id in ids {
+        let b = base_tasks.get(id);
+        let (o, t) = (find(&our_tasks, id), find(&their_tasks, id));
+        let task = match (o, t) {
+            (o, t) if o == t || t.as_ref() == b => o,
+            (o, t) if o.as_ref() == b => t,
+            // Deleted on one side and edited on the other: keep the edit
+            (Some(task), None) | (None, Some(task)) => {
+                let kept = Value::Object(task.clone());
+                report(Some(&task), "task", &kept, &Value::Null);
+                Some(task)
+            }
+            (Some(o), Some(t)) => {
+                let prefer_ours = is_newer(o.get("updated_at"), t.get("updated_at"));
+                let empty = Map::new();
+                let mut task = merge_fields(
+                    b.unwrap_or(&empty),
+                    &o,
+                    &t,
+                    prefer_ours,
+                    |field, kept, discarded| {
+                        if field != "updated_at" {
+                            report(Some(&o), field, kept, discarded)
+                        }
+                    },
+                );
+                let newest = if prefer_ours { &o } else { &t };
+                if let Some(updated_at) = newest.get("updated_at") {
+                    task.insert("updated_at".into(), updated_at.clone());
+                }
+                Some(task)
+            }
+            (None, None) => None,
+        };
+        tasks.extend(task.map(Value::Object));
+    }
+    merged.insert("tasks".into(), Value::Array(tasks));
+    let project = serde_json::from_value(Value::Object(merged)).map_err(|e| e.to_string())?;
+    Ok((project, conflicts))
+}
+
+/// Stamps `updated_at` on tasks that differ from `previous` without the
+/// editor having stamped them already.
+pub(crate) fn stamp_updates(project: &mut Project, previous: Option<&Project>, now: &str) {
+    for task in &mut project.tasks {
+        let before = previous.and_then(|p| p.tasks.iter().find(|t| t.id == task.id));
+        let changed = before.is_none_or(|before| {
+            let mut before = before.clone();
+            before.updated_at = task.updated_at.clone();
+            serde_json::to_value(&before).ok() != serde_json::to_value(&*task).ok()
+        });
+        let stamped = before.is_some_and(|b| b.updated_at != task.updated_at);
+        if changed && !stamped {
+            task.updated_at = Some(now.to_string());
+        }
+    }
+}
+
+fn conflicts_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join("sync_conflicts.json"))
+}
+
+fn read_conflicts(app: &AppHandle) -> Result<Vec<SyncConflict>, String> {
+    let path = conflicts_path(app)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let json = crate::encryption::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&json).map_err(|e| e.to_string())
+}
+
+fn write_conflicts(app: &AppHandle, conflicts: &[SyncConflict]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(conflicts).map_err(|e| e.to_string())?;
+    crate::encryption::write(conflicts_path(app)?, json).map_err(|e| e.to_string())
+}
+
+pub(crate) fn record_conflicts(app: &AppHandle, found: Vec<SyncConflict>) -> Result<(), String> {
+    if found.is_empty() {
+        return Ok(());
+    }
+    let mut conflicts = read_conflicts(app)?;
+    conflicts.extend(found);
+    let excess = conflicts.len().saturating_sub(MAX_CONFLICTS);
+    conflicts.drain(..excess);
+    write_conflicts(app, &conflicts)
+}
+
+/// Conflicts found while merging saves, oldest first; for one project when
+/// `project_id` is given.
+#[tauri::command]
+pub fn get_sync_conflicts(
+    app: AppHandle,
+    project_id: Option<String>,
+) -> Result<Vec<SyncConflict>, String> {
+    let mut conflicts = read_conflicts(&app)?;
+    conflicts.retain(|c| project_id.as_ref().is_none_or(|id| &c.project_id == id));
+    Ok(conflicts)
+}
+
+/// Dismisses reported conflicts; all of them unless `project_id` is given.
+#[tauri::command]
+pub fn clear_sync_conflicts(app: AppHandle, project_id: Option<String>) -> Result<(), String> {
+    let mut conflicts = read_conflicts(&app)?;
+    conflicts.retain(|c| project_id.as_ref().is_some_and(|id| &c.project_id != id));
+    write_conflicts(&app, &conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::Task;
+
+    fn task(id: &str, name: &str, updated_at: &str) -> Task {
+        Task {
+            id: id.into(),
+            name: name.into(),
+            updated_at: Some(updated_at.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_concurrent_edits_are_merged_field_by_field() {
+        let base = Project {
+            id: "p".into(),
+            name: "Thesis".into(),
+            last_modified: "2026-03-01T09:00:00+00:00".into(),
+            tasks: vec![
+                task("a", "Draft", "2026-03-01T09:00:00Z"),
+                task("b", "Review", "2026-03-01T09:00:00Z"),
+                task("c", "Print", "2026-03-01T09:00:00Z"),
+            ],
+            ..Default::default()
+        };
+        // Here: renamed the project, finished "a", retitled "b" and deleted "c"
+        let mut ours = base.clone();
+        ours.name = "PhD thesis".into();
+        ours.tasks[0].completed = true;
+        ours.tasks[0].updated_at = Some("2026-03-02T10:00:00Z".into());
+        ours.tasks[1] = task("b", "Review chapter 1", "2026-03-02T10:00:00Z");
+        ours.tasks.remove(2);
+        // Elsewhere, a little later: noted "a", retitled "b" and added "d"
+        let mut theirs = base.clone();
+        theirs.last_modified = "2026-03-02T11:00:00+00:00".into();
+        theirs.tasks[0].notes = Some("see outline".into());
+        theirs.tasks[0].updated_at = Some("2026-03-02T11:00:00Z".into());
+        theirs.tasks[1] = task("b", "Review all chapters", "2026-03-02T11:00:00Z");
+        theirs.tasks.push(task("d", "Bind", "2026-03-02T11:00:00Z"));
+
+        let (merged, conflicts) = merge_projects(&base, &ours, &theirs, "now").unwrap();
+        assert_eq!(merged.name, "PhD thesis");
+        let names: Vec<_> = merged.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Draft", "Review all chapters", "Bind"]);
+        assert!(merged.tasks[0].completed);
+        assert_eq!(merged.tasks[0].notes.as_deref(), Some("see outline"));
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].task_id.as_deref(), Some("b"));
+        assert_eq!(conflicts[0].field, "name");
+        assert_eq!(conflicts[0].discarded, "Review chapter 1");
+
+        // Only tasks changed without a fresh stamp get one
+        let mut saved = merged.clone();
+        saved.tasks[2].name = "Bind (hardcover)".into();
+        stamp_updates(&mut saved, Some(&merged), "2026-03-03T08:00:00Z");
+        let stamps: Vec<_> = saved
+            .tasks
+            .iter()
+            .map(|t| t.updated_at.as_deref())
+            .collect();
+        assert_eq!(
+            stamps,
+            vec![
+                Some("2026-03-02T11:00:00Z"),
+                Some("2026-03-02T11:00:00Z"),
+                Some("2026-03-03T08:00:00Z")
+            ]
+        );
+    }
+}
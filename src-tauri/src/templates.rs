@@ -0,0 +1,381 @@
+//! Project templates.
+//!
+//! A template is a project's task graph without dates or progress. Anchors
+//! are kept as offsets before the template's final anchor, so a new project
+//! only needs one date. User templates are stored in
+//! `<app data>/templates/<id>.json`; a few are bundled with the app.
+
+use crate::project::{parse_date_or_datetime, save_project, Project};
+use crate::scheduler::{ExclusionGroup, Task, TaskDuration};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use uuid::Uuid;
+
+const BUNDLED_PREFIX: &str = "bundled-";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProjectTemplate {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Shipped with the app rather than saved by the user; can't be deleted.
+    #[serde(default)]
+    pub bundled: bool,
+    pub tasks: Vec<Task>,
+    /// Anchored task IDs, by minutes before the latest anchor.
+    #[serde(default)]
+    pub anchor_offsets: HashMap<String, i64>,
+    #[serde(default)]
+    pub exclusions: Vec<ExclusionGroup>,
+    #[serde(default)]
+    pub serialize_work: bool,
+    #[serde(default)]
+    pub spread_slack: bool,
+}
+
+fn templates_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("templates");
+    if !dir.exists() {
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    }
+    Ok(dir)
+}
+
+/// Captures `project`'s task graph as a template, dropping completion state.
+fn from_project(project: &Project, name: String, description: Option<String>) -> ProjectTemplate {
+    let tasks = project
+        .tasks
+        .iter()
+        .map(|task| {
+            let mut task = task.clone();
+            task.completed = false;
+            task.completed_at = None;
+            for subtask in &mut task.subtasks {
+                subtask.completed = false;
+            }
+            task
+        })
+        .collect::<Vec<_>>();
+
+    let anchors: Vec<(&String, chrono::NaiveDateTime)> = project
+        .anchors
+        .iter()
+        .filter(|(id, _)| tasks.iter().any(|t| &t.id == *id))
+        .filter_map(|(id, date)| Some((id, parse_date_or_datetime(date)?)))
+        .collect();
+    let anchor_offsets = match anchors.iter().map(|(_, date)| *date).max() {
+        Some(last) => anchors
+            .iter()
+            .map(|(id, date)| (id.to_string(), (last - *date).num_minutes()))
+            .collect(),
+        // Without anchors, the tasks nothing depends on are the deliverables
+        None => tasks
+            .iter()
+            .filter(|t| !tasks.iter().any(|other| other.dependencies.contains(&t.id)))
+            .map(|t| (t.id.clone(), 0))
+            .collect(),
+    };
+
+    ProjectTemplate {
+        id: Uuid::new_v4().to_string(),
+        name,
+        description,
+        bundled: false,
+        tasks,
+        anchor_offsets,
+        exclusions: project.exclusions.clone(),
+        serialize_work: project.serialize_work,
+        spread_slack: project.spread_slack,
+    }
+}
+
+/// Builds a new project from `template` with its final anchor at `anchor_date`.
+/// Task IDs are fresh so several projects can come from the same template.
+fn instantiate(
+    template: &ProjectTemplate,
+    name: String,
+    anchor_date: &str,
+) -> Result<Project, String> {
+    let date = parse_date_or_datetime(anchor_date)
+        .ok_or_else(|| format!("Invalid anchor date: {}", anchor_date))?;
+    let ids: HashMap<&str, String> = template
+        .tasks
+        .iter()
+        .map(|t| (t.id.as_str(), Uuid::new_v4().to_string()))
+        .collect();
+    let remap = |id: &String| ids.get(id.as_str()).cloned().unwrap_or_else(|| id.clone());
+
+    let tasks = template
+        .tasks
+        .iter()
+        .map(|task| Task {
+            id: remap(&task.id),
+            dependencies: task.dependencies.iter().map(remap).collect(),
+            start_after_percent: task
+                .start_after_percent
+                .iter()
+                .map(|(id, percent)| (remap(id), *percent))
+                .collect(),
+            ..task.clone()
+        })
+        .collect();
+    let anchors = template
+        .anchor_offsets
+        .iter()
+        .filter(|(id, _)| ids.contains_key(id.as_str()))
+        .map(|(id, minutes)| {
+            let at = date - chrono::Duration::minutes(*minutes);
+            (remap(id), at.format("%Y-%m-%dT%H:%M:%S").to_string())
+        })
+        .collect();
+    let exclusions = template
+        .exclusions
+        .iter()
+        .map(|group| ExclusionGroup {
+            resource: group.resource.clone(),
+            task_ids: group.task_ids.iter().map(remap).collect(),
+        })
+        .collect();
+
+    let now = chrono::Local::now().to_rfc3339();
+    Ok(Project {
+        id: Uuid::new_v4().to_string(),
+        name,
+        created_at: now.clone(),
+        last_modified: now,
+        tasks,
+        anchors,
+        exclusions,
+        serialize_work: template.serialize_work,
+        spread_slack: template.spread_slack,
+        ..Default::default()
+    })
+}
+
+fn bundled() -> Vec<ProjectTemplate> {
+    let task = |id: &str, name: &str, duration: TaskDuration, deps: &[&str]| Task {
+        id: id.into(),
+        name: name.into(),
+        duration,
+        dependencies: deps.iter().map(|d| d.to_string()).collect(),
+        is_milestone: duration.value == 0,
+        ..Default::default()
+    };
+    let days = TaskDuration::days;
+    let template = |id: &str, name: &str, description: &str, tasks: Vec<Task>| {
+        let last = tasks.last().map(|t| t.id.clone()).unwrap_or_default();
+        ProjectTemplate {
+            id: format!("{}{}", BUNDLED_PREFIX, id),
+            name: name.into(),
+            description: Some(description.into()),
+            bundled: true,
+            tasks,
+            anchor_offsets: [(last, 0)].into(),
+            ..Default::default()
+        }
+    };
+    vec![
+        template(
+            "paper-submission",
+            "Paper submission",
+            "From literature review to a submitted manuscript.",
+            vec![
+                task("review", "Literature review", days(10), &[]),
+                task("experiments", "Run experiments", days(15), &["review"]),
+                task("figures", "Prepare figures", days(4), &["experiments"]),
+                task("draft", "Write first draft", days(10), &["experiments"]),
+                task(
+                    "feedback",
+                    "Co-author feedback",
+                    days(5),
+                    &["draft", "figures"],
+                ),
+                task("revise", "Revise", days(4), &["feedback"]),
+                task("format", "Format for venue", days(2), &["revise"]),
+                task("submit", "Submit", days(0), &["format"]),
+            ],
+        ),
+        template(
+            "product-launch",
+            "Product launch",
+            "Feature freeze through launch day.",
+            vec![
+                task("freeze", "Feature freeze", days(0), &[]),
+                task("qa", "QA and bug fixing", days(10), &["freeze"]),
+                task("docs", "Documentation", days(7), &["freeze"]),
+                task("marketing", "Marketing site and assets", days(12), &[]),
+                task("press", "Press and announcement", days(5), &["marketing"]),
+                task("release", "Release candidate", days(3), &["qa", "docs"]),
+                task("launch", "Launch", days(0), &["release", "press"]),
+            ],
+        ),
+        template(
+            "event",
+            "Event",
+            "Venue, speakers and promotion for a one-day event.",
+            vec![
+                task("venue", "Book venue", days(5), &[]),
+                task("speakers", "Confirm speakers", days(14), &[]),
+                task("program", "Publish programme", days(2), &["speakers"]),
+                task(
+                    "promote",
+                    "Promote and register attendees",
+                    days(21),
+                    &["venue", "program"],
+                ),
+                task("logistics", "Catering and logistics", days(5), &["venue"]),
+                task(
+                    "event",
+                    "Event day",
+                    TaskDuration::hours(8),
+                    &["promote", "logistics"],
+                ),
+            ],
+        ),
+    ]
+}
+
+fn find_template(app: &AppHandle, template_id: &str) -> Result<ProjectTemplate, String> {
+    if let Some(template) = bundled().into_iter().find(|t| t.id == template_id) {
+        return Ok(template);
+    }
+    let path = templates_dir(app)?.join(format!("{}.json", template_id));
+    let json =
+        fs::read_to_string(path).map_err(|_| format!("Template {} not found", template_id))?;
+    serde_json::from_str(&json).map_err(|e| e.to_string())
+}
+
+/// Bundled templates followed by the user's, by name.
+#[tauri::command]
+pub fn list_templates(app: AppHandle) -> Result<Vec<ProjectTemplate>, String> {
+    let mut saved: Vec<ProjectTemplate> = fs::read_dir(templates_dir(&app)?)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .filter(|file| file.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|file| {
+            let json = fs::read_to_string(file.path()).ok()?;
+            serde_json::from_str(&json).ok()
+        })
+        .collect();
+    saved.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut templates = bundled();
+    templates.extend(saved);
+    Ok(templates)
+}
+
+/// Saves a project's tasks as a new template.
+#[tauri::command]
+pub fn create_template(
+    app: AppHandle,
+    project_id: String,
+    name: String,
+    description: Option<String>,
+) -> Result<ProjectTemplate, String> {
+    let project = crate::project::load_project(app.clone(), project_id)?;
+    let template = from_project(&project, name, description);
+    let json = serde_json::to_string_pretty(&template).map_err(|e| e.to_string())?;
+    fs::write(
+        templates_dir(&app)?.join(format!("{}.json", template.id)),
+        json,
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(template)
+}
+
+#[tauri::command]
+pub fn delete_template(app: AppHandle, template_id: String) -> Result<(), String> {
+    if template_id.starts_with(BUNDLED_PREFIX) {
+        return Err("Bundled templates can't be deleted".to_string());
+    }
+    let path = templates_dir(&app)?.join(format!("{}.json", template_id));
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Creates and saves a project from a template, with its final anchor at
+/// `anchor_date` (ISO 8601 DateTime or YYYY-MM-DD).
+#[tauri::command]
+pub fn instantiate_template(
+    app: AppHandle,
+    template_id: String,
+    name: String,
+    anchor_date: String,
+) -> Result<Project, String> {
+    let template = find_template(&app, &template_id)?;
+    let project = instantiate(&template, name, &anchor_date)?;
+    save_project(app, project.clone())?;
+    Ok(project)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::calculate_backwards_schedule;
+
+    #[test]
+    fn test_template_round_trip_keeps_graph_and_anchor_offsets() {
+        let task = |id: &str, deps: &[&str]| Task {
+            id: id.into(),
+            name: id.to_uppercase(),
+            duration: TaskDuration::days(2),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            completed: id == "a",
+            completed_at: (id == "a").then(|| "2026-01-01T00:00:00Z".into()),
+            ..Default::default()
+        };
+        let project = Project {
+            tasks: vec![task("a", &[]), task("b", &["a"]), task("c", &["b"])],
+            anchors: [
+                ("b".into(), "2026-03-03T17:00:00".into()),
+                ("c".into(), "2026-03-10T17:00:00".into()),
+            ]
+            .into(),
+            ..Default::default()
+        };
+
+        let template = from_project(&project, "Flow".into(), None);
+        assert!(template
+            .tasks
+            .iter()
+            .all(|t| !t.completed && t.completed_at.is_none()));
+        assert_eq!(template.anchor_offsets["b"], 7 * 24 * 60);
+        assert_eq!(template.anchor_offsets["c"], 0);
+
+        let new = instantiate(&template, "Next".into(), "2026-06-30T12:00:00").unwrap();
+        assert!(new
+            .tasks
+            .iter()
+            .all(|t| !["a", "b", "c"].contains(&t.id.as_str())));
+        let id = |name: &str| {
+            new.tasks
+                .iter()
+                .find(|t| t.name == name)
+                .unwrap()
+                .id
+                .clone()
+        };
+        assert_eq!(new.tasks[2].dependencies, vec![id("B")]);
+        assert_eq!(new.anchors[&id("C")], "2026-06-30T12:00:00");
+        assert_eq!(new.anchors[&id("B")], "2026-06-23T12:00:00");
+        assert!(instantiate(&template, "Bad".into(), "soon").is_err());
+    }
+
+    #[test]
+    fn test_bundled_templates_schedule() {
+        for template in bundled() {
+            let project = instantiate(&template, template.name.clone(), "2026-09-01").unwrap();
+            assert_eq!(project.anchors.len(), 1, "{}", template.id);
+            calculate_backwards_schedule(project.schedule_request()).unwrap();
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Time tracking.
+//!
+//! The task timer records work sessions in the project file. One timer runs
+//! at a time; starting another stops it first. Tracked time shows up as
+//! [`ScheduledTask::actual_minutes`](crate::scheduler::ScheduledTask) against
+//! the estimate, and an unfinished task is scheduled with only the work it
+//! has left.
+
+use crate::events::ChangeKind;
+use crate::project::{load_all_projects, load_project, update_task};
+use crate::scheduler::{Task, WorkSession};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// The task being timed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RunningTimer {
+    pub project_id: String,
+    pub project_name: String,
+    pub task_id: String,
+    pub task_name: String,
+    pub started_at: String,
+}
+
+fn format_time(at: NaiveDateTime) -> String {
+    at.format("%Y-%m-%dT%H:%M:%S").to_string()
+}
+
+/// Opens a session on `task`, unless one is already open.
+pub(crate) fn start_session(task: &mut Task, now: NaiveDateTime) -> bool {
+    if task.sessions.iter().any(|s| s.ended_at.is_none()) {
+        return false;
+    }
+    task.sessions.push(WorkSession {
+        started_at: format_time(now),
+        ended_at: None,
+    });
+    true
+}
+
+/// Closes `task`'s open session, returning it.
+pub(crate) fn stop_session(task: &mut Task, now: NaiveDateTime) -> Option<WorkSession> {
+    let session = task.sessions.iter_mut().find(|s| s.ended_at.is_none())?;
+    session.ended_at = Some(format_time(now));
+    Some(session.clone())
+}
+
+fn running(app: &AppHandle) -> Result<Option<RunningTimer>, String> {
+    for project in load_all_projects(app)? {
+        for task in &project.tasks {
+            if let Some(session) = task.sessions.iter().find(|s| s.ended_at.is_none()) {
+                return Ok(Some(RunningTimer {
+                    project_id: project.id.clone(),
+                    project_name: project.name.clone(),
+                    task_id: task.id.clone(),
+                    task_name: task.name.clone(),
+                    started_at: session.started_at.clone(),
+                }));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The timer that's running, if any.
+#[tauri::command]
+pub fn active_timer(app: AppHandle) -> Result<Option<RunningTimer>, String> {
+    running(&app)
+}
+
+/// Starts timing a task, stopping the timer on any other task first.
+#[tauri::command]
+pub fn start_timer(
+    app: AppHandle,
+    project_id: String,
+    task_id: String,
+) -> Result<RunningTimer, String> {
+    if let Some(timer) = running(&app)? {
+        if timer.project_id == project_id && timer.task_id == task_id {
+            return Ok(timer);
+        }
+        stop_timer(app.clone())?;
+    }
+    let now = chrono::Local::now().naive_local();
+    update_task(&app, &project_id, &task_id, ChangeKind::Tracked, |task| {
+        start_session(task, now)
+    })?;
+    let project = load_project(app, project_id)?;
+    let task = project.tasks.iter().find(|t| t.id == task_id);
+    Ok(RunningTimer {
+        project_id: project.id.clone(),
+        project_name: project.name.clone(),
+        task_id,
+        task_name: task.map(|t| t.name.clone()).unwrap_or_default(),
+        started_at: format_time(now),
+    })
+}
+
+/// Stops the running timer, returning the session it recorded. The task is
+/// rescheduled with the work it has left.
+#[tauri::command]
+pub fn stop_timer(app: AppHandle) -> Result<Option<WorkSession>, String> {
+    let Some(timer) = running(&app)? else {
+        return Ok(None);
+    };
+    let now = chrono::Local::now().naive_local();
+    let mut session = None;
+    update_task(
+        &app,
+        &timer.project_id,
+        &timer.task_id,
+        ChangeKind::Tracked,
+        |task| {
+            session = stop_session(task, now);
+            session.is_some()
+        },
+    )?;
+    Ok(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{calculate_backwards_schedule, ScheduleRequest, TaskDuration};
+
+    #[test]
+    fn test_tracked_time_reschedules_remaining_work() {
+        let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap();
+        let mut task = Task {
+            id: "draft".into(),
+            name: "Draft".into(),
+            duration: TaskDuration::hours(5),
+            ..Default::default()
+        };
+        assert!(start_session(&mut task, at("2026-03-09T09:00:00")));
+        assert!(!start_session(&mut task, at("2026-03-09T09:30:00")));
+        // A running timer doesn't count yet
+        assert_eq!(task.actual_minutes(), 0);
+        let session = stop_session(&mut task, at("2026-03-09T11:00:00")).unwrap();
+        assert_eq!(session.minutes(), 120);
+        assert!(stop_session(&mut task, at("2026-03-09T12:00:00")).is_none());
+
+        let schedule = |task: &Task| {
+            let request = ScheduleRequest {
+                tasks: vec![task.clone()],
+                anchors: [("draft".into(), "2026-03-10T17:00:00".into())].into(),
+                reference_time: Some("2026-03-09T12:00:00".into()),
+                ..Default::default()
+            };
+            calculate_backwards_schedule(request)
+                .unwrap()
+                .scheduled_tasks[0]
+                .clone()
+        };
+        let scheduled = schedule(&task);
+        assert_eq!(scheduled.actual_minutes, 120);
+        assert_eq!(scheduled.estimate_minutes, 300);
+        assert_eq!(scheduled.start_date, "2026-03-10T14:00:00");
+
+        // Done is done, whatever was tracked
+        task.completed = true;
+        assert_eq!(schedule(&task).start_date, "2026-03-10T12:00:00");
+    }
+}
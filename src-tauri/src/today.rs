@@ -0,0 +1,194 @@
+//! Today view.
+//!
+//! The slice of the schedule that needs attention today: unfinished tasks
+//! already under way or due to start before the day is out, with how much
+//! of each fits in what is left of today's working hours. Meant for the
+//! compact widget, where a whole project doesn't fit.
+
+use crate::agenda::WorkingHours;
+use crate::cache::ScheduleCache;
+use crate::config::load_config;
+use crate::project::{load_all_projects, load_project, Project, ALL_PROJECTS};
+use crate::scheduler::ScheduledTask;
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TodayTask {
+    pub id: String,
+    pub name: String,
+    pub project_id: String,
+    pub project_name: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub is_milestone: bool,
+    /// "active" once started, otherwise "starts_today".
+    pub status: String,
+    /// Working minutes left today within the task's scheduled span, capped
+    /// at the day's capacity.
+    pub minutes_left_today: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TodayView {
+    /// YYYY-MM-DD
+    pub date: String,
+    /// False on days off, when nothing gets time today.
+    pub working_day: bool,
+    /// Working minutes left in the day, capped at its capacity.
+    pub minutes_left_today: i64,
+    /// Soonest to finish first.
+    pub tasks: Vec<TodayTask>,
+}
+
+/// Today's tasks from each project's schedule.
+pub fn plan_today(
+    schedules: &[(&Project, Vec<ScheduledTask>)],
+    working: &WorkingHours,
+    now: NaiveDateTime,
+) -> Result<TodayView, String> {
+    let today = now.date();
+    let window = working.hours_on(today)?.map(|(start, end, capacity)| {
+        let open = today.and_time(start).max(now);
+        let close = today.and_time(end);
+        (
+            open,
+            close,
+            capacity.min((close - open).num_minutes()).max(0),
+        )
+    });
+    let end_of_today = today.and_hms_opt(0, 0, 0).unwrap_or(now) + Duration::days(1);
+
+    let mut tasks: Vec<(NaiveDateTime, TodayTask)> = Vec::new();
+    for (project, schedule) in schedules {
+        for task in schedule.iter().filter(|t| !t.completed) {
+            let (Ok(start), Ok(end)) = (
+                NaiveDateTime::parse_from_str(&task.start_date, "%Y-%m-%dT%H:%M:%S"),
+                NaiveDateTime::parse_from_str(&task.end_date, "%Y-%m-%dT%H:%M:%S"),
+            ) else {
+                continue;
+            };
+            if start >= end_of_today || end.date() < today {
+                continue;
+            }
+            let minutes_left_today = window.map_or(0, |(open, close, capacity)| {
+                let from = open.max(start);
+                let to = close.min(end);
+                (to - from).num_minutes().clamp(0, capacity)
+            });
+            tasks.push((
+                end,
+                TodayTask {
+                    id: task.id.clone(),
+                    name: task.name.clone(),
+                    project_id: project.id.clone(),
+                    project_name: project.name.clone(),
+                    start_date: task.start_date.clone(),
+                    end_date: task.end_date.clone(),
+                    is_milestone: task.is_milestone,
+                    status: if start <= now {
+                        "active"
+                    } else {
+                        "starts_today"
+                    }
+                    .to_string(),
+                    minutes_left_today,
+                },
+            ));
+        }
+    }
+    tasks.sort_by(|a, b| (a.0, &a.1.name).cmp(&(b.0, &b.1.name)));
+
+    Ok(TodayView {
+        date: today.format("%Y-%m-%d").to_string(),
+        working_day: window.is_some(),
+        minutes_left_today: window.map_or(0, |(_, _, capacity)| capacity),
+        tasks: tasks.into_iter().map(|(_, t)| t).collect(),
+    })
+}
+
+/// Today's tasks in one project, or across every project when `project_id`
+/// is unset or the widget's [`ALL_PROJECTS`]. Overdue work counts as
+/// starting now.
+#[tauri::command]
+pub fn get_today(app: AppHandle, project_id: Option<String>) -> Result<TodayView, String> {
+    let working = load_config(app.clone())?.working_hours;
+    let projects = match project_id.filter(|id| id != ALL_PROJECTS) {
+        Some(id) => vec![load_project(app.clone(), id)?],
+        None => load_all_projects(&app)?,
+    };
+    let now = chrono::Local::now().naive_local();
+    let mut schedules = Vec::new();
+    for project in &projects {
+        match app.state::<ScheduleCache>().schedule_from_now(project, now) {
+            Ok((_, floored)) => schedules.push((project, floored)),
+            Err(e) if projects.len() > 1 => {
+                eprintln!("Skipping {} in today's tasks: {}", project.name, e)
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+    plan_today(&schedules, &working, now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_today_slice_and_time_left() {
+        let task = |id: &str, start: &str, end: &str| ScheduledTask {
+            id: id.into(),
+            name: id.into(),
+            start_date: start.into(),
+            end_date: end.into(),
+            ..Default::default()
+        };
+        let project = Project {
+            id: "p".into(),
+            name: "Thesis".into(),
+            ..Default::default()
+        };
+        let schedule = vec![
+            task("writing", "2026-03-09T09:00:00", "2026-03-12T17:00:00"),
+            task("call", "2026-03-10T15:00:00", "2026-03-10T16:00:00"),
+            task("tomorrow", "2026-03-11T09:00:00", "2026-03-11T17:00:00"),
+            task("finished", "2026-03-09T09:00:00", "2026-03-09T17:00:00"),
+            ScheduledTask {
+                completed: true,
+                ..task("done", "2026-03-10T09:00:00", "2026-03-10T12:00:00")
+            },
+        ];
+        // A Tuesday
+        let now =
+            NaiveDateTime::parse_from_str("2026-03-10T13:30:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+
+        let view = plan_today(
+            &[(&project, schedule.clone())],
+            &WorkingHours::default(),
+            now,
+        )
+        .unwrap();
+        assert!(view.working_day);
+        assert_eq!(view.minutes_left_today, 210);
+        let summary: Vec<_> = view
+            .tasks
+            .iter()
+            .map(|t| (t.id.as_str(), t.status.as_str(), t.minutes_left_today))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("call", "starts_today", 60), ("writing", "active", 210)]
+        );
+        assert_eq!(view.tasks[0].project_name, "Thesis");
+
+        // Saturday: still listed, but no working time
+        let saturday =
+            NaiveDateTime::parse_from_str("2026-03-14T10:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        let weekend = vec![task("chores", "2026-03-14T09:00:00", "2026-03-15T17:00:00")];
+        let view = plan_today(&[(&project, weekend)], &WorkingHours::default(), saturday).unwrap();
+        assert!(!view.working_day);
+        assert_eq!(view.tasks[0].minutes_left_today, 0);
+    }
+}
@@ -0,0 +1,322 @@
+//! Todoist import.
+//!
+//! Reads a Todoist project either through the API with a personal token
+//! ([`list_todoist_projects`], [`import_todoist`]) or from its CSV export,
+//! which [`crate::import`] recognises by its `TYPE,CONTENT,...` header.
+//! Sections become phases, labels become tags and due dates become anchors.
+//! Todoist has no dependencies, so those are left to add afterwards.
+
+use crate::import::{csv_records, new_project, new_task, ImportFormat, ImportPreview};
+use crate::project::{save_project, Project};
+use crate::scheduler::TaskDuration;
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+const API_URL: &str = "https://api.todoist.com/api/v1";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TodoistProject {
+    pub id: String,
+    pub name: String,
+}
+
+/// A Todoist task as either source describes it.
+#[derive(Debug, Default)]
+struct TodoistTask {
+    content: String,
+    description: Option<String>,
+    section: Option<String>,
+    labels: Vec<String>,
+    /// As written in Todoist: a date, a date and time, or (from CSV) words.
+    due: Option<String>,
+    recurring: bool,
+    duration: Option<TaskDuration>,
+}
+
+/// Due dates as Todoist writes them; `None` for phrases like "every monday".
+fn parse_due(due: &str) -> Option<String> {
+    let due = due.trim();
+    if let Ok(date) = crate::dates::normalize(due, chrono::Local::now().naive_local()) {
+        return Some(date);
+    }
+    if let Ok(at) = DateTime::parse_from_rfc3339(due) {
+        let local = at.with_timezone(&chrono::Local).naive_local();
+        return Some(local.format("%Y-%m-%dT%H:%M:%S").to_string());
+    }
+    if let Ok(at) = NaiveDateTime::parse_from_str(due, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Some(at.format("%Y-%m-%dT%H:%M:%S").to_string());
+    }
+    ["%b %d %Y", "%d %b %Y", "%B %d %Y", "%d %B %Y"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(due, format).ok())
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+fn parse_duration(amount: i64, unit: &str) -> Option<TaskDuration> {
+    match unit.to_ascii_lowercase().as_str() {
+        "minute" => Some(crate::import::duration_from_minutes(amount)),
+        "day" => Some(TaskDuration::days(amount)),
+        _ => None,
+    }
+}
+
+/// Builds the project; tasks without a duration take a day.
+fn build(name: &str, tasks: Vec<TodoistTask>) -> (Project, Vec<String>) {
+    let mut project = new_project(name);
+    let mut warnings = Vec::new();
+    for todo in tasks {
+        let mut task = new_task(&todo.content);
+        task.notes = todo.description.filter(|d| !d.trim().is_empty());
+        task.phase = todo.section;
+        task.tags = todo.labels;
+        task.duration = todo.duration.unwrap_or(TaskDuration::days(1));
+        if let Some(due) = todo.due.filter(|d| !d.trim().is_empty()) {
+            match parse_due(&due) {
+                Some(anchor) => {
+                    project.anchors.insert(task.id.clone(), anchor);
+                }
+                None => warnings.push(format!(
+                    "'{}': due date '{}' not understood; set its anchor by hand",
+                    task.name, due
+                )),
+            }
+            if todo.recurring {
+                warnings.push(format!(
+                    "'{}' repeats in Todoist; only the next occurrence was imported",
+                    task.name
+                ));
+            }
+        }
+        project.tasks.push(task);
+    }
+    (project, warnings)
+}
+
+/// Imports a Todoist CSV export. Section rows set the phase of the tasks
+/// below them and note rows are added to the task above.
+pub(crate) fn import_csv(name: &str, text: &str) -> Result<(Project, Vec<String>), String> {
+    let text = text.trim_start_matches('\u{feff}');
+    let mut records = csv_records(text, ',').into_iter();
+    let header: Vec<String> = records
+        .next()
+        .ok_or("The file is empty")?
+        .iter()
+        .map(|h| h.trim().to_ascii_uppercase())
+        .collect();
+    let column = |name: &str| header.iter().position(|h| h == name);
+    let (type_col, content_col) = column("TYPE")
+        .zip(column("CONTENT"))
+        .ok_or("Not a Todoist export: no TYPE and CONTENT columns")?;
+    let description_col = column("DESCRIPTION");
+    let date_col = column("DATE");
+    let duration_col = column("DURATION");
+    let unit_col = column("DURATION_UNIT");
+
+    let mut tasks: Vec<TodoistTask> = Vec::new();
+    let mut section = None;
+    let mut warnings = Vec::new();
+    for (row, record) in records.enumerate() {
+        let get = |col: Option<usize>| {
+            col.and_then(|c| record.get(c))
+                .map(|f| f.trim())
+                .filter(|f| !f.is_empty())
+        };
+        let content = get(Some(content_col)).unwrap_or_default();
+        match get(Some(type_col)).unwrap_or_default() {
+            "section" => section = Some(content.to_string()),
+            "task" => {
+                // Labels are written inline as "@label"
+                let (labels, words): (Vec<&str>, Vec<&str>) = content
+                    .split_whitespace()
+                    .partition(|w| w.len() > 1 && w.starts_with('@'));
+                tasks.push(TodoistTask {
+                    content: words.join(" "),
+                    description: get(description_col).map(str::to_string),
+                    section: section.clone(),
+                    labels: labels.iter().map(|l| l[1..].to_string()).collect(),
+                    due: get(date_col).map(str::to_string),
+                    duration: get(duration_col)
+                        .and_then(|d| d.parse().ok())
+                        .and_then(|amount| parse_duration(amount, get(unit_col)?)),
+                    ..Default::default()
+                });
+            }
+            "note" => match tasks.last_mut() {
+                Some(task) => {
+                    let notes = task.description.get_or_insert_with(String::new);
+                    if !notes.is_empty() {
+                        notes.push_str("\n\n");
+                    }
+                    notes.push_str(content);
+                }
+                None => warnings.push(format!("Line {}: note without a task, skipped", row + 2)),
+            },
+            _ => {}
+        }
+    }
+    let (project, mut build_warnings) = build(name, tasks);
+    warnings.append(&mut build_warnings);
+    Ok((project, warnings))
+}
+
+/// Maps API sections and tasks (v1 JSON) onto [`TodoistTask`]s. Subtasks are
+/// imported alongside their parents.
+fn from_api(sections: &[Value], tasks: &[Value]) -> Vec<TodoistTask> {
+    let text = |v: &Value| v.as_str().map(str::to_string);
+    let section_names: HashMap<String, String> = sections
+        .iter()
+        .filter_map(|s| Some((text(&s["id"])?, text(&s["name"])?)))
+        .collect();
+    tasks
+        .iter()
+        .filter(|t| !t["checked"].as_bool().unwrap_or(false))
+        .map(|t| TodoistTask {
+            content: text(&t["content"]).unwrap_or_default(),
+            description: text(&t["description"]),
+            section: text(&t["section_id"]).and_then(|id| section_names.get(&id).cloned()),
+            labels: t["labels"]
+                .as_array()
+                .map(|labels| labels.iter().filter_map(text).collect())
+                .unwrap_or_default(),
+            due: text(&t["due"]["date"]),
+            recurring: t["due"]["is_recurring"].as_bool().unwrap_or(false),
+            duration: t["duration"]["amount"]
+                .as_i64()
+                .zip(t["duration"]["unit"].as_str())
+                .and_then(|(amount, unit)| parse_duration(amount, unit)),
+        })
+        .collect()
+}
+
+/// Every item of a paginated listing.
+fn fetch_all(path: &str, token: &str, query: &[(&str, &str)]) -> Result<Vec<Value>, String> {
+    let mut items = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut request = ureq::get(&format!("{}{}", API_URL, path))
+            .set("Authorization", &format!("Bearer {}", token));
+        for (key, value) in query {
+            request = request.query(key, value);
+        }
+        if let Some(cursor) = &cursor {
+            request = request.query("cursor", cursor);
+        }
+        let response: Value = request
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(401 | 403, _) => "Todoist rejected the token".to_string(),
+                e => e.to_string(),
+            })?
+            .into_json()
+            .map_err(|e| e.to_string())?;
+        items.extend(response["results"].as_array().cloned().unwrap_or_default());
+        cursor = response["next_cursor"].as_str().map(str::to_string);
+        if cursor.is_none() {
+            return Ok(items);
+        }
+    }
+}
+
+/// The projects a Todoist token can read.
+#[tauri::command]
+pub async fn list_todoist_projects(token: String) -> Result<Vec<TodoistProject>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let projects = fetch_all("/projects", &token, &[])?;
+        Ok(projects
+            .iter()
+            .filter_map(|p| {
+                Some(TodoistProject {
+                    id: p["id"].as_str()?.to_string(),
+                    name: p["name"].as_str()?.to_string(),
+                })
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Imports a Todoist project's open tasks as a new project. With `dry_run`
+/// nothing is saved. The token is used for this call only.
+#[tauri::command]
+pub async fn import_todoist(
+    app: AppHandle,
+    token: String,
+    project: TodoistProject,
+    dry_run: bool,
+) -> Result<ImportPreview, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let query = [("project_id", project.id.as_str())];
+        let sections = fetch_all("/sections", &token, &query)?;
+        let tasks = fetch_all("/tasks", &token, &query)?;
+        let (imported, warnings) = build(&project.name, from_api(&sections, &tasks));
+        let preview = crate::import::preview_of(
+            format!("todoist:{}", project.id),
+            ImportFormat::Todoist,
+            imported,
+            warnings,
+            Vec::new(),
+        );
+        if !dry_run {
+            save_project(app, preview.project.clone())?;
+        }
+        Ok(preview)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_csv_export_and_api_map_to_tasks() {
+        let csv = "TYPE,CONTENT,DESCRIPTION,PRIORITY,INDENT,AUTHOR,RESPONSIBLE,DATE,DATE_LANG,TIMEZONE,DURATION,DURATION_UNIT\n\
+                   section,Research,,,,,,,,,,\n\
+                   task,Read papers @reading,,4,1,,,2026-03-12,en,,90,minute\n\
+                   note,Start with the survey,,,,,,,,,,\n\
+                   section,Writing,,,,,,,,,,\n\
+                   task,Draft chapter,,1,1,,,every monday,en,,,\n";
+        let (project, warnings) = import_csv("Thesis", csv).unwrap();
+        assert_eq!(project.name, "Thesis");
+        let read = &project.tasks[0];
+        assert_eq!(read.name, "Read papers");
+        assert_eq!(read.tags, vec!["reading"]);
+        assert_eq!(read.phase.as_deref(), Some("Research"));
+        assert_eq!(read.notes.as_deref(), Some("Start with the survey"));
+        assert_eq!(read.duration, TaskDuration::minutes(90));
+        assert_eq!(project.anchors[&read.id], "2026-03-12");
+
+        let draft = &project.tasks[1];
+        assert_eq!(draft.phase.as_deref(), Some("Writing"));
+        assert_eq!(draft.duration, TaskDuration::days(1));
+        assert!(!project.anchors.contains_key(&draft.id));
+        assert_eq!(warnings.len(), 1);
+        assert!(project.tasks.iter().all(|t| t.dependencies.is_empty()));
+
+        let sections = [json!({"id": "s1", "name": "Launch"})];
+        let tasks = [
+            json!({
+                "content": "Ship",
+                "section_id": "s1",
+                "labels": ["release"],
+                "due": {"date": "2026-04-01T17:00:00", "is_recurring": false},
+                "duration": {"amount": 2, "unit": "day"},
+                "checked": false
+            }),
+            json!({"content": "Done already", "checked": true}),
+        ];
+        let (project, warnings) = build("Launch", from_api(&sections, &tasks));
+        assert!(warnings.is_empty());
+        assert_eq!(project.tasks.len(), 1);
+        let ship = &project.tasks[0];
+        assert_eq!(ship.phase.as_deref(), Some("Launch"));
+        assert_eq!(ship.duration, TaskDuration::days(2));
+        assert_eq!(project.anchors[&ship.id], "2026-04-01T17:00:00");
+    }
+}
@@ -0,0 +1,232 @@
+//! Trash for deleted projects.
+//!
+//! `delete_project` moves a project and its archive into
+//! `projects/trash/<id>.json` instead of removing them. Entries can be
+//! restored until they're purged, either explicitly or once they're older
+//! than `AppConfig::trash_retention_days` (checked at launch).
+
+use crate::events::ChangeKind;
+use crate::project::get_projects_dir;
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// A deleted project with everything needed to bring it back. Files are kept
+/// as raw JSON so restoring writes back exactly what was deleted.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct TrashEntry {
+    deleted_at: String,
+    project: Value,
+    #[serde(default)]
+    archive: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TrashedProject {
+    pub project_id: String,
+    pub name: String,
+    pub task_count: usize,
+    pub deleted_at: String,
+}
+
+fn trash_dir(projects_dir: &Path) -> Result<PathBuf, String> {
+    let dir = projects_dir.join("trash");
+    if !dir.exists() {
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    }
+    Ok(dir)
+}
+
+fn read_json(path: &Path) -> Result<Value, String> {
+    let json = crate::encryption::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&json).map_err(|e| e.to_string())
+}
+
+fn read_entry(path: &Path) -> Option<TrashEntry> {
+    let json = crate::encryption::read_to_string(path).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Moves project `id` (and its archive) from `projects_dir` into the trash.
+pub(crate) fn move_to_trash(projects_dir: &Path, id: &str) -> Result<(), String> {
+    let path = projects_dir.join(format!("{}.json", id));
+    let archive = projects_dir.join("archive").join(format!("{}.json", id));
+    let entry = TrashEntry {
+        deleted_at: Local::now().to_rfc3339(),
+        project: read_json(&path)?,
+        archive: read_json(&archive).ok(),
+    };
+    let json = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+    let json = crate::encryption::seal(json).map_err(|e| e.to_string())?;
+    crate::repair::write_atomic(
+        &trash_dir(projects_dir)?.join(format!("{}.json", id)),
+        &json,
+    )?;
+
+    fs::remove_file(&path).map_err(|e| e.to_string())?;
+    if archive.exists() {
+        fs::remove_file(archive).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn list(projects_dir: &Path) -> Result<Vec<TrashedProject>, String> {
+    let mut trashed: Vec<TrashedProject> = fs::read_dir(trash_dir(projects_dir)?)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .filter_map(|file| {
+            let name = file.file_name().into_string().ok()?;
+            let id = name.strip_suffix(".json")?.to_string();
+            let entry = read_entry(&file.path())?;
+            Some(TrashedProject {
+                project_id: id,
+                name: entry.project["name"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                task_count: entry.project["tasks"].as_array().map_or(0, Vec::len),
+                deleted_at: entry.deleted_at,
+            })
+        })
+        .collect();
+    trashed.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(trashed)
+}
+
+fn restore(projects_dir: &Path, id: &str) -> Result<(), String> {
+    let path = projects_dir.join(format!("{}.json", id));
+    if path.exists() {
+        return Err(format!("A project with ID {} already exists", id));
+    }
+    let trashed = trash_dir(projects_dir)?.join(format!("{}.json", id));
+    let entry =
+        read_entry(&trashed).ok_or_else(|| format!("Project {} is not in the trash", id))?;
+
+    if let Some(archive) = &entry.archive {
+        let dir = projects_dir.join("archive");
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let json = serde_json::to_string_pretty(archive).map_err(|e| e.to_string())?;
+        crate::encryption::write(dir.join(format!("{}.json", id)), json)
+            .map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(&entry.project).map_err(|e| e.to_string())?;
+    let json = crate::encryption::seal(json).map_err(|e| e.to_string())?;
+    crate::repair::write_atomic(&path, &json)?;
+    fs::remove_file(&trashed).map_err(|e| e.to_string())?;
+    crate::repair::remove_sidecars(&trashed);
+    Ok(())
+}
+
+/// Removes entries deleted more than `days` ago, or all of them. Returns how
+/// many were removed.
+fn purge(projects_dir: &Path, days: Option<u32>, now: DateTime<Local>) -> Result<usize, String> {
+    let cutoff = days.map(|days| now - Duration::days(days as i64));
+    let mut removed = 0;
+    for file in fs::read_dir(trash_dir(projects_dir)?)
+        .map_err(|e| e.to_string())?
+        .flatten()
+    {
+        let path = file.path();
+        let expired = match cutoff {
+            None => true,
+            // Unreadable entries can't be restored anyway
+            Some(cutoff) => read_entry(&path)
+                .and_then(|e| DateTime::parse_from_rfc3339(&e.deleted_at).ok())
+                .is_none_or(|deleted| deleted < cutoff),
+        };
+        if expired {
+            fs::remove_file(&path).map_err(|e| e.to_string())?;
+            if let Some(id) = path
+                .file_stem()
+                .filter(|_| path.extension() == Some("json".as_ref()))
+            {
+                let attachments = crate::attachments::attachments_dir(projects_dir).join(id);
+                if attachments.exists() {
+                    fs::remove_dir_all(attachments).map_err(|e| e.to_string())?;
+                }
+                removed += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/// Purges entries older than the configured retention period, if any.
+pub(crate) fn purge_expired(app: &AppHandle) {
+    let Ok(config) = crate::config::load_config(app.clone()) else {
+        return;
+    };
+    // Locked entries would look unreadable and be purged early
+    if config.trash_retention_days == 0 || crate::encryption::is_locked() {
+        return;
+    }
+    let result = get_projects_dir(app)
+        .and_then(|dir| purge(&dir, Some(config.trash_retention_days), Local::now()));
+    if let Err(e) = result {
+        eprintln!("Failed to empty trash: {}", e);
+    }
+}
+
+/// Deleted projects that can still be restored, most recently deleted first.
+#[tauri::command]
+pub fn list_trashed(app: AppHandle) -> Result<Vec<TrashedProject>, String> {
+    list(&get_projects_dir(&app)?)
+}
+
+#[tauri::command]
+pub fn restore_project(app: AppHandle, project_id: String) -> Result<(), String> {
+    restore(&get_projects_dir(&app)?, &project_id)?;
+    crate::events::project_changed(&app, Some(&project_id), ChangeKind::Restored, true);
+    Ok(())
+}
+
+/// Permanently removes projects deleted more than `days` ago, or every
+/// trashed project when `days` is unset. Returns how many were removed.
+#[tauri::command]
+pub fn purge_trash(app: AppHandle, days: Option<u32>) -> Result<usize, String> {
+    purge(&get_projects_dir(&app)?, days, Local::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trash_restore_and_purge() {
+        let dir = std::env::temp_dir().join(format!("anchor-trash-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("archive")).unwrap();
+        let project = r#"{"id": "p", "name": "Thesis", "tasks": [{"id": "a"}]}"#;
+        fs::write(dir.join("p.json"), project).unwrap();
+        fs::write(
+            dir.join("archive/p.json"),
+            r#"{"project_id": "p", "tasks": []}"#,
+        )
+        .unwrap();
+
+        move_to_trash(&dir, "p").unwrap();
+        assert!(!dir.join("p.json").exists() && !dir.join("archive/p.json").exists());
+        let trashed = list(&dir).unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(
+            (trashed[0].name.as_str(), trashed[0].task_count),
+            ("Thesis", 1)
+        );
+
+        restore(&dir, "p").unwrap();
+        assert_eq!(read_json(&dir.join("p.json")).unwrap()["name"], "Thesis");
+        assert!(dir.join("archive/p.json").exists());
+        assert!(list(&dir).unwrap().is_empty());
+        assert!(restore(&dir, "p").is_err());
+
+        // Only entries past the retention period are purged
+        move_to_trash(&dir, "p").unwrap();
+        assert_eq!(purge(&dir, Some(30), Local::now()).unwrap(), 0);
+        let later = Local::now() + Duration::days(31);
+        assert_eq!(purge(&dir, Some(30), later).unwrap(), 1);
+        assert!(list(&dir).unwrap().is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,302 @@
+//! System tray.
+//!
+//! The tray menu shows the task to focus on in the project due soonest, the
+//! time left to that project's next deadline and how many tasks are
+//! overdue, with quick actions: mark
+//! the task done, open the project, snooze reminders. It's rebuilt from the
+//! project index (and so the schedule cache) whenever a project changes,
+//! and once a minute so the countdown keeps up.
+
+use crate::deeplink::DeepLinkTarget;
+use crate::index::IndexEntry;
+use crate::project::{next_up, parse_date_or_datetime};
+use chrono::{Duration, NaiveDateTime};
+use std::sync::Mutex;
+use tauri::menu::{IsMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Manager, Wry};
+
+const TRAY_ID: &str = "main";
+const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+const SNOOZE_MINUTES: i64 = 60;
+
+/// What the tray menu is about.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrayFocus {
+    pub project_id: String,
+    pub project_name: String,
+    /// The project's chosen focus while unfinished, otherwise its next task.
+    pub task_id: Option<String>,
+    pub task_name: Option<String>,
+    pub deadline: NaiveDateTime,
+}
+
+/// The focus the quick actions apply to, as last shown.
+static FOCUS: Mutex<Option<TrayFocus>> = Mutex::new(None);
+
+/// The project with the soonest deadline still ahead, and its focus task.
+pub fn tray_focus(entries: &[IndexEntry], now: NaiveDateTime) -> Option<TrayFocus> {
+    let (deadline, entry) = entries
+        .iter()
+        .filter_map(|entry| {
+            let deadline = entry
+                .deadlines
+                .iter()
+                .filter_map(|d| parse_date_or_datetime(d))
+                .filter(|d| *d >= now)
+                .min()?;
+            Some((deadline, entry))
+        })
+        .min_by(|a, b| (a.0, &a.1.name).cmp(&(b.0, &b.1.name)))?;
+    let upcoming = entry.upcoming.as_deref().unwrap_or_default();
+    let task = entry
+        .focus_task_id
+        .as_deref()
+        .and_then(|id| upcoming.iter().find(|t| t.id == id && !t.completed))
+        .or_else(|| next_up(upcoming, now).map(|(_, _, task)| task));
+    Some(TrayFocus {
+        project_id: entry.id.clone(),
+        project_name: entry.name.clone(),
+        task_id: task.map(|t| t.id.clone()),
+        task_name: task.map(|t| t.name.clone()),
+        deadline,
+    })
+}
+
+/// "2d 4h", "3h 20m" or "15m".
+fn time_left(now: NaiveDateTime, until: NaiveDateTime) -> String {
+    let left = until - now;
+    match (
+        left.num_days(),
+        left.num_hours() % 24,
+        left.num_minutes() % 60,
+    ) {
+        (0, 0, minutes) => format!("{}m", minutes.max(0)),
+        (0, hours, minutes) => format!("{}h {}m", hours, minutes),
+        (days, hours, _) => format!("{}d {}h", days, hours),
+    }
+}
+
+fn build_menu(
+    app: &AppHandle,
+    focus: Option<&TrayFocus>,
+    overdue: usize,
+    snoozed_until: Option<NaiveDateTime>,
+    now: NaiveDateTime,
+) -> tauri::Result<Menu<Wry>> {
+    let task_name = focus.and_then(|f| f.task_name.as_deref());
+    let focus_text = match (focus, task_name) {
+        (Some(focus), Some(task)) => format!("▶ {} · {}", task, focus.project_name),
+        (Some(focus), None) => format!("{}: all tasks completed", focus.project_name),
+        (None, _) => "Nothing due".to_string(),
+    };
+    let deadline_text = match focus {
+        Some(focus) => format!(
+            "Next deadline in {} · {}",
+            time_left(now, focus.deadline),
+            focus.project_name
+        ),
+        None => "No upcoming deadlines".to_string(),
+    };
+    let snooze_text = match snoozed_until {
+        Some(until) => format!("Resume reminders (snoozed until {})", until.format("%H:%M")),
+        None => "Snooze reminders for 1 hour".to_string(),
+    };
+
+    let focus_i = MenuItem::with_id(app, "focus", &focus_text, false, None::<&str>)?;
+    let deadline_i = MenuItem::with_id(app, "deadline", &deadline_text, false, None::<&str>)?;
+    let overdue_text = format!("⚠ {} overdue task(s)", overdue);
+    let overdue_i = MenuItem::with_id(app, "overdue", &overdue_text, false, None::<&str>)?;
+    let done_i = MenuItem::with_id(app, "done", "Mark done", task_name.is_some(), None::<&str>)?;
+    let open_i = MenuItem::with_id(app, "open", "Open project", focus.is_some(), None::<&str>)?;
+    let snooze_i = MenuItem::with_id(app, "snooze", &snooze_text, true, None::<&str>)?;
+    let show_i = MenuItem::with_id(app, "show", "Show Anchor", true, None::<&str>)?;
+    let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let mut items: Vec<&dyn IsMenuItem<Wry>> = vec![&focus_i, &deadline_i];
+    if overdue > 0 {
+        items.push(&overdue_i);
+    }
+    items.extend([
+        &separator as &dyn IsMenuItem<Wry>,
+        &done_i,
+        &open_i,
+        &snooze_i,
+        &separator,
+        &show_i,
+        &quit_i,
+    ]);
+    Menu::with_items(app, &items)
+}
+
+/// Rebuilds the menu from the current schedules.
+pub fn refresh(app: &AppHandle) {
+    let now = chrono::Local::now().naive_local();
+    let (focus, overdue) = match crate::index::entries(app, now) {
+        Ok(entries) => (
+            tray_focus(&entries, now),
+            crate::monitor::overdue_tasks(&entries, now).len(),
+        ),
+        Err(e) => {
+            eprintln!("Failed to refresh the tray: {}", e);
+            return;
+        }
+    };
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let tooltip = match &focus {
+        Some(TrayFocus {
+            task_name: Some(task),
+            ..
+        }) => format!("Anchor · {}", task),
+        _ => "Anchor".to_string(),
+    };
+    match build_menu(
+        app,
+        focus.as_ref(),
+        overdue,
+        crate::reminders::snoozed_until(now),
+        now,
+    ) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+            let _ = tray.set_tooltip(Some(&tooltip));
+        }
+        Err(e) => eprintln!("Failed to build the tray menu: {}", e),
+    }
+    *FOCUS.lock().unwrap_or_else(|e| e.into_inner()) = focus;
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    let focus = FOCUS.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    match event.id.as_ref() {
+        "done" => {
+            if let Some(TrayFocus {
+                project_id,
+                task_id: Some(task_id),
+                ..
+            }) = focus
+            {
+                if let Err(e) = crate::project::complete_task(app, &project_id, &task_id) {
+                    eprintln!("Failed to mark {} done: {}", task_id, e);
+                }
+                // The change event refreshes the tray
+            }
+        }
+        "open" => {
+            if let Some(focus) = focus {
+                crate::deeplink::open(
+                    app,
+                    DeepLinkTarget {
+                        project_id: focus.project_id,
+                        task_id: focus.task_id,
+                    },
+                );
+            }
+        }
+        "snooze" => {
+            let now = chrono::Local::now().naive_local();
+            match crate::reminders::snoozed_until(now) {
+                Some(_) => crate::reminders::snooze(None),
+                None => crate::reminders::snooze(Some(now + Duration::minutes(SNOOZE_MINUTES))),
+            }
+            refresh(app);
+        }
+        "show" => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+        "quit" => app.exit(0),
+        _ => {}
+    }
+}
+
+/// Creates the tray icon and keeps its menu fresh.
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let now = chrono::Local::now().naive_local();
+    let menu = build_menu(app, None, 0, None, now)?;
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .tooltip("Anchor")
+        .on_menu_event(on_menu_event);
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        refresh(&app);
+        std::thread::sleep(REFRESH_INTERVAL);
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::ScheduledTask;
+
+    #[test]
+    fn test_focus_follows_soonest_deadline() {
+        let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap();
+        let task = |id: &str, start: &str, end: &str| ScheduledTask {
+            id: id.into(),
+            name: id.into(),
+            start_date: start.into(),
+            end_date: end.into(),
+            ..Default::default()
+        };
+        let entry = |id: &str, deadline: &str, upcoming: Vec<ScheduledTask>| IndexEntry {
+            id: id.into(),
+            name: id.into(),
+            color: None,
+            icon: None,
+            description: None,
+            created_at: String::new(),
+            last_modified: String::new(),
+            task_count: upcoming.len(),
+            deadlines: vec![deadline.into()],
+            upcoming: Some(upcoming),
+            forecast: None,
+            on_time: Vec::new(),
+            completion: None,
+            focus_task_id: None,
+            reminder_lead_minutes: None,
+            task_reminders: Default::default(),
+            indexed_on: String::new(),
+            file_modified: 0,
+        };
+        let now = at("2026-03-10T12:00:00");
+        let mut entries = vec![
+            entry(
+                "thesis",
+                "2026-03-20",
+                vec![task("write", "2026-03-09T09:00:00", "2026-03-19T17:00:00")],
+            ),
+            entry(
+                "taxes",
+                "2026-03-12T16:00:00",
+                vec![
+                    task("receipts", "2026-03-10T09:00:00", "2026-03-11T12:00:00"),
+                    task("file", "2026-03-11T12:00:00", "2026-03-12T16:00:00"),
+                ],
+            ),
+            entry("expired", "2026-03-01", Vec::new()),
+        ];
+
+        let focus = tray_focus(&entries, now).unwrap();
+        assert_eq!(focus.project_id, "taxes");
+        assert_eq!(focus.task_id.as_deref(), Some("receipts"));
+        assert_eq!(time_left(now, focus.deadline), "2d 4h");
+
+        // A chosen focus wins
+        entries[1].focus_task_id = Some("file".into());
+        let focus = tray_focus(&entries, now).unwrap();
+        assert_eq!(focus.task_name.as_deref(), Some("file"));
+        assert_eq!(time_left(now, at("2026-03-10T13:05:00")), "1h 5m");
+    }
+}
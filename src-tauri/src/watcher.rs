@@ -0,0 +1,143 @@
+//! Watches the projects directory for changes made outside the app (sync
+//! folders, manual edits). Polls file sizes and modification times; when a
+//! project changes, its cached schedule, index entry and undo history are
+//! dropped and a `changed_on_disk` `project-update` is emitted for it.
+
+use crate::events::ChangeKind;
+use crate::project::get_projects_dir;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Modification time (ms since the epoch) and size of a project file.
+type Stamp = (u64, u64);
+
+/// Last seen stamp per project ID.
+#[derive(Default)]
+pub struct ProjectWatcher {
+    known: Mutex<HashMap<String, Stamp>>,
+}
+
+fn stamp(path: &Path) -> Option<Stamp> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((modified.as_millis() as u64, metadata.len()))
+}
+
+fn project_id(path: &Path) -> Option<String> {
+    if path.extension().and_then(|s| s.to_str()) != Some("json") {
+        return None;
+    }
+    path.file_stem()?.to_str().map(String::from)
+}
+
+fn scan(dir: &Path) -> HashMap<String, Stamp> {
+    let Ok(files) = fs::read_dir(dir) else {
+        return HashMap::new();
+    };
+    files
+        .flatten()
+        .filter_map(|file| {
+            let path = file.path();
+            Some((project_id(&path)?, stamp(&path)?))
+        })
+        .collect()
+}
+
+/// IDs added, removed or modified between two scans, sorted.
+fn changes(before: &HashMap<String, Stamp>, after: &HashMap<String, Stamp>) -> Vec<String> {
+    let mut changed: Vec<String> = after
+        .iter()
+        .filter(|(id, stamp)| before.get(*id) != Some(stamp))
+        .map(|(id, _)| id.clone())
+        .chain(before.keys().filter(|id| !after.contains_key(*id)).cloned())
+        .collect();
+    changed.sort();
+    changed
+}
+
+impl ProjectWatcher {
+    /// Runs the app's own write to a project file so it isn't reported as
+    /// an external change.
+    pub(crate) fn write<T>(&self, path: &Path, write: impl FnOnce() -> T) -> T {
+        let mut known = self.known.lock().unwrap();
+        let result = write();
+        if let Some(id) = project_id(path) {
+            match stamp(path) {
+                Some(stamp) => known.insert(id, stamp),
+                None => known.remove(&id),
+            };
+        }
+        result
+    }
+
+    fn poll(&self, dir: &Path) -> Vec<String> {
+        let mut known = self.known.lock().unwrap();
+        let current = scan(dir);
+        let changed = changes(&known, &current);
+        *known = current;
+        changed
+    }
+}
+
+/// Starts polling the projects directory in the background.
+pub(crate) fn start(app: &AppHandle) -> Result<(), String> {
+    let dir = get_projects_dir(app)?;
+    // The first scan only establishes what's there
+    app.state::<ProjectWatcher>().poll(&dir);
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(POLL_INTERVAL);
+        for id in app.state::<ProjectWatcher>().poll(&dir) {
+            app.state::<crate::cache::ScheduleCache>().invalidate(&id);
+            app.state::<crate::history::History>().forget(&id);
+            if let Err(e) = crate::index::invalidate(&app, &id) {
+                eprintln!("{}", e);
+            }
+            crate::events::project_changed(&app, Some(&id), ChangeKind::ChangedOnDisk, true);
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    #[test]
+    fn test_external_changes_are_reported_but_own_writes_are_not() {
+        let dir = std::env::temp_dir().join(format!("anchor-watch-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.json"), "{}").unwrap();
+        fs::write(dir.join("b.json"), "{}").unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+        let watcher = ProjectWatcher::default();
+        assert_eq!(watcher.poll(&dir), vec!["a", "b"]);
+        assert!(watcher.poll(&dir).is_empty());
+
+        // Edited elsewhere (with a later timestamp), removed, and added
+        fs::write(dir.join("a.json"), "{\"name\": \"x\"}").unwrap();
+        let later = SystemTime::now() + Duration::from_secs(5);
+        fs::File::options()
+            .write(true)
+            .open(dir.join("a.json"))
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        fs::remove_file(dir.join("b.json")).unwrap();
+        fs::write(dir.join("c.json"), "{}").unwrap();
+        assert_eq!(watcher.poll(&dir), vec!["a", "b", "c"]);
+
+        watcher.write(&dir.join("c.json"), || {
+            fs::write(dir.join("c.json"), "{\"saved\": true}").unwrap()
+        });
+        assert!(watcher.poll(&dir).is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
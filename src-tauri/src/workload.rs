@@ -0,0 +1,163 @@
+//! Resource over-allocation report.
+//!
+//! Sums each assignee's scheduled work per calendar day and flags days over
+//! capacity. Read-only: the schedule itself is left as it is.
+
+use crate::cache::ScheduleCache;
+use crate::project::load_project;
+use crate::scheduler::{DurationUnit, ScheduledTask, Task};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tauri::{AppHandle, Manager};
+
+/// Working hours in a day-based task's day, and the default capacity.
+const WORKDAY_HOURS: f64 = 8.0;
+
+/// One person's load on one day.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResourceDay {
+    pub assignee: String,
+    /// YYYY-MM-DD
+    pub date: String,
+    pub scheduled_hours: f64,
+    pub capacity_hours: f64,
+    pub over_capacity: bool,
+    /// Tasks contributing to the day, by ID.
+    pub task_ids: Vec<String>,
+}
+
+/// Per-person daily load for the incomplete, assigned tasks in `schedule`.
+///
+/// Minute and hour durations count their actual hours on each day. Day and
+/// week durations count a workday (8h) for each full calendar day they
+/// cover, prorated at the edges. Elapsed tasks and milestones take no one's
+/// time.
+pub fn resource_load(
+    tasks: &[Task],
+    schedule: &[ScheduledTask],
+    capacity_hours: f64,
+) -> Vec<ResourceDay> {
+    let definitions: HashMap<&str, &Task> = tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+    let mut days: BTreeMap<(&str, NaiveDate), (f64, Vec<String>)> = BTreeMap::new();
+
+    for row in schedule {
+        let Some(task) = definitions.get(row.id.as_str()) else {
+            continue;
+        };
+        let Some(assignee) = task.assignee.as_deref().filter(|a| !a.is_empty()) else {
+            continue;
+        };
+        if row.completed || row.is_milestone || row.is_elapsed {
+            continue;
+        }
+        let (Ok(start), Ok(end)) = (
+            NaiveDateTime::parse_from_str(&row.start_date, "%Y-%m-%dT%H:%M:%S"),
+            NaiveDateTime::parse_from_str(&row.end_date, "%Y-%m-%dT%H:%M:%S"),
+        ) else {
+            continue;
+        };
+        let hours_per_minute = match task.duration.unit {
+            DurationUnit::Minutes | DurationUnit::Hours => 1.0 / 60.0,
+            DurationUnit::Days | DurationUnit::Weeks => WORKDAY_HOURS / (24.0 * 60.0),
+        };
+
+        let mut date = start.date();
+        while date.and_time(chrono::NaiveTime::MIN) < end {
+            let day_start = date.and_time(chrono::NaiveTime::MIN);
+            let overlap =
+                (end.min(day_start + Duration::days(1)) - start.max(day_start)).num_minutes();
+            if overlap > 0 {
+                let entry = days.entry((assignee, date)).or_default();
+                entry.0 += overlap as f64 * hours_per_minute;
+                entry.1.push(row.id.clone());
+            }
+            let Some(next) = date.succ_opt() else { break };
+            date = next;
+        }
+    }
+
+    days.into_iter()
+        .map(|((assignee, date), (hours, task_ids))| ResourceDay {
+            assignee: assignee.to_string(),
+            date: date.format("%Y-%m-%d").to_string(),
+            scheduled_hours: (hours * 100.0).round() / 100.0,
+            capacity_hours,
+            over_capacity: hours > capacity_hours + 1e-9,
+            task_ids,
+        })
+        .collect()
+}
+
+/// Daily load per assignee for a saved project; capacity defaults to 8 hours.
+#[tauri::command]
+pub fn get_resource_load(
+    app: AppHandle,
+    project_id: String,
+    capacity_hours: Option<f64>,
+) -> Result<Vec<ResourceDay>, String> {
+    let project = load_project(app.clone(), project_id)?;
+    let now = chrono::Local::now().naive_local();
+    let result = app
+        .state::<ScheduleCache>()
+        .schedule(&project, now)
+        .map_err(|e| e.to_string())?;
+    Ok(resource_load(
+        &project.tasks,
+        &result.scheduled_tasks,
+        capacity_hours.unwrap_or(WORKDAY_HOURS),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{calculate_backwards_schedule, ScheduleRequest, TaskDuration};
+
+    fn task(id: &str, duration: TaskDuration, assignee: &str) -> Task {
+        Task {
+            id: id.into(),
+            name: id.into(),
+            duration,
+            assignee: Some(assignee.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_concurrent_tasks_overload_assignee() {
+        // Ana's review falls on the last day of her 2-day draft
+        let tasks = vec![
+            task("draft", TaskDuration::days(2), "ana"),
+            task("review", TaskDuration::hours(3), "ana"),
+            task("layout", TaskDuration::hours(4), "ben"),
+        ];
+        let request = ScheduleRequest {
+            tasks: tasks.clone(),
+            anchors: [
+                ("draft".into(), "2026-03-11T00:00:00".into()),
+                ("review".into(), "2026-03-10T18:00:00".into()),
+                ("layout".into(), "2026-03-10T18:00:00".into()),
+            ]
+            .into(),
+            timezone: Some("UTC".into()),
+            ..Default::default()
+        };
+        let result = calculate_backwards_schedule(request).expect("Schedule failed");
+
+        let load = resource_load(&tasks, &result.scheduled_tasks, 8.0);
+        let day = |who: &str, date: &str| {
+            load.iter()
+                .find(|d| d.assignee == who && d.date == date)
+                .unwrap()
+        };
+
+        assert_eq!(load.len(), 3);
+        assert_eq!(day("ana", "2026-03-09").scheduled_hours, 8.0);
+        assert!(!day("ana", "2026-03-09").over_capacity);
+        assert_eq!(day("ana", "2026-03-10").scheduled_hours, 11.0);
+        assert!(day("ana", "2026-03-10").over_capacity);
+        assert_eq!(day("ana", "2026-03-10").task_ids, vec!["draft", "review"]);
+        assert_eq!(day("ben", "2026-03-10").scheduled_hours, 4.0);
+    }
+}
@@ -19,6 +19,10 @@ import { useNotificationScheduler } from './hooks/useNotificationScheduler';
 
 import { TaskDetailsView } from "./components/TaskDetailsView";
 import { useProjectsList } from "./hooks/useProjectsList";
+import { useFileImport } from "./hooks/useFileImport";
+import { useUnsavedRecovery } from "./hooks/useUnsavedRecovery";
+import { DeepLinkTarget, ImportPreview } from "./types";
+import { ConfirmationModal } from "./components/ConfirmationModal";
 import { AnimatePresence, motion, Variants } from "framer-motion";
 
 // Helper for page transitions
@@ -74,8 +78,10 @@ const tabVariants: Variants = {
 
 function App() {
   const [activeProjectId, setActiveProjectId] = useState<string | null>(null);
-  const { theme, loaded: configLoaded } = useConfig();
+  const { theme, recalculation, loaded: configLoaded } = useConfig();
   const { projects: projectList, loading: loadingList, refreshProjects } = useProjectsList();
+  const fileImport = useFileImport(setActiveProjectId);
+  const recovery = useUnsavedRecovery(setActiveProjectId);
 
   const {
     project,
@@ -91,8 +97,12 @@ function App() {
     updateTaskAnchor,
     undo,
     redo,
-    anchorTaskIds
-  } = useProject(activeProjectId);
+    anchorTaskIds,
+    scheduleStale,
+    recalculate,
+    setSerializeWork,
+    setSpreadSlack
+  } = useProject(activeProjectId, recalculation);
 
   // Initialize notification scheduler
   useNotificationScheduler(scheduledTasks);
@@ -193,6 +203,35 @@ function App() {
     };
   }, [activeProjectId]); // Re-bind if activeProjectId changes
 
+  // anchor:// links, including one the app was launched with before we listened
+  useEffect(() => {
+    const open = (target: DeepLinkTarget) => {
+      refreshProjects();
+      if (target.project_id !== activeProjectId) {
+        setActiveProjectId(target.project_id);
+        if (target.task_id) setPendingDeepLink({ taskId: target.task_id });
+        return;
+      }
+      if (target.task_id) {
+        setSelectedTaskId(target.task_id);
+        setViewMode('details');
+        setStartInEditMode(false);
+      }
+    };
+    invoke<DeepLinkTarget | null>('take_deep_link').then(target => target && open(target));
+    const unlisten = listen<DeepLinkTarget>('deep-link', () => {
+      invoke<DeepLinkTarget | null>('take_deep_link').then(target => target && open(target));
+    });
+    const unlistenFailed = listen<{ url: string; error: string }>('deep-link-failed', (event) => {
+      console.error(`Couldn't open ${event.payload.url}: ${event.payload.error}`);
+    });
+
+    return () => {
+      unlisten.then(f => f());
+      unlistenFailed.then(f => f());
+    };
+  }, [activeProjectId, refreshProjects]);
+
   const handleTaskMove = useCallback(async (taskId: string, newDate: string) => {
     // When moving a task, we now anchor JUST that task to the new date
     updateTaskAnchor(taskId, newDate);
@@ -213,8 +252,7 @@ function App() {
     // even if it was originally days (since resizing implies fine-tuning)
     const updatedTask = {
       ...task,
-      duration_minutes: newDurationMinutes,
-      duration_days: 0 // Clear days to ensure minutes are used
+      duration: { value: Math.round(newDurationMinutes), unit: 'minutes' as const }
     };
 
     editTask(updatedTask);
@@ -342,6 +380,39 @@ function App() {
                     </div>
 
                     <div className="flex items-center gap-2">
+                      {scheduleStale && (
+                        <button
+                          onClick={recalculate}
+                          className="px-3 py-1.5 rounded-md text-sm font-medium text-brand hover:bg-brand/10 transition-all"
+                          title="The schedule doesn't reflect your latest edits yet"
+                        >
+                          Recalculate
+                        </button>
+                      )}
+                      {project && (
+                        <button
+                          onClick={() => setSerializeWork(!project.serialize_work)}
+                          className={`px-3 py-1.5 rounded-md text-sm font-medium transition-all ${project.serialize_work
+                            ? 'bg-brand/10 text-brand'
+                            : 'text-text-muted hover:text-text'
+                            }`}
+                          title="Schedule one task at a time"
+                        >
+                          One at a time
+                        </button>
+                      )}
+                      {project && (
+                        <button
+                          onClick={() => setSpreadSlack(!project.spread_slack)}
+                          className={`px-3 py-1.5 rounded-md text-sm font-medium transition-all ${project.spread_slack
+                            ? 'bg-brand/10 text-brand'
+                            : 'text-text-muted hover:text-text'
+                            }`}
+                          title="Spread slack along each chain instead of planning everything as late as possible"
+                        >
+                          Breathing room
+                        </button>
+                      )}
                       <div className="bg-surface/20 rounded-lg p-1 flex gap-1">
                         <button
                           onClick={() => setViewMode('timeline')}
@@ -484,8 +555,53 @@ function App() {
           )}
         </AnimatePresence>
       </div>
+
+      <ConfirmationModal
+        isOpen={!!fileImport.preview}
+        onClose={fileImport.dismiss}
+        onConfirm={fileImport.confirm}
+        title="Import Project"
+        message={fileImport.preview ? describeImport(fileImport.preview) : ''}
+        confirmText="Import"
+        variant={fileImport.preview?.schedule_error || fileImport.preview?.warnings.length ? 'warning' : 'info'}
+      />
+      <ConfirmationModal
+        isOpen={!fileImport.preview && !!fileImport.failure}
+        onClose={fileImport.clearFailure}
+        onConfirm={fileImport.clearFailure}
+        title="Import Failed"
+        message={fileImport.failure ? `${fileName(fileImport.failure.path)}: ${fileImport.failure.error}` : ''}
+        confirmText="OK"
+        variant="danger"
+      />
+      <ConfirmationModal
+        isOpen={!fileImport.preview && !fileImport.failure && !!recovery.pending}
+        onClose={recovery.dismiss}
+        onConfirm={recovery.recover}
+        title="Recover Unsaved Changes"
+        message={recovery.pending ? `Anchor closed before your latest edits to "${recovery.pending.project_name}" were saved. Restore them?` : ''}
+        confirmText="Restore"
+        cancelText="Later"
+        variant="warning"
+      />
     </div>
   );
 }
 
+function fileName(path: string): string {
+  return path.split(/[\\/]/).pop() || path;
+}
+
+function describeImport(preview: ImportPreview): string {
+  const count = preview.project.tasks.length;
+  let message = `Create "${preview.project.name}" with ${count} task${count !== 1 ? 's' : ''} from ${fileName(preview.path)}?`;
+  if (preview.warnings.length) {
+    message += `\n\n${preview.warnings.length} note${preview.warnings.length !== 1 ? 's' : ''}: ${preview.warnings.slice(0, 3).join('; ')}`;
+  }
+  if (preview.schedule_error) {
+    message += `\n\nIt can't be scheduled yet: ${preview.schedule_error}`;
+  }
+  return message;
+}
+
 export default App;
@@ -0,0 +1,105 @@
+import { KeyboardEvent, useEffect, useRef, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { useConfig } from "./hooks/useConfig";
+import { useProjectsList } from "./hooks/useProjectsList";
+import { QuickAddResult } from "./types";
+
+/** Capture window opened by the global shortcut. */
+function QuickAddApp() {
+    const { theme, loaded: configLoaded } = useConfig();
+    const { projects } = useProjectsList();
+    const [projectId, setProjectId] = useState<string | null>(null);
+    const [text, setText] = useState("");
+    const [error, setError] = useState<string | null>(null);
+    const [added, setAdded] = useState<string | null>(null);
+    const inputRef = useRef<HTMLInputElement>(null);
+
+    // Default to the first (pinned or most urgent) project
+    useEffect(() => {
+        if (!projectId && projects.length > 0) setProjectId(projects[0].id);
+    }, [projects, projectId]);
+
+    // Fresh input each time the window is shown
+    useEffect(() => {
+        const unlisten = getCurrentWindow().onFocusChanged(({ payload: focused }) => {
+            if (focused) {
+                setError(null);
+                inputRef.current?.focus();
+            }
+        });
+        return () => {
+            unlisten.then(f => f());
+        };
+    }, []);
+
+    useEffect(() => {
+        if (!configLoaded) return;
+        const isDark = theme === 'dark' || (theme === 'system' && window.matchMedia('(prefers-color-scheme: dark)').matches);
+        document.documentElement.classList.toggle('dark', isDark);
+    }, [theme, configLoaded]);
+
+    const hide = () => {
+        setText("");
+        setAdded(null);
+        getCurrentWindow().hide();
+    };
+
+    const submit = async () => {
+        if (!projectId || !text.trim()) return;
+        try {
+            const result = await invoke<QuickAddResult>("quick_add_task", { projectId, text });
+            const scheduled = result.schedule.scheduled_tasks.find(t => t.id === result.task.id);
+            setAdded(scheduled ? `${result.task.name} · starts ${scheduled.start_date.slice(0, 10)}` : result.task.name);
+            setText("");
+            setError(null);
+        } catch (e) {
+            setError(String(e));
+        }
+    };
+
+    const onKeyDown = (e: KeyboardEvent) => {
+        if (e.key === 'Enter') submit();
+        if (e.key === 'Escape') hide();
+    };
+
+    return (
+        <div
+            data-tauri-drag-region
+            className="h-screen w-screen bg-surface/40 rounded-2xl border border-border/10 p-3 text-text overflow-hidden flex flex-col gap-2 select-none backdrop-blur-md"
+        >
+            <div className="flex items-center gap-2">
+                <select
+                    value={projectId ?? ''}
+                    onChange={e => setProjectId(e.target.value)}
+                    onKeyDown={onKeyDown}
+                    className="bg-surface-alt/40 rounded-lg px-2 py-1.5 text-xs text-text-muted outline-none max-w-[35%]"
+                >
+                    {projects.map(p => (
+                        <option key={p.id} value={p.id}>{p.icon ? `${p.icon} ` : ''}{p.name}</option>
+                    ))}
+                </select>
+                <input
+                    ref={inputRef}
+                    autoFocus
+                    value={text}
+                    onChange={e => setText(e.target.value)}
+                    onKeyDown={onKeyDown}
+                    placeholder="Draft intro 3h #writing !2026-03-20"
+                    className="flex-1 bg-transparent text-sm outline-none placeholder:text-text-muted/60"
+                />
+            </div>
+            <div className="text-[11px] truncate">
+                {error ? (
+                    <span className="text-red-500">{error}</span>
+                ) : added ? (
+                    <span className="text-text-muted">Added {added}</span>
+                ) : (
+                    <span className="text-text-muted/70">Enter to add · Esc to close</span>
+                )}
+            </div>
+        </div>
+    );
+}
+
+export default QuickAddApp;
@@ -6,6 +6,7 @@ import { ChevronLeftIcon, ChevronRightIcon, DiamondIcon } from "./components/ico
 import { MiniCalendar } from "./components/MiniCalendar";
 import { CircularProgress } from "./components/CircularProgress";
 import { useConfig } from "./hooks/useConfig";
+import { CalendarWeek } from "./types";
 
 
 interface WidgetTask {
@@ -16,24 +17,52 @@ interface WidgetTask {
     completed: boolean;
     is_milestone?: boolean;
     status: 'active' | 'future' | 'overdue';
+    tags?: string[];
+    // Set in the all-projects view
+    project_id?: string | null;
+    project_name?: string | null;
+    project_color?: string | null;
+    subtask_progress?: number | null; // Share of subtasks done
+    days_overdue?: number | null; // In overdue_tasks
 }
 
 interface ProjectSummary {
     id: string;
     name: string;
+    color: string | null;
+    icon: string | null;
+}
+
+/** Time left to an unfinished anchor, for "T-minus" counters. */
+interface AnchorCountdown {
+    task_id: string;
+    name: string;
+    date: string;
+    days_remaining: number; // Negative once passed
+    hours_remaining: number;
+    elapsed_percent: number; // 0-100, from the project's start
+    project_id: string | null; // All-projects view only
+    project_name: string | null;
 }
 
 interface WidgetInfo {
     project_id: string;
     project_name: string;
+    project_color: string | null;
+    project_icon: string | null;
+    project_description: string | null;
     next_deadline: string | null;
     status: string;
     current_focus: string | null;
     upcoming_tasks: WidgetTask[];
     calendar_tasks: WidgetTask[];
+    calendar_weeks: CalendarWeek[]; // calendar_tasks by week and day
     all_projects: ProjectSummary[];
     task_progress: number | null;
     active_task: WidgetTask | null;
+    countdowns: AnchorCountdown[];
+    overdue_tasks: WidgetTask[]; // Past their required finish, most overdue first
+    project_completion: number | null; // Percent of work done
 }
 
 function WidgetApp() {
@@ -41,7 +70,7 @@ function WidgetApp() {
     const currentProjectIdRef = useRef<string | null>(null);
     const [liveProgress, setLiveProgress] = useState<number | null>(null);
     const [activeTab, setActiveTab] = useState<'focus' | 'list' | 'calendar'>('focus');
-    const { theme, loaded: configLoaded } = useConfig();
+    const { theme, widget, loaded: configLoaded } = useConfig();
 
     const fetchProject = async (projectId?: string) => {
         try {
@@ -89,8 +118,11 @@ function WidgetApp() {
     useEffect(() => {
         fetchProject();
 
-        // Listen for updates from the main window
-        const unlistenPromise = listen("project-update", () => {
+        // Only changes to what the widget shows
+        const unlistenPromise = listen("widget-refresh", () => {
+            fetchProject();
+        });
+        const unlistenStatusPromise = listen("project-status", () => {
             fetchProject();
         });
 
@@ -105,11 +137,17 @@ function WidgetApp() {
 
         return () => {
             unlistenPromise.then(unlisten => unlisten());
+            unlistenStatusPromise.then(unlisten => unlisten());
             window.removeEventListener("focus", handleFocus);
             clearInterval(intervalId);
         };
     }, []);
 
+    // Widget settings changed in the main window
+    useEffect(() => {
+        if (configLoaded) fetchProject();
+    }, [widget, configLoaded]);
+
     // Apply theme
     useEffect(() => {
         if (!configLoaded) return;
@@ -138,6 +176,12 @@ function WidgetApp() {
             return;
         }
 
+        // Subtask progress doesn't move with the clock
+        if (info.active_task.subtask_progress != null) {
+            setLiveProgress(info.active_task.subtask_progress);
+            return;
+        }
+
         const updateProgress = () => {
             const now = new Date();
             const start = parseISO(info.active_task!.start_date);
@@ -289,7 +333,7 @@ function WidgetApp() {
                                     if (info.active_task) {
                                         emit('open-task-details', {
                                             taskId: info.active_task.id,
-                                            projectId: info.project_id
+                                            projectId: info.active_task.project_id ?? info.project_id
                                         });
                                     }
                                 }}
@@ -308,6 +352,27 @@ function WidgetApp() {
                         <div className="text-[10px] font-semibold text-text-muted uppercase tracking-wider mb-2 pl-1 truncate">
                             {info.project_name}
                         </div>
+                        {info.overdue_tasks.length > 0 && (
+                            <div className="space-y-1 pl-1">
+                                {info.overdue_tasks.map((task) => (
+                                    <div
+                                        key={task.id}
+                                        className="flex items-baseline justify-between gap-2 cursor-pointer text-xs text-danger hover:text-danger/80"
+                                        onClick={() => {
+                                            emit('open-task-details', {
+                                                taskId: task.id,
+                                                projectId: task.project_id ?? info.project_id
+                                            });
+                                        }}
+                                    >
+                                        <span className="truncate font-medium">{task.name}</span>
+                                        <span className="text-[10px] shrink-0">
+                                            {task.days_overdue ? `${task.days_overdue}d overdue` : 'Overdue'}
+                                        </span>
+                                    </div>
+                                ))}
+                            </div>
+                        )}
                         {info.upcoming_tasks.length === 0 ? (
                             <div className="flex flex-col items-center justify-center h-full text-text-muted text-xs italic pb-4">
                                 No upcoming tasks
@@ -324,7 +389,7 @@ function WidgetApp() {
                                         onClick={() => {
                                             emit('open-task-details', {
                                                 taskId: task.id,
-                                                projectId: info.project_id
+                                                projectId: task.project_id ?? info.project_id
                                             });
                                         }}
                                     >
@@ -347,6 +412,11 @@ function WidgetApp() {
                                                 }`}>
                                                 {task.name}
                                             </span>
+                                            {task.project_name && (
+                                                <span className="text-[10px] truncate" style={{ color: task.project_color ?? undefined }}>
+                                                    {task.project_name}
+                                                </span>
+                                            )}
                                             <span className="text-[10px] text-text-faint">
                                                 {(() => {
                                                     const start = parseISO(task.start_date);
@@ -377,7 +447,7 @@ function WidgetApp() {
                         )}
                     </div>
                 ) : (
-                    <MiniCalendar tasks={info.calendar_tasks} />
+                    <MiniCalendar tasks={info.calendar_tasks} weeks={info.calendar_weeks} />
                 )}
             </div>
         </div>
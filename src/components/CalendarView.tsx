@@ -9,6 +9,7 @@ import './CalendarView.css';
 import { ChevronLeftIcon, ChevronRightIcon } from './icons';
 import { YearView } from './YearView';
 import { Task } from '../types'; // Import Task definition
+import { isCalendarDuration } from '../utils/duration';
 
 export type CalendarViewType = View | 'year';
 
@@ -121,9 +122,9 @@ export function CalendarView({
         const start = parseISO(task.start_date);
         const end = parseISO(task.end_date);
 
-        // Determine if it should be an all-day event
+        // Tasks planned in days or weeks span whole calendar days
         // If it was defined with days (not minutes) or is >= 24 hours
-        const isAllDay = def ? (def.duration_minutes === undefined || def.duration_minutes === null) : true;
+        const isAllDay = def ? isCalendarDuration(def.duration) : true;
 
         return {
             id: task.id,
@@ -34,7 +34,7 @@ export function ConfirmationModal({
             >
                 <div className="p-6">
                     <h3 className="text-lg font-bold text-text mb-2">{title}</h3>
-                    <p className="text-text-muted mb-6">{message}</p>
+                    <p className="text-text-muted mb-6 whitespace-pre-line">{message}</p>
 
                     <div className="flex justify-end gap-3">
                         <button
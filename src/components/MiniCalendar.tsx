@@ -14,6 +14,7 @@ import {
     parseISO
 } from 'date-fns';
 import { ChevronLeftIcon, ChevronRightIcon } from './icons';
+import { CalendarWeek } from '../types';
 
 
 interface MiniCalendarProps {
@@ -25,9 +26,10 @@ interface MiniCalendarProps {
         completed: boolean;
         is_milestone?: boolean;
     }[];
+    weeks: CalendarWeek[];
 }
 
-export function MiniCalendar({ tasks }: MiniCalendarProps) {
+export function MiniCalendar({ tasks, weeks }: MiniCalendarProps) {
     const [currentDate, setCurrentDate] = useState(new Date());
 
     const nextMonth = () => setCurrentDate(addMonths(currentDate, 1));
@@ -51,15 +53,15 @@ export function MiniCalendar({ tasks }: MiniCalendarProps) {
         ? tasks.reduce((max, t) => t.end_date > max ? t.end_date : max, tasks[0].end_date)
         : null;
 
+    // Days are grouped in Rust, shared with the main calendar view
+    const byId = new Map(tasks.map(t => [t.id, t]));
+    const byDate = new Map(weeks.flatMap(w => w.days).map(d => [d.date, d]));
+
     const getDayStatus = (day: Date) => {
-        // Simple check if any task falls on this day
-        // This could be optimized if task list is huge, but for widget tasks it's likely fine
-        const dayTasks = tasks.filter(task => {
-            // Check if day is between start and end (inclusive) Naive check
-            const start = parseISO(task.start_date);
-            const end = parseISO(task.end_date);
-            return (day >= start && day <= end) || isSameDay(day, start) || isSameDay(day, end);
-        });
+        const entry = byDate.get(format(day, 'yyyy-MM-dd'));
+        if (!entry) return null;
+        const ids = [...entry.starting, ...entry.ending, ...entry.in_progress, ...entry.milestones];
+        const dayTasks = ids.flatMap(id => byId.get(id) ?? []);
 
         // Priority: Completed > Overdue > Deadline > Milestone > Active > Future
         if (dayTasks.some(t => t.completed)) return 'completed';
@@ -69,7 +71,7 @@ export function MiniCalendar({ tasks }: MiniCalendarProps) {
         if (derivedDeadline && isSameDay(day, parseISO(derivedDeadline))) return 'deadline';
 
         // Milestone end date - Purple
-        if (dayTasks.some(t => t.is_milestone && isSameDay(day, parseISO(t.end_date)))) return 'milestone';
+        if (entry.milestones.length > 0) return 'milestone';
 
         if (dayTasks.some(t => t.status === 'active')) return 'active';
         if (dayTasks.length > 0) return 'future';
@@ -2,7 +2,7 @@ import { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { motion, Variants } from "framer-motion";
 import { ProjectMetadata } from "../types";
-import { getStatusBgColor } from "../utils/status";
+import { getStatusBgColor, getStatusLabel } from "../utils/status";
 // ThemeToggle removed - using system settings
 // import { ThemeToggle, Theme } from "./ThemeToggle";
 
@@ -149,7 +149,7 @@ export function ProjectDashboard({ onOpenProject, projects, loading, onProjectCh
                                         {project.status !== 'empty' && (
                                             <div
                                                 className={`w-2.5 h-2.5 rounded-full ring-2 ring-surface ${getStatusBgColor(project.status)}`}
-                                                title={project.status.replace('_', ' ')}
+                                                title={getStatusLabel(project.status, project.on_time)}
                                             />
                                         )}
                                     </div>
@@ -212,7 +212,7 @@ export function ProjectDashboard({ onOpenProject, projects, loading, onProjectCh
                 onClose={() => setProjectToDelete(null)}
                 onConfirm={confirmDelete}
                 title="Delete Project"
-                message="Are you sure you want to delete this project? This action cannot be undone."
+                message="Are you sure you want to delete this project? It will be moved to the trash, where it can be restored until the trash is emptied."
                 confirmText="Delete"
                 variant="danger"
             />
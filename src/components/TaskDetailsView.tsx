@@ -1,11 +1,12 @@
 import { useState, useEffect, useRef, useMemo } from 'react';
 import ReactMarkdown from 'react-markdown';
-import { Task, ScheduledTask, SubTask } from '../types';
+import { DurationUnit, Task, ScheduledTask, SubTask } from '../types';
 import { MemoIcon, CalendarIcon, CheckIcon, CloseIcon, BackIcon, EditIcon, DiamondIcon, TimelineIcon, AnchorIcon, PlusIcon } from './icons';
 import { Checkbox } from './Checkbox';
 import { format, parseISO } from 'date-fns';
 import { SmartDurationInput } from './ui/SmartDurationInput';
 import { useDebounce } from '../hooks/useDebounce';
+import { formatDurationLong, formatMinutes } from '../utils/duration';
 
 interface TaskDetailsViewProps {
     taskId: string | null;
@@ -20,6 +21,12 @@ interface TaskDetailsViewProps {
     onToggleAnchor?: (taskId: string) => void;
 }
 
+// Keeps overlaps for current dependencies only; 100% is the default
+const overlapsFor = (dependencies: string[], percents: Record<string, number>): Record<string, number> | undefined => {
+    const entries = Object.entries(percents).filter(([id, percent]) => dependencies.includes(id) && percent < 100);
+    return entries.length > 0 ? Object.fromEntries(entries) : undefined;
+};
+
 export function TaskDetailsView({
     taskId,
     tasks,
@@ -38,10 +45,13 @@ export function TaskDetailsView({
     const [editName, setEditName] = useState('');
     const [editNotes, setEditNotes] = useState('');
     const [isMilestoneEditing, setIsMilestoneEditing] = useState(false);
+    const [isElapsedEditing, setIsElapsedEditing] = useState(false);
     const [editDuration, setEditDuration] = useState(1);
-    const [editDurationUnit, setEditDurationUnit] = useState<'minutes' | 'hours' | 'days'>('days');
+    const [editDurationUnit, setEditDurationUnit] = useState<DurationUnit>('days');
     const [editDependencies, setEditDependencies] = useState<string[]>([]);
+    const [editStartAfter, setEditStartAfter] = useState<Record<string, number>>({});
     const [editAnchorDate, setEditAnchorDate] = useState<string>('');
+    const [editPinnedStart, setEditPinnedStart] = useState<string>('');
     const [editSubtasks, setEditSubtasks] = useState<SubTask[]>([]);
     const [newSubtaskName, setNewSubtaskName] = useState('');
 
@@ -69,24 +79,16 @@ export function TaskDetailsView({
             const prevFormData = prevFormDataRef.current;
             const prevTask = prevTaskDefRef.current;
 
-            let durationMinutes: number | undefined = undefined;
-            let durationDays = 0;
-            if (prevFormData.editDurationUnit === 'days') {
-                durationDays = prevFormData.editDuration;
-            } else if (prevFormData.editDurationUnit === 'hours') {
-                durationMinutes = prevFormData.editDuration * 60;
-            } else {
-                durationMinutes = prevFormData.editDuration;
-            }
-
             onUpdateTask({
                 ...prevTask,
                 name: prevFormData.editName,
                 notes: prevFormData.editNotes.trim() || undefined,
                 is_milestone: prevFormData.isMilestoneEditing,
-                duration_days: durationDays,
-                duration_minutes: durationMinutes,
+                is_elapsed: prevFormData.isElapsedEditing,
+                pinned_start: prevFormData.editPinnedStart || undefined,
+                duration: { value: prevFormData.editDuration, unit: prevFormData.editDurationUnit },
                 dependencies: prevFormData.editDependencies,
+                start_after_percent: overlapsFor(prevFormData.editDependencies, prevFormData.editStartAfter),
                 subtasks: prevFormData.editSubtasks
             });
         }
@@ -98,25 +100,15 @@ export function TaskDetailsView({
             setEditName(taskDef.name);
             setEditNotes(taskDef.notes || '');
             setIsMilestoneEditing(taskDef.is_milestone || false);
+            setIsElapsedEditing(taskDef.is_elapsed || false);
 
-            if (taskDef.duration_minutes) {
-                if (taskDef.duration_minutes % 1440 === 0) {
-                    setEditDuration(taskDef.duration_minutes / 1440);
-                    setEditDurationUnit('days');
-                } else if (taskDef.duration_minutes % 60 === 0) {
-                    setEditDuration(taskDef.duration_minutes / 60);
-                    setEditDurationUnit('hours');
-                } else {
-                    setEditDuration(taskDef.duration_minutes);
-                    setEditDurationUnit('minutes');
-                }
-            } else {
-                setEditDuration(taskDef.duration_days);
-                setEditDurationUnit('days');
-            }
+            setEditDuration(taskDef.duration.value);
+            setEditDurationUnit(taskDef.duration.unit);
 
             setEditDependencies(taskDef.dependencies || []);
+            setEditStartAfter(taskDef.start_after_percent || {});
             setEditAnchorDate(taskAnchorDate || '');
+            setEditPinnedStart(taskDef.pinned_start || '');
             setEditSubtasks(taskDef.subtasks || []);
             setNewSubtaskName('');
             setIsDirty(false);
@@ -133,6 +125,7 @@ export function TaskDetailsView({
             setEditName(taskDef.name);
             setEditNotes(taskDef.notes || '');
             setIsMilestoneEditing(taskDef.is_milestone || false);
+            setIsElapsedEditing(taskDef.is_elapsed || false);
             // Duration logic... might be complex to repeat, but essential for sync. 
             // Ideally we extract the "init form from task" logic.
             // For now, let's just sync subtasks and basics that might be toggled in view mode (like completion?). 
@@ -147,12 +140,15 @@ export function TaskDetailsView({
         editName,
         editNotes,
         isMilestoneEditing,
+        isElapsedEditing,
         editDuration,
         editDurationUnit,
         editDependencies,
+        editStartAfter,
         editAnchorDate,
+        editPinnedStart,
         editSubtasks
-    }), [editName, editNotes, isMilestoneEditing, editDuration, editDurationUnit, editDependencies, editAnchorDate, editSubtasks]);
+    }), [editName, editNotes, isMilestoneEditing, isElapsedEditing, editDuration, editDurationUnit, editDependencies, editStartAfter, editAnchorDate, editPinnedStart, editSubtasks]);
 
     // Debounce the form data - save only triggers after user stops typing for 1.5s
     const debouncedFormData = useDebounce(formData, 1500);
@@ -161,25 +157,16 @@ export function TaskDetailsView({
     const buildTaskFromFormData = (data: typeof formData): Task | null => {
         if (!taskDef) return null;
 
-        let durationMinutes: number | undefined = undefined;
-        let durationDays = 0;
-
-        if (data.editDurationUnit === 'days') {
-            durationDays = data.editDuration;
-        } else if (data.editDurationUnit === 'hours') {
-            durationMinutes = data.editDuration * 60;
-        } else {
-            durationMinutes = data.editDuration;
-        }
-
         return {
             ...taskDef,
             name: data.editName,
             notes: data.editNotes.trim() || undefined,
             is_milestone: data.isMilestoneEditing,
-            duration_days: durationDays,
-            duration_minutes: durationMinutes,
+            is_elapsed: data.isElapsedEditing,
+            pinned_start: data.editPinnedStart || undefined,
+            duration: { value: data.editDuration, unit: data.editDurationUnit },
             dependencies: data.editDependencies,
+            start_after_percent: overlapsFor(data.editDependencies, data.editStartAfter),
             subtasks: data.editSubtasks
         };
     };
@@ -369,6 +356,35 @@ export function TaskDetailsView({
                                             className="w-24"
                                         />
                                     </div>
+                                    <Checkbox
+                                        checked={isElapsedEditing}
+                                        onChange={(val) => handleValueChange(setIsElapsedEditing, val)}
+                                        label="Elapsed time"
+                                        className="text-text-muted hover:text-text"
+                                    />
+                                    <div className="w-px h-4 bg-border" />
+
+                                    {/* Pinned Start Input (manual scheduling) */}
+                                    <div className="flex items-center gap-2" title="Pinned start">
+                                        <input
+                                            type="datetime-local"
+                                            className="bg-transparent border-none p-0 text-text text-sm focus:ring-0 outline-none"
+                                            value={editPinnedStart.slice(0, 16)}
+                                            onChange={(e) => {
+                                                const val = e.target.value;
+                                                handleValueChange(setEditPinnedStart, val.length === 16 ? `${val}:00` : val);
+                                            }}
+                                        />
+                                        {editPinnedStart && (
+                                            <button
+                                                onClick={() => handleValueChange(setEditPinnedStart, '')}
+                                                className="text-text-muted hover:text-text"
+                                                title="Unpin"
+                                            >
+                                                <CloseIcon className="w-3 h-3" />
+                                            </button>
+                                        )}
+                                    </div>
                                     <div className="w-px h-4 bg-border" />
 
                                     {/* Anchor Date Input */}
@@ -418,7 +434,15 @@ export function TaskDetailsView({
                                 </h2>
 
                                 <div className="flex items-center gap-4 text-sm text-text-muted">
-                                    <span className="font-medium bg-surface-alt px-2 py-0.5 rounded text-xs">{taskDef.duration_days} days</span>
+                                    <span className="font-medium bg-surface-alt px-2 py-0.5 rounded text-xs">{formatDurationLong(taskDef.duration)}</span>
+                                    {taskSched && taskSched.actual_minutes > 0 && (
+                                        <span
+                                            className={`text-xs ${taskSched.actual_minutes > taskSched.estimate_minutes ? 'text-red-500' : 'text-text-faint'}`}
+                                            title="Time tracked against the estimate"
+                                        >
+                                            {formatMinutes(taskSched.actual_minutes)} of {formatMinutes(taskSched.estimate_minutes)} tracked
+                                        </span>
+                                    )}
                                     {taskSched && (
                                         <div className="flex items-center gap-1.5 text-text-faint">
                                             <CalendarIcon className="w-4 h-4" />
@@ -584,6 +608,27 @@ export function TaskDetailsView({
                                             <p className="text-sm text-text-muted italic">No other tasks available to depend on.</p>
                                         )}
                                     </div>
+                                    {editDependencies.length > 0 && (
+                                        <div className="mt-2 space-y-1">
+                                            {editDependencies.map(depId => (
+                                                <label key={depId} className="flex items-center gap-2 text-xs text-text-muted">
+                                                    Start when {tasks.find(t => t.id === depId)?.name ?? depId} is
+                                                    <input
+                                                        type="number"
+                                                        min={0}
+                                                        max={100}
+                                                        className="w-14 bg-surface border border-border rounded px-1 py-0.5 text-text"
+                                                        value={editStartAfter[depId] ?? 100}
+                                                        onChange={(e) => {
+                                                            const percent = Math.min(100, Math.max(0, Number(e.target.value)));
+                                                            handleValueChange(setEditStartAfter, { ...editStartAfter, [depId]: percent });
+                                                        }}
+                                                    />
+                                                    % done
+                                                </label>
+                                            ))}
+                                        </div>
+                                    )}
                                 </div>
 
                                 <div className="flex-1 flex flex-col min-h-[200px]">
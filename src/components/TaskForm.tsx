@@ -1,9 +1,10 @@
 import { useState } from 'react';
 import { motion, AnimatePresence } from 'framer-motion';
-import { Task } from '../types';
+import { DurationUnit, Task } from '../types';
 import { AnchorIcon, CloseIcon, EditIcon, CheckIcon, MemoIcon, DiamondIcon, ChevronRightIcon, ChevronDownIcon } from './icons';
 import { Checkbox } from './Checkbox';
 import { SmartDurationInput } from './ui/SmartDurationInput';
+import { formatDurationShort } from '../utils/duration';
 
 interface TaskFormProps {
     tasks: Task[];
@@ -30,7 +31,7 @@ export function TaskForm({
 }: TaskFormProps) {
     const [newTaskName, setNewTaskName] = useState('');
     const [newTaskDuration, setNewTaskDuration] = useState(1);
-    const [durationUnit, setDurationUnit] = useState<'days' | 'hours' | 'minutes'>('days');
+    const [durationUnit, setDurationUnit] = useState<DurationUnit>('days');
     const [selectedDependencies, setSelectedDependencies] = useState<string[]>([]);
     const [newTaskNotes, setNewTaskNotes] = useState('');
     const [isMilestone, setIsMilestone] = useState(false);
@@ -46,7 +47,7 @@ export function TaskForm({
             const updatedTask: Task = {
                 id: editingTaskId,
                 name: newTaskName.trim(),
-                duration_days: newTaskDuration,
+                duration: { value: newTaskDuration, unit: durationUnit },
                 dependencies: selectedDependencies,
                 completed: originalTask?.completed || false,
                 notes: newTaskNotes.trim() || undefined,
@@ -58,8 +59,7 @@ export function TaskForm({
             const newTask: Task = {
                 id: crypto.randomUUID(),
                 name: newTaskName.trim(),
-                duration_days: durationUnit === 'days' ? newTaskDuration : 0,
-                duration_minutes: durationUnit === 'minutes' ? newTaskDuration : durationUnit === 'hours' ? newTaskDuration * 60 : undefined,
+                duration: { value: newTaskDuration, unit: durationUnit },
                 dependencies: selectedDependencies,
                 notes: newTaskNotes.trim() || undefined,
                 is_milestone: isMilestone,
@@ -284,11 +284,7 @@ export function TaskForm({
                                                         {task.name}
                                                     </span>
                                                     <span className="text-xs text-text-faint">
-                                                        {task.duration_minutes
-                                                            ? (task.duration_minutes >= 60
-                                                                ? `${Math.round(task.duration_minutes / 60 * 10) / 10}h`
-                                                                : `${task.duration_minutes}m`)
-                                                            : `${task.duration_days}d`}
+                                                        {formatDurationShort(task.duration)}
                                                     </span>
                                                     {task.is_milestone && (
                                                         <span className="text-purple-500" title="Milestone">
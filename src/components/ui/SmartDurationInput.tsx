@@ -1,6 +1,15 @@
 import { useState, useEffect, useRef } from 'react';
+import type { DurationUnit } from '../../types';
 
-export type DurationUnit = 'minutes' | 'hours' | 'days';
+export type { DurationUnit };
+
+// Next smaller unit, used to keep fractional input whole (1.5h -> 90m)
+const SMALLER: Record<DurationUnit, [DurationUnit, number] | null> = {
+    weeks: ['days', 7],
+    days: ['hours', 24],
+    hours: ['minutes', 60],
+    minutes: null,
+};
 
 interface SmartDurationInputProps {
     value: number; // Value in the specified unit
@@ -15,7 +24,7 @@ export function SmartDurationInput({
     unit,
     onChange,
     className = '',
-    placeholder = 'e.g. 1d, 4h, 30m'
+    placeholder = 'e.g. 2w, 1d, 4h, 30m'
 }: SmartDurationInputProps) {
     const [inputValue, setInputValue] = useState('');
     const [isFocused, setIsFocused] = useState(false);
@@ -23,6 +32,7 @@ export function SmartDurationInput({
 
     // Format the display string based on value and unit
     const formatDisplay = (val: number, u: DurationUnit) => {
+        if (u === 'weeks') return `${val} week${val !== 1 ? 's' : ''}`;
         if (u === 'days') return `${val} day${val !== 1 ? 's' : ''}`;
         if (u === 'hours') return `${val} hour${val !== 1 ? 's' : ''}`;
         return `${val} minute${val !== 1 ? 's' : ''}`;
@@ -30,6 +40,7 @@ export function SmartDurationInput({
 
     // Format the edit string (shorthand)
     const formatEdit = (val: number, u: DurationUnit) => {
+        if (u === 'weeks') return `${val}w`;
         if (u === 'days') return `${val}d`;
         if (u === 'hours') return `${val}h`;
         return `${val}m`;
@@ -54,7 +65,7 @@ export function SmartDurationInput({
 
         // Match number and optional unit
         // Regex allows integer or float, followed by optional whitespace and unit char
-        const match = text.match(/^(\d*(?:\.\d+)?)\s*([dhm])?$/);
+        const match = text.match(/^(\d*(?:\.\d+)?)\s*([wdhm])?$/);
 
         if (!match) {
             // If completely invalid or empty, revert to current value
@@ -74,7 +85,9 @@ export function SmartDurationInput({
 
         if (isNaN(val) || val <= 0) val = 1;
 
-        if (unitChar === 'd') {
+        if (unitChar === 'w') {
+            newUnit = 'weeks';
+        } else if (unitChar === 'd') {
             newUnit = 'days';
         } else if (unitChar === 'h') {
             newUnit = 'hours';
@@ -86,7 +99,15 @@ export function SmartDurationInput({
             newUnit = unit;
         }
 
-        return { newVal: val, newUnit };
+        // Durations are stored in whole units
+        let smaller = SMALLER[newUnit];
+        while (!Number.isInteger(val) && smaller) {
+            val *= smaller[1];
+            newUnit = smaller[0];
+            smaller = SMALLER[newUnit];
+        }
+
+        return { newVal: Math.max(1, Math.round(val)), newUnit };
     };
 
     const handleBlur = () => {
@@ -4,10 +4,60 @@ import { listen } from '@tauri-apps/api/event';
 
 export type MyAppTheme = 'light' | 'dark' | 'system';
 
+export type RecalculationTrigger =
+    | { mode: 'on_edit' }
+    | { mode: 'on_save' }
+    | { mode: 'manual' }
+    | { mode: 'timer'; interval_seconds: number };
+
+export type Weekday = 'Mon' | 'Tue' | 'Wed' | 'Thu' | 'Fri' | 'Sat' | 'Sun';
+
+export interface WorkingHours {
+    day_start: string; // HH:MM
+    day_end: string; // HH:MM
+    days: Weekday[];
+    capacity_hours: number;
+    exceptions?: WorkingException[];
+}
+
+/** Different hours for one date (wins) or every given weekday; unset fields keep the usual value. */
+export interface WorkingException {
+    date?: string; // YYYY-MM-DD
+    weekday?: Weekday;
+    day_start?: string; // HH:MM
+    day_end?: string; // HH:MM
+    capacity_hours?: number; // 0 makes the day off
+}
+
+/** Which tasks the desktop widget lists; the focus view ignores these. */
+export interface WidgetSettings {
+    max_tasks: number; // Length of "Up Next", default 5
+    lookahead_days?: number | null; // Month view horizon
+    milestones_only?: boolean;
+    hide_future_after_days?: number | null; // Active and overdue tasks always show
+}
+
+/** Follow-up on tasks past their late finish. */
+export interface OverdueSettings {
+    notify: boolean; // Once as each task becomes overdue
+    daily_summary_hour?: number | null; // Per-project summary from this hour, default 9
+}
+
 export interface AppConfig {
     theme: MyAppTheme;
+    recalculation?: RecalculationTrigger;
+    working_hours?: WorkingHours;
+    trash_retention_days?: number; // 0 keeps deleted projects until purged
+    projects_dir?: string | null; // e.g. a Dropbox or Syncthing folder
+    pinned_projects?: string[];
+    project_order?: string[]; // Set through reorder_projects
+    widget?: WidgetSettings;
+    overdue?: OverdueSettings;
+    quick_add_shortcut?: string | null; // e.g. "CommandOrControl+Shift+Space"; read at launch
 }
 
+const ON_EDIT: RecalculationTrigger = { mode: 'on_edit' };
+
 export function useConfig() {
     const [config, setConfig] = useState<AppConfig>({ theme: 'system' });
     const [loaded, setLoaded] = useState(false);
@@ -47,6 +97,8 @@ export function useConfig() {
     return {
         theme: config.theme,
         setTheme: updateTheme,
+        recalculation: config.recalculation ?? ON_EDIT,
+        widget: config.widget,
         loaded
     };
 }
@@ -0,0 +1,48 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { ImportFailure, ImportPreview } from "../types";
+
+/** Queues previews of files dropped on the window until confirmed or dismissed. */
+export function useFileImport(onImported: (projectId: string) => void) {
+    const [previews, setPreviews] = useState<ImportPreview[]>([]);
+    const [failure, setFailure] = useState<ImportFailure | null>(null);
+
+    useEffect(() => {
+        const unlistenPreview = listen<ImportPreview>("import-preview", (event) => {
+            setPreviews(queue => [...queue, event.payload]);
+        });
+        const unlistenFailure = listen<ImportFailure>("import-failed", (event) => {
+            setFailure(event.payload);
+        });
+
+        return () => {
+            unlistenPreview.then(f => f());
+            unlistenFailure.then(f => f());
+        };
+    }, []);
+
+    const dismiss = useCallback(() => {
+        setPreviews(queue => queue.slice(1));
+    }, []);
+
+    const confirm = useCallback(async () => {
+        const preview = previews[0];
+        if (!preview) return;
+        try {
+            const imported = await invoke<ImportPreview>("import_file", { path: preview.path, dryRun: false });
+            onImported(imported.project.id);
+        } catch (e) {
+            setFailure({ path: preview.path, error: String(e) });
+        }
+        dismiss();
+    }, [previews, onImported, dismiss]);
+
+    return {
+        preview: previews[0] ?? null,
+        failure,
+        confirm,
+        dismiss,
+        clearFailure: () => setFailure(null),
+    };
+}
@@ -1,7 +1,7 @@
 import { useEffect } from 'react';
 import { ScheduledTask } from '../types';
 import { useNotifications } from './useNotifications';
-import { format, parseISO, isSameDay, isPast, isFuture } from 'date-fns';
+import { format, parseISO, isPast, isFuture } from 'date-fns';
 
 export const useNotificationScheduler = (
     scheduledTasks: ScheduledTask[] | undefined
@@ -38,33 +38,13 @@ export const useNotificationScheduler = (
                 const start = parseISO(task.start_date);
                 const end = parseISO(task.end_date);
 
-                // Logic 1: Task starts "soon" or "now" (within last 15 mins or next 5 mins?)
-                // Actually, if it's Minute granularity, we want to notify exactly when it starts.
-                // Or if it started recently and we haven't notified.
-                // Simple logic: If start time is in the past (active) and we haven't notified today.
-                // Constraint: Don't notify for tasks strictly in the past (started yesterday).
-                // So: isSameDay(start, now) && isPast(start)
-
-                if (isSameDay(start, now) && isPast(start)) {
-                    notify(
-                        '⚓ Time to Start!',
-                        `"${task.name}" is scheduled to start now.`
-                    );
-                    localStorage.setItem(notificationKey, 'true');
-                }
-
-                // Logic 2: Critical Path Warning
-                else if (
+                // Start and due-soon reminders come from the backend (reminders.rs)
+                if (
                     task.is_critical &&
                     isPast(start) &&
                     isFuture(end)
                 ) {
-                    // Only notify if we haven't already
-                    // This duplicates the above if start is today.
-                    // Prioritize "Time to Start".
-
-                    // We might want a separate key or check for critical warning? 
-                    // reusing same key means only 1 notification per task per day.
+                    // Only 1 notification per task per day
                     notify(
                         '🔥 Critical Task',
                         `"${task.name}" is critical and active. Any delay will push the deadline!`
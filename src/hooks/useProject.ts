@@ -1,17 +1,29 @@
 import { useState, useCallback, useEffect, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { Project, ScheduledTask, Task } from "../types";
+import { listen } from "@tauri-apps/api/event";
+import { Project, ProjectUpdate, ScheduleFeasibility, ScheduleResult, ScheduledTask, Task } from "../types";
 import { useDebounce } from "./useDebounce";
+import { RecalculationTrigger } from "./useConfig";
 
-export function useProject(projectId: string | null) {
+export function useProject(projectId: string | null, recalculation: RecalculationTrigger = { mode: 'on_edit' }) {
     const [project, setProject] = useState<Project | null>(null);
     const [scheduledTasks, setScheduledTasks] = useState<ScheduledTask[]>([]);
+    const [feasibility, setFeasibility] = useState<ScheduleFeasibility | null>(null);
     const [loading, setLoading] = useState(false);
     const [error, setError] = useState<string | null>(null);
     const [anchorDate, setAnchorDate] = useState<string>("");
+    // True when edits haven't been scheduled yet (modes other than on_edit)
+    const [scheduleStale, setScheduleStale] = useState(false);
+    // Project the current schedule was computed for; the first load always schedules
+    const scheduledIdRef = useRef<string | null>(null);
 
     // Ref to track if we have unsaved changes
     const isDirty = useRef(false);
+    // Latest state and the last snapshot saved, to tell whether a save left edits pending
+    const projectRef = useRef<Project | null>(null);
+    const savedRef = useRef<Project | null>(null);
+    // Bumped to reload the project after it changed on disk
+    const [reloadKey, setReloadKey] = useState(0);
 
     // History stacks for Undo/Redo
     const historyRef = useRef<Project[]>([]);
@@ -20,6 +32,14 @@ export function useProject(projectId: string | null) {
     const updateProject = (newProject: Project) => {
         if (!project) return;
 
+        // Stamp edited tasks so concurrent edits from synced copies can be settled
+        const previous = new Map(project.tasks.map(t => [t.id, t]));
+        const now = new Date().toISOString();
+        newProject = {
+            ...newProject,
+            tasks: newProject.tasks.map(t => previous.get(t.id) === t ? t : { ...t, updated_at: now }),
+        };
+
         // Push current state to history
         historyRef.current.push(project);
 
@@ -61,6 +81,7 @@ export function useProject(projectId: string | null) {
             setScheduledTasks([]);
             return;
         }
+        scheduledIdRef.current = null;
 
         const load = async () => {
             setLoading(true);
@@ -82,13 +103,44 @@ export function useProject(projectId: string | null) {
             }
         };
         load();
+    }, [projectId, reloadKey]);
+
+    useEffect(() => {
+        projectRef.current = project;
+    }, [project]);
+
+    // Pick up edits made outside the app (or merged in on save), unless there are unsaved changes
+    useEffect(() => {
+        if (!projectId) return;
+        const unlisten = listen<ProjectUpdate>("project-update", (event) => {
+            const { project_id, kind } = event.payload;
+            const changed = ['merged', 'changed_on_disk', 'renamed', 'focused', 'completed', 'task_added', 'tracked'].includes(kind);
+            const saved = isDirty.current && projectRef.current === savedRef.current;
+            if (changed && project_id === projectId && (!isDirty.current || saved)) {
+                setReloadKey(key => key + 1);
+            }
+        });
+        return () => {
+            unlisten.then(f => f());
+        };
     }, [projectId]);
 
+    const applyResult = (result: ScheduleResult) => {
+        setScheduledTasks(result.scheduled_tasks);
+        setFeasibility(result.feasibility);
+        setScheduleStale(false);
+        setError(null);
+    };
+
     // Schedule calculation
     const calculateSchedule = useCallback(async (currentProject: Project) => {
-        const anchorIds = Object.keys(currentProject.anchors);
+        const anchorIds = [
+            ...Object.keys(currentProject.anchors),
+            ...Object.keys(currentProject.anchor_windows ?? {}),
+        ];
         if (currentProject.tasks.length === 0 || anchorIds.length === 0) {
             setScheduledTasks([]);
+            setFeasibility(null);
             return;
         }
 
@@ -99,11 +151,19 @@ export function useProject(projectId: string | null) {
             // Sync with project state
             const updatedProject = { ...currentProject, anchors: effectiveAnchors };
 
-            const result = await invoke<ScheduledTask[]>("schedule", {
-                request: { tasks: updatedProject.tasks, anchors: effectiveAnchors }
+            const result = await invoke<ScheduleResult>("schedule", {
+                request: {
+                    tasks: updatedProject.tasks,
+                    anchors: effectiveAnchors,
+                    anchor_windows: currentProject.anchor_windows ?? {},
+                    timezone: currentProject.timezone,
+                    exclusions: currentProject.exclusions ?? [],
+                    anchor_spacing: currentProject.anchor_spacing ?? [],
+                    serialize_work: currentProject.serialize_work ?? false,
+                    spread_slack: currentProject.spread_slack ?? false,
+                }
             });
-            setScheduledTasks(result);
-            setError(null);
+            applyResult(result);
         } catch (e) {
             console.error(e);
             setError(typeof e === 'string' ? e : "Scheduling failed");
@@ -112,16 +172,63 @@ export function useProject(projectId: string | null) {
 
     // Effect to run schedule when project data changes
     useEffect(() => {
-        if (project) {
+        if (!project) return;
+        if (recalculation.mode === 'on_edit' || scheduledIdRef.current !== project.id) {
+            scheduledIdRef.current = project.id;
+            calculateSchedule(project);
+            setScheduleStale(false);
+        } else {
+            setScheduleStale(true);
+        }
+    }, [project, calculateSchedule, recalculation.mode]);
+
+    // Timer mode: pick up pending edits periodically
+    const projectRef = useRef(project);
+    projectRef.current = project;
+    const timerSeconds = recalculation.mode === 'timer' ? recalculation.interval_seconds : null;
+    useEffect(() => {
+        if (!timerSeconds || !scheduleStale) return;
+        const timer = setInterval(() => {
+            if (projectRef.current) calculateSchedule(projectRef.current);
+        }, timerSeconds * 1000);
+        return () => clearInterval(timer);
+    }, [timerSeconds, scheduleStale, calculateSchedule]);
+
+    // Manual mode: save pending edits, then schedule the saved project
+    const recalculate = useCallback(async () => {
+        if (!project || !projectId) return;
+        if (project.tasks.length === 0 || Object.keys(project.anchors).length === 0) {
             calculateSchedule(project);
+            return;
         }
-    }, [project, calculateSchedule]);
+        try {
+            await invoke("save_project", { project });
+            const result = await invoke<ScheduleResult>("recalculate", { projectId });
+            applyResult(result);
+        } catch (e) {
+            console.error(e);
+            setError(typeof e === 'string' ? e : "Scheduling failed");
+        }
+    }, [project, projectId, calculateSchedule]);
+
+    // Journal every edit right away so a crash before the debounced save can be recovered
+    useEffect(() => {
+        if (project && isDirty.current) {
+            invoke("journal_project", { project }).catch(e => {
+                console.error("Journaling failed:", e);
+            });
+        }
+    }, [project]);
 
     // Auto-save logic
     const debouncedProject = useDebounce(project, 1000);
     useEffect(() => {
         if (debouncedProject && projectId && isDirty.current) {
+            savedRef.current = debouncedProject;
             invoke("save_project", { project: debouncedProject }).then(() => {
+                if (recalculation.mode === 'on_save') {
+                    calculateSchedule(debouncedProject);
+                }
                 // We don't necessarily reset dirty here because new changes might be pending?
                 // But for this simple implementation, if we saved, we are clean relative to that state.
                 // However, debouncedProject is behind real state.
@@ -134,7 +241,7 @@ export function useProject(projectId: string | null) {
                 console.error("Auto-save failed:", e);
             });
         }
-    }, [debouncedProject, projectId]);
+    }, [debouncedProject, projectId, recalculation.mode, calculateSchedule]);
 
     // Actions
     const addTask = (task: Task) => {
@@ -193,12 +300,27 @@ export function useProject(projectId: string | null) {
         setAnchorDate(newDate);
     };
 
+    // Single-user mode: level all work into one sequence
+    const setSerializeWork = (enabled: boolean) => {
+        if (!project) return;
+        updateProject({ ...project, serialize_work: enabled });
+    };
+
+    // Share slack along chains instead of planning everything as late as possible
+    const setSpreadSlack = (enabled: boolean) => {
+        if (!project) return;
+        updateProject({ ...project, spread_slack: enabled });
+    };
+
     // Derived state for UI
     const anchorTaskIds = project ? Object.keys(project.anchors) : [];
 
     return {
         project,
         scheduledTasks,
+        feasibility,
+        scheduleStale,
+        recalculate,
         loading,
         error,
         anchorDate,
@@ -208,6 +330,8 @@ export function useProject(projectId: string | null) {
         removeTask,
         editTask,
         toggleAnchor,
+        setSerializeWork,
+        setSpreadSlack,
         undo,
         redo,
         anchorTaskIds
@@ -10,7 +10,7 @@ export function useProjectsList() {
     const loadProjects = useCallback(async () => {
         try {
             // setLoading(true); // Don't set loading true on refresh to avoid flashing
-            const list = await invoke<ProjectMetadata[]>("list_projects");
+            const list = await invoke<ProjectMetadata[]>("get_dashboard");
             setProjects(list);
         } catch (e) {
             console.error(e);
@@ -26,9 +26,14 @@ export function useProjectsList() {
         const unlisten = listen("project-update", () => {
             loadProjects();
         });
+        // A project turned urgent or overdue with nothing else changing
+        const unlistenStatus = listen("project-status", () => {
+            loadProjects();
+        });
 
         return () => {
             unlisten.then(f => f());
+            unlistenStatus.then(f => f());
         };
     }, [loadProjects]);
 
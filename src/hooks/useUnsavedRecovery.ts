@@ -0,0 +1,39 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { Project, UnsavedChanges } from "../types";
+
+/** Offers, one project at a time, to recover edits a crash kept from being saved. */
+export function useUnsavedRecovery(onRecovered: (projectId: string) => void) {
+    const [pending, setPending] = useState<UnsavedChanges[]>([]);
+
+    useEffect(() => {
+        // The launch event can fire before we listen, so ask as well
+        invoke<UnsavedChanges[]>("list_unsaved_changes").then(setPending).catch(console.error);
+        const unlisten = listen<UnsavedChanges[]>("unsaved-changes", (event) => {
+            setPending(event.payload);
+        });
+
+        return () => {
+            unlisten.then(f => f());
+        };
+    }, []);
+
+    // Leaves the entry journaled; the project's next edit and save settle it
+    const dismiss = useCallback(() => {
+        setPending(queue => queue.slice(1));
+    }, []);
+
+    const recover = useCallback(async () => {
+        const entry = pending[0];
+        if (!entry) return;
+        try {
+            const project = await invoke<Project>("recover_unsaved_changes", { projectId: entry.project_id });
+            onRecovered(project.id);
+        } catch (e) {
+            console.error("Recovery failed:", e);
+        }
+    }, [pending, onRecovered]);
+
+    return { pending: pending[0] ?? null, recover, dismiss };
+}
@@ -0,0 +1,10 @@
+import React from "react";
+import ReactDOM from "react-dom/client";
+import QuickAddApp from "./QuickAddApp";
+import "./App.css";
+
+ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
+    <React.StrictMode>
+        <QuickAddApp />
+    </React.StrictMode>
+);
@@ -1,13 +1,59 @@
+export type DurationUnit = 'minutes' | 'hours' | 'days' | 'weeks';
+
+/** Minutes and hours are elapsed time; days and weeks are calendar days. */
+export interface TaskDuration {
+    value: number; // Whole units
+    unit: DurationUnit;
+}
+
 export interface Task {
     id: string;
     name: string;
-    duration_days: number;
-    duration_minutes?: number; // Optional minute precision
+    duration: TaskDuration;
     dependencies: string[];
     completed?: boolean;
     notes?: string;
     is_milestone?: boolean;
+    is_elapsed?: boolean; // Real elapsed time (curing, shipping) rather than work
+    pinned_start?: string; // Fixed start date set by hand
+    start_after_percent?: Record<string, number>; // Dependency ID -> % done before this can start
     subtasks?: SubTask[];
+    completed_at?: string;
+    assignee?: string;
+    phase?: string; // Group shown together on the timeline, e.g. "Design"
+    tags?: string[];
+    buffer_percent?: number;
+    optimistic?: TaskDuration; // Three-point estimate; duration is the most likely
+    pessimistic?: TaskDuration;
+    only_during?: AllowedWindow[];
+    updated_at?: string; // Last edit (RFC 3339), settles conflicting synced edits
+    attachments?: Attachment[];
+    reminders?: TaskReminder[]; // Replace the project's default reminders
+    sessions?: WorkSession[]; // From the task timer
+}
+
+/** Local times; `ended_at` is unset while the timer runs. */
+export interface WorkSession {
+    started_at: string;
+    ended_at?: string | null;
+}
+
+export interface TaskReminder {
+    minutes_before: number;
+    relative_to: 'start' | 'deadline'; // Deadline is the scheduled finish
+}
+
+export interface Attachment {
+    id: string;
+    name: string;
+    url?: string | null; // Web or mail link
+    file?: string | null; // Copy under projects/attachments
+    added_at: string;
+}
+
+export interface AllowedWindow {
+    start: string;
+    end: string;
 }
 
 export interface SubTask {
@@ -26,29 +72,686 @@ export interface ScheduledTask {
     is_critical: boolean;
     slack_days: number;
     is_milestone: boolean;
+    is_elapsed: boolean;
+    is_pinned: boolean;
+    phase?: string | null;
+    tags?: string[];
+    starts_in_past: boolean;
+    estimate_minutes: number;
+    buffer_minutes: number;
+    actual_minutes: number; // Tracked so far; unfinished tasks are scheduled for what's left
+}
+
+export interface AnchorWindow {
+    earliest: string;
+    latest: string;
+}
+
+export interface ExclusionGroup {
+    resource: string;
+    task_ids: string[];
+}
+
+export interface AnchorWindowPlacement {
+    task_id: string;
+    earliest: string;
+    latest: string;
+    placed_at: string;
+    position: number;
+    within_window: boolean;
 }
 
 export interface ScheduleRequest {
     tasks: Task[];
     anchors: Record<string, string>; // TaskId -> YYYY-MM-DD
+    anchor_windows?: Record<string, AnchorWindow>;
+    reference_time?: string;
+    timezone?: string; // IANA name, e.g. "America/New_York"
+    exclusions?: ExclusionGroup[];
+    anchor_spacing?: AnchorSpacing[];
+    serialize_work?: boolean; // Single-user mode: never two tasks at once
+    spread_slack?: boolean; // Plan between early and late dates instead of as late as possible
+    floor_starts?: boolean; // Never plan incomplete work to start before start_floor
+    start_floor?: string; // Defaults to reference_time
+    busy?: BusySlot[]; // Minute- and hour-based tasks are split around these
+}
+
+export interface BusySlot {
+    start: string;
+    end: string;
+    label?: string;
+}
+
+export interface AnchorSpacing {
+    before: string;
+    after: string;
+    min_gap_days: number;
+}
+
+export interface SpacingViolation {
+    before: string;
+    after: string;
+    min_gap_days: number;
+    shortfall_minutes: number;
+}
+
+export interface ScheduleFeasibility {
+    is_feasible: boolean;
+    late_task_ids: string[];
+    shortfall_minutes: number;
+    required_daily_overrun: number | null;
+    spacing_violations: SpacingViolation[];
+    anchor_conflicts: AnchorConflict[];
+}
+
+/** Two anchors too close together for the work between them. */
+export interface AnchorConflict {
+    upstream: string;
+    downstream: string;
+    upstream_anchor: string;
+    downstream_anchor: string;
+    required_gap_minutes: number;
+    shortfall_minutes: number;
+}
+
+export interface ChainBuffer {
+    anchor_id: string;
+    task_ids: string[];
+    estimate_minutes: number;
+    buffer_minutes: number;
+}
+
+export interface AnchorSuggestion {
+    task_id: string;
+    earliest_finish: string;
+    current_anchor: string | null;
+    slack_minutes: number | null; // Negative when the current anchor can't be met
+}
+
+export interface PhaseSummary {
+    phase: string;
+    start_date: string;
+    end_date: string;
+    slack_minutes: number;
+    is_critical: boolean;
+    task_count: number;
+    completed_count: number;
+}
+
+export type ScheduleWarning = {
+    kind: 'anchor_overridden';
+    task_id: string;
+    anchor_date: string;
+    scheduled_end: string;
+    overridden_by_minutes: number;
+    constrained_by: string | null;
+} | {
+    kind: 'no_allowed_window';
+    task_id: string;
+    required_finish: string;
+} | {
+    kind: 'exclusion_conflict';
+    resource: string;
+    task_ids: string[];
+    overlap_minutes: number;
+} | {
+    kind: 'serial_work_overlap';
+    task_ids: string[];
+    overlap_minutes: number;
+} | {
+    kind: 'serial_work_starts_in_past';
+    task_id: string;
+    required_start: string;
+    shortfall_minutes: number;
+} | {
+    kind: 'floor_overrun';
+    task_id: string;
+    anchor_date: string;
+    projected_finish: string;
+    overrun_minutes: number;
+} | {
+    kind: 'pin_conflict';
+    task_id: string;
+    constrained_by: string | null;
+    overlap_minutes: number;
+} | {
+    kind: 'unanchored'; // No deadline; placed as soon as dependencies allow
+    task_ids: string[];
+} | {
+    kind: 'negative_slack';
+    task_id: string;
+    slack_minutes: number;
+};
+
+export interface ScheduleResult {
+    scheduled_tasks: ScheduledTask[];
+    feasibility: ScheduleFeasibility;
+    chain_buffers: ChainBuffer[];
+    critical_chains: string[][]; // Per critical anchor, task IDs from first task to anchor
+    phases: PhaseSummary[];
+    anchor_windows: AnchorWindowPlacement[];
+    warnings: ScheduleWarning[];
+}
+
+export interface ScheduleDelta {
+    updated: ScheduledTask[];
+    feasibility: ScheduleFeasibility;
+    phases: PhaseSummary[];
+    full_recompute: boolean;
 }
 
 export interface Project {
+    /** File format version; stamped by the backend on save. */
+    schema_version?: number;
     id: string;
     name: string;
+    color?: string | null; // CSS color, e.g. "#3b82f6"
+    icon?: string | null; // Emoji or icon name
+    description?: string | null;
     created_at: string;
     last_modified: string;
     tasks: Task[];
     anchors: Record<string, string>;
+    anchor_windows?: Record<string, AnchorWindow>;
+    timezone?: string;
+    exclusions?: ExclusionGroup[];
+    anchor_spacing?: AnchorSpacing[];
+    serialize_work?: boolean; // Single-user mode: never two tasks at once
+    spread_slack?: boolean; // Plan between early and late dates instead of as late as possible
+    /** Append-only; kept by the backend, edits here are ignored. */
+    activity?: ActivityEntry[];
+    focus_task_id?: string | null; // Set through set_focus_task
+}
+
+export type Activity =
+    | { kind: 'created' }
+    | { kind: 'renamed'; from: string; to: string }
+    | { kind: 'duration_changed'; from: TaskDuration; to: TaskDuration }
+    | { kind: 'anchor_changed'; from?: string | null; to?: string | null }
+    | { kind: 'completed' }
+    | { kind: 'reopened' }
+    | { kind: 'deleted' }
+    | { kind: 'comment'; text: string };
+
+export type ActivityEntry = Activity & {
+    task_id: string;
+    at: string;
+};
+
+/** One change in an `apply_task_operations` batch. */
+/** Journaled edits found at launch that were never saved. */
+export interface UnsavedChanges {
+    project_id: string;
+    project_name: string;
+    journaled_at: string;
+}
+
+/** Result of checking one project file with `repair_project`. */
+export interface RepairReport {
+    project_id: string;
+    status: 'ok' | 'restored' | 'unrecoverable';
+    error?: string | null;
+    restored_from?: string | null;
+}
+
+export type RestoreMode = 'merge' | 'replace';
+
+export interface BackupSummary {
+    project_count: number;
+    includes_config: boolean;
+}
+
+export interface RestoreSummary {
+    added: string[];
+    replaced: string[];
+    kept: string[];
+    config_restored: boolean;
+}
+
+export type ConflictPolicy = 'prefer_anchor' | 'prefer_calendar';
+
+/** A project's CalDAV calendar, stored in the app config. */
+export interface CalDavLink {
+    project_id: string;
+    calendar_url: string;
+    username: string;
+    password: string;
+    component?: 'event' | 'todo';
+    conflicts?: ConflictPolicy;
+}
+
+export interface SyncReport {
+    pulled: string[];
+    pushed: number;
+    removed: number;
+    conflicts: { task_id: string; task_name: string; resolution: ConflictPolicy }[];
+    errors: string[];
+}
+
+export interface GoogleCalendar {
+    id: string;
+    summary: string;
+    primary: boolean;
+}
+
+export interface GoogleSyncReport {
+    inserted: number;
+    updated: number;
+    deleted: number;
+}
+
+export interface GanttOptions {
+    path?: string;
+    width?: number;
+    row_height?: number;
+    include_completed?: boolean;
+    show_dependencies?: boolean;
+}
+
+export interface TrashedProject {
+    project_id: string;
+    name: string;
+    task_count: number;
+    deleted_at: string;
+}
+
+export interface ProjectTemplate {
+    id: string;
+    name: string;
+    description?: string;
+    bundled: boolean;
+    tasks: Task[];
+    /** Anchored task IDs, by minutes before the latest anchor. */
+    anchor_offsets: Record<string, number>;
+    exclusions: ExclusionGroup[];
+    serialize_work: boolean;
+    spread_slack: boolean;
+}
+
+export type TaskStatus = 'active' | 'future' | 'overdue' | 'completed';
+
+export interface TaskFilter {
+    tags?: string[]; // All must match; case-insensitive, leading '#' ignored
+    status?: TaskStatus[];
+    due_from?: string; // ISO 8601 DateTime or YYYY-MM-DD
+    due_to?: string;
+}
+
+export type SearchField = 'project_name' | 'task_name' | 'tag' | 'subtask' | 'notes';
+
+export interface SearchHit {
+    project_id: string;
+    project_name: string;
+    task_id?: string | null; // Unset when the project itself matched
+    task_name?: string | null;
+    field: SearchField;
+    snippet: string;
+    score: number;
+}
+
+export interface SyncConflict {
+    project_id: string;
+    task_id?: string | null; // Unset for project-level fields
+    task_name?: string | null;
+    field: string; // "task" when one side deleted a task the other edited
+    kept: unknown;
+    discarded: unknown;
+    detected_at: string;
+}
+
+export interface EncryptionStatus {
+    enabled: boolean;
+    unlocked: boolean; // False until unlock_projects after each launch
+}
+
+export interface ProgressPoint {
+    date: string; // YYYY-MM-DD
+    completed_minutes: number;
+    remaining_minutes: number;
+}
+
+export interface BurndownPoint {
+    date: string;
+    remaining_minutes?: number | null; // Unset for days still to come
+    ideal_minutes: number;
+}
+
+export interface AnchorBurndown {
+    task_id: string;
+    task_name: string;
+    anchor_date: string;
+    total_minutes: number;
+    points: BurndownPoint[];
+}
+
+export interface ProjectStats {
+    progress: ProgressPoint[];
+    burndowns: AnchorBurndown[];
+    average_slip_days?: number | null; // Negative when work finishes early
+    status_counts: Record<TaskStatus, number>;
+}
+
+export interface PortfolioAnchor {
+    project_id: string;
+    project_name: string;
+    task_id: string;
+    task_name: string;
+    date: string;
+    completed: boolean;
+}
+
+export interface WeekLoad {
+    week_start: string; // Monday, YYYY-MM-DD
+    work_minutes: number;
+    by_project: Record<string, number>; // Project ID to minutes
+}
+
+export interface DeadlineConflict {
+    first: PortfolioAnchor;
+    second: PortfolioAnchor;
+    overlap_minutes: number;
+}
+
+export interface Portfolio {
+    anchors: PortfolioAnchor[];
+    weekly_load: WeekLoad[];
+    conflicts: DeadlineConflict[];
+    most_urgent?: { project_id: string; project_name: string; task: ScheduledTask } | null;
+}
+
+export interface NextAction {
+    project_id: string;
+    project_name: string;
+    task: ScheduledTask;
+    late_finish: string; // Scheduled end plus slack
+    pressure_minutes: number; // Negative once it's too late
+}
+
+export interface TodayTask {
+    id: string;
+    name: string;
+    project_id: string;
+    project_name: string;
+    start_date: string;
+    end_date: string;
+    is_milestone: boolean;
+    status: 'active' | 'starts_today';
+    minutes_left_today: number; // Working time left today in the task's span
+}
+
+/** get_today: tasks under way or starting today, soonest to finish first. */
+export interface TodayView {
+    date: string; // YYYY-MM-DD
+    working_day: boolean;
+    minutes_left_today: number;
+    tasks: TodayTask[];
+}
+
+export type ChangeKind =
+    | 'created'
+    | 'saved'
+    | 'merged' // Saved over changes made elsewhere; editors reload
+    | 'renamed'
+    | 'focused'
+    | 'completed'
+    | 'task_added' // Appended outside the editor, e.g. by quick capture
+    | 'tracked' // A task's timer started or stopped
+    | 'deleted'
+    | 'restored'
+    | 'changed_on_disk'
+    | 'reordered'
+    | 'unlocked';
+
+/** Payload of `project-update` (debounced) and `widget-refresh`. */
+export interface ProjectUpdate {
+    project_id: string | null; // Unset when any project may have changed
+    kind: ChangeKind;
+}
+
+/** One day of group_calendar / the widget's calendar_weeks, as task IDs. */
+export interface CalendarDay {
+    date: string; // YYYY-MM-DD
+    starting: string[];
+    ending: string[];
+    in_progress: string[];
+    milestones: string[];
+}
+
+/** A Sunday-first week; only days with something on them are listed. */
+export interface CalendarWeek {
+    week_start: string; // YYYY-MM-DD
+    days: CalendarDay[];
+}
+
+/** A reminder that went off (also the `reminder` event's payload). */
+export interface FiredReminder {
+    id: string; // One occurrence; pass to snooze_reminder / dismiss_reminder
+    project_id: string;
+    task_id: string;
+    at: string; // When it fell due
+    title: string;
+    body: string;
+    snoozed_until: string | null;
+}
+
+/** Payload of `project-status`: a project just became urgent or overdue. */
+export interface StatusChange {
+    project_id: string;
+    project_name: string;
+    from: string;
+    to: 'urgent' | 'overdue';
+    next_deadline: string | null;
+}
+
+/** Returned by `quick_add_task`. */
+export interface QuickAddResult {
+    task: Task;
+    schedule: ScheduleResult;
+}
+
+/** Returned by `parse_task_entry`, to confirm before adding the task. */
+export interface TaskEntryParse {
+    task: Task;
+    anchor: string | null; // YYYY-MM-DD, from "due ..." or "by ..."
+    unresolved: string[]; // Names after "after" matching no task in the project
+}
+
+/** Returned by `start_timer` and `active_timer`. */
+export interface RunningTimer {
+    project_id: string;
+    project_name: string;
+    task_id: string;
+    task_name: string;
+    started_at: string;
+}
+
+/** Where an anchor:// link asked the main window to go. */
+export interface DeepLinkTarget {
+    project_id: string;
+    task_id: string | null;
+}
+
+export type TaskOperation =
+    | { op: 'add'; task: Task; anchor?: string; anchor_window?: AnchorWindow }
+    | { op: 'update'; task: Task }
+    | { op: 'delete'; task_id: string }
+    | { op: 'complete'; task_id: string; completed?: boolean }
+    | { op: 'set_anchor'; task_id: string; date?: string | null };
+
+export interface ProjectListing {
+    id: string;
+    name: string;
+    pinned: boolean;
+    color: string | null;
+    icon: string | null;
+    description: string | null;
+    created_at: string;
+    last_modified: string;
+    task_count: number;
 }
 
 export interface ProjectMetadata {
     id: string;
     name: string;
+    pinned: boolean;
+    color: string | null;
+    icon: string | null;
+    description: string | null;
     created_at: string;
     last_modified: string;
     task_count: number;
     next_deadline: string | null;
     current_focus: string | null;
-    status: 'empty' | 'on_track' | 'urgent' | 'overdue';
+    status: 'empty' | 'on_track' | 'at_risk' | 'urgent' | 'overdue';
+    on_time?: AnchorProbability[];
+    completion: number | null; // Percent of work done, subtasks included
+}
+
+/** Result of get_next_deadline: the soonest deadline across all projects. */
+export interface NextDeadline {
+    next: ProjectMetadata | null;
+    runners_up: ProjectMetadata[]; // Soonest first
+}
+
+/** Chance of an anchor finishing by its date, from three-point estimates. */
+export interface AnchorProbability {
+    task_id: string;
+    anchor_date: string;
+    expected_finish: string;
+    probability: number; // 0 to 1
+}
+
+export interface ArchivedTask {
+    task: Task;
+    anchor: string | null;
+    archived_at: string;
+}
+
+export interface ProjectArchive {
+    project_id: string;
+    tasks: ArchivedTask[];
+}
+
+export type ImportFormat = 'csv' | 'ics' | 'mspdi' | 'bundle' | 'todoist' | 'trello' | 'jira';
+
+/** Header names for each field of a mapped CSV import. */
+export interface ColumnMapping {
+    name: string;
+    id?: string | null;
+    duration?: string | null;
+    dependencies?: string | null;
+    deadline?: string | null;
+    notes?: string | null;
+    assignee?: string | null;
+    milestone?: string | null;
+    completed?: string | null;
+    separator?: string | null; // ";" when unset; Notion uses ","
+}
+
+export interface CsvColumns {
+    headers: string[];
+    sample_rows: string[][];
+}
+
+export interface JiraConnection {
+    base_url: string; // e.g. https://example.atlassian.net
+    email?: string | null; // Jira Cloud; unset to send the token as a personal access token
+    token: string;
+}
+
+export interface TodoistProject {
+    id: string;
+    name: string;
+}
+
+/** Dry-run result for a dropped file, emitted as `import-preview`. */
+export interface ImportPreview {
+    path: string;
+    format: ImportFormat;
+    project: Project;
+    warnings: string[];
+    schedule_error: string | null;
+    /** Tasks the import adds; all of them unless importing into a project. */
+    created_task_ids: string[];
+    unresolved_dependencies: UnresolvedDependency[];
+}
+
+/** A dependency reference in a task list that matched no task. */
+export interface UnresolvedDependency {
+    task_name: string;
+    reference: string;
+}
+
+export interface ImportFailure {
+    path: string;
+    error: string;
+}
+
+export interface ResourceDay {
+    assignee: string;
+    date: string; // YYYY-MM-DD
+    scheduled_hours: number;
+    capacity_hours: number;
+    over_capacity: boolean;
+    task_ids: string[];
+}
+
+export interface AgendaBlock {
+    task_id: string;
+    task_name: string;
+    start: string;
+    end: string;
+    hours: number;
+    past_deadline: boolean; // Ends after the task's scheduled finish
+}
+
+export interface AgendaDay {
+    date: string; // YYYY-MM-DD
+    blocks: AgendaBlock[];
+    hours: number;
+}
+
+export interface Agenda {
+    days: AgendaDay[];
+    unallocated: { task_id: string; hours: number }[]; // Work that didn't fit before the last deadline
+}
+
+/** The work feeding one anchor, tightest first. */
+export interface ChainSlack {
+    anchor_id: string;
+    anchor_name: string;
+    task_ids: string[];
+    slack_minutes: number | null; // Null once the anchor is done
+    tightest_task_id: string | null;
+    is_driving: boolean;
+}
+
+export type HealthSeverity = 'info' | 'warning' | 'error';
+
+export type HealthCheckKind =
+    | 'unschedulable'
+    | 'missing_dependency'
+    | 'no_successors'
+    | 'dangling'
+    | 'long_duration'
+    | 'negative_slack'
+    | 'high_slack'
+    | 'high_fan_in';
+
+export interface HealthCheck {
+    kind: HealthCheckKind;
+    severity: HealthSeverity;
+    message: string;
+    task_ids: string[];
+}
+
+export type ExportFormat = 'ics';
+
+export interface Capabilities {
+    version: string;
+    features: string[];
+    commands: string[];
+    import_formats: ImportFormat[];
+    export_formats: ExportFormat[];
+    schemas: { project: number; archive: number; config: number; api: number };
 }
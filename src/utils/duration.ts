@@ -0,0 +1,27 @@
+import type { DurationUnit, TaskDuration } from '../types';
+
+const SHORT: Record<DurationUnit, string> = { minutes: 'm', hours: 'h', days: 'd', weeks: 'w' };
+
+/** Compact label, e.g. "2w" or "30m". */
+export function formatDurationShort(duration: TaskDuration): string {
+    return `${duration.value}${SHORT[duration.unit]}`;
+}
+
+/** Label with the unit spelled out, e.g. "1 day" or "3 weeks". */
+export function formatDurationLong(duration: TaskDuration): string {
+    const unit = duration.value === 1 ? duration.unit.slice(0, -1) : duration.unit;
+    return `${duration.value} ${unit}`;
+}
+
+/** Worked time, e.g. "2h 15m" or "40m". */
+export function formatMinutes(minutes: number): string {
+    const hours = Math.floor(minutes / 60);
+    const rest = minutes % 60;
+    if (hours === 0) return `${rest}m`;
+    return rest === 0 ? `${hours}h` : `${hours}h ${rest}m`;
+}
+
+/** True for durations that span whole calendar days. */
+export function isCalendarDuration(duration: TaskDuration): boolean {
+    return duration.unit === 'days' || duration.unit === 'weeks';
+}
@@ -1,4 +1,6 @@
-export type ProjectStatus = 'overdue' | 'urgent' | 'on_track' | 'empty';
+import type { AnchorProbability } from '../types';
+
+export type ProjectStatus = 'overdue' | 'urgent' | 'at_risk' | 'on_track' | 'empty';
 
 export const URGENT_THRESHOLD_DAYS = 5;
 
@@ -12,6 +14,7 @@ export function getStatusColor(status: string): string {
     switch (status) {
         case 'on_track': return 'text-success';
         case 'urgent': return 'text-warning';
+        case 'at_risk': return 'text-warning';
         case 'overdue': return 'text-danger';
         default: return 'text-text-muted';
     }
@@ -21,7 +24,16 @@ export function getStatusBgColor(status: string): string {
     switch (status) {
         case 'on_track': return 'bg-success';
         case 'urgent': return 'bg-warning';
+        case 'at_risk': return 'bg-warning';
         case 'overdue': return 'bg-danger';
         default: return 'bg-text-muted';
     }
 }
+
+/** e.g. "at risk, 60%" using the least likely anchor. */
+export function getStatusLabel(status: string, onTime: AnchorProbability[] = []): string {
+    const label = status.replace('_', ' ');
+    if (status !== 'at_risk' || onTime.length === 0) return label;
+    const worst = Math.min(...onTime.map(p => p.probability));
+    return `${label}, ${Math.round(worst * 100)}%`;
+}
@@ -37,6 +37,7 @@ export default defineConfig(async () => ({
       input: {
         main: resolve(__dirname, 'index.html'),
         widget: resolve(__dirname, 'widget.html'),
+        quickAdd: resolve(__dirname, 'quick-add.html'),
       },
     },
   },